@@ -0,0 +1,32 @@
+use api_shared::requests::gmail_scanner_request;
+use worker::*;
+
+/// Have the scanner write the labels of newly classified emails back to
+/// Gmail. It skips users who haven't turned that on, and failures are only
+/// logged since labels never block processing.
+pub async fn sync_gmail_labels(env: &Env, user_id: &str, email_ids: &[String]) {
+    if email_ids.is_empty() {
+        return;
+    }
+    let result = async {
+        let body = serde_json::to_string(&serde_json::json!({
+            "user_id": user_id,
+            "email_ids": email_ids
+        }))?;
+        let request = gmail_scanner_request(env, "/labels/sync", &body)?;
+        let mut response = Fetch::Request(request).send().await?;
+        if response.status_code() != 200 {
+            let text = response.text().await?;
+            return Err(worker::Error::RustError(format!(
+                "Gmail scanner error: {}",
+                text
+            )));
+        }
+        Ok(())
+    }
+    .await;
+
+    if let Err(e) = result {
+        console_log!("Error syncing Gmail labels: {}", e);
+    }
+}
@@ -1,3 +1,4 @@
 pub mod cors;
 pub mod db;
+pub mod gmail_scanner;
 pub mod uuid;
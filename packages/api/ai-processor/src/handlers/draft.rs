@@ -0,0 +1,56 @@
+use crate::services::ai::{draft_email, DRAFT_KINDS};
+use crate::services::db::get_draft_context;
+use api_shared::quota::QuotaExceeded;
+use api_shared::usage::too_many_requests;
+use worker::*;
+
+pub async fn draft(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let job_id = ctx
+        .param("job_id")
+        .ok_or_else(|| worker::Error::RustError("Missing job_id".to_string()))?;
+
+    #[derive(serde::Deserialize)]
+    struct DraftRequest {
+        user_id: String,
+        kind: String,
+        reply_to_email_id: Option<String>,
+        instructions: Option<String>,
+    }
+
+    let draft_req: DraftRequest = req.json().await?;
+
+    if !DRAFT_KINDS.contains(&draft_req.kind.as_str()) {
+        return Response::error(
+            format!("kind must be one of: {}", DRAFT_KINDS.join(", ")),
+            400,
+        );
+    }
+
+    let context = match get_draft_context(
+        &ctx.env,
+        job_id,
+        &draft_req.user_id,
+        draft_req.reply_to_email_id.as_deref(),
+    )
+    .await
+    {
+        Ok(context) => context,
+        Err(e) => return Response::error(format!("{}", e), 404),
+    };
+
+    match draft_email(
+        &ctx.env,
+        &draft_req.user_id,
+        &context,
+        &draft_req.kind,
+        draft_req.instructions.as_deref(),
+    )
+    .await
+    {
+        Ok(draft) => Response::from_json(&draft),
+        Err(e) => match e.downcast_ref::<QuotaExceeded>() {
+            Some(exceeded) => too_many_requests(exceeded),
+            None => Response::error(format!("Drafting failed: {}", e), 500),
+        },
+    }
+}
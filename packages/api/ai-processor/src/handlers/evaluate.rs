@@ -0,0 +1,92 @@
+use crate::services::ai::{classify_email_with_prompt, extract_info_with_prompt};
+use crate::services::db::{get_active_prompt, EmailData};
+use futures::stream::{self, StreamExt};
+use worker::*;
+
+/// Emails evaluated at the same time
+const EVAL_CONCURRENCY: usize = 4;
+
+/// Run the classify and extract stages on a batch of emails and return the
+/// predictions without saving anything. Prompts that aren't given fall back
+/// to the active prompt for their stage.
+pub async fn evaluate(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    #[derive(serde::Deserialize)]
+    struct EvalEmail {
+        id: String,
+        from_email: Option<String>,
+        subject: Option<String>,
+        body: Option<String>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct EvaluateRequest {
+        classify_prompt: Option<String>,
+        extract_prompt: Option<String>,
+        emails: Vec<EvalEmail>,
+    }
+
+    let eval_req: EvaluateRequest = req.json().await?;
+
+    let classify_prompt = match eval_req.classify_prompt {
+        Some(prompt) => prompt,
+        None => get_active_prompt(&ctx.env, "classify")
+            .await
+            .map_err(|e| worker::Error::RustError(format!("{}", e)))?,
+    };
+    let extract_prompt = match eval_req.extract_prompt {
+        Some(prompt) => prompt,
+        None => get_active_prompt(&ctx.env, "extract")
+            .await
+            .map_err(|e| worker::Error::RustError(format!("{}", e)))?,
+    };
+
+    // A few emails at a time, keeping the predictions in request order
+    let env = &ctx.env;
+    let classify_prompt = classify_prompt.as_str();
+    let extract_prompt = extract_prompt.as_str();
+    let predictions: Vec<serde_json::Value> = stream::iter(eval_req.emails)
+        .map(|eval_email| async move {
+            let email = EmailData {
+                gmail_id: eval_email.id.clone(),
+                user_id: String::new(),
+                subject: eval_email.subject,
+                from: eval_email.from_email,
+                to: None,
+                snippet: None,
+                body: eval_email.body,
+            };
+
+            // A failed stage is reported on the prediction instead of failing the run
+            let classification =
+                match classify_email_with_prompt(env, &email, classify_prompt).await {
+                    Ok((classification, _)) => classification,
+                    Err(e) => {
+                        return serde_json::json!({
+                            "id": eval_email.id,
+                            "error": format!("{}", e)
+                        });
+                    }
+                };
+            let extraction =
+                extract_info_with_prompt(env, &email, &classification.category, extract_prompt)
+                    .await;
+
+            let mut prediction = serde_json::json!({
+                "id": eval_email.id,
+                "category": classification.category,
+                "confidence": classification.confidence
+            });
+            match extraction {
+                Ok((extraction, _)) => prediction["extraction"] = serde_json::json!(extraction),
+                Err(e) => prediction["error"] = serde_json::json!(format!("{}", e)),
+            }
+            prediction
+        })
+        .buffered(EVAL_CONCURRENCY)
+        .collect()
+        .await;
+
+    Response::from_json(&serde_json::json!({
+        "predictions": predictions
+    }))
+}
@@ -1,2 +1,6 @@
+pub mod draft;
+pub mod evaluate;
 pub mod health;
 pub mod process;
+pub mod sandbox;
+pub mod tailor;
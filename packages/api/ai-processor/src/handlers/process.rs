@@ -1,6 +1,13 @@
+use crate::common::gmail_scanner::sync_gmail_labels;
 use crate::services::ai::process_email;
+use api_shared::quota::{QuotaExceeded, AI_CALLS};
+use api_shared::usage::{get_quota, now_secs, too_many_requests};
+use futures::stream::{self, StreamExt};
 use worker::*;
 
+/// Emails of a batch processed at the same time
+const BATCH_CONCURRENCY: usize = 4;
+
 pub async fn process_single(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
     let email_id = ctx
         .param("email_id")
@@ -10,13 +17,29 @@ pub async fn process_single(mut req: Request, ctx: RouteContext<()>) -> Result<R
     #[derive(serde::Deserialize)]
     struct ProcessRequest {
         user_id: String,
+        /// Run the models again instead of reusing a cached result
+        #[serde(default)]
+        bypass_cache: bool,
     }
 
     let process_req: ProcessRequest = req.json().await?;
 
-    match process_email(&ctx.env, email_id, &process_req.user_id).await {
-        Ok(_) => Response::ok("Processing completed"),
-        Err(e) => Response::error(format!("Processing failed: {}", e), 500),
+    match process_email(
+        &ctx.env,
+        email_id,
+        &process_req.user_id,
+        process_req.bypass_cache,
+    )
+    .await
+    {
+        Ok(_) => {
+            sync_gmail_labels(&ctx.env, &process_req.user_id, &[email_id.to_string()]).await;
+            Response::ok("Processing completed")
+        }
+        Err(e) => match e.downcast_ref::<QuotaExceeded>() {
+            Some(exceeded) => too_many_requests(exceeded),
+            None => Response::error(format!("Processing failed: {}", e), 500),
+        },
     }
 }
 
@@ -29,21 +52,43 @@ pub async fn process_batch(mut req: Request, ctx: RouteContext<()>) -> Result<Re
 
     let batch_req: BatchRequest = req.json().await?;
 
-    let mut results = Vec::new();
-    for email_id in batch_req.email_ids {
-        match process_email(&ctx.env, &email_id, &batch_req.user_id).await {
-            Ok(_) => results.push(serde_json::json!({
-                "email_id": email_id,
-                "status": "success"
-            })),
-            Err(e) => results.push(serde_json::json!({
-                "email_id": email_id,
-                "status": "error",
-                "error": format!("{}", e)
-            })),
-        }
+    // Don't start a batch for a user who has no AI calls left today
+    let quota = get_quota(&ctx.env, &batch_req.user_id, AI_CALLS)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to get quota: {}", e)))?;
+    if let Err(exceeded) = quota.check(1, now_secs()) {
+        return too_many_requests(&exceeded);
     }
 
+    // A few emails at a time, keeping the results in request order
+    let env = &ctx.env;
+    let user_id = batch_req.user_id.as_str();
+    let results: Vec<serde_json::Value> = stream::iter(batch_req.email_ids)
+        .map(|email_id| async move {
+            match process_email(env, &email_id, user_id, false).await {
+                Ok(_) => serde_json::json!({
+                    "email_id": email_id,
+                    "status": "success"
+                }),
+                Err(e) => serde_json::json!({
+                    "email_id": email_id,
+                    "status": if e.is::<QuotaExceeded>() { "quota_exceeded" } else { "error" },
+                    "error": format!("{}", e)
+                }),
+            }
+        })
+        .buffered(BATCH_CONCURRENCY)
+        .collect()
+        .await;
+
+    // Label the classified emails in Gmail for users who turned that on
+    let processed: Vec<String> = results
+        .iter()
+        .filter(|r| r["status"] == "success")
+        .filter_map(|r| r["email_id"].as_str().map(String::from))
+        .collect();
+    sync_gmail_labels(env, user_id, &processed).await;
+
     Response::from_json(&serde_json::json!({
         "results": results
     }))
@@ -0,0 +1,103 @@
+use crate::services::ai::{trace_stage, StageTrace};
+use crate::services::db::{get_active_prompt, get_email_data};
+use crate::services::schema::{stage_schema, validate};
+use std::collections::HashMap;
+use worker::*;
+
+/// Stages a sandbox run goes through, in pipeline order
+const SANDBOX_STAGES: [&str; 3] = ["classify", "extract", "summarize"];
+
+/// Record an error when the parsed output doesn't match the stage's schema,
+/// which is where the real pipeline would ask the model for a repair
+fn check_schema(trace: &mut StageTrace) {
+    let (Some(parsed), Some(schema)) = (trace.parsed.as_ref(), stage_schema(&trace.stage)) else {
+        return;
+    };
+    if let Err(errors) = validate(&schema, parsed) {
+        trace.error = Some(format!(
+            "Output doesn't match the {} schema: {}",
+            trace.stage,
+            errors.join("; ")
+        ));
+    }
+}
+
+/// Run emails through classify, extract and summarize with draft prompts and
+/// return a trace of every stage. Stages without a draft prompt use the active
+/// one. Nothing is written to the database.
+pub async fn sandbox(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    #[derive(serde::Deserialize)]
+    struct SandboxRequest {
+        email_ids: Vec<String>,
+        #[serde(default)]
+        prompts: HashMap<String, String>,
+    }
+
+    let sandbox_req: SandboxRequest = req.json().await?;
+
+    let mut templates = HashMap::new();
+    for stage in SANDBOX_STAGES {
+        let template = match sandbox_req.prompts.get(stage) {
+            Some(prompt) => prompt.clone(),
+            None => get_active_prompt(&ctx.env, stage)
+                .await
+                .map_err(|e| worker::Error::RustError(format!("{}", e)))?,
+        };
+        templates.insert(stage, template);
+    }
+
+    let mut results = Vec::new();
+    for email_id in sandbox_req.email_ids {
+        let email = match get_email_data(&ctx.env, &email_id).await {
+            Ok(email) => email,
+            Err(e) => {
+                results.push(serde_json::json!({
+                    "email_id": email_id,
+                    "error": format!("{}", e)
+                }));
+                continue;
+            }
+        };
+
+        let body = email
+            .body
+            .as_deref()
+            .or(email.snippet.as_deref())
+            .unwrap_or("");
+        let mut variables = HashMap::new();
+        variables.insert("from_email", email.from.as_deref().unwrap_or(""));
+        variables.insert("subject", email.subject.as_deref().unwrap_or(""));
+        variables.insert("body", body);
+
+        let mut classify =
+            trace_stage(&ctx.env, "classify", &templates["classify"], &variables).await;
+        check_schema(&mut classify);
+
+        // Later stages get the predicted category, as in the real pipeline
+        let category = classify
+            .parsed
+            .as_ref()
+            .and_then(|p| p.get("category"))
+            .and_then(|c| c.as_str())
+            .unwrap_or("other")
+            .to_string();
+        variables.insert("category", &category);
+
+        let mut extract = trace_stage(&ctx.env, "extract", &templates["extract"], &variables).await;
+        check_schema(&mut extract);
+
+        let mut summarize =
+            trace_stage(&ctx.env, "summarize", &templates["summarize"], &variables).await;
+        check_schema(&mut summarize);
+
+        results.push(serde_json::json!({
+            "email_id": email_id,
+            "subject": email.subject,
+            "stages": [classify, extract, summarize]
+        }));
+    }
+
+    Response::from_json(&serde_json::json!({
+        "results": results
+    }))
+}
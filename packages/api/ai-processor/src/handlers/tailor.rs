@@ -0,0 +1,62 @@
+use crate::common::uuid::generate_uuid;
+use crate::services::ai::tailor_documents;
+use crate::services::db::{get_tailor_context, save_job_document, TailorContextError};
+use api_shared::quota::QuotaExceeded;
+use api_shared::usage::too_many_requests;
+use worker::*;
+
+pub async fn tailor(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let job_id = ctx
+        .param("job_id")
+        .ok_or_else(|| worker::Error::RustError("Missing job_id".to_string()))?;
+
+    #[derive(serde::Deserialize)]
+    struct TailorRequest {
+        user_id: String,
+        instructions: Option<String>,
+    }
+
+    let tailor_req: TailorRequest = req.json().await?;
+
+    let context = match get_tailor_context(&ctx.env, job_id, &tailor_req.user_id).await {
+        Ok(context) => context,
+        Err(e) => {
+            return match e.downcast_ref::<TailorContextError>() {
+                Some(missing) => Response::error(missing.to_string(), missing.status()),
+                None => Response::error(format!("Failed to load job: {}", e), 500),
+            };
+        }
+    };
+
+    let result = match tailor_documents(
+        &ctx.env,
+        &tailor_req.user_id,
+        &context,
+        tailor_req.instructions.as_deref(),
+    )
+    .await
+    {
+        Ok(result) => result,
+        Err(e) => {
+            return match e.downcast_ref::<QuotaExceeded>() {
+                Some(exceeded) => too_many_requests(exceeded),
+                None => Response::error(format!("Tailoring failed: {}", e), 500),
+            };
+        }
+    };
+
+    let id = generate_uuid().map_err(|e| worker::Error::RustError(format!("{}", e)))?;
+    match save_job_document(
+        &ctx.env,
+        &id,
+        job_id,
+        &tailor_req.user_id,
+        &result.cover_letter,
+        &result.resume_bullets,
+    )
+    .await
+    {
+        Ok(document) => Response::from_json(&document),
+        Err(e) => Response::error(format!("Failed to save documents: {}", e), 500),
+    }
+}
@@ -2,15 +2,24 @@ use worker::*;
 
 mod common;
 mod handlers;
-mod services;
+pub mod services;
 
+use api_shared::requests::require_signature;
 use common::cors::get_cors;
-use handlers::{health, process};
+use handlers::{draft, evaluate, health, process, sandbox, tailor};
 
 #[event(fetch)]
 async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
     let cors = get_cors(&env);
 
+    // Everything but the health check is only for the other workers
+    if req.path() != "/health" && req.method() != Method::Options {
+        if let Err(e) = require_signature(&req, &env).await {
+            let error_resp = Response::error(format!("Unauthorized: {}", e), 401)?;
+            return apply_cors(error_resp, &cors);
+        }
+    }
+
     let router = Router::new();
 
     let response = router
@@ -23,9 +32,25 @@ async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
         .post_async("/process/batch", |req, ctx| async move {
             process::process_batch(req, ctx).await
         })
+        .post_async("/draft/:job_id", |req, ctx| async move {
+            draft::draft(req, ctx).await
+        })
+        .post_async("/tailor/:job_id", |req, ctx| async move {
+            tailor::tailor(req, ctx).await
+        })
+        .post_async("/evaluate", |req, ctx| async move {
+            evaluate::evaluate(req, ctx).await
+        })
+        .post_async("/sandbox", |req, ctx| async move {
+            sandbox::sandbox(req, ctx).await
+        })
         .options("/health", |_, _| Response::ok(""))
         .options("/process/:email_id", |_, _| Response::ok(""))
         .options("/process/batch", |_, _| Response::ok(""))
+        .options("/draft/:job_id", |_, _| Response::ok(""))
+        .options("/tailor/:job_id", |_, _| Response::ok(""))
+        .options("/evaluate", |_, _| Response::ok(""))
+        .options("/sandbox", |_, _| Response::ok(""))
         .run(req, env)
         .await;
 
@@ -1,8 +1,13 @@
-use crate::services::db::get_active_prompt;
-use crate::services::db::EmailData;
+use crate::services::cache::{cache_version, content_hash, normalize_content};
+use crate::services::db::{get_active_prompt, get_model_settings, get_review_examples};
+use crate::services::db::{DraftContext, EmailData, ResultLineage, ReviewExample, TailorContext};
+use crate::services::models::{complete_with_fallback, ModelBackend, ModelSpec, Provider};
+use crate::services::schema::{extract_json_from_text, parse_output, repair_prompt, stage_schema};
 use anyhow::{anyhow, Result};
+use api_shared::quota::{QuotaExceeded, AI_CALLS};
+use api_shared::usage::{get_quota, now_secs, use_quota};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use worker::*;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -11,7 +16,7 @@ pub struct ClassificationResult {
     pub confidence: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ExtractionResult {
     pub company: Option<String>,
     pub job_title: Option<String>,
@@ -27,74 +32,200 @@ pub struct SummarizationResult {
     pub summary: String,
 }
 
-#[derive(Debug, Serialize)]
-struct ChatMessage {
-    role: String,
-    content: String,
+/// Output of all processing stages, from the combined prompt or from running
+/// the stages one by one
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CombinedResult {
+    pub category: String,
+    pub confidence: f64,
+    #[serde(default)]
+    pub extraction: ExtractionResult,
+    pub summary: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DraftResult {
+    pub subject: String,
+    pub body: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TailorResult {
+    pub cover_letter: String,
+    #[serde(default)]
+    pub resume_bullets: Vec<String>,
 }
 
+/// What one stage did during a sandbox run: the prompt sent, the raw model
+/// output, the JSON parsed from it and how long the call took
 #[derive(Debug, Serialize)]
-struct AiInput {
-    messages: Vec<ChatMessage>,
-    max_tokens: u32,
+pub struct StageTrace {
+    pub stage: String,
+    pub rendered_prompt: String,
+    pub raw_output: Option<String>,
+    pub parsed: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// Most review corrections added to classify, extract and combined prompts
+const FEW_SHOT_EXAMPLES: u32 = 3;
+
+/// Characters of each example body included in a prompt
+const FEW_SHOT_BODY_CHARS: usize = 500;
+
+/// Kinds of email the draft stage writes
+pub const DRAFT_KINDS: [&str; 2] = ["thank_you", "follow_up"];
+
+/// Times a processing stage's invalid output is sent back to be repaired
+const REPAIR_ATTEMPTS: usize = 1;
+
+/// Stages an email goes through unless the combined prompt is used
+const PROCESSING_STAGES: [&str; 3] = ["classify", "extract", "summarize"];
+
+/// Most model runs a stage can make with a fallback chain of `chain_length`
+/// models: every model of the chain for the first try and for each repair
+pub fn max_stage_runs(chain_length: usize) -> u32 {
+    (chain_length.max(1) * (REPAIR_ATTEMPTS + 1)) as u32
+}
+
+/// Runs models through the Workers AI binding or an OpenAI-compatible endpoint,
+/// counting every run against the user's AI calls
+struct WorkerBackend<'a> {
+    env: &'a Env,
+    /// User the runs are counted for; admin runs aren't counted
+    user_id: Option<&'a str>,
+}
+
+impl ModelBackend for WorkerBackend<'_> {
+    async fn run(&self, model: &ModelSpec, input: &serde_json::Value) -> Result<serde_json::Value> {
+        if let Some(user_id) = self.user_id {
+            use_quota(self.env, user_id, AI_CALLS, 1).await?;
+        }
+        match model.provider {
+            Provider::WorkersAi => {
+                let ai = self.env.ai("AI")
+                    .map_err(|e| anyhow!("Failed to get AI binding: {:?}. Make sure [ai] binding = \"AI\" is configured in wrangler.toml", e))?;
+                ai.run(&model.model, input)
+                    .await
+                    .map_err(|e| anyhow!("AI API error: {:?}", e))
+            }
+            Provider::OpenAi => call_openai_compatible(self.env, &model.model, input).await,
+        }
+    }
 }
 
-pub async fn call_ai(env: &Env, prompt: &str) -> Result<String> {
-    // Validate prompt is not empty
-    if prompt.trim().is_empty() {
-        return Err(anyhow!("Prompt cannot be empty"));
+/// Call the chat completions endpoint of an OpenAI-compatible server at
+/// `OPENAI_BASE_URL`, e.g. `http://localhost:11434/v1` for a local model
+/// server. `OPENAI_API_KEY` is sent as a bearer token when set.
+async fn call_openai_compatible(
+    env: &Env,
+    model: &str,
+    input: &serde_json::Value,
+) -> Result<serde_json::Value> {
+    let base_url = env
+        .var("OPENAI_BASE_URL")
+        .map(|v| v.to_string())
+        .map_err(|_| anyhow!("OPENAI_BASE_URL is not configured"))?;
+    let url = format!("{}/chat/completions", base_url.trim_end_matches('/'));
+
+    let mut body = input.clone();
+    body["model"] = serde_json::Value::String(model.to_string());
+
+    let mut init = RequestInit::new();
+    init.with_method(Method::Post);
+    init.with_body(Some(serde_json::to_string(&body)?.into()));
+    let headers = Headers::new();
+    headers.set("Content-Type", "application/json")?;
+    if let Ok(api_key) = env.secret("OPENAI_API_KEY") {
+        headers.set("Authorization", &format!("Bearer {}", api_key))?;
+    }
+    init.with_headers(headers);
+    let request = Request::new_with_init(&url, &init)?;
+
+    let mut response = Fetch::Request(request).send().await?;
+    let status = response.status_code();
+    if !(200..300).contains(&status) {
+        let text = response.text().await.unwrap_or_default();
+        return Err(anyhow!("HTTP {}: {}", status, text));
     }
 
-    // Use Cloudflare Workers AI binding
-    let ai = env.ai("AI")
-        .map_err(|e| anyhow!("Failed to get AI binding: {:?}. Make sure [ai] binding = \"AI\" is configured in wrangler.toml", e))?;
-
-    // Build input with messages and max_tokens, matching TypeScript examples
-    let input = AiInput {
-        messages: vec![ChatMessage {
-            role: "user".to_string(),
-            content: prompt.to_string(),
-        }],
-        max_tokens: 1024,
-    };
+    Ok(response.json().await?)
+}
 
-    let output: serde_json::Value = ai
-        .run("@cf/meta/llama-3.1-8b-instruct-fast", input)
-        .await
-        .map_err(|e| anyhow!("AI API error: {:?}", e))?;
-
-    // Extract text from response - try common formats
-    let response_text = output
-        .as_str()
-        .map(|s| s.to_string())
-        .or_else(|| {
-            output
-                .as_object()?
-                .get("response")?
-                .as_str()
-                .map(|s| s.to_string())
-        })
-        .or_else(|| {
-            output
-                .as_object()?
-                .get("result")?
-                .as_str()
-                .map(|s| s.to_string())
-        })
-        .ok_or_else(|| {
-            console_log!("Unexpected AI response format: {}", serde_json::to_string_pretty(&output).unwrap_or_default());
-            anyhow!("Invalid AI API response format: {:?}", output)
-        })?;
-
-    Ok(response_text)
-}
-
-fn extract_json_from_text(text: &str) -> Result<String> {
-    let start = text.find('{')
-        .ok_or_else(|| anyhow!("No JSON object found in response: {}", text))?;
-    let end = text.rfind('}')
-        .ok_or_else(|| anyhow!("No closing brace found in JSON: {}", text))?;
-    Ok(text[start..=end].to_string())
+/// Run a prompt with the models configured for the stage, falling back
+/// through the chain when a model fails. Every model run counts as one of the
+/// user's AI calls.
+pub async fn call_ai(
+    env: &Env,
+    user_id: Option<&str>,
+    stage: &str,
+    prompt: &str,
+) -> Result<String> {
+    Ok(call_ai_with_model(env, user_id, stage, prompt).await?.0)
+}
+
+/// Like `call_ai`, also returning the model that answered
+async fn call_ai_with_model(
+    env: &Env,
+    user_id: Option<&str>,
+    stage: &str,
+    prompt: &str,
+) -> Result<(String, ModelSpec)> {
+    let settings = get_model_settings(env, stage).await?;
+    let backend = WorkerBackend { env, user_id };
+    let (text, model) = complete_with_fallback(&backend, &settings, prompt).await?;
+
+    if settings.models.first() != Some(&model) {
+        console_log!("The {} stage fell back to {}", stage, model);
+    }
+
+    Ok((text, model))
+}
+
+/// Run a processing stage and parse its output against the stage's schema.
+/// Output that doesn't parse or validate is sent back to the model with the
+/// problem, up to `REPAIR_ATTEMPTS` times, before the stage fails. Returns the
+/// output with the model that gave it.
+async fn run_stage<T: serde::de::DeserializeOwned>(
+    env: &Env,
+    user_id: Option<&str>,
+    stage: &str,
+    prompt: &str,
+) -> Result<(T, ModelSpec)> {
+    let schema =
+        stage_schema(stage).ok_or_else(|| anyhow!("No output schema for stage: {}", stage))?;
+    let (mut response, mut model) = call_ai_with_model(env, user_id, stage, prompt).await?;
+    let mut attempts = 0;
+
+    loop {
+        match parse_output(&schema, &response) {
+            Ok(value) => {
+                let output = serde_json::from_value(value)
+                    .map_err(|e| anyhow!("Invalid {} output: {}", stage, e))?;
+                return Ok((output, model));
+            }
+            Err(error) if attempts < REPAIR_ATTEMPTS => {
+                attempts += 1;
+                console_log!(
+                    "Repairing {} output: {} - Response: {}",
+                    stage,
+                    error,
+                    response
+                );
+                let repair = repair_prompt(prompt, &response, &error, &schema);
+                (response, model) = call_ai_with_model(env, user_id, stage, &repair).await?;
+            }
+            Err(error) => {
+                return Err(anyhow!(
+                    "Invalid {} output: {} - Response: {}",
+                    stage,
+                    error,
+                    response
+                ));
+            }
+        }
+    }
 }
 
 fn substitute_variables(template: &str, variables: &HashMap<&str, &str>) -> String {
@@ -106,9 +237,121 @@ fn substitute_variables(template: &str, variables: &HashMap<&str, &str>) -> Stri
     result
 }
 
-pub async fn classify_email(env: &Env, email: &EmailData) -> Result<ClassificationResult> {
+/// Run one stage with the given template and variables, recording each step
+/// instead of falling back to defaults. Nothing is saved.
+pub async fn trace_stage(
+    env: &Env,
+    stage: &str,
+    prompt_template: &str,
+    variables: &HashMap<&str, &str>,
+) -> StageTrace {
+    let rendered_prompt = substitute_variables(prompt_template, variables);
+    let started = Date::now().as_millis();
+    let output = call_ai(env, None, stage, &rendered_prompt).await;
+    let duration_ms = Date::now().as_millis().saturating_sub(started);
+
+    let mut trace = StageTrace {
+        stage: stage.to_string(),
+        rendered_prompt,
+        raw_output: None,
+        parsed: None,
+        error: None,
+        duration_ms,
+    };
+
+    let raw_output = match output {
+        Ok(r) => r,
+        Err(e) => {
+            trace.error = Some(format!("AI call failed: {}", e));
+            return trace;
+        }
+    };
+
+    let parsed = extract_json_from_text(&raw_output).and_then(|json_text| {
+        serde_json::from_str::<serde_json::Value>(&json_text).map_err(|e| anyhow!(e))
+    });
+    match parsed {
+        Ok(parsed) => trace.parsed = Some(parsed),
+        Err(e) => trace.error = Some(format!("Failed to parse JSON: {}", e)),
+    }
+    trace.raw_output = Some(raw_output);
+
+    trace
+}
+
+/// Add the user's corrections to a prompt template as worked examples, at
+/// `{{examples}}` when the template has it and before the prompt otherwise
+fn with_examples(
+    template: &str,
+    examples: &[ReviewExample],
+    answer: impl Fn(&ReviewExample) -> serde_json::Value,
+) -> String {
+    if examples.is_empty() {
+        return template.replace("{{examples}}", "");
+    }
+
+    let mut block = String::from("Examples of emails from this user with the correct answer:\n\n");
+    for example in examples {
+        let body: String = example
+            .body
+            .as_deref()
+            .unwrap_or("")
+            .chars()
+            .take(FEW_SHOT_BODY_CHARS)
+            .collect();
+        block.push_str(&format!(
+            "From: {}\nSubject: {}\nBody: {}\nAnswer: {}\n\n",
+            example.from_email.as_deref().unwrap_or(""),
+            example.subject.as_deref().unwrap_or(""),
+            body,
+            answer(example)
+        ));
+    }
+
+    if template.contains("{{examples}}") {
+        template.replace("{{examples}}", &block)
+    } else {
+        format!("{}{}", block, template)
+    }
+}
+
+/// User an email's model runs are counted for. Emails of evaluation runs have
+/// no user and aren't counted.
+fn billed_user(email: &EmailData) -> Option<&str> {
+    Some(email.user_id.as_str()).filter(|id| !id.is_empty())
+}
+
+/// The user's review corrections, or none when they can't be loaded
+async fn user_examples(env: &Env, user_id: &str) -> Vec<ReviewExample> {
+    match get_review_examples(env, user_id, FEW_SHOT_EXAMPLES).await {
+        Ok(examples) => examples,
+        Err(e) => {
+            console_log!("Failed to load review examples: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+pub async fn classify_email(
+    env: &Env,
+    email: &EmailData,
+) -> Result<(ClassificationResult, ModelSpec)> {
     let prompt_template = get_active_prompt(env, "classify").await?;
+    let examples = user_examples(env, &email.user_id).await;
+    let prompt_template = with_examples(
+        &prompt_template,
+        &examples,
+        |e| serde_json::json!({ "category": e.category, "confidence": 1.0 }),
+    );
+    classify_email_with_prompt(env, email, &prompt_template).await
+}
 
+/// Classify an email with the given prompt template instead of the active one
+pub async fn classify_email_with_prompt(
+    env: &Env,
+    email: &EmailData,
+    prompt_template: &str,
+) -> Result<(ClassificationResult, ModelSpec)> {
     let mut variables = HashMap::new();
     variables.insert("from_email", email.from.as_deref().unwrap_or(""));
     variables.insert("subject", email.subject.as_deref().unwrap_or(""));
@@ -121,50 +364,33 @@ pub async fn classify_email(env: &Env, email: &EmailData) -> Result<Classificati
             .unwrap_or(""),
     );
 
-    let prompt = substitute_variables(&prompt_template, &variables);
-    let response = match call_ai(env, &prompt).await {
-        Ok(r) => r,
-        Err(e) => {
-            console_log!("AI call failed: {}", e);
-            return Ok(ClassificationResult {
-                category: "other".to_string(),
-                confidence: 0.0,
-            });
-        }
-    };
-
-    // Extract JSON from response (may contain extra text)
-    let json_text = match extract_json_from_text(&response) {
-        Ok(j) => j,
-        Err(e) => {
-            console_log!("Failed to extract JSON: {} - Response: {}", e, response);
-            return Ok(ClassificationResult {
-                category: "other".to_string(),
-                confidence: 0.0,
-            });
-        }
-    };
-
-    // Parse JSON response, default to "other" with low confidence on failure
-    match serde_json::from_str::<ClassificationResult>(&json_text) {
-        Ok(result) => Ok(result),
-        Err(e) => {
-            console_log!("Failed to parse classification result: {} - JSON: {}", e, json_text);
-            Ok(ClassificationResult {
-                category: "other".to_string(),
-                confidence: 0.0,
-            })
-        }
-    }
+    let prompt = substitute_variables(prompt_template, &variables);
+    run_stage(env, billed_user(email), "classify", &prompt).await
 }
 
 pub async fn extract_info(
     env: &Env,
     email: &EmailData,
     category: &str,
-) -> Result<ExtractionResult> {
+) -> Result<(ExtractionResult, ModelSpec)> {
     let prompt_template = get_active_prompt(env, "extract").await?;
+    let examples = user_examples(env, &email.user_id).await;
+    let prompt_template = with_examples(&prompt_template, &examples, |e| {
+        e.fields
+            .as_deref()
+            .and_then(|f| serde_json::from_str(f).ok())
+            .unwrap_or_else(|| serde_json::json!({}))
+    });
+    extract_info_with_prompt(env, email, category, &prompt_template).await
+}
 
+/// Extract information with the given prompt template instead of the active one
+pub async fn extract_info_with_prompt(
+    env: &Env,
+    email: &EmailData,
+    category: &str,
+    prompt_template: &str,
+) -> Result<(ExtractionResult, ModelSpec)> {
     let mut variables = HashMap::new();
     variables.insert("category", category);
     variables.insert("from_email", email.from.as_deref().unwrap_or(""));
@@ -178,63 +404,15 @@ pub async fn extract_info(
             .unwrap_or(""),
     );
 
-    let prompt = substitute_variables(&prompt_template, &variables);
-    let response = match call_ai(env, &prompt).await {
-        Ok(r) => r,
-        Err(e) => {
-            console_log!("AI call failed: {}", e);
-            return Ok(ExtractionResult {
-                company: None,
-                job_title: None,
-                recruiter_name: None,
-                recruiter_email: None,
-                interview_date: None,
-                location: None,
-                remote: None,
-            });
-        }
-    };
-
-    // Extract JSON from response (may contain extra text)
-    let json_text = match extract_json_from_text(&response) {
-        Ok(j) => j,
-        Err(e) => {
-            console_log!("Failed to extract JSON: {} - Response: {}", e, response);
-            return Ok(ExtractionResult {
-                company: None,
-                job_title: None,
-                recruiter_name: None,
-                recruiter_email: None,
-                interview_date: None,
-                location: None,
-                remote: None,
-            });
-        }
-    };
-
-    // Parse JSON response, default to empty result on failure
-    match serde_json::from_str::<ExtractionResult>(&json_text) {
-        Ok(result) => Ok(result),
-        Err(e) => {
-            console_log!("Failed to parse extraction result: {} - JSON: {}", e, json_text);
-            Ok(ExtractionResult {
-                company: None,
-                job_title: None,
-                recruiter_name: None,
-                recruiter_email: None,
-                interview_date: None,
-                location: None,
-                remote: None,
-            })
-        }
-    }
+    let prompt = substitute_variables(prompt_template, &variables);
+    run_stage(env, billed_user(email), "extract", &prompt).await
 }
 
 pub async fn summarize_email(
     env: &Env,
     email: &EmailData,
     category: &str,
-) -> Result<SummarizationResult> {
+) -> Result<(SummarizationResult, ModelSpec)> {
     let prompt_template = get_active_prompt(env, "summarize").await?;
 
     let mut variables = HashMap::new();
@@ -251,71 +429,337 @@ pub async fn summarize_email(
     );
 
     let prompt = substitute_variables(&prompt_template, &variables);
-    let response = match call_ai(env, &prompt).await {
-        Ok(r) => r,
-        Err(e) => {
-            console_log!("AI call failed: {}", e);
-            return Ok(SummarizationResult {
-                summary: "Unable to generate summary".to_string(),
-            });
-        }
-    };
+    run_stage(env, billed_user(email), "summarize", &prompt).await
+}
 
-    // Extract JSON from response (may contain extra text)
-    let json_text = match extract_json_from_text(&response) {
-        Ok(j) => j,
-        Err(e) => {
-            console_log!("Failed to extract JSON: {} - Response: {}", e, response);
-            return Ok(SummarizationResult {
-                summary: "Unable to generate summary".to_string(),
-            });
-        }
-    };
+/// Whether emails are processed with one call to the combined prompt instead
+/// of a call per stage, set with `AI_PROCESSING_MODE = "combined"`
+fn combined_mode(env: &Env) -> bool {
+    env.var("AI_PROCESSING_MODE")
+        .map(|v| v.to_string() == "combined")
+        .unwrap_or(false)
+}
 
-    // Parse JSON response, default to unclear summary on failure
-    match serde_json::from_str::<SummarizationResult>(&json_text) {
-        Ok(result) => Ok(result),
-        Err(e) => {
-            console_log!("Failed to parse summarization result: {} - JSON: {}", e, json_text);
-            Ok(SummarizationResult {
-                summary: "Unable to generate summary".to_string(),
-            })
-        }
+/// Classify, extract and summarize an email with a single call
+pub async fn process_combined(env: &Env, email: &EmailData) -> Result<(CombinedResult, ModelSpec)> {
+    let prompt_template = get_active_prompt(env, "combined").await?;
+    let examples = user_examples(env, &email.user_id).await;
+    let prompt_template = with_examples(&prompt_template, &examples, |e| {
+        let fields = e
+            .fields
+            .as_deref()
+            .and_then(|f| serde_json::from_str(f).ok())
+            .unwrap_or_else(|| serde_json::json!({}));
+        serde_json::json!({ "category": e.category, "confidence": 1.0, "extraction": fields })
+    });
+
+    let mut variables = HashMap::new();
+    variables.insert("from_email", email.from.as_deref().unwrap_or(""));
+    variables.insert("subject", email.subject.as_deref().unwrap_or(""));
+    variables.insert(
+        "body",
+        email
+            .body
+            .as_deref()
+            .or(email.snippet.as_deref())
+            .unwrap_or(""),
+    );
+
+    let prompt = substitute_variables(&prompt_template, &variables);
+    run_stage(env, billed_user(email), "combined", &prompt).await
+}
+
+/// Run an email through the processing stages, returning the result with the
+/// model each stage used, or the stage that failed along with the error
+async fn run_pipeline(
+    env: &Env,
+    email: &EmailData,
+    combined: bool,
+) -> std::result::Result<(CombinedResult, BTreeMap<String, String>), (&'static str, anyhow::Error)>
+{
+    let mut models = BTreeMap::new();
+
+    if combined {
+        let (result, model) = process_combined(env, email)
+            .await
+            .map_err(|e| ("combined", e))?;
+        models.insert("combined".to_string(), model.to_string());
+        return Ok((result, models));
+    }
+
+    let (classification, model) = classify_email(env, email)
+        .await
+        .map_err(|e| ("classify", e))?;
+    models.insert("classify".to_string(), model.to_string());
+    let (extraction, model) = extract_info(env, email, &classification.category)
+        .await
+        .map_err(|e| ("extract", e))?;
+    models.insert("extract".to_string(), model.to_string());
+    let (summarization, model) = summarize_email(env, email, &classification.category)
+        .await
+        .map_err(|e| ("summarize", e))?;
+    models.insert("summarize".to_string(), model.to_string());
+
+    Ok((
+        CombinedResult {
+            category: classification.category,
+            confidence: classification.confidence,
+            extraction,
+            summary: summarization.summary,
+        },
+        models,
+    ))
+}
+
+/// Draft an email for a job. Unlike the processing stages, failures are
+/// returned to the caller since the user is waiting on the draft.
+pub async fn draft_email(
+    env: &Env,
+    user_id: &str,
+    context: &DraftContext,
+    kind: &str,
+    instructions: Option<&str>,
+) -> Result<DraftResult> {
+    let prompt_template = get_active_prompt(env, "draft").await?;
+    let prompt = draft_prompt(&prompt_template, context, kind, instructions);
+    let response = call_ai(env, Some(user_id), "draft", &prompt).await?;
+
+    let json_text = extract_json_from_text(&response)?;
+    let mut result = serde_json::from_str::<DraftResult>(&json_text)
+        .map_err(|e| anyhow!("Failed to parse draft: {} - JSON: {}", e, json_text))?;
+
+    // Replies keep the thread's subject so Gmail groups them together
+    if let Some(subject) = context.reply_subject.as_deref() {
+        result.subject = reply_subject(subject);
+    }
+
+    Ok(result)
+}
+
+/// Fill the draft prompt template with the job, thread, contacts and notes
+pub fn draft_prompt(
+    template: &str,
+    context: &DraftContext,
+    kind: &str,
+    instructions: Option<&str>,
+) -> String {
+    let contacts = context.contacts.join("\n");
+    let thread = context.thread.join("\n\n");
+    let notes = context.notes.join("\n");
+
+    let mut variables = HashMap::new();
+    variables.insert("kind", kind);
+    variables.insert("user_name", context.user_name.as_str());
+    variables.insert("job_title", context.job_title.as_str());
+    variables.insert("company", context.company.as_str());
+    variables.insert("description", context.description.as_deref().unwrap_or(""));
+    variables.insert("contacts", contacts.as_str());
+    variables.insert("thread", thread.as_str());
+    variables.insert("notes", notes.as_str());
+    variables.insert("instructions", instructions.unwrap_or(""));
+
+    substitute_variables(template, &variables)
+}
+
+/// Subject of a reply: the original subject with a single "Re: " prefix
+pub fn reply_subject(subject: &str) -> String {
+    if subject.to_lowercase().starts_with("re:") {
+        subject.to_string()
+    } else {
+        format!("Re: {}", subject)
+    }
+}
+
+/// Write a cover letter and resume bullet suggestions for a job posting
+pub async fn tailor_documents(
+    env: &Env,
+    user_id: &str,
+    context: &TailorContext,
+    instructions: Option<&str>,
+) -> Result<TailorResult> {
+    let prompt_template = get_active_prompt(env, "tailor").await?;
+
+    let mut variables = HashMap::new();
+    variables.insert("user_name", context.user_name.as_str());
+    variables.insert("resume", context.resume.as_str());
+    variables.insert("job_title", context.job_title.as_str());
+    variables.insert("company", context.company.as_str());
+    variables.insert("description", context.description.as_str());
+    variables.insert("instructions", instructions.unwrap_or(""));
+
+    let prompt = substitute_variables(&prompt_template, &variables);
+    let response = call_ai(env, Some(user_id), "tailor", &prompt).await?;
+
+    let json_text = extract_json_from_text(&response)?;
+    let mut result = serde_json::from_str::<TailorResult>(&json_text)
+        .map_err(|e| anyhow!("Failed to parse documents: {} - JSON: {}", e, json_text))?;
+    if result.cover_letter.trim().is_empty() {
+        return Err(anyhow!("The cover letter is empty"));
     }
+    result.resume_bullets.retain(|b| !b.trim().is_empty());
+
+    Ok(result)
 }
 
-pub async fn process_email(env: &Env, email_id: &str, user_id: &str) -> Result<()> {
+/// Store a failed stage on the AI result and the email instead of made-up
+/// values, and hand the error back to the caller
+async fn record_failure(
+    env: &Env,
+    email_id: &str,
+    user_id: &str,
+    stage: &str,
+    error: anyhow::Error,
+    lineage: &ResultLineage,
+) -> Result<()> {
     use crate::common::uuid::generate_uuid;
-    use crate::services::db::{get_email_data, save_ai_result, update_email_ai_status};
+    use crate::services::db::{mark_email_ai_failed, save_failed_ai_result};
 
-    // Get email data
-    let email = get_email_data(env, email_id).await?;
+    let message = format!("{}", error);
+    console_log!(
+        "AI processing of {} failed at {}: {}",
+        email_id,
+        stage,
+        message
+    );
+
+    let result_id = generate_uuid()?;
+    save_failed_ai_result(env, &result_id, email_id, user_id, stage, &message, lineage).await?;
+    mark_email_ai_failed(env, email_id, &message).await?;
 
-    // Stage 1: Classify
-    let classification = classify_email(env, &email).await?;
-    let needs_review = classification.confidence < 0.7;
+    Err(anyhow!("The {} stage failed: {}", stage, message))
+}
+
+/// Classify, extract and summarize an email and save the result as its next
+/// version. `bypass_cache` runs the models even when an identical email has a
+/// cached result, and replaces that result.
+pub async fn process_email(
+    env: &Env,
+    email_id: &str,
+    user_id: &str,
+    bypass_cache: bool,
+) -> Result<()> {
+    use crate::common::uuid::generate_uuid;
+    use crate::services::db::{
+        get_active_prompts, get_cached_result, get_email_data, save_ai_result, save_cached_result,
+        update_email_ai_status, CachedResult,
+    };
 
-    // Stage 2: Extract
-    let extraction = extract_info(env, &email, &classification.category).await?;
+    // Get email data, refusing emails of other users than the caller named
+    let email = get_email_data(env, email_id).await?;
+    if email.user_id != user_id {
+        return Err(anyhow!("Email not found"));
+    }
 
-    // Stage 3: Summarize
-    let summarization = summarize_email(env, &email, &classification.category).await?;
+    let combined = combined_mode(env);
+    let stages: &[&str] = if combined {
+        &["combined"]
+    } else {
+        &PROCESSING_STAGES
+    };
+    let prompts = get_active_prompts(env, stages).await?;
+    let prompt_version = prompts
+        .iter()
+        .map(|p| format!("{}={}@{}", p.stage, p.id, p.updated_at))
+        .collect::<Vec<_>>()
+        .join(";");
+    let prompt_ids: BTreeMap<&str, &str> = prompts
+        .iter()
+        .map(|p| (p.stage.as_str(), p.id.as_str()))
+        .collect();
+    let mut lineage = ResultLineage {
+        prompt_ids: serde_json::to_string(&prompt_ids)?,
+        models: "{}".to_string(),
+    };
+    let content = normalize_content(
+        email.from.as_deref().unwrap_or(""),
+        email.subject.as_deref().unwrap_or(""),
+        email
+            .body
+            .as_deref()
+            .or(email.snippet.as_deref())
+            .unwrap_or(""),
+    );
+    // The stages add the user's corrections to the prompts, so they are part
+    // of the key too
+    let examples = user_examples(env, user_id).await;
+    let cache_key = content_hash(&cache_version(&prompt_version, &examples), &content);
+
+    // Identical emails processed with the same prompts reuse the result
+    let cached = if bypass_cache {
+        None
+    } else {
+        match get_cached_result(env, user_id, &cache_key).await {
+            Ok(cached) => cached,
+            Err(e) => {
+                console_log!("Failed to read the AI result cache: {}", e);
+                None
+            }
+        }
+    };
+    let result = match cached {
+        Some(cached) => {
+            console_log!("Using cached AI result for {}", email_id);
+            if let Some(models) = cached.models {
+                lineage.models = models;
+            }
+            CombinedResult {
+                category: cached.category,
+                confidence: cached.confidence,
+                extraction: serde_json::from_str(&cached.extracted_data).unwrap_or_default(),
+                summary: cached.summary,
+            }
+        }
+        None => {
+            // Every model run is counted as it's made, cached results don't
+            // count. Don't start a run the user can't finish, even when every
+            // stage needs all its fallbacks and repairs.
+            let mut max_runs = 0;
+            for stage in stages {
+                max_runs += max_stage_runs(get_model_settings(env, stage).await?.models.len());
+            }
+            get_quota(env, user_id, AI_CALLS)
+                .await?
+                .check(max_runs, now_secs())?;
+            let (result, models) = match run_pipeline(env, &email, combined).await {
+                Ok(output) => output,
+                // Running out of calls halfway isn't a failure of the email
+                Err((_, e)) if e.is::<QuotaExceeded>() => return Err(e),
+                Err((stage, e)) => {
+                    return record_failure(env, email_id, user_id, stage, e, &lineage).await
+                }
+            };
+            lineage.models = serde_json::to_string(&models)?;
+            let entry = CachedResult {
+                category: result.category.clone(),
+                confidence: result.confidence,
+                extracted_data: serde_json::to_string(&result.extraction)?,
+                summary: result.summary.clone(),
+                models: Some(lineage.models.clone()),
+            };
+            if let Err(e) = save_cached_result(env, user_id, &cache_key, &entry).await {
+                console_log!("Failed to cache AI result: {}", e);
+            }
+            result
+        }
+    };
+    let needs_review = result.confidence < 0.7;
 
     // Save results
     let result_id = generate_uuid()?;
-    let extracted_data_json = serde_json::to_string(&extraction)?;
+    let extracted_data_json = serde_json::to_string(&result.extraction)?;
 
     save_ai_result(
         env,
         &result_id,
         email_id,
         user_id,
-        Some(&classification.category),
-        Some(classification.confidence),
-        extraction.company.as_deref(),
-        extraction.job_title.as_deref(),
-        Some(&summarization.summary),
+        Some(&result.category),
+        Some(result.confidence),
+        result.extraction.company.as_deref(),
+        result.extraction.job_title.as_deref(),
+        Some(&result.summary),
         Some(&extracted_data_json),
+        &lineage,
     )
     .await?;
 
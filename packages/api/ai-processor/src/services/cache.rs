@@ -0,0 +1,68 @@
+//! Keys of the AI result cache. Emails with the same content, processed with
+//! the same prompt versions and the same few-shot examples, get the same key.
+
+use crate::services::db::ReviewExample;
+use sha2::{Digest, Sha256};
+
+fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Collapse whitespace runs and drop invisible characters, so emails that
+/// only differ in line wrapping or tracking characters are the same
+pub fn normalize_text(text: &str) -> String {
+    text.chars()
+        .filter(|c| {
+            !matches!(
+                c,
+                '\u{200b}'..='\u{200d}' | '\u{2060}' | '\u{feff}' | '\u{034f}' | '\u{00ad}'
+            )
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Normalised sender, subject and body, one per line
+pub fn normalize_content(from: &str, subject: &str, body: &str) -> String {
+    [from, subject, body]
+        .iter()
+        .map(|part| normalize_text(part))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Hex SHA-256 of the prompt version and normalised content
+pub fn content_hash(prompt_version: &str, normalized_content: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(prompt_version.as_bytes());
+    hasher.update([0u8]);
+    hasher.update(normalized_content.as_bytes());
+    hex(&hasher.finalize())
+}
+
+/// The prompt version extended with a digest of the user's review corrections,
+/// which are added to the prompts as few-shot examples. Results are only
+/// reused while the examples stay the same; users without any keep the plain
+/// prompt version.
+pub fn cache_version(prompt_version: &str, examples: &[ReviewExample]) -> String {
+    if examples.is_empty() {
+        return prompt_version.to_string();
+    }
+
+    let mut hasher = Sha256::new();
+    for example in examples {
+        for part in [
+            example.from_email.as_deref(),
+            example.subject.as_deref(),
+            example.body.as_deref(),
+            Some(example.category.as_str()),
+            example.fields.as_deref(),
+        ] {
+            hasher.update(part.unwrap_or("").as_bytes());
+            hasher.update([0u8]);
+        }
+    }
+    format!("{};examples={}", prompt_version, hex(&hasher.finalize()))
+}
@@ -1,4 +1,5 @@
 use crate::common::db::get_d1;
+use crate::services::models::ModelSettings;
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use worker::*;
@@ -49,6 +50,40 @@ pub async fn get_email_data(env: &Env, email_id: &str) -> Result<EmailData> {
     Ok(email_data)
 }
 
+/// Prompts and models that produced an AI result, as JSON objects keyed by stage
+#[derive(Debug, Default)]
+pub struct ResultLineage {
+    pub prompt_ids: String,
+    pub models: String,
+}
+
+/// Attempts at saving a result version before giving up, when concurrent runs
+/// of the same email keep taking the same version number
+const VERSION_ATTEMPTS: u32 = 3;
+
+/// Whether a D1 error is a UNIQUE constraint violation
+pub fn is_unique_violation(message: &str) -> bool {
+    message.contains("UNIQUE constraint failed")
+}
+
+/// Run an insert that numbers the email's next version as MAX(version) + 1.
+/// The UNIQUE(email_id, version) index rejects a number a concurrent insert
+/// already took, and the insert is run again to take the next one.
+async fn insert_version(statement: D1PreparedStatement) -> Result<()> {
+    let mut attempt = 1;
+    loop {
+        match statement.run().await {
+            Ok(_) => return Ok(()),
+            Err(e) if attempt < VERSION_ATTEMPTS && is_unique_violation(&e.to_string()) => {
+                attempt += 1;
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+/// Store an AI result as the next version for the email. Earlier versions are
+/// kept as its history.
 #[allow(clippy::too_many_arguments)]
 pub async fn save_ai_result(
     env: &Env,
@@ -61,123 +96,30 @@ pub async fn save_ai_result(
     job_title: Option<&str>,
     summary: Option<&str>,
     extracted_data: Option<&str>,
+    lineage: &ResultLineage,
 ) -> Result<()> {
     let db = get_d1(env)?;
 
-    // Handle optional fields - use NULL in SQL for None values
-    match (
-        category,
-        confidence,
-        company,
-        job_title,
-        summary,
-        extracted_data,
-    ) {
-        (Some(cat), Some(conf), Some(comp), Some(title), Some(sum), Some(ext)) => {
-            db.prepare(
-                "INSERT INTO ai_results (id, email_id, user_id, category, confidence, company, job_title, summary, extracted_data) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
-            )
-            .bind(&[
-                result_id.into(),
-                email_id.into(),
-                user_id.into(),
-                cat.into(),
-                conf.into(),
-                comp.into(),
-                title.into(),
-                sum.into(),
-                ext.into(),
-            ])?
-            .run()
-            .await?;
-        }
-        (Some(cat), Some(conf), Some(comp), Some(title), Some(sum), None) => {
-            db.prepare(
-                "INSERT INTO ai_results (id, email_id, user_id, category, confidence, company, job_title, summary, extracted_data) VALUES (?, ?, ?, ?, ?, ?, ?, ?, NULL)"
-            )
-            .bind(&[
-                result_id.into(),
-                email_id.into(),
-                user_id.into(),
-                cat.into(),
-                conf.into(),
-                comp.into(),
-                title.into(),
-                sum.into(),
-            ])?
-            .run()
-            .await?;
-        }
-        _ => {
-            // Handle other combinations - for simplicity, insert with what we have
-            let mut query = "INSERT INTO ai_results (id, email_id, user_id".to_string();
-            let mut values = "VALUES (?, ?, ?".to_string();
-            let mut bindings: Vec<worker::wasm_bindgen::JsValue> =
-                vec![result_id.into(), email_id.into(), user_id.into()];
-
-            if let Some(cat) = category {
-                query.push_str(", category");
-                values.push_str(", ?");
-                bindings.push(cat.into());
-            } else {
-                query.push_str(", category");
-                values.push_str(", NULL");
-            }
-
-            if let Some(conf) = confidence {
-                query.push_str(", confidence");
-                values.push_str(", ?");
-                bindings.push(conf.into());
-            } else {
-                query.push_str(", confidence");
-                values.push_str(", NULL");
-            }
-
-            if let Some(comp) = company {
-                query.push_str(", company");
-                values.push_str(", ?");
-                bindings.push(comp.into());
-            } else {
-                query.push_str(", company");
-                values.push_str(", NULL");
-            }
-
-            if let Some(title) = job_title {
-                query.push_str(", job_title");
-                values.push_str(", ?");
-                bindings.push(title.into());
-            } else {
-                query.push_str(", job_title");
-                values.push_str(", NULL");
-            }
-
-            if let Some(sum) = summary {
-                query.push_str(", summary");
-                values.push_str(", ?");
-                bindings.push(sum.into());
-            } else {
-                query.push_str(", summary");
-                values.push_str(", NULL");
-            }
-
-            if let Some(ext) = extracted_data {
-                query.push_str(", extracted_data");
-                values.push_str(", ?");
-                bindings.push(ext.into());
-            } else {
-                query.push_str(", extracted_data");
-                values.push_str(", NULL");
-            }
-
-            query.push_str(") ");
-            values.push(')');
-            query.push_str(&values);
-
-            db.prepare(&query).bind(&bindings)?.run().await?;
-        }
-    }
+    let statement = db.prepare(
+        "INSERT INTO ai_results (id, email_id, user_id, category, confidence, company, job_title, summary, extracted_data, prompt_ids, models, version)
+         SELECT ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, COALESCE(MAX(version), 0) + 1 FROM ai_results WHERE email_id = ?",
+    )
+    .bind(&[
+        result_id.into(),
+        email_id.into(),
+        user_id.into(),
+        category.into(),
+        confidence.into(),
+        company.into(),
+        job_title.into(),
+        summary.into(),
+        extracted_data.into(),
+        lineage.prompt_ids.as_str().into(),
+        lineage.models.as_str().into(),
+        email_id.into(),
+    ])?;
 
-    Ok(())
+    insert_version(statement).await
 }
 
 pub async fn update_email_ai_status(
@@ -188,7 +130,7 @@ pub async fn update_email_ai_status(
 ) -> Result<()> {
     let db = get_d1(env)?;
 
-    db.prepare("UPDATE emails SET ai_processed = ?, needs_review = ? WHERE gmail_id = ?")
+    db.prepare("UPDATE emails SET ai_processed = ?, needs_review = ?, ai_status = 'processed', ai_error = NULL WHERE gmail_id = ?")
         .bind(&[
             (if ai_processed { 1 } else { 0 }).into(),
             (if needs_review { 1 } else { 0 }).into(),
@@ -200,6 +142,52 @@ pub async fn update_email_ai_status(
     Ok(())
 }
 
+/// Record a processing run that failed at a stage. The row has no category
+/// or fields so nothing downstream mistakes it for a real result.
+pub async fn save_failed_ai_result(
+    env: &Env,
+    result_id: &str,
+    email_id: &str,
+    user_id: &str,
+    stage: &str,
+    error: &str,
+    lineage: &ResultLineage,
+) -> Result<()> {
+    let db = get_d1(env)?;
+
+    let statement = db.prepare(
+        "INSERT INTO ai_results (id, email_id, user_id, status, failed_stage, error, prompt_ids, models, version)
+         SELECT ?, ?, ?, 'failed', ?, ?, ?, ?, COALESCE(MAX(version), 0) + 1 FROM ai_results WHERE email_id = ?",
+    )
+    .bind(&[
+        result_id.into(),
+        email_id.into(),
+        user_id.into(),
+        stage.into(),
+        error.into(),
+        lineage.prompt_ids.as_str().into(),
+        lineage.models.as_str().into(),
+        email_id.into(),
+    ])?;
+
+    insert_version(statement).await
+}
+
+/// Mark an email as processed with a failure, so it isn't picked up again
+/// until it's reprocessed
+pub async fn mark_email_ai_failed(env: &Env, email_id: &str, error: &str) -> Result<()> {
+    let db = get_d1(env)?;
+
+    db.prepare(
+        "UPDATE emails SET ai_processed = 1, needs_review = 0, ai_status = 'failed', ai_error = ? WHERE gmail_id = ?",
+    )
+    .bind(&[error.into(), email_id.into()])?
+    .run()
+    .await?;
+
+    Ok(())
+}
+
 pub async fn get_active_prompt(env: &Env, stage: &str) -> Result<String> {
     let db = get_d1(env)?;
 
@@ -216,3 +204,494 @@ pub async fn get_active_prompt(env: &Env, stage: &str) -> Result<String> {
         .ok_or_else(|| anyhow!("Invalid prompt format"))?;
     Ok(prompt.to_string())
 }
+
+/// The active prompt of a stage, identified by id and activation time
+#[derive(Debug, Deserialize)]
+pub struct ActivePrompt {
+    pub stage: String,
+    pub id: String,
+    pub updated_at: String,
+}
+
+/// Active prompts of the given stages, in the same order
+pub async fn get_active_prompts(env: &Env, stages: &[&str]) -> Result<Vec<ActivePrompt>> {
+    let db = get_d1(env)?;
+
+    let mut prompts = Vec::with_capacity(stages.len());
+    for stage in stages {
+        let prompt = db
+            .prepare("SELECT stage, id, updated_at FROM ai_prompts WHERE stage = ? AND is_active = true LIMIT 1")
+            .bind(&[(*stage).into()])?
+            .first::<ActivePrompt>(None)
+            .await?
+            .ok_or_else(|| anyhow!("No active prompt found for stage: {}", stage))?;
+        prompts.push(prompt);
+    }
+
+    Ok(prompts)
+}
+
+/// Days a cached AI result is reused for; the main worker's cron deletes older
+/// ones
+const RESULT_CACHE_DAYS: u32 = 30;
+
+/// A cached processing result
+#[derive(Debug, Deserialize)]
+pub struct CachedResult {
+    pub category: String,
+    pub confidence: f64,
+    pub extracted_data: String,
+    pub summary: String,
+    /// Models that produced the result, as a JSON object keyed by stage
+    #[serde(default)]
+    pub models: Option<String>,
+}
+
+pub async fn get_cached_result(
+    env: &Env,
+    user_id: &str,
+    content_hash: &str,
+) -> Result<Option<CachedResult>> {
+    let db = get_d1(env)?;
+
+    let result = db
+        .prepare(
+            "SELECT category, confidence, extracted_data, summary, models FROM ai_result_cache
+             WHERE user_id = ? AND content_hash = ? AND created_at >= datetime('now', ?)",
+        )
+        .bind(&[
+            user_id.into(),
+            content_hash.into(),
+            format!("-{} days", RESULT_CACHE_DAYS).into(),
+        ])?
+        .first::<CachedResult>(None)
+        .await?;
+
+    Ok(result)
+}
+
+pub async fn save_cached_result(
+    env: &Env,
+    user_id: &str,
+    content_hash: &str,
+    result: &CachedResult,
+) -> Result<()> {
+    let db = get_d1(env)?;
+
+    db.prepare(
+        "INSERT OR REPLACE INTO ai_result_cache (user_id, content_hash, category, confidence, extracted_data, summary, models)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&[
+        user_id.into(),
+        content_hash.into(),
+        result.category.clone().into(),
+        result.confidence.into(),
+        result.extracted_data.clone().into(),
+        result.summary.clone().into(),
+        result.models.as_deref().into(),
+    ])?
+    .run()
+    .await?;
+
+    Ok(())
+}
+
+/// Models and sampling settings of the active prompt of a stage. Stages
+/// without an active prompt use the defaults.
+pub async fn get_model_settings(env: &Env, stage: &str) -> Result<ModelSettings> {
+    let db = get_d1(env)?;
+
+    let result = db
+        .prepare(
+            "SELECT models, temperature, max_tokens FROM ai_prompts WHERE stage = ? AND is_active = true LIMIT 1",
+        )
+        .bind(&[stage.into()])?
+        .first::<serde_json::Value>(None)
+        .await?;
+
+    let Some(row) = result else {
+        return Ok(ModelSettings::default());
+    };
+    ModelSettings::from_columns(
+        row.get("models").and_then(|v| v.as_str()),
+        row.get("temperature").and_then(|v| v.as_f64()),
+        // D1 hands integers over as JS numbers
+        row.get("max_tokens")
+            .and_then(|v| v.as_f64())
+            .map(|v| v as u32),
+    )
+    .map_err(|e| anyhow!("Invalid model settings for stage {}: {}", stage, e))
+}
+
+/// A corrected review of the user's, used as a few-shot example
+#[derive(Debug, Deserialize)]
+pub struct ReviewExample {
+    pub from_email: Option<String>,
+    pub subject: Option<String>,
+    pub body: Option<String>,
+    pub category: String,
+    pub fields: Option<String>,
+}
+
+/// The user's most recent corrections of AI results
+pub async fn get_review_examples(
+    env: &Env,
+    user_id: &str,
+    limit: u32,
+) -> Result<Vec<ReviewExample>> {
+    let db = get_d1(env)?;
+
+    let result = db
+        .prepare(
+            "SELECT from_email, subject, body, category, fields FROM ai_reviews
+             WHERE user_id = ? AND status = 'corrected'
+             ORDER BY created_at DESC LIMIT ?",
+        )
+        .bind(&[user_id.into(), limit.into()])?
+        .all()
+        .await?;
+
+    Ok(result.results::<ReviewExample>()?)
+}
+
+/// Everything the draft stage knows about a job
+#[derive(Debug, Default)]
+pub struct DraftContext {
+    pub user_name: String,
+    pub job_title: String,
+    pub company: String,
+    pub description: Option<String>,
+    /// "Name <email>" of the people the user has emailed about the job,
+    /// followed by the user's notes on them
+    pub contacts: Vec<String>,
+    /// "From / Subject / Snippet" of the thread's emails, newest first
+    pub thread: Vec<String>,
+    /// The user's comments on the job, newest first
+    pub notes: Vec<String>,
+    /// Subject of the email being replied to
+    pub reply_subject: Option<String>,
+}
+
+fn text(row: &serde_json::Value, key: &str) -> Option<String> {
+    row.get(key)
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Address part of a "Name <email>" header value, lowercased
+fn address(field: &str) -> String {
+    match (field.rfind('<'), field.rfind('>')) {
+        (Some(open), Some(close)) if open < close => &field[open + 1..close],
+        _ => field,
+    }
+    .trim()
+    .to_lowercase()
+}
+
+/// Maximum number of emails from the thread included in a draft prompt
+const DRAFT_THREAD_LIMIT: u32 = 5;
+
+/// Addresses looked up per contacts query
+const MAX_BOUND_ADDRESSES: usize = 90;
+
+/// "From / Subject / Snippet" entries of a thread's emails, and the addresses
+/// on them other than the user's, in order of first appearance
+pub fn thread_entries(
+    emails: &[serde_json::Value],
+    user_email: Option<&str>,
+) -> (Vec<String>, Vec<String>) {
+    let mut thread = Vec::new();
+    let mut addresses: Vec<String> = Vec::new();
+    for email in emails {
+        thread.push(format!(
+            "From: {}\nSubject: {}\n{}",
+            text(email, "from").unwrap_or_default(),
+            text(email, "subject").unwrap_or_default(),
+            text(email, "snippet").unwrap_or_default()
+        ));
+        for field in ["from", "to", "cc"] {
+            for entry in text(email, field).unwrap_or_default().split(',') {
+                let entry = address(entry);
+                if entry.contains('@')
+                    && Some(entry.as_str()) != user_email
+                    && !addresses.contains(&entry)
+                {
+                    addresses.push(entry);
+                }
+            }
+        }
+    }
+    (thread, addresses)
+}
+
+/// "Name <email> - notes" lines for the addresses of a thread from the user's
+/// saved contacts. System senders are left out; addresses without a contact
+/// are listed bare.
+pub fn contact_lines(addresses: &[String], contacts: &[serde_json::Value]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for email in addresses {
+        let contact = contacts
+            .iter()
+            .find(|c| text(c, "email").map(|e| e.to_lowercase()).as_ref() == Some(email));
+        let is_system = contact
+            .and_then(|c| c.get("is_system"))
+            .and_then(|v| v.as_i64())
+            .unwrap_or(0)
+            == 1;
+        if is_system {
+            continue;
+        }
+        let mut line = match contact.and_then(|c| text(c, "name")) {
+            Some(name) => format!("{} <{}>", name, email),
+            None => email.clone(),
+        };
+        if let Some(notes) = contact.and_then(|c| text(c, "notes")) {
+            line.push_str(&format!(" - {}", notes));
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Load the job, its email thread, contacts and the user's notes for drafting
+/// an email. The thread is the one of `reply_to_email_id` when given, and the
+/// job's most recent emails otherwise.
+pub async fn get_draft_context(
+    env: &Env,
+    job_id: &str,
+    user_id: &str,
+    reply_to_email_id: Option<&str>,
+) -> Result<DraftContext> {
+    let db = get_d1(env)?;
+
+    let job = db
+        .prepare("SELECT title, company, description FROM jobs WHERE id = ? AND deleted_at IS NULL")
+        .bind(&[job_id.into()])?
+        .first::<serde_json::Value>(None)
+        .await?
+        .ok_or_else(|| anyhow!("Job not found"))?;
+
+    let user = db
+        .prepare("SELECT name, email FROM users WHERE id = ?")
+        .bind(&[user_id.into()])?
+        .first::<serde_json::Value>(None)
+        .await?
+        .ok_or_else(|| anyhow!("User not found"))?;
+    let user_email = text(&user, "email").map(|e| e.to_lowercase());
+
+    let mut context = DraftContext {
+        user_name: text(&user, "name").unwrap_or_else(|| "the candidate".to_string()),
+        job_title: text(&job, "title").unwrap_or_default(),
+        company: text(&job, "company").unwrap_or_default(),
+        description: text(&job, "description"),
+        ..Default::default()
+    };
+
+    let emails = match reply_to_email_id {
+        Some(email_id) => {
+            let parent = db
+                .prepare("SELECT thread_id, subject FROM emails WHERE gmail_id = ? AND user_id = ?")
+                .bind(&[email_id.into(), user_id.into()])?
+                .first::<serde_json::Value>(None)
+                .await?
+                .ok_or_else(|| anyhow!("Email not found"))?;
+            context.reply_subject = text(&parent, "subject");
+
+            db.prepare(
+                "SELECT \"from\", \"to\", cc, subject, snippet FROM emails WHERE user_id = ? AND thread_id = ? ORDER BY date DESC LIMIT ?",
+            )
+            .bind(&[
+                user_id.into(),
+                text(&parent, "thread_id").unwrap_or_default().into(),
+                DRAFT_THREAD_LIMIT.into(),
+            ])?
+            .all()
+            .await?
+            .results::<serde_json::Value>()?
+        }
+        None => db
+            .prepare(
+                "SELECT \"from\", \"to\", cc, subject, snippet FROM emails WHERE user_id = ? AND job_id = ? ORDER BY date DESC LIMIT ?",
+            )
+            .bind(&[user_id.into(), job_id.into(), DRAFT_THREAD_LIMIT.into()])?
+            .all()
+            .await?
+            .results::<serde_json::Value>()?,
+    };
+
+    let (thread, addresses) = thread_entries(&emails, user_email.as_deref());
+    context.thread = thread;
+
+    // One lookup for every address, chunked to stay under D1's bound
+    // parameter limit
+    let mut contacts = Vec::new();
+    for chunk in addresses.chunks(MAX_BOUND_ADDRESSES) {
+        let placeholders = vec!["?"; chunk.len()].join(", ");
+        let mut bindings: Vec<worker::wasm_bindgen::JsValue> = vec![user_id.into()];
+        bindings.extend(chunk.iter().map(|a| a.as_str().into()));
+        let rows = db
+            .prepare(format!(
+                "SELECT email, name, notes, is_system FROM email_contacts WHERE user_id = ? AND lower(email) IN ({})",
+                placeholders
+            ))
+            .bind(&bindings)?
+            .all()
+            .await?
+            .results::<serde_json::Value>()?;
+        contacts.extend(rows);
+    }
+    context.contacts = contact_lines(&addresses, &contacts);
+
+    context.notes = db
+        .prepare(
+            "SELECT content FROM job_comments WHERE job_id = ? AND user_id = ? AND deleted_at IS NULL ORDER BY created_at DESC",
+        )
+        .bind(&[job_id.into(), user_id.into()])?
+        .all()
+        .await?
+        .results::<serde_json::Value>()?
+        .iter()
+        .filter_map(|row| text(row, "content"))
+        .collect();
+
+    Ok(context)
+}
+
+/// The job posting and the user's resume, for tailoring documents
+#[derive(Debug)]
+pub struct TailorContext {
+    pub user_name: String,
+    pub resume: String,
+    pub job_title: String,
+    pub company: String,
+    pub description: String,
+}
+
+/// A cover letter and resume suggestions saved against a job
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JobDocument {
+    pub id: String,
+    pub job_id: String,
+    pub version: i64,
+    pub cover_letter: String,
+    pub resume_suggestions: Vec<String>,
+    pub created_at: String,
+}
+
+/// Why a job can't be tailored for
+#[derive(Debug, Clone, PartialEq)]
+pub enum TailorContextError {
+    JobNotFound,
+    UserNotFound,
+    MissingResume,
+    MissingDescription,
+}
+
+impl TailorContextError {
+    /// HTTP status the error is reported with
+    pub fn status(&self) -> u16 {
+        match self {
+            Self::JobNotFound | Self::UserNotFound => 404,
+            Self::MissingResume | Self::MissingDescription => 400,
+        }
+    }
+}
+
+impl std::fmt::Display for TailorContextError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::JobNotFound => "Job not found",
+            Self::UserNotFound => "User not found",
+            Self::MissingResume => "Add your resume in account settings first",
+            Self::MissingDescription => "Add the job description first",
+        })
+    }
+}
+
+impl std::error::Error for TailorContextError {}
+
+/// Build the tailoring context from the job and user rows. The resume and the
+/// job description are both required.
+pub fn tailor_context(
+    job: Option<&serde_json::Value>,
+    user: Option<&serde_json::Value>,
+) -> std::result::Result<TailorContext, TailorContextError> {
+    let job = job.ok_or(TailorContextError::JobNotFound)?;
+    let user = user.ok_or(TailorContextError::UserNotFound)?;
+
+    Ok(TailorContext {
+        user_name: text(user, "name").unwrap_or_else(|| "the candidate".to_string()),
+        resume: text(user, "resume").ok_or(TailorContextError::MissingResume)?,
+        job_title: text(job, "title").unwrap_or_default(),
+        company: text(job, "company").unwrap_or_default(),
+        description: text(job, "description").ok_or(TailorContextError::MissingDescription)?,
+    })
+}
+
+/// Load the job posting and the user's resume. Fails with a
+/// `TailorContextError` when either is missing.
+pub async fn get_tailor_context(env: &Env, job_id: &str, user_id: &str) -> Result<TailorContext> {
+    let db = get_d1(env)?;
+
+    let job = db
+        .prepare("SELECT title, company, description FROM jobs WHERE id = ? AND deleted_at IS NULL")
+        .bind(&[job_id.into()])?
+        .first::<serde_json::Value>(None)
+        .await?;
+
+    let user = db
+        .prepare("SELECT name, resume FROM users WHERE id = ?")
+        .bind(&[user_id.into()])?
+        .first::<serde_json::Value>(None)
+        .await?;
+
+    Ok(tailor_context(job.as_ref(), user.as_ref())?)
+}
+
+/// Save a cover letter and resume suggestions as the next version for the job
+pub async fn save_job_document(
+    env: &Env,
+    id: &str,
+    job_id: &str,
+    user_id: &str,
+    cover_letter: &str,
+    resume_suggestions: &[String],
+) -> Result<JobDocument> {
+    let db = get_d1(env)?;
+    let suggestions_json = serde_json::to_string(resume_suggestions)?;
+
+    // Number the version in the insert itself so concurrent saves can't share one
+    db.prepare(
+        "INSERT INTO job_documents (id, job_id, user_id, version, cover_letter, resume_suggestions)
+         SELECT ?, ?, ?, COALESCE(MAX(version), 0) + 1, ?, ? FROM job_documents WHERE job_id = ? AND user_id = ?",
+    )
+    .bind(&[
+        id.into(),
+        job_id.into(),
+        user_id.into(),
+        cover_letter.into(),
+        suggestions_json.into(),
+        job_id.into(),
+        user_id.into(),
+    ])?
+    .run()
+    .await?;
+
+    let row = db
+        .prepare("SELECT version, created_at FROM job_documents WHERE id = ?")
+        .bind(&[id.into()])?
+        .first::<serde_json::Value>(None)
+        .await?
+        .ok_or_else(|| anyhow!("Failed to save document"))?;
+
+    Ok(JobDocument {
+        id: id.to_string(),
+        job_id: job_id.to_string(),
+        version: row.get("version").and_then(|v| v.as_i64()).unwrap_or(1),
+        cover_letter: cover_letter.to_string(),
+        resume_suggestions: resume_suggestions.to_vec(),
+        created_at: text(&row, "created_at").unwrap_or_default(),
+    })
+}
@@ -1,2 +1,5 @@
 pub mod ai;
+pub mod cache;
 pub mod db;
+pub mod models;
+pub mod schema;
@@ -0,0 +1,186 @@
+//! Model settings of a prompt stage and the fallback chain for AI calls
+
+use anyhow::{anyhow, Result};
+use api_shared::quota::QuotaExceeded;
+use serde_json::{json, Value};
+
+/// Model used when a prompt doesn't name one
+pub const DEFAULT_MODEL: &str = "@cf/meta/llama-3.1-8b-instruct-fast";
+pub const DEFAULT_MAX_TOKENS: u32 = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    /// The Workers AI binding
+    WorkersAi,
+    /// An OpenAI-compatible chat completions endpoint at `OPENAI_BASE_URL`
+    OpenAi,
+}
+
+impl Provider {
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Provider::WorkersAi => "workers-ai",
+            Provider::OpenAi => "openai",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelSpec {
+    pub provider: Provider,
+    pub model: String,
+}
+
+impl ModelSpec {
+    /// Parse `workers-ai:<model>` or `openai:<model>`. Anything without a
+    /// known provider prefix is a Workers AI model, so `llama3.1:8b` needs
+    /// to be written `openai:llama3.1:8b`.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        let (provider, model) = match spec.split_once(':') {
+            Some(("workers-ai", model)) => (Provider::WorkersAi, model),
+            Some(("openai", model)) => (Provider::OpenAi, model),
+            _ => (Provider::WorkersAi, spec),
+        };
+        let model = model.trim();
+        if model.is_empty() {
+            return Err(anyhow!("Model name is missing in: {}", spec));
+        }
+        Ok(ModelSpec {
+            provider,
+            model: model.to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for ModelSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.provider.prefix(), self.model)
+    }
+}
+
+/// Models and sampling settings of a prompt stage
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelSettings {
+    /// Models tried in order until one returns text
+    pub models: Vec<ModelSpec>,
+    pub temperature: Option<f64>,
+    pub max_tokens: u32,
+}
+
+impl Default for ModelSettings {
+    fn default() -> Self {
+        ModelSettings {
+            models: vec![ModelSpec {
+                provider: Provider::WorkersAi,
+                model: DEFAULT_MODEL.to_string(),
+            }],
+            temperature: None,
+            max_tokens: DEFAULT_MAX_TOKENS,
+        }
+    }
+}
+
+impl ModelSettings {
+    /// Settings from the `models`, `temperature` and `max_tokens` columns of
+    /// an `ai_prompts` row. `models` is a JSON array of model specs; missing
+    /// values fall back to the defaults.
+    pub fn from_columns(
+        models: Option<&str>,
+        temperature: Option<f64>,
+        max_tokens: Option<u32>,
+    ) -> Result<Self> {
+        let defaults = ModelSettings::default();
+
+        let models = match models.map(str::trim).filter(|m| !m.is_empty()) {
+            Some(json_text) => {
+                let specs: Vec<String> = serde_json::from_str(json_text)
+                    .map_err(|e| anyhow!("models must be a JSON array of strings: {}", e))?;
+                let parsed = specs
+                    .iter()
+                    .map(|spec| ModelSpec::parse(spec))
+                    .collect::<Result<Vec<_>>>()?;
+                if parsed.is_empty() {
+                    defaults.models
+                } else {
+                    parsed
+                }
+            }
+            None => defaults.models,
+        };
+
+        Ok(ModelSettings {
+            models,
+            temperature,
+            max_tokens: max_tokens.filter(|t| *t > 0).unwrap_or(defaults.max_tokens),
+        })
+    }
+
+    /// Chat input for a single user prompt. The OpenAI backend adds `model`.
+    pub fn chat_input(&self, prompt: &str) -> Value {
+        let mut input = json!({
+            "messages": [{ "role": "user", "content": prompt }],
+            "max_tokens": self.max_tokens,
+        });
+        if let Some(temperature) = self.temperature {
+            input["temperature"] = json!(temperature);
+        }
+        input
+    }
+}
+
+/// Text of a chat completion: a bare string or `response`/`result` from
+/// Workers AI, or `choices[0].message.content` from OpenAI-compatible servers
+pub fn response_text(output: &Value) -> Option<String> {
+    output
+        .as_str()
+        .or_else(|| output.get("response")?.as_str())
+        .or_else(|| output.get("result")?.as_str())
+        .or_else(|| {
+            output
+                .get("choices")?
+                .get(0)?
+                .get("message")?
+                .get("content")?
+                .as_str()
+        })
+        .map(|s| s.to_string())
+}
+
+/// Something that can run a chat input against a model and return the raw
+/// output: the Workers AI binding and HTTP endpoints in the worker, or a stub
+/// in tests
+#[allow(async_fn_in_trait)]
+pub trait ModelBackend {
+    async fn run(&self, model: &ModelSpec, input: &Value) -> Result<Value>;
+}
+
+/// Try each model of the settings in order and return the first text with the
+/// model that produced it. Fails with every model's error when none works, or
+/// right away with a `QuotaExceeded`.
+pub async fn complete_with_fallback<B: ModelBackend>(
+    backend: &B,
+    settings: &ModelSettings,
+    prompt: &str,
+) -> Result<(String, ModelSpec)> {
+    if prompt.trim().is_empty() {
+        return Err(anyhow!("Prompt cannot be empty"));
+    }
+
+    let input = settings.chat_input(prompt);
+    let mut errors = Vec::new();
+
+    for model in &settings.models {
+        match backend.run(model, &input).await {
+            Ok(output) => match response_text(&output) {
+                Some(text) => return Ok((text, model.clone())),
+                None => errors.push(format!("{}: unexpected response format: {}", model, output)),
+            },
+            // No other model can run for a user who is out of calls
+            Err(e) if e.is::<QuotaExceeded>() => return Err(e),
+            Err(e) => errors.push(format!("{}: {}", model, e)),
+        }
+    }
+
+    Err(anyhow!("All models failed: {}", errors.join("; ")))
+}
@@ -0,0 +1,205 @@
+//! JSON schemas of the processing stages' output, a validator for the part of
+//! JSON Schema they use, and the prompt that asks a model to repair its answer
+
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+
+/// Categories the classify stage can answer with
+pub const CATEGORIES: [&str; 5] = [
+    "interview",
+    "rejection",
+    "new_job",
+    "application_sent",
+    "other",
+];
+
+/// Schema of a processing stage's output, if the stage has one
+pub fn stage_schema(stage: &str) -> Option<Value> {
+    let nullable_string = json!({ "type": ["string", "null"] });
+    match stage {
+        "classify" => Some(json!({
+            "type": "object",
+            "required": ["category", "confidence"],
+            "properties": {
+                "category": { "type": "string", "enum": CATEGORIES },
+                "confidence": { "type": "number", "minimum": 0, "maximum": 1 }
+            }
+        })),
+        "extract" => Some(json!({
+            "type": "object",
+            "properties": {
+                "company": nullable_string,
+                "job_title": nullable_string,
+                "recruiter_name": nullable_string,
+                "recruiter_email": nullable_string,
+                "interview_date": nullable_string,
+                "location": nullable_string,
+                "remote": { "type": ["boolean", "null"] }
+            }
+        })),
+        "summarize" => Some(json!({
+            "type": "object",
+            "required": ["summary"],
+            "properties": {
+                "summary": { "type": "string", "minLength": 1 }
+            }
+        })),
+        // All three stages in one answer, with the extract output nested
+        "combined" => {
+            let classify = stage_schema("classify")?;
+            let summarize = stage_schema("summarize")?;
+            Some(json!({
+                "type": "object",
+                "required": ["category", "confidence", "summary"],
+                "properties": {
+                    "category": classify["properties"]["category"],
+                    "confidence": classify["properties"]["confidence"],
+                    "extraction": stage_schema("extract")?,
+                    "summary": summarize["properties"]["summary"]
+                }
+            }))
+        }
+        _ => None,
+    }
+}
+
+/// The JSON object in a model response, which may have text around it
+pub fn extract_json_from_text(text: &str) -> Result<String> {
+    let start = text
+        .find('{')
+        .ok_or_else(|| anyhow!("No JSON object found in response: {}", text))?;
+    let end = text
+        .rfind('}')
+        .ok_or_else(|| anyhow!("No closing brace found in JSON: {}", text))?;
+    if end < start {
+        return Err(anyhow!("No JSON object found in response: {}", text));
+    }
+    Ok(text[start..=end].to_string())
+}
+
+fn type_matches(expected: &str, value: &Value) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => false,
+    }
+}
+
+fn validate_at(schema: &Value, value: &Value, path: &str, errors: &mut Vec<String>) {
+    let at = if path.is_empty() { "output" } else { path };
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(|t| t.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        if !types.iter().any(|t| type_matches(t, value)) {
+            errors.push(format!(
+                "{} must be of type {}, got {}",
+                at,
+                types.join(" or "),
+                value
+            ));
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(|e| e.as_array()) {
+        if !allowed.contains(value) {
+            let allowed: Vec<String> = allowed.iter().map(|a| a.to_string()).collect();
+            errors.push(format!(
+                "{} must be one of {}, got {}",
+                at,
+                allowed.join(", "),
+                value
+            ));
+        }
+    }
+
+    if let Some(number) = value.as_f64() {
+        if let Some(minimum) = schema.get("minimum").and_then(|m| m.as_f64()) {
+            if number < minimum {
+                errors.push(format!(
+                    "{} must be at least {}, got {}",
+                    at, minimum, number
+                ));
+            }
+        }
+        if let Some(maximum) = schema.get("maximum").and_then(|m| m.as_f64()) {
+            if number > maximum {
+                errors.push(format!(
+                    "{} must be at most {}, got {}",
+                    at, maximum, number
+                ));
+            }
+        }
+    }
+
+    if let (Some(text), Some(min_length)) = (
+        value.as_str(),
+        schema.get("minLength").and_then(|m| m.as_u64()),
+    ) {
+        if (text.trim().chars().count() as u64) < min_length {
+            errors.push(format!("{} must not be empty", at));
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        if let Some(required) = schema.get("required").and_then(|r| r.as_array()) {
+            for key in required.iter().filter_map(|k| k.as_str()) {
+                if !object.contains_key(key) {
+                    errors.push(format!("{}.{} is required", at, key));
+                }
+            }
+        }
+        if let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) {
+            for (key, property_schema) in properties {
+                if let Some(property) = object.get(key) {
+                    validate_at(
+                        property_schema,
+                        property,
+                        &format!("{}.{}", at, key),
+                        errors,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Check a value against a schema, returning every problem found
+pub fn validate(schema: &Value, value: &Value) -> std::result::Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    validate_at(schema, value, "", &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Find, parse and validate the JSON object in a model response. The error
+/// explains what's wrong in words the model can act on.
+pub fn parse_output(schema: &Value, response: &str) -> std::result::Result<Value, String> {
+    let json_text = extract_json_from_text(response)
+        .map_err(|_| "The answer doesn't contain a JSON object.".to_string())?;
+    let value: Value = serde_json::from_str(&json_text)
+        .map_err(|e| format!("The answer isn't valid JSON: {}.", e))?;
+    validate(schema, &value)
+        .map_err(|errors| format!("The JSON doesn't match the schema: {}.", errors.join("; ")))?;
+    Ok(value)
+}
+
+/// Prompt asking the model to fix an answer that failed to parse or validate
+pub fn repair_prompt(prompt: &str, response: &str, error: &str, schema: &Value) -> String {
+    format!(
+        "{}\n\nYour previous answer was:\n{}\n\n{}\nReply with only a corrected JSON object matching this JSON schema, without any other text:\n{}",
+        prompt, response, error, schema
+    )
+}
@@ -0,0 +1,74 @@
+//! Tests for AI result cache keys
+
+use api_ai_processor::services::cache::{
+    cache_version, content_hash, normalize_content, normalize_text,
+};
+use api_ai_processor::services::db::ReviewExample;
+
+#[test]
+fn test_normalize_text_ignores_wrapping_and_invisible_characters() {
+    assert_eq!(
+        normalize_text("  Thanks for\r\n\r\napplying\u{200b} to   Acme!\t"),
+        "Thanks for applying to Acme!"
+    );
+    assert_eq!(normalize_text("\u{feff}\n "), "");
+}
+
+#[test]
+fn test_same_content_gets_same_hash() {
+    let a = normalize_content("jobs@acme.com", "Application received", "We got it.\n\nThanks");
+    let b = normalize_content(" jobs@acme.com", "Application  received", "We got it. Thanks ");
+    assert_eq!(a, b);
+    assert_eq!(content_hash("classify=v1", &a), content_hash("classify=v1", &b));
+
+    let hash = content_hash("classify=v1", &a);
+    assert_eq!(hash.len(), 64);
+    assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+}
+
+#[test]
+fn test_hash_changes_with_content_or_prompt_version() {
+    let content = normalize_content("jobs@acme.com", "Interview", "Monday at 10");
+    let hash = content_hash("classify=v1", &content);
+
+    assert_ne!(hash, content_hash("classify=v2", &content));
+    assert_ne!(
+        hash,
+        content_hash("classify=v1", &normalize_content("jobs@acme.com", "Interview", "Tuesday at 10"))
+    );
+    // Moving text between fields is a different email
+    assert_ne!(
+        normalize_content("a", "b c", ""),
+        normalize_content("a", "b", "c")
+    );
+}
+
+fn example(category: &str) -> ReviewExample {
+    ReviewExample {
+        from_email: Some("jobs@acme.com".to_string()),
+        subject: Some("Next steps".to_string()),
+        body: Some("Let's talk".to_string()),
+        category: category.to_string(),
+        fields: None,
+    }
+}
+
+#[test]
+fn test_cache_version_covers_review_examples() {
+    assert_eq!(cache_version("classify=v1", &[]), "classify=v1");
+
+    let interview = cache_version("classify=v1", &[example("interview")]);
+    assert!(interview.starts_with("classify=v1;examples="));
+    assert_eq!(
+        interview,
+        cache_version("classify=v1", &[example("interview")])
+    );
+    assert_ne!(
+        interview,
+        cache_version("classify=v1", &[example("rejection")])
+    );
+    assert_ne!(
+        interview,
+        cache_version("classify=v1", &[example("interview"), example("interview")])
+    );
+}
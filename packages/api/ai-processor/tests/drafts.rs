@@ -0,0 +1,88 @@
+//! Tests for building draft prompts and their context
+
+use api_ai_processor::services::ai::{draft_prompt, reply_subject};
+use api_ai_processor::services::db::{contact_lines, thread_entries, DraftContext};
+use serde_json::json;
+
+const USER: &str = "me@example.com";
+
+#[test]
+fn test_thread_entries_collect_other_addresses() {
+    let emails = vec![
+        json!({
+            "from": "Jane Recruiter <Jane@Acme.com>",
+            "to": "Me <me@example.com>",
+            "cc": "bob@acme.com, Hiring <hiring@acme.com>",
+            "subject": "Interview",
+            "snippet": "Are you free Tuesday?"
+        }),
+        json!({
+            "from": "me@example.com",
+            "to": "jane@acme.com",
+            "cc": null,
+            "subject": "Re: Interview",
+            "snippet": "Tuesday works"
+        }),
+    ];
+
+    let (thread, addresses) = thread_entries(&emails, Some(USER));
+    assert_eq!(
+        thread,
+        vec![
+            "From: Jane Recruiter <Jane@Acme.com>\nSubject: Interview\nAre you free Tuesday?",
+            "From: me@example.com\nSubject: Re: Interview\nTuesday works",
+        ]
+    );
+    assert_eq!(
+        addresses,
+        vec!["jane@acme.com", "bob@acme.com", "hiring@acme.com"]
+    );
+}
+
+#[test]
+fn test_contact_lines_use_saved_contacts() {
+    let addresses: Vec<String> = ["jane@acme.com", "noreply@acme.com", "bob@acme.com"]
+        .iter()
+        .map(|a| a.to_string())
+        .collect();
+    let contacts = vec![
+        json!({ "email": "jane@acme.com", "name": "Jane", "notes": "Met at the meetup", "is_system": 0 }),
+        json!({ "email": "noreply@acme.com", "name": null, "notes": null, "is_system": 1 }),
+    ];
+
+    assert_eq!(
+        contact_lines(&addresses, &contacts),
+        vec!["Jane <jane@acme.com> - Met at the meetup", "bob@acme.com"]
+    );
+}
+
+#[test]
+fn test_draft_prompt_fills_every_variable() {
+    let context = DraftContext {
+        user_name: "Alex".to_string(),
+        job_title: "Engineer".to_string(),
+        company: "Acme".to_string(),
+        description: None,
+        contacts: vec!["Jane <jane@acme.com>".to_string()],
+        thread: vec!["first".to_string(), "second".to_string()],
+        notes: vec!["Great team".to_string()],
+        reply_subject: None,
+    };
+    let template =
+        "{{kind}} from {{user_name}} for {{job_title}} at {{company}} [{{description}}]\n\
+                    {{contacts}}\n{{thread}}\n{{notes}}\n{{instructions}}";
+
+    assert_eq!(
+        draft_prompt(template, &context, "thank_you", Some("Keep it short")),
+        "thank_you from Alex for Engineer at Acme []\n\
+         Jane <jane@acme.com>\nfirst\n\nsecond\nGreat team\nKeep it short"
+    );
+    assert!(draft_prompt(template, &context, "follow_up", None).ends_with("Great team\n"));
+}
+
+#[test]
+fn test_reply_subject_adds_one_prefix() {
+    assert_eq!(reply_subject("Interview"), "Re: Interview");
+    assert_eq!(reply_subject("RE: Interview"), "RE: Interview");
+    assert_eq!(reply_subject("Re: Interview"), "Re: Interview");
+}
@@ -0,0 +1,213 @@
+//! Tests for model settings and the provider fallback chain
+
+use anyhow::{anyhow, Result};
+use api_ai_processor::services::models::{
+    complete_with_fallback, response_text, ModelBackend, ModelSettings, ModelSpec, Provider,
+    DEFAULT_MAX_TOKENS, DEFAULT_MODEL,
+};
+use api_ai_processor::services::ai::max_stage_runs;
+use api_shared::quota::{QuotaExceeded, AI_CALLS};
+use serde_json::{json, Value};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+/// Run a future that never waits, which holds for the stub backend
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    match future.as_mut().poll(&mut cx) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("stub future should not be pending"),
+    }
+}
+
+/// Backend returning canned outputs per model and recording the calls
+#[derive(Default)]
+struct StubBackend {
+    outputs: HashMap<String, Result<Value, String>>,
+    calls: RefCell<Vec<(String, Value)>>,
+}
+
+impl StubBackend {
+    fn with(mut self, model: &str, output: Result<Value, &str>) -> Self {
+        self.outputs
+            .insert(model.to_string(), output.map_err(|e| e.to_string()));
+        self
+    }
+
+    fn called_models(&self) -> Vec<String> {
+        self.calls.borrow().iter().map(|(m, _)| m.clone()).collect()
+    }
+}
+
+impl ModelBackend for StubBackend {
+    async fn run(&self, model: &ModelSpec, input: &Value) -> Result<Value> {
+        let key = model.to_string();
+        self.calls.borrow_mut().push((key.clone(), input.clone()));
+        match self.outputs.get(&key) {
+            Some(Ok(output)) => Ok(output.clone()),
+            Some(Err(e)) => Err(anyhow!("{}", e)),
+            None => Err(anyhow!("unknown model")),
+        }
+    }
+}
+
+fn settings(models: &[&str]) -> ModelSettings {
+    ModelSettings {
+        models: models.iter().map(|m| ModelSpec::parse(m).unwrap()).collect(),
+        ..ModelSettings::default()
+    }
+}
+
+#[test]
+fn test_parse_model_specs() {
+    let spec = ModelSpec::parse("openai:llama3.1:8b").unwrap();
+    assert_eq!(spec.provider, Provider::OpenAi);
+    assert_eq!(spec.model, "llama3.1:8b");
+
+    let spec = ModelSpec::parse("workers-ai:@cf/meta/llama-3.1-8b-instruct").unwrap();
+    assert_eq!(spec.provider, Provider::WorkersAi);
+    assert_eq!(spec.model, "@cf/meta/llama-3.1-8b-instruct");
+
+    let spec = ModelSpec::parse(" @cf/mistral/mistral-7b-instruct-v0.1 ").unwrap();
+    assert_eq!(spec.provider, Provider::WorkersAi);
+    assert_eq!(spec.to_string(), "workers-ai:@cf/mistral/mistral-7b-instruct-v0.1");
+
+    assert!(ModelSpec::parse("openai:").is_err());
+    assert!(ModelSpec::parse("  ").is_err());
+}
+
+#[test]
+fn test_settings_from_columns() {
+    let defaults = ModelSettings::from_columns(None, None, None).unwrap();
+    assert_eq!(defaults, ModelSettings::default());
+    assert_eq!(defaults.models[0].model, DEFAULT_MODEL);
+    assert_eq!(defaults.max_tokens, DEFAULT_MAX_TOKENS);
+
+    let configured = ModelSettings::from_columns(
+        Some(r#"["openai:qwen2.5", "@cf/meta/llama-3.1-8b-instruct"]"#),
+        Some(0.2),
+        Some(512),
+    )
+    .unwrap();
+    assert_eq!(configured.models.len(), 2);
+    assert_eq!(configured.models[0].provider, Provider::OpenAi);
+    assert_eq!(configured.temperature, Some(0.2));
+    assert_eq!(configured.max_tokens, 512);
+
+    // An empty list and a zero token limit fall back to the defaults
+    let empty = ModelSettings::from_columns(Some("[]"), None, Some(0)).unwrap();
+    assert_eq!(empty, ModelSettings::default());
+
+    assert!(ModelSettings::from_columns(Some("not json"), None, None).is_err());
+    assert!(ModelSettings::from_columns(Some(r#"["openai:"]"#), None, None).is_err());
+}
+
+#[test]
+fn test_chat_input_includes_sampling_settings() {
+    let mut settings = ModelSettings::default();
+    let input = settings.chat_input("Hello");
+    assert_eq!(input["messages"][0]["content"], "Hello");
+    assert_eq!(input["max_tokens"], DEFAULT_MAX_TOKENS);
+    assert!(input.get("temperature").is_none());
+
+    settings.temperature = Some(0.5);
+    assert_eq!(settings.chat_input("Hello")["temperature"], 0.5);
+}
+
+#[test]
+fn test_fallback_uses_first_working_model() {
+    let backend = StubBackend::default()
+        .with("workers-ai:primary", Err("capacity exceeded"))
+        .with("openai:local", Ok(json!({ "weird": true })))
+        .with(
+            "openai:backup",
+            Ok(json!({ "choices": [{ "message": { "content": "from backup" } }] })),
+        )
+        .with("workers-ai:last", Ok(json!({ "response": "from last" })));
+    let settings = settings(&["primary", "openai:local", "openai:backup", "workers-ai:last"]);
+
+    let (text, model) = block_on(complete_with_fallback(&backend, &settings, "Hi")).unwrap();
+
+    assert_eq!(text, "from backup");
+    assert_eq!(model.to_string(), "openai:backup");
+    assert_eq!(
+        backend.called_models(),
+        vec!["workers-ai:primary", "openai:local", "openai:backup"]
+    );
+    // Every model gets the same input
+    let calls = backend.calls.borrow();
+    assert!(calls.iter().all(|(_, input)| *input == calls[0].1));
+}
+
+#[test]
+fn test_fallback_reports_every_error() {
+    let backend = StubBackend::default()
+        .with("workers-ai:primary", Err("capacity exceeded"))
+        .with("openai:local", Err("connection refused"));
+    let settings = settings(&["primary", "openai:local"]);
+
+    let error = block_on(complete_with_fallback(&backend, &settings, "Hi"))
+        .unwrap_err()
+        .to_string();
+
+    assert!(error.contains("workers-ai:primary: capacity exceeded"));
+    assert!(error.contains("openai:local: connection refused"));
+
+    assert!(block_on(complete_with_fallback(&backend, &settings, "  ")).is_err());
+    assert_eq!(backend.called_models().len(), 2);
+}
+
+/// Backend for a user who has no AI calls left
+#[derive(Default)]
+struct OutOfCalls {
+    runs: Cell<u32>,
+}
+
+impl ModelBackend for OutOfCalls {
+    async fn run(&self, _model: &ModelSpec, _input: &Value) -> Result<Value> {
+        self.runs.set(self.runs.get() + 1);
+        Err(QuotaExceeded {
+            kind: AI_CALLS.to_string(),
+            limit: 10,
+            retry_after: 60,
+        }
+        .into())
+    }
+}
+
+#[test]
+fn test_fallback_stops_when_out_of_calls() {
+    let backend = OutOfCalls::default();
+    let settings = settings(&["primary", "openai:local"]);
+
+    let error = block_on(complete_with_fallback(&backend, &settings, "Hi")).unwrap_err();
+
+    assert!(error.is::<QuotaExceeded>());
+    assert_eq!(backend.runs.get(), 1);
+}
+
+#[test]
+fn test_response_text_formats() {
+    assert_eq!(response_text(&json!("plain")), Some("plain".to_string()));
+    assert_eq!(response_text(&json!({ "response": "workers" })), Some("workers".to_string()));
+    assert_eq!(response_text(&json!({ "result": "result" })), Some("result".to_string()));
+    assert_eq!(
+        response_text(&json!({ "choices": [{ "message": { "content": "openai" } }] })),
+        Some("openai".to_string())
+    );
+    assert_eq!(response_text(&json!({ "choices": [] })), None);
+}
+
+#[test]
+fn test_max_stage_runs_cover_fallbacks_and_repairs() {
+    // One model, tried once and repaired once
+    assert_eq!(max_stage_runs(1), 2);
+    // Every model of the chain can run for the try and the repair
+    assert_eq!(max_stage_runs(3), 6);
+    // Settings without models still run the default one
+    assert_eq!(max_stage_runs(0), 2);
+}
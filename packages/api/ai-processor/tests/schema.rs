@@ -0,0 +1,103 @@
+//! Tests for stage output schemas and repair prompts
+
+use api_ai_processor::services::schema::{
+    extract_json_from_text, parse_output, repair_prompt, stage_schema, validate,
+};
+use serde_json::json;
+
+#[test]
+fn test_valid_stage_outputs() {
+    let classify = stage_schema("classify").unwrap();
+    assert!(validate(&classify, &json!({ "category": "interview", "confidence": 0.9 })).is_ok());
+
+    let extract = stage_schema("extract").unwrap();
+    assert!(validate(&extract, &json!({})).is_ok());
+    assert!(validate(
+        &extract,
+        &json!({ "company": "Acme", "job_title": null, "remote": true, "extra": 1 })
+    )
+    .is_ok());
+
+    let summarize = stage_schema("summarize").unwrap();
+    assert!(validate(&summarize, &json!({ "summary": "Interview on Monday." })).is_ok());
+
+    assert!(stage_schema("draft").is_none());
+}
+
+#[test]
+fn test_invalid_stage_outputs_name_every_problem() {
+    let classify = stage_schema("classify").unwrap();
+    let errors = validate(&classify, &json!({ "category": "spam", "confidence": 1.5 })).unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].contains("output.category must be one of"));
+    assert!(errors[1].contains("output.confidence must be at most 1"));
+
+    let errors = validate(&classify, &json!({ "confidence": "high" })).unwrap_err();
+    assert!(errors.contains(&"output.category is required".to_string()));
+    assert!(errors.iter().any(|e| e.contains("output.confidence must be of type number")));
+
+    let extract = stage_schema("extract").unwrap();
+    let errors = validate(&extract, &json!({ "remote": "yes" })).unwrap_err();
+    assert!(errors[0].contains("output.remote must be of type boolean or null"));
+
+    let summarize = stage_schema("summarize").unwrap();
+    let errors = validate(&summarize, &json!({ "summary": "  " })).unwrap_err();
+    assert_eq!(errors, vec!["output.summary must not be empty".to_string()]);
+
+    assert!(validate(&summarize, &json!([])).is_err());
+}
+
+#[test]
+fn test_parse_output_finds_json_in_text() {
+    let classify = stage_schema("classify").unwrap();
+
+    let value = parse_output(
+        &classify,
+        "Sure! Here it is:\n{\"category\": \"rejection\", \"confidence\": 0.8}\nThanks",
+    )
+    .unwrap();
+    assert_eq!(value["category"], "rejection");
+
+    let error = parse_output(&classify, "I think this is an interview").unwrap_err();
+    assert!(error.contains("doesn't contain a JSON object"));
+
+    let error = parse_output(&classify, "{\"category\": \"interview\", }").unwrap_err();
+    assert!(error.contains("isn't valid JSON"));
+
+    let error = parse_output(&classify, "{\"category\": \"interview\"}").unwrap_err();
+    assert!(error.contains("output.confidence is required"));
+
+    assert!(extract_json_from_text("} backwards {").is_err());
+}
+
+#[test]
+fn test_repair_prompt_includes_answer_error_and_schema() {
+    let schema = stage_schema("summarize").unwrap();
+    let prompt = repair_prompt("Summarize this.", "{}", "The JSON doesn't match.", &schema);
+
+    assert!(prompt.starts_with("Summarize this."));
+    assert!(prompt.contains("Your previous answer was:\n{}"));
+    assert!(prompt.contains("The JSON doesn't match."));
+    assert!(prompt.contains("\"required\":[\"summary\"]"));
+}
+
+#[test]
+fn test_combined_schema_nests_extraction() {
+    let combined = stage_schema("combined").unwrap();
+    let valid = json!({
+        "category": "interview",
+        "confidence": 0.95,
+        "extraction": { "company": "Acme", "remote": null },
+        "summary": "Acme invited you to an interview."
+    });
+    assert!(validate(&combined, &valid).is_ok());
+
+    let errors = validate(
+        &combined,
+        &json!({ "category": "interview", "confidence": 0.5, "extraction": { "remote": "no" } }),
+    )
+    .unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].contains("output.summary is required"));
+    assert!(errors[1].contains("output.extraction.remote must be of type boolean or null"));
+}
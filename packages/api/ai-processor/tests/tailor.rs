@@ -0,0 +1,47 @@
+//! Tests for loading the context documents are tailored from
+
+use api_ai_processor::services::db::{tailor_context, TailorContextError};
+use serde_json::json;
+
+#[test]
+fn test_tailor_context_from_rows() {
+    let job = json!({ "title": "Engineer", "company": "Acme", "description": " Build things " });
+    let user = json!({ "name": null, "resume": "Ten years of Rust" });
+
+    let context = tailor_context(Some(&job), Some(&user)).unwrap();
+    assert_eq!(context.user_name, "the candidate");
+    assert_eq!(context.resume, "Ten years of Rust");
+    assert_eq!(context.job_title, "Engineer");
+    assert_eq!(context.company, "Acme");
+    assert_eq!(context.description, "Build things");
+}
+
+#[test]
+fn test_missing_rows_are_not_found() {
+    let job = json!({ "title": "Engineer", "description": "Build things" });
+    let user = json!({ "name": "Alex", "resume": "Rust" });
+
+    let err = tailor_context(None, Some(&user)).unwrap_err();
+    assert_eq!(err, TailorContextError::JobNotFound);
+    assert_eq!(err.status(), 404);
+
+    let err = tailor_context(Some(&job), None).unwrap_err();
+    assert_eq!(err, TailorContextError::UserNotFound);
+    assert_eq!(err.status(), 404);
+}
+
+#[test]
+fn test_missing_resume_or_description_is_bad_request() {
+    let job = json!({ "title": "Engineer", "description": "Build things" });
+    let blank_job = json!({ "title": "Engineer", "description": "  " });
+    let user = json!({ "name": "Alex", "resume": "Rust" });
+    let no_resume = json!({ "name": "Alex", "resume": null });
+
+    let err = tailor_context(Some(&job), Some(&no_resume)).unwrap_err();
+    assert_eq!(err, TailorContextError::MissingResume);
+    assert_eq!(err.status(), 400);
+
+    let err = tailor_context(Some(&blank_job), Some(&user)).unwrap_err();
+    assert_eq!(err, TailorContextError::MissingDescription);
+    assert_eq!(err.status(), 400);
+}
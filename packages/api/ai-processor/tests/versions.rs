@@ -0,0 +1,14 @@
+//! Tests for numbering AI result versions
+
+use api_ai_processor::services::db::is_unique_violation;
+
+#[test]
+fn test_unique_violation_is_detected() {
+    assert!(is_unique_violation(
+        "D1_ERROR: UNIQUE constraint failed: ai_results.email_id, ai_results.version: SQLITE_CONSTRAINT"
+    ));
+    assert!(!is_unique_violation("D1_ERROR: no such table: ai_results"));
+    assert!(!is_unique_violation(
+        "D1_ERROR: NOT NULL constraint failed: ai_results.email_id"
+    ));
+}
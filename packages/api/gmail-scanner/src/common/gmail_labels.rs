@@ -0,0 +1,109 @@
+use crate::common::db::get_d1;
+use crate::services::gmail_api;
+use crate::services::gmail_labels::{label_change, LABELS};
+use crate::services::gmail_tokens::get_access_token;
+use anyhow::Result;
+use std::collections::HashMap;
+use worker::*;
+
+/// Whether the user turned on writing labels back to Gmail
+pub async fn label_sync_enabled(db: &D1Database, user_id: &str) -> Result<bool> {
+    #[derive(serde::Deserialize)]
+    struct SyncRow {
+        gmail_label_sync: Option<i64>,
+    }
+
+    let row = db
+        .prepare("SELECT gmail_label_sync FROM users WHERE id = ?")
+        .bind(&[user_id.into()])?
+        .first::<SyncRow>(None)
+        .await?;
+
+    Ok(row.and_then(|r| r.gmail_label_sync).unwrap_or(0) != 0)
+}
+
+pub async fn set_label_sync(db: &D1Database, user_id: &str, enabled: bool) -> Result<()> {
+    db.prepare("UPDATE users SET gmail_label_sync = ? WHERE id = ?")
+        .bind(&[(enabled as i32).into(), user_id.into()])?
+        .run()
+        .await?;
+    Ok(())
+}
+
+/// Gmail IDs of the ApplyMonitor labels by name, creating the ones missing
+pub async fn ensure_labels(access_token: &str) -> Result<HashMap<&'static str, String>> {
+    let existing = gmail_api::list_labels(access_token).await?;
+
+    let mut ids = HashMap::new();
+    for name in LABELS {
+        let id = match existing.iter().find(|l| l.name == name) {
+            Some(label) => label.id.clone(),
+            None => gmail_api::create_label(access_token, name).await?.id,
+        };
+        ids.insert(name, id);
+    }
+    Ok(ids)
+}
+
+/// Bring the ApplyMonitor labels of emails in line with their classification
+/// and job. Does nothing unless the user turned it on and granted label
+/// access. Returns how many emails were labeled.
+pub async fn sync_labels(env: &Env, user_id: &str, gmail_ids: &[String]) -> Result<usize> {
+    let db = get_d1(env)?;
+    if gmail_ids.is_empty() || !label_sync_enabled(&db, user_id).await? {
+        return Ok(0);
+    }
+    let access = match get_access_token(&db, env, user_id).await? {
+        Some(access) if access.can_modify() => access,
+        _ => return Ok(0),
+    };
+    let label_ids = ensure_labels(&access.access_token).await?;
+    let ids_of = |names: &[&'static str]| -> Vec<String> {
+        names
+            .iter()
+            .filter_map(|name| label_ids.get(name).cloned())
+            .collect()
+    };
+
+    #[derive(serde::Deserialize)]
+    struct EmailState {
+        job_id: Option<String>,
+        category: Option<String>,
+    }
+
+    let mut synced = 0;
+    for gmail_id in gmail_ids {
+        // A reviewed category wins over the latest AI result
+        let state = db
+            .prepare(
+                "SELECT e.job_id,
+                   COALESCE(
+                     (SELECT r.category FROM ai_reviews r WHERE r.email_id = e.gmail_id AND r.user_id = e.user_id),
+                     (SELECT a.category FROM ai_results a WHERE a.email_id = e.gmail_id AND a.status = 'ok'
+                      ORDER BY a.version DESC LIMIT 1)
+                   ) AS category
+                 FROM emails e WHERE e.gmail_id = ? AND e.user_id = ?",
+            )
+            .bind(&[gmail_id.as_str().into(), user_id.into()])?
+            .first::<EmailState>(None)
+            .await?;
+        let Some(state) = state else {
+            console_log!("Email {} not found for labeling", gmail_id);
+            continue;
+        };
+
+        let change = label_change(state.category.as_deref(), state.job_id.is_some());
+        match gmail_api::modify_message_labels(
+            &access.access_token,
+            gmail_id,
+            &ids_of(&change.add),
+            &ids_of(&change.remove),
+        )
+        .await
+        {
+            Ok(()) => synced += 1,
+            Err(e) => console_log!("Error labeling email {}: {}", gmail_id, e),
+        }
+    }
+    Ok(synced)
+}
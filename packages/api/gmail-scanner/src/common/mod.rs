@@ -1,4 +1,6 @@
 pub mod auth;
 pub mod cors;
 pub mod db;
+pub mod gmail_labels;
+pub mod scan_filters;
 pub mod uuid;
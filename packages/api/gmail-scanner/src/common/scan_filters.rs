@@ -0,0 +1,36 @@
+use crate::services::scan_filters::ScanFilters;
+use anyhow::Result;
+use worker::*;
+
+/// A user's scan filters, none when they haven't saved any
+pub async fn get_scan_filters(db: &D1Database, user_id: &str) -> Result<ScanFilters> {
+    #[derive(serde::Deserialize)]
+    struct FiltersRow {
+        filters: String,
+    }
+
+    let row = db
+        .prepare("SELECT filters FROM scan_filters WHERE user_id = ?")
+        .bind(&[user_id.into()])?
+        .first::<FiltersRow>(None)
+        .await?;
+
+    Ok(row
+        .and_then(|r| serde_json::from_str(&r.filters).ok())
+        .unwrap_or_default())
+}
+
+pub async fn save_scan_filters(
+    db: &D1Database,
+    user_id: &str,
+    filters: &ScanFilters,
+) -> Result<()> {
+    db.prepare(
+        "INSERT INTO scan_filters (user_id, filters) VALUES (?, ?)
+         ON CONFLICT (user_id) DO UPDATE SET filters = excluded.filters, updated_at = CURRENT_TIMESTAMP",
+    )
+    .bind(&[user_id.into(), serde_json::to_string(filters)?.into()])?
+    .run()
+    .await?;
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use crate::common::auth::require_auth;
 use crate::common::db::get_d1;
+use crate::common::gmail_labels::label_sync_enabled;
 use crate::services::gmail_oauth;
 use base64::engine::general_purpose;
 use base64::Engine;
@@ -26,7 +27,21 @@ pub async fn initiate_auth(req: Request, env: Env) -> worker::Result<Response> {
         .map_err(|e| worker::Error::RustError(format!("Failed to generate state: {}", e)))?;
     let state = general_purpose::STANDARD.encode(state_bytes);
 
-    let auth_url = gmail_oauth::build_authorization_url(&client_id, &redirect_uri, &state)
+    // ?scope=send or ?scope=modify asks for send or label access on top of
+    // the scopes already granted
+    let url = req.url()?;
+    let extra_scope = url
+        .query_pairs()
+        .find(|(key, _)| key == "scope")
+        .and_then(|(_, value)| match value.as_ref() {
+            "send" => Some(gmail_oauth::SEND_SCOPE),
+            "modify" => Some(gmail_oauth::MODIFY_SCOPE),
+            _ => None,
+        });
+    let mut scopes = vec![gmail_oauth::READONLY_SCOPE];
+    scopes.extend(extra_scope);
+
+    let auth_url = gmail_oauth::build_authorization_url(&client_id, &redirect_uri, &state, &scopes)
         .map_err(|e| worker::Error::RustError(format!("Failed to build auth URL: {}", e)))?;
 
     Response::redirect(
@@ -69,13 +84,14 @@ pub async fn callback(req: Request, env: Env) -> worker::Result<Response> {
     let expires_at = Utc::now() + Duration::seconds(token_response.expires_in);
 
     db.prepare(
-        "INSERT OR REPLACE INTO gmail_tokens (user_id, access_token, refresh_token, expires_at, updated_at) VALUES (?, ?, ?, ?, CURRENT_TIMESTAMP)",
+        "INSERT OR REPLACE INTO gmail_tokens (user_id, access_token, refresh_token, expires_at, scopes, updated_at) VALUES (?, ?, ?, ?, ?, CURRENT_TIMESTAMP)",
     )
     .bind(&[
         user_id.into(),
         token_response.access_token.into(),
         token_response.refresh_token.into(),
         expires_at.to_rfc3339().into(),
+        token_response.scope.as_deref().into(),
     ])?
     .run()
     .await?;
@@ -111,8 +127,11 @@ pub async fn status(req: Request, env: Env) -> worker::Result<Response> {
         .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
 
     let db = get_d1(&env)?;
+    let label_sync = label_sync_enabled(&db, &user_id)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to get label setting: {}", e)))?;
     let result = db
-        .prepare("SELECT refresh_token, expires_at FROM gmail_tokens WHERE user_id = ?")
+        .prepare("SELECT refresh_token, expires_at, scopes FROM gmail_tokens WHERE user_id = ?")
         .bind(&[user_id.into()])?
         .first::<Value>(None)
         .await?;
@@ -131,12 +150,24 @@ pub async fn status(req: Request, env: Env) -> worker::Result<Response> {
         // Connection is valid if refresh_token exists (can refresh access token)
         let is_connected = refresh_token.is_some();
 
+        let scopes = row.get("scopes").and_then(|v| v.as_str());
+        let can_send = gmail_oauth::has_scope(scopes, gmail_oauth::SEND_SCOPE);
+        let can_modify = gmail_oauth::has_scope(scopes, gmail_oauth::MODIFY_SCOPE);
+
         Response::from_json(&serde_json::json!({
             "connected": is_connected,
-            "expires_at": expires_at
+            "expires_at": expires_at,
+            "can_send": can_send,
+            "can_modify": can_modify,
+            "label_sync": label_sync
         }))
     } else {
-        Response::from_json(&serde_json::json!({ "connected": false }))
+        Response::from_json(&serde_json::json!({
+            "connected": false,
+            "can_send": false,
+            "can_modify": false,
+            "label_sync": label_sync
+        }))
     }
 }
 
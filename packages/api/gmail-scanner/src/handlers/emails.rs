@@ -1,5 +1,7 @@
 use crate::common::auth::require_auth;
 use crate::common::db::get_d1;
+use crate::common::gmail_labels::sync_labels;
+use crate::services::job_sources::infer_job_source;
 use serde::Deserialize;
 use serde_json::Value;
 use worker::*;
@@ -96,9 +98,9 @@ pub async fn assign_email_to_job(
     // If job_id is None and create_job is None, unassign email from job
     if body.create_job.is_none() {
         if let Some(job_id) = body.job_id {
-            // Verify job exists
+            // Verify job exists and is not in the trash
             let job_exists = db
-                .prepare("SELECT id FROM jobs WHERE id = ?")
+                .prepare("SELECT id FROM jobs WHERE id = ? AND deleted_at IS NULL")
                 .bind(&[job_id.clone().into()])?
                 .first::<Value>(None)
                 .await?;
@@ -112,10 +114,12 @@ pub async fn assign_email_to_job(
                 .bind(&[
                     job_id.clone().into(),
                     gmail_id.clone().into(),
-                    user_id.into(),
+                    user_id.clone().into(),
                 ])?
                 .run()
                 .await?;
+            infer_assigned_job_source(&db, &job_id).await;
+            sync_assignment_labels(&env, &user_id, &gmail_id).await;
 
             return Response::from_json(&serde_json::json!({
                 "success": true,
@@ -125,9 +129,10 @@ pub async fn assign_email_to_job(
         } else {
             // Unassign email from job (set job_id to NULL)
             db.prepare("UPDATE emails SET job_id = NULL WHERE gmail_id = ? AND user_id = ?")
-                .bind(&[gmail_id.clone().into(), user_id.into()])?
+                .bind(&[gmail_id.clone().into(), user_id.clone().into()])?
                 .run()
                 .await?;
+            sync_assignment_labels(&env, &user_id, &gmail_id).await;
 
             return Response::from_json(&serde_json::json!({
                 "success": true,
@@ -180,10 +185,12 @@ pub async fn assign_email_to_job(
             .bind(&[
                 job_id.clone().into(),
                 gmail_id.clone().into(),
-                user_id.into(),
+                user_id.clone().into(),
             ])?
             .run()
             .await?;
+        infer_assigned_job_source(&db, &job_id).await;
+        sync_assignment_labels(&env, &user_id, &gmail_id).await;
 
         return Response::from_json(&serde_json::json!({
             "success": true,
@@ -194,3 +201,19 @@ pub async fn assign_email_to_job(
 
     Response::error("Either job_id or create_job must be provided", 400)
 }
+
+/// Fill in the source of a job an email was just linked to. Inference is
+/// best effort, so failures don't fail the assignment.
+async fn infer_assigned_job_source(db: &D1Database, job_id: &str) {
+    if let Err(e) = infer_job_source(db, job_id).await {
+        console_log!("Error inferring source of job {}: {}", job_id, e);
+    }
+}
+
+/// Update the Gmail labels of an email after its job changed. Labels are
+/// best effort, so failures don't fail the assignment.
+async fn sync_assignment_labels(env: &Env, user_id: &str, gmail_id: &str) {
+    if let Err(e) = sync_labels(env, user_id, &[gmail_id.to_string()]).await {
+        console_log!("Error syncing labels of email {}: {}", gmail_id, e);
+    }
+}
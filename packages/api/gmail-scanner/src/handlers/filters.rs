@@ -0,0 +1,102 @@
+use crate::common::auth::require_auth;
+use crate::common::db::get_d1;
+use crate::common::scan_filters::{get_scan_filters, save_scan_filters};
+use crate::handlers::scan::scan_range;
+use crate::services::gmail_api;
+use crate::services::gmail_tokens::get_access_token;
+use crate::services::scan_filters::{build_query, ScanFilters};
+use serde::Deserialize;
+use worker::*;
+
+#[derive(Debug, Deserialize)]
+pub struct PreviewRequest {
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    /// Filters to try before saving them, the saved ones when missing
+    pub filters: Option<ScanFilters>,
+}
+
+pub async fn get_filters(req: Request, env: Env) -> worker::Result<Response> {
+    let user_id = require_auth(&req, &env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let db = get_d1(&env)?;
+    let filters = get_scan_filters(&db, &user_id)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to get scan filters: {}", e)))?;
+
+    Response::from_json(&filters)
+}
+
+pub async fn update_filters(mut req: Request, env: Env) -> worker::Result<Response> {
+    let user_id = require_auth(&req, &env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let body: ScanFilters = req
+        .json()
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Invalid JSON: {}", e)))?;
+
+    let filters = body.normalized();
+    if let Err(message) = filters.validate() {
+        return Response::error(message, 400);
+    }
+
+    let db = get_d1(&env)?;
+    save_scan_filters(&db, &user_id, &filters)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to save scan filters: {}", e)))?;
+
+    Response::from_json(&filters)
+}
+
+/// The search a scan would run and Gmail's estimate of how many messages
+/// it matches, without fetching any of them
+pub async fn preview(mut req: Request, env: Env) -> worker::Result<Response> {
+    let user_id = require_auth(&req, &env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let body: PreviewRequest = req.json().await?;
+
+    let (start_date, end_date) =
+        match scan_range(body.start_date.as_deref(), body.end_date.as_deref()) {
+            Ok(range) => range,
+            Err(message) => return Response::error(message, 400),
+        };
+
+    let db = get_d1(&env)?;
+
+    let filters = match body.filters {
+        Some(filters) => {
+            let filters = filters.normalized();
+            if let Err(message) = filters.validate() {
+                return Response::error(message, 400);
+            }
+            filters
+        }
+        None => get_scan_filters(&db, &user_id)
+            .await
+            .map_err(|e| worker::Error::RustError(format!("Failed to get scan filters: {}", e)))?,
+    };
+
+    let access_token = match get_access_token(&db, &env, &user_id).await? {
+        Some(access) => access.access_token,
+        None => return Response::error("Gmail not connected", 401),
+    };
+
+    let query = build_query(
+        &gmail_api::build_date_query(&start_date, &end_date),
+        &filters,
+    );
+    let response = gmail_api::list_messages(&access_token, Some(&query), Some(1), None)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Gmail API error: {}", e)))?;
+
+    Response::from_json(&serde_json::json!({
+        "query": query,
+        "estimated_count": response.result_size_estimate.unwrap_or(0)
+    }))
+}
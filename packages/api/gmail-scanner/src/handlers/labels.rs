@@ -0,0 +1,63 @@
+use crate::common::auth::require_auth;
+use crate::common::db::get_d1;
+use crate::common::gmail_labels::{ensure_labels, set_label_sync, sync_labels};
+use crate::services::gmail_tokens::get_access_token;
+use api_shared::requests::require_signature;
+use serde::Deserialize;
+use worker::*;
+
+#[derive(Debug, Deserialize)]
+pub struct LabelSettingsRequest {
+    pub enabled: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SyncLabelsRequest {
+    pub user_id: String,
+    pub email_ids: Vec<String>,
+}
+
+/// Turn writing labels back to Gmail on or off. Turning it on needs label
+/// access and creates the label hierarchy right away.
+pub async fn update_settings(mut req: Request, env: Env) -> worker::Result<Response> {
+    let user_id = require_auth(&req, &env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let body: LabelSettingsRequest = req.json().await?;
+    let db = get_d1(&env)?;
+
+    if body.enabled {
+        let access = match get_access_token(&db, &env, &user_id).await? {
+            Some(access) => access,
+            None => return Response::error("Gmail not connected", 401),
+        };
+        if !access.can_modify() {
+            return Response::error("Label access has not been granted", 403);
+        }
+        ensure_labels(&access.access_token)
+            .await
+            .map_err(|e| worker::Error::RustError(format!("Failed to create labels: {}", e)))?;
+    }
+
+    set_label_sync(&db, &user_id, body.enabled)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to save label setting: {}", e)))?;
+
+    Response::from_json(&serde_json::json!({ "label_sync": body.enabled }))
+}
+
+/// Label emails after the AI processor classified them. Only accepts
+/// requests signed with the shared AI_WORKER_SECRET.
+pub async fn sync(mut req: Request, env: Env) -> worker::Result<Response> {
+    if let Err(e) = require_signature(&req, &env).await {
+        return Response::error(format!("Unauthorized: {}", e), 401);
+    }
+
+    let body: SyncLabelsRequest = req.json().await?;
+    let synced = sync_labels(&env, &body.user_id, &body.email_ids)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to sync labels: {}", e)))?;
+
+    Response::from_json(&serde_json::json!({ "synced": synced }))
+}
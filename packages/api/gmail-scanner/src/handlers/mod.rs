@@ -1,3 +1,6 @@
 pub mod auth;
 pub mod emails;
+pub mod filters;
+pub mod labels;
 pub mod scan;
+pub mod send;
@@ -1,8 +1,13 @@
 use crate::common::auth::require_auth;
 use crate::common::db::get_d1;
+use crate::common::scan_filters::get_scan_filters;
 use crate::common::uuid;
 use crate::services::gmail_api;
-use crate::services::gmail_oauth;
+use crate::services::gmail_tokens::get_access_token;
+use crate::services::scan_filters::build_query;
+use api_shared::quota::{QuotaExceeded, MESSAGES, SCANS};
+use api_shared::requests::ai_worker_request;
+use api_shared::usage::{get_quota, now_secs, record_usage, too_many_requests, use_quota};
 use chrono::{DateTime, Duration, Utc};
 use serde::Deserialize;
 use serde_json::Value;
@@ -14,111 +19,46 @@ pub struct ScanRequest {
     pub end_date: Option<String>,
 }
 
-pub async fn scan_emails(mut req: Request, env: Env) -> worker::Result<Response> {
+pub async fn scan_emails(mut req: Request, env: Env, ctx: Context) -> worker::Result<Response> {
     let user_id = require_auth(&req, &env)
         .await
         .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
 
     let body: ScanRequest = req.json().await?;
 
-    let end_date = body
-        .end_date
-        .as_ref()
-        .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
-        .map(|dt| dt.with_timezone(&Utc))
-        .unwrap_or_else(Utc::now);
-
-    let start_date = body
-        .start_date
-        .as_ref()
-        .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
-        .map(|dt| dt.with_timezone(&Utc))
-        .unwrap_or_else(|| end_date - Duration::days(7));
-
-    if start_date >= end_date {
-        return Response::error("start_date must be before end_date", 400);
-    }
-
-    let max_days = Duration::days(90);
-    if end_date - start_date > max_days {
-        return Response::error("Date range cannot exceed 90 days", 400);
-    }
+    let (start_date, end_date) =
+        match scan_range(body.start_date.as_deref(), body.end_date.as_deref()) {
+            Ok(range) => range,
+            Err(message) => return Response::error(message, 400),
+        };
 
     let db = get_d1(&env)?;
 
-    let token_row = db
-        .prepare(
-            "SELECT access_token, refresh_token, expires_at FROM gmail_tokens WHERE user_id = ?",
-        )
-        .bind(&[user_id.clone().into()])?
-        .first::<Value>(None)
-        .await?;
-
-    let (access_token, _refresh_token) = if let Some(row) = token_row {
-        let expires_at_str = row
-            .get("expires_at")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| worker::Error::RustError("Missing expires_at".to_string()))?;
-
-        let expires_at = DateTime::parse_from_rfc3339(expires_at_str)
-            .map_err(|e| worker::Error::RustError(format!("Invalid expires_at: {}", e)))?
-            .with_timezone(&Utc);
-
-        let stored_access_token = row
-            .get("access_token")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| worker::Error::RustError("Missing access_token".to_string()))?
-            .to_string();
-
-        let stored_refresh_token = row
-            .get("refresh_token")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| worker::Error::RustError("Missing refresh_token".to_string()))?
-            .to_string();
-
-        if expires_at <= Utc::now() {
-            let client_id = env
-                .secret("GMAIL_CLIENT_ID")
-                .map_err(|_| {
-                    worker::Error::RustError("GMAIL_CLIENT_ID secret not found".to_string())
-                })?
-                .to_string();
-
-            let client_secret = env
-                .secret("GMAIL_CLIENT_SECRET")
-                .map_err(|_| {
-                    worker::Error::RustError("GMAIL_CLIENT_SECRET secret not found".to_string())
-                })?
-                .to_string();
-
-            let token_response =
-                gmail_oauth::refresh_token(&stored_refresh_token, &client_id, &client_secret)
-                    .await
-                    .map_err(|e| {
-                        worker::Error::RustError(format!("Token refresh failed: {}", e))
-                    })?;
-
-            let new_expires_at = Utc::now() + Duration::seconds(token_response.expires_in);
-
-            db.prepare(
-                "UPDATE gmail_tokens SET access_token = ?, expires_at = ?, updated_at = CURRENT_TIMESTAMP WHERE user_id = ?",
-            )
-            .bind(&[
-                token_response.access_token.clone().into(),
-                new_expires_at.to_rfc3339().into(),
-                user_id.clone().into(),
-            ])?
-            .run()
-            .await?;
-
-            (token_response.access_token, stored_refresh_token)
-        } else {
-            (stored_access_token, stored_refresh_token)
-        }
-    } else {
-        return Response::error("Gmail not connected", 401);
+    let access_token = match get_access_token(&db, &env, &user_id).await? {
+        Some(access) => access.access_token,
+        None => return Response::error("Gmail not connected", 401),
     };
 
+    let filters = get_scan_filters(&db, &user_id)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to get scan filters: {}", e)))?;
+
+    // A scan needs at least one message left to fetch, and counts as a scan
+    // however many it finds
+    let messages_quota = get_quota(&env, &user_id, MESSAGES)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to get quota: {}", e)))?;
+    if let Err(exceeded) = messages_quota.check(1, now_secs()) {
+        return too_many_requests(&exceeded);
+    }
+    if let Err(e) = use_quota(&env, &user_id, SCANS, 1).await {
+        return match e.downcast_ref::<QuotaExceeded>() {
+            Some(exceeded) => too_many_requests(exceeded),
+            None => Response::error(format!("Failed to check quota: {}", e), 500),
+        };
+    }
+    let max_messages = messages_quota.remaining() as usize;
+
     let scan_id = uuid::generate_uuid()
         .map_err(|e| worker::Error::RustError(format!("Failed to generate UUID: {}", e)))?;
 
@@ -134,15 +74,21 @@ pub async fn scan_emails(mut req: Request, env: Env) -> worker::Result<Response>
     .run()
     .await?;
 
-    let query = gmail_api::build_date_query(&start_date, &end_date);
+    let query = build_query(
+        &gmail_api::build_date_query(&start_date, &end_date),
+        &filters,
+    );
     let mut all_messages = Vec::new();
     let mut page_token: Option<String> = None;
+    // Every fetch uses Gmail quota, failed ones too
+    let mut fetched = 0;
+    let mut message_limit_reached = false;
 
-    loop {
+    'pages: loop {
         let response = gmail_api::list_messages(
             &access_token,
             Some(&query),
-            Some(100),
+            Some(100.min(max_messages - fetched)),
             page_token.as_deref(),
         )
         .await
@@ -150,6 +96,11 @@ pub async fn scan_emails(mut req: Request, env: Env) -> worker::Result<Response>
 
         if let Some(messages) = response.messages {
             for msg_item in messages {
+                if fetched >= max_messages {
+                    message_limit_reached = true;
+                    break 'pages;
+                }
+                fetched += 1;
                 match gmail_api::get_message(&access_token, &msg_item.id).await {
                     Ok(msg) => all_messages.push(msg),
                     Err(e) => {
@@ -163,21 +114,21 @@ pub async fn scan_emails(mut req: Request, env: Env) -> worker::Result<Response>
         if page_token.is_none() {
             break;
         }
+        if fetched >= max_messages {
+            message_limit_reached = true;
+            break;
+        }
+    }
+
+    if let Err(e) = record_usage(&env, &user_id, MESSAGES, fetched as u32).await {
+        console_log!("Error recording message usage of scan {}: {}", scan_id, e);
     }
 
     // Store emails in database
     let mut stored_count = 0;
     for msg in &all_messages {
         // Parse date if available
-        let date_str = msg.date.as_ref().and_then(|d| {
-            DateTime::parse_from_rfc3339(d)
-                .ok()
-                .or_else(|| {
-                    // Try other date formats
-                    chrono::DateTime::parse_from_str(d, "%a, %d %b %Y %H:%M:%S %z").ok()
-                })
-                .map(|dt| dt.with_timezone(&Utc).to_rfc3339())
-        });
+        let date_str = msg.date.as_deref().and_then(gmail_api::normalize_date);
 
         // Handle optional cc and bcc fields by duplicating INSERT statements
         let result = match (&msg.cc, &msg.bcc) {
@@ -279,20 +230,84 @@ pub async fn scan_emails(mut req: Request, env: Env) -> worker::Result<Response>
     .run()
     .await?;
 
-    // TODO: Trigger AI processing for stored emails
-    // For now, emails will be processed via a separate endpoint or scheduled job
-    // In production, consider using Cloudflare Queues or a scheduled worker
-    // let stored_email_ids: Vec<String> = all_messages.iter().map(|m| m.id.clone()).collect();
-    // Call AI worker at /process/batch with email_ids and user_id
+    // Emails stored before keep their scan_id, so these are only the new ones
+    #[derive(Deserialize)]
+    struct NewEmail {
+        gmail_id: String,
+    }
+    let new_emails: Vec<NewEmail> = db
+        .prepare("SELECT gmail_id FROM emails WHERE user_id = ? AND scan_id = ?")
+        .bind(&[user_id.clone().into(), scan_id.clone().into()])?
+        .all()
+        .await?
+        .results()?;
+    if !new_emails.is_empty() {
+        // Classifying takes much longer than the scan, so it runs after the
+        // response is sent
+        let email_ids: Vec<String> = new_emails.into_iter().map(|e| e.gmail_id).collect();
+        let (env, user_id, scan_id) = (env.clone(), user_id.clone(), scan_id.clone());
+        ctx.wait_until(async move {
+            if let Err(e) = process_emails(&env, &user_id, email_ids).await {
+                console_log!("Error processing emails of scan {}: {}", scan_id, e);
+            }
+        });
+    }
 
     Response::from_json(&serde_json::json!({
         "scan_id": scan_id,
         "emails_found": all_messages.len(),
         "messages": all_messages,
-        "stored_count": stored_count
+        "stored_count": stored_count,
+        "message_limit_reached": message_limit_reached
     }))
 }
 
+/// The dates a scan covers, the last 7 days by default
+pub fn scan_range(
+    start_date: Option<&str>,
+    end_date: Option<&str>,
+) -> std::result::Result<(DateTime<Utc>, DateTime<Utc>), &'static str> {
+    let end_date = end_date
+        .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now);
+
+    let start_date = start_date
+        .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|| end_date - Duration::days(7));
+
+    if start_date >= end_date {
+        return Err("start_date must be before end_date");
+    }
+
+    let max_days = Duration::days(90);
+    if end_date - start_date > max_days {
+        return Err("Date range cannot exceed 90 days");
+    }
+
+    Ok((start_date, end_date))
+}
+
+/// Have the AI worker classify and summarize newly stored emails
+async fn process_emails(env: &Env, user_id: &str, email_ids: Vec<String>) -> worker::Result<()> {
+    let body = serde_json::to_string(&serde_json::json!({
+        "email_ids": email_ids,
+        "user_id": user_id
+    }))?;
+    let request = ai_worker_request(env, "/process/batch", &body)?;
+
+    let mut response = Fetch::Request(request).send().await?;
+    if response.status_code() != 200 {
+        let text = response.text().await?;
+        return Err(worker::Error::RustError(format!(
+            "AI worker error: {}",
+            text
+        )));
+    }
+    Ok(())
+}
+
 pub async fn get_scan(req: Request, env: Env, scan_id: String) -> worker::Result<Response> {
     let user_id = require_auth(&req, &env)
         .await
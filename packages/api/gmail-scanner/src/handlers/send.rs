@@ -0,0 +1,190 @@
+use crate::common::auth::require_auth;
+use crate::common::db::get_d1;
+use crate::services::gmail_api;
+use crate::services::gmail_tokens::get_access_token;
+use crate::services::mime::{
+    build_message, build_references, encode_raw, parse_address_list, reply_subject, OutgoingMessage,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use worker::*;
+
+#[derive(Debug, Deserialize)]
+pub struct SendEmailRequest {
+    /// Recipients; defaults to the sender of the email being replied to
+    pub to: Option<String>,
+    pub cc: Option<String>,
+    /// Subject; defaults to "Re: " and the subject of the email being replied to
+    pub subject: Option<String>,
+    pub body: String,
+    /// Gmail ID of the email being replied to
+    pub reply_to_gmail_id: Option<String>,
+    /// Job to link the sent email to; defaults to the job of the thread
+    pub job_id: Option<String>,
+}
+
+fn text(row: &Value, key: &str) -> Option<String> {
+    row.get(key)
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Send an email, or a reply to a stored email, through the user's Gmail
+/// account and record it in `emails`
+pub async fn send_email(mut req: Request, env: Env) -> worker::Result<Response> {
+    let user_id = require_auth(&req, &env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let body: SendEmailRequest = match req.json().await {
+        Ok(body) => body,
+        Err(_) => return Response::error("Invalid request body", 400),
+    };
+    if body.body.trim().is_empty() {
+        return Response::error("body is required", 400);
+    }
+
+    let db = get_d1(&env)?;
+
+    let access = match get_access_token(&db, &env, &user_id).await? {
+        Some(access) => access,
+        None => return Response::error("Gmail not connected", 401),
+    };
+    if !access.can_send() {
+        return Response::error("Gmail send permission not granted", 403);
+    }
+
+    let mut to = body.to.filter(|t| !t.trim().is_empty());
+    let mut subject = body.subject.filter(|s| !s.trim().is_empty());
+    let mut job_id = body.job_id.filter(|j| !j.is_empty());
+    let mut in_reply_to = None;
+    let mut references = None;
+    let mut thread_id = None;
+
+    if let Some(parent_id) = body.reply_to_gmail_id {
+        let parent_row = db
+            .prepare("SELECT thread_id, subject, \"from\", job_id FROM emails WHERE gmail_id = ? AND user_id = ?")
+            .bind(&[parent_id.clone().into(), user_id.clone().into()])?
+            .first::<Value>(None)
+            .await?;
+        let Some(parent_row) = parent_row else {
+            return Response::error("Email not found", 404);
+        };
+
+        // Message-ID and References aren't stored, so read them from Gmail
+        let parent = gmail_api::get_message(&access.access_token, &parent_id)
+            .await
+            .map_err(|e| worker::Error::RustError(format!("Gmail API error: {}", e)))?;
+
+        if to.is_none() {
+            to = parent.reply_to.clone().or(parent.from.clone());
+        }
+        if subject.is_none() {
+            subject = Some(reply_subject(parent.subject.as_deref().unwrap_or_default()));
+        }
+        if let Some(message_id) = parent.message_id.as_deref() {
+            in_reply_to = Some(message_id.to_string());
+            references = Some(build_references(parent.references.as_deref(), message_id));
+        }
+        thread_id = text(&parent_row, "thread_id").or(Some(parent.thread_id));
+        if job_id.is_none() {
+            job_id = text(&parent_row, "job_id");
+        }
+    }
+
+    let Some(to) = to else {
+        return Response::error("to is required", 400);
+    };
+    let Some(subject) = subject else {
+        return Response::error("subject is required", 400);
+    };
+    if parse_address_list(&to).is_none_or(|addresses| addresses.is_empty()) {
+        return Response::error("to must contain valid email addresses", 400);
+    }
+    let cc = body.cc.filter(|c| !c.trim().is_empty());
+    if cc
+        .as_deref()
+        .is_some_and(|cc| parse_address_list(cc).is_none())
+    {
+        return Response::error("cc must contain valid email addresses", 400);
+    }
+
+    if let Some(id) = job_id.as_deref() {
+        let job_exists = db
+            .prepare("SELECT id FROM jobs WHERE id = ? AND deleted_at IS NULL")
+            .bind(&[id.into()])?
+            .first::<Value>(None)
+            .await?;
+        if job_exists.is_none() {
+            return Response::error("Job not found", 404);
+        }
+    }
+
+    let message = OutgoingMessage {
+        to: to.clone(),
+        cc: cc.clone(),
+        subject: subject.clone(),
+        body: body.body,
+        in_reply_to,
+        references,
+    };
+    let raw = encode_raw(&build_message(&message));
+
+    let sent = gmail_api::send_message(&access.access_token, &raw, thread_id.as_deref())
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to send email: {}", e)))?;
+
+    // A new message in a thread that is already linked to a job belongs to it
+    if job_id.is_none() {
+        job_id = db
+            .prepare(
+                "SELECT job_id FROM emails WHERE user_id = ? AND thread_id = ? AND job_id IS NOT NULL LIMIT 1",
+            )
+            .bind(&[user_id.clone().into(), sent.thread_id.clone().into()])?
+            .first::<Value>(None)
+            .await?
+            .and_then(|row| text(&row, "job_id"));
+    }
+
+    // Read the sent message back for the From, Date and snippet Gmail set
+    let stored = gmail_api::get_message(&access.access_token, &sent.id)
+        .await
+        .ok();
+    let from = stored.as_ref().and_then(|m| m.from.clone());
+    let date = stored
+        .as_ref()
+        .and_then(|m| m.date.as_deref())
+        .and_then(gmail_api::normalize_date)
+        .unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
+    let snippet = stored
+        .as_ref()
+        .map(|m| m.snippet.clone())
+        .unwrap_or_else(|| message.body.chars().take(200).collect());
+
+    db.prepare(
+        "INSERT OR IGNORE INTO emails (gmail_id, user_id, thread_id, subject, \"from\", \"to\", cc, snippet, date, job_id) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&[
+        sent.id.clone().into(),
+        user_id.clone().into(),
+        sent.thread_id.clone().into(),
+        subject.into(),
+        from.as_deref().into(),
+        to.into(),
+        cc.as_deref().into(),
+        snippet.into(),
+        date.into(),
+        job_id.as_deref().into(),
+    ])?
+    .run()
+    .await?;
+
+    let email = db
+        .prepare("SELECT * FROM emails WHERE gmail_id = ? AND user_id = ?")
+        .bind(&[sent.id.into(), user_id.into()])?
+        .first::<Value>(None)
+        .await?;
+
+    Response::from_json(&email)
+}
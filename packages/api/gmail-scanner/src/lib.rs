@@ -2,17 +2,18 @@ use worker::*;
 
 mod common;
 mod handlers;
-mod services;
+pub mod services;
 
 use common::cors::get_cors;
-use handlers::{auth, emails, scan};
+use handlers::{auth, emails, filters, labels, scan, send};
 
 #[event(fetch)]
-async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
+async fn main(req: Request, env: Env, ctx: Context) -> Result<Response> {
     // Create CORS config early so we can use it for error responses too
     let cors = get_cors(&env);
 
-    let router = Router::new();
+    // Handlers get the context to keep work running after they respond
+    let router = Router::with_data(ctx);
 
     let response = router
         .get_async("/status", |req, ctx| async move {
@@ -28,7 +29,7 @@ async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
             auth::disconnect(req, ctx.env).await
         })
         .post_async("/scan", |req, ctx| async move {
-            scan::scan_emails(req, ctx.env).await
+            scan::scan_emails(req, ctx.env, ctx.data).await
         })
         .get_async("/scan/:id", |req, ctx| async move {
             let scan_id = ctx
@@ -40,6 +41,21 @@ async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
         .get_async("/scans", |req, ctx| async move {
             scan::list_scans(req, ctx.env).await
         })
+        .get_async("/filters", |req, ctx| async move {
+            filters::get_filters(req, ctx.env).await
+        })
+        .put_async("/filters", |req, ctx| async move {
+            filters::update_filters(req, ctx.env).await
+        })
+        .post_async("/filters/preview", |req, ctx| async move {
+            filters::preview(req, ctx.env).await
+        })
+        .put_async("/labels", |req, ctx| async move {
+            labels::update_settings(req, ctx.env).await
+        })
+        .post_async("/labels/sync", |req, ctx| async move {
+            labels::sync(req, ctx.env).await
+        })
         .get_async("/emails", |req, ctx| async move {
             emails::list_emails(req, ctx.env).await
         })
@@ -57,6 +73,9 @@ async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
                 .to_string();
             emails::assign_email_to_job(req, ctx.env, gmail_id).await
         })
+        .post_async("/send", |req, ctx| async move {
+            send::send_email(req, ctx.env).await
+        })
         .options("/status", |_, _| Response::ok(""))
         .options("/auth", |_, _| Response::ok(""))
         .options("/gmail/callback", |_, _| Response::ok(""))
@@ -64,9 +83,13 @@ async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
         .options("/scan", |_, _| Response::ok(""))
         .options("/scan/:id", |_, _| Response::ok(""))
         .options("/scans", |_, _| Response::ok(""))
+        .options("/filters", |_, _| Response::ok(""))
+        .options("/filters/preview", |_, _| Response::ok(""))
+        .options("/labels", |_, _| Response::ok(""))
         .options("/emails", |_, _| Response::ok(""))
         .options("/emails/:id", |_, _| Response::ok(""))
         .options("/emails/:id/assign-job", |_, _| Response::ok(""))
+        .options("/send", |_, _| Response::ok(""))
         .run(req, env)
         .await;
 
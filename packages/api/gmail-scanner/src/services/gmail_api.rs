@@ -16,6 +16,16 @@ pub struct GmailMessage {
     pub cc: Option<String>,
     pub bcc: Option<String>,
     pub date: Option<String>,
+    pub message_id: Option<String>,
+    pub references: Option<String>,
+    pub reply_to: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SentMessage {
+    pub id: String,
+    pub thread_id: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -23,6 +33,8 @@ pub struct GmailMessage {
 pub struct GmailListResponse {
     pub messages: Option<Vec<GmailMessageListItem>>,
     pub next_page_token: Option<String>,
+    /// Gmail's rough count of all messages matching the query
+    pub result_size_estimate: Option<u32>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -78,7 +90,10 @@ pub async fn get_message(access_token: &str, message_id: &str) -> Result<GmailMe
         .append_pair("metadataHeaders", "To")
         .append_pair("metadataHeaders", "Cc")
         .append_pair("metadataHeaders", "Bcc")
-        .append_pair("metadataHeaders", "Date");
+        .append_pair("metadataHeaders", "Date")
+        .append_pair("metadataHeaders", "Message-ID")
+        .append_pair("metadataHeaders", "References")
+        .append_pair("metadataHeaders", "Reply-To");
 
     let mut request = Request::new(url.as_str(), Method::Get)?;
     request
@@ -103,6 +118,9 @@ pub async fn get_message(access_token: &str, message_id: &str) -> Result<GmailMe
     let mut cc = None;
     let mut bcc = None;
     let mut date = None;
+    let mut header_message_id = None;
+    let mut references = None;
+    let mut reply_to = None;
 
     for header in headers {
         let name = header["name"].as_str().unwrap_or("").to_lowercase();
@@ -115,6 +133,9 @@ pub async fn get_message(access_token: &str, message_id: &str) -> Result<GmailMe
             "cc" => cc = value,
             "bcc" => bcc = value,
             "date" => date = value,
+            "message-id" => header_message_id = value,
+            "references" => references = value,
+            "reply-to" => reply_to = value,
             _ => {}
         }
     }
@@ -135,9 +156,152 @@ pub async fn get_message(access_token: &str, message_id: &str) -> Result<GmailMe
         cc,
         bcc,
         date,
+        message_id: header_message_id,
+        references,
+        reply_to,
     })
 }
 
+/// Send a base64url-encoded RFC 2822 message. Replies pass the thread ID so
+/// Gmail keeps them in the same conversation.
+pub async fn send_message(
+    access_token: &str,
+    raw: &str,
+    thread_id: Option<&str>,
+) -> Result<SentMessage> {
+    let mut body = serde_json::json!({ "raw": raw });
+    if let Some(thread_id) = thread_id {
+        body["threadId"] = serde_json::Value::String(thread_id.to_string());
+    }
+
+    let mut init = RequestInit::new();
+    init.with_method(Method::Post);
+    init.with_body(Some(body.to_string().into()));
+    let headers = Headers::new();
+    headers.set("Authorization", &format!("Bearer {}", access_token))?;
+    headers.set("Content-Type", "application/json")?;
+    init.with_headers(headers);
+
+    let request = Request::new_with_init(
+        "https://gmail.googleapis.com/gmail/v1/users/me/messages/send",
+        &init,
+    )?;
+    let mut response = Fetch::Request(request).send().await?;
+    if response.status_code() != 200 {
+        let text = response.text().await?;
+        return Err(anyhow!("Gmail API error: {}", text));
+    }
+
+    let sent: SentMessage = response.json().await?;
+    Ok(sent)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GmailLabel {
+    pub id: String,
+    pub name: String,
+}
+
+/// The labels of the mailbox, system ones included
+pub async fn list_labels(access_token: &str) -> Result<Vec<GmailLabel>> {
+    #[derive(Deserialize)]
+    struct LabelsResponse {
+        labels: Option<Vec<GmailLabel>>,
+    }
+
+    let mut request = Request::new(
+        "https://gmail.googleapis.com/gmail/v1/users/me/labels",
+        Method::Get,
+    )?;
+    request
+        .headers_mut()?
+        .set("Authorization", &format!("Bearer {}", access_token))?;
+
+    let mut response = Fetch::Request(request).send().await?;
+    if response.status_code() != 200 {
+        let text = response.text().await?;
+        return Err(anyhow!("Gmail API error: {}", text));
+    }
+
+    let data: LabelsResponse = response.json().await?;
+    Ok(data.labels.unwrap_or_default())
+}
+
+/// Create a user label. Names with a `/` are shown nested under their parent.
+pub async fn create_label(access_token: &str, name: &str) -> Result<GmailLabel> {
+    let body = serde_json::json!({
+        "name": name,
+        "labelListVisibility": "labelShow",
+        "messageListVisibility": "show"
+    });
+
+    let mut init = RequestInit::new();
+    init.with_method(Method::Post);
+    init.with_body(Some(body.to_string().into()));
+    let headers = Headers::new();
+    headers.set("Authorization", &format!("Bearer {}", access_token))?;
+    headers.set("Content-Type", "application/json")?;
+    init.with_headers(headers);
+
+    let request = Request::new_with_init(
+        "https://gmail.googleapis.com/gmail/v1/users/me/labels",
+        &init,
+    )?;
+    let mut response = Fetch::Request(request).send().await?;
+    if response.status_code() != 200 {
+        let text = response.text().await?;
+        return Err(anyhow!("Gmail API error: {}", text));
+    }
+
+    let label: GmailLabel = response.json().await?;
+    Ok(label)
+}
+
+/// Add and remove labels of a message by label ID
+pub async fn modify_message_labels(
+    access_token: &str,
+    message_id: &str,
+    add_label_ids: &[String],
+    remove_label_ids: &[String],
+) -> Result<()> {
+    let body = serde_json::json!({
+        "addLabelIds": add_label_ids,
+        "removeLabelIds": remove_label_ids
+    });
+
+    let mut init = RequestInit::new();
+    init.with_method(Method::Post);
+    init.with_body(Some(body.to_string().into()));
+    let headers = Headers::new();
+    headers.set("Authorization", &format!("Bearer {}", access_token))?;
+    headers.set("Content-Type", "application/json")?;
+    init.with_headers(headers);
+
+    let request = Request::new_with_init(
+        &format!(
+            "https://gmail.googleapis.com/gmail/v1/users/me/messages/{}/modify",
+            message_id
+        ),
+        &init,
+    )?;
+    let mut response = Fetch::Request(request).send().await?;
+    if response.status_code() != 200 {
+        let text = response.text().await?;
+        return Err(anyhow!("Gmail API error: {}", text));
+    }
+
+    Ok(())
+}
+
+/// Convert a Date header (RFC 2822, or RFC 3339) to RFC 3339 in UTC
+pub fn normalize_date(date: &str) -> Option<String> {
+    DateTime::parse_from_rfc3339(date)
+        .ok()
+        .or_else(|| DateTime::parse_from_rfc2822(date).ok())
+        .or_else(|| DateTime::parse_from_str(date, "%a, %d %b %Y %H:%M:%S %z").ok())
+        .map(|dt| dt.with_timezone(&Utc).to_rfc3339())
+}
+
 #[allow(dead_code)]
 pub async fn get_message_body(access_token: &str, message_id: &str) -> Result<String> {
     let url = format!(
@@ -0,0 +1,57 @@
+//! The "ApplyMonitor" label hierarchy written back to Gmail
+//!
+//! An email gets one label for its classification and "Linked" while it's
+//! assigned to a job. Every sync sets the full set, so labels that no longer
+//! apply are removed in the same change.
+
+/// Parent of the other labels, never applied to messages itself
+pub const ROOT_LABEL: &str = "ApplyMonitor";
+pub const INTERVIEW_LABEL: &str = "ApplyMonitor/Interview";
+pub const REJECTION_LABEL: &str = "ApplyMonitor/Rejection";
+pub const APPLICATION_LABEL: &str = "ApplyMonitor/Application";
+pub const LINKED_LABEL: &str = "ApplyMonitor/Linked";
+
+/// Every label of the hierarchy, parents first so they're created first
+pub const LABELS: [&str; 5] = [
+    ROOT_LABEL,
+    INTERVIEW_LABEL,
+    REJECTION_LABEL,
+    APPLICATION_LABEL,
+    LINKED_LABEL,
+];
+
+/// The label of a classification, if it has one
+pub fn category_label(category: &str) -> Option<&'static str> {
+    match category {
+        "interview" => Some(INTERVIEW_LABEL),
+        "rejection" => Some(REJECTION_LABEL),
+        "application_sent" => Some(APPLICATION_LABEL),
+        _ => None,
+    }
+}
+
+/// Labels to add to and remove from a message
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LabelChange {
+    pub add: Vec<&'static str>,
+    pub remove: Vec<&'static str>,
+}
+
+/// The change that leaves a message with exactly the labels of its
+/// classification and job assignment
+pub fn label_change(category: Option<&str>, linked: bool) -> LabelChange {
+    let mut wanted: Vec<&'static str> = category.and_then(category_label).into_iter().collect();
+    if linked {
+        wanted.push(LINKED_LABEL);
+    }
+
+    let mut change = LabelChange::default();
+    for label in &LABELS[1..] {
+        if wanted.contains(label) {
+            change.add.push(label);
+        } else {
+            change.remove.push(label);
+        }
+    }
+    change
+}
@@ -4,20 +4,45 @@ use serde_urlencoded;
 use url::Url;
 use worker::*;
 
+/// Read access, requested when Gmail is first connected
+pub const READONLY_SCOPE: &str = "https://www.googleapis.com/auth/gmail.readonly";
+
+/// Send access, requested incrementally the first time the user sends mail
+pub const SEND_SCOPE: &str = "https://www.googleapis.com/auth/gmail.send";
+
+/// Label access, requested when the user turns on writing labels back to Gmail
+pub const MODIFY_SCOPE: &str = "https://www.googleapis.com/auth/gmail.modify";
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TokenResponse {
     pub access_token: String,
     pub refresh_token: String,
     pub expires_in: i64,
+    /// Space-separated scopes granted to the token
+    pub scope: Option<String>,
 }
 
-pub fn build_authorization_url(client_id: &str, redirect_uri: &str, state: &str) -> Result<String> {
+/// Check whether a space-separated scope list includes a scope
+pub fn has_scope(scopes: Option<&str>, scope: &str) -> bool {
+    scopes
+        .unwrap_or_default()
+        .split_whitespace()
+        .any(|granted| granted == scope)
+}
+
+pub fn build_authorization_url(
+    client_id: &str,
+    redirect_uri: &str,
+    state: &str,
+    scopes: &[&str],
+) -> Result<String> {
     let mut url = Url::parse("https://accounts.google.com/o/oauth2/v2/auth")?;
     url.query_pairs_mut()
         .append_pair("client_id", client_id)
         .append_pair("redirect_uri", redirect_uri)
         .append_pair("response_type", "code")
-        .append_pair("scope", "https://www.googleapis.com/auth/gmail.readonly")
+        .append_pair("scope", &scopes.join(" "))
+        .append_pair("include_granted_scopes", "true")
         .append_pair("access_type", "offline")
         .append_pair("prompt", "consent")
         .append_pair("state", state);
@@ -69,6 +94,7 @@ pub async fn exchange_code(
         expires_in: token_data["expires_in"]
             .as_i64()
             .ok_or_else(|| anyhow!("Missing expires_in"))?,
+        scope: token_data["scope"].as_str().map(|s| s.to_string()),
     })
 }
 
@@ -111,5 +137,6 @@ pub async fn refresh_token(
         expires_in: token_data["expires_in"]
             .as_i64()
             .ok_or_else(|| anyhow!("Missing expires_in"))?,
+        scope: token_data["scope"].as_str().map(|s| s.to_string()),
     })
 }
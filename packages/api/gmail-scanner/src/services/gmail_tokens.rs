@@ -0,0 +1,109 @@
+use crate::services::gmail_oauth;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::Value;
+use worker::*;
+
+/// A valid access token and the scopes granted to it
+#[derive(Debug)]
+pub struct GmailAccess {
+    pub access_token: String,
+    pub scopes: Option<String>,
+}
+
+impl GmailAccess {
+    pub fn can_send(&self) -> bool {
+        gmail_oauth::has_scope(self.scopes.as_deref(), gmail_oauth::SEND_SCOPE)
+    }
+
+    pub fn can_modify(&self) -> bool {
+        gmail_oauth::has_scope(self.scopes.as_deref(), gmail_oauth::MODIFY_SCOPE)
+    }
+}
+
+/// Get the user's Gmail access token, refreshing it if it has expired.
+/// Returns `None` when Gmail isn't connected.
+pub async fn get_access_token(
+    db: &D1Database,
+    env: &Env,
+    user_id: &str,
+) -> Result<Option<GmailAccess>> {
+    let token_row = db
+        .prepare(
+            "SELECT access_token, refresh_token, expires_at, scopes FROM gmail_tokens WHERE user_id = ?",
+        )
+        .bind(&[user_id.into()])?
+        .first::<Value>(None)
+        .await?;
+
+    let Some(row) = token_row else {
+        return Ok(None);
+    };
+
+    let expires_at_str = row
+        .get("expires_at")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| worker::Error::RustError("Missing expires_at".to_string()))?;
+
+    let expires_at = DateTime::parse_from_rfc3339(expires_at_str)
+        .map_err(|e| worker::Error::RustError(format!("Invalid expires_at: {}", e)))?
+        .with_timezone(&Utc);
+
+    let stored_access_token = row
+        .get("access_token")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| worker::Error::RustError("Missing access_token".to_string()))?
+        .to_string();
+
+    let stored_refresh_token = row
+        .get("refresh_token")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| worker::Error::RustError("Missing refresh_token".to_string()))?
+        .to_string();
+
+    let scopes = row
+        .get("scopes")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    if expires_at > Utc::now() {
+        return Ok(Some(GmailAccess {
+            access_token: stored_access_token,
+            scopes,
+        }));
+    }
+
+    let client_id = env
+        .secret("GMAIL_CLIENT_ID")
+        .map_err(|_| worker::Error::RustError("GMAIL_CLIENT_ID secret not found".to_string()))?
+        .to_string();
+
+    let client_secret = env
+        .secret("GMAIL_CLIENT_SECRET")
+        .map_err(|_| worker::Error::RustError("GMAIL_CLIENT_SECRET secret not found".to_string()))?
+        .to_string();
+
+    let token_response =
+        gmail_oauth::refresh_token(&stored_refresh_token, &client_id, &client_secret)
+            .await
+            .map_err(|e| worker::Error::RustError(format!("Token refresh failed: {}", e)))?;
+
+    let new_expires_at = Utc::now() + Duration::seconds(token_response.expires_in);
+    let scopes = token_response.scope.or(scopes);
+
+    db.prepare(
+        "UPDATE gmail_tokens SET access_token = ?, expires_at = ?, scopes = ?, updated_at = CURRENT_TIMESTAMP WHERE user_id = ?",
+    )
+    .bind(&[
+        token_response.access_token.clone().into(),
+        new_expires_at.to_rfc3339().into(),
+        scopes.as_deref().into(),
+        user_id.into(),
+    ])?
+    .run()
+    .await?;
+
+    Ok(Some(GmailAccess {
+        access_token: token_response.access_token,
+        scopes,
+    }))
+}
@@ -0,0 +1,97 @@
+//! Inferring where a job application came from
+//!
+//! A job without a source takes the source mapped to the domain of its first
+//! linked email's sender (`system_email_domains.source_id`). Sources are only
+//! mapped to `*.domain` patterns, which match the domain and its subdomains.
+
+use serde::Deserialize;
+use serde_json::Value;
+use worker::*;
+
+/// A `*.domain` pattern mapped to a job source
+#[derive(Debug, Deserialize)]
+pub struct SourcePattern {
+    pub domain_pattern: String,
+    pub source_id: String,
+}
+
+/// The lowercased domain of a sender ("Name <user@example.com>" or a bare
+/// address)
+pub fn sender_domain(sender: &str) -> Option<String> {
+    let address = match (sender.find('<'), sender.find('>')) {
+        (Some(start), Some(end)) if start < end => &sender[start + 1..end],
+        _ => sender,
+    };
+    let domain = address.trim().rsplit_once('@')?.1.trim();
+    if domain.is_empty() {
+        None
+    } else {
+        Some(domain.to_lowercase())
+    }
+}
+
+/// Whether a `*.domain` pattern matches the domain itself or one of its
+/// subdomains. Anything after the `*.` is compared literally.
+pub fn matches_source_pattern(pattern: &str, domain: &str) -> bool {
+    let Some(base) = pattern.strip_prefix("*.") else {
+        return false;
+    };
+    domain == base
+        || domain
+            .strip_suffix(base)
+            .is_some_and(|sub| sub.ends_with('.'))
+}
+
+/// The source of the first pattern matching the sender's domain
+pub fn source_for_domain<'a>(domain: &str, patterns: &'a [SourcePattern]) -> Option<&'a str> {
+    patterns
+        .iter()
+        .find(|p| matches_source_pattern(&p.domain_pattern, domain))
+        .map(|p| p.source_id.as_str())
+}
+
+/// Fill in the source of a job that doesn't have one from its first linked
+/// email. Called whenever an email is linked to a job.
+pub async fn infer_job_source(db: &D1Database, job_id: &str) -> Result<()> {
+    let first_email = db
+        .prepare(
+            "SELECT e.\"from\" as sender
+             FROM emails e
+             JOIN jobs j ON j.id = e.job_id
+             WHERE e.job_id = ? AND j.source_id IS NULL AND j.deleted_at IS NULL
+             ORDER BY COALESCE(e.date, e.created_at) ASC
+             LIMIT 1",
+        )
+        .bind(&[job_id.into()])?
+        .first::<Value>(None)
+        .await?;
+
+    let Some(domain) = first_email
+        .as_ref()
+        .and_then(|row| row.get("sender"))
+        .and_then(|v| v.as_str())
+        .and_then(sender_domain)
+    else {
+        return Ok(());
+    };
+
+    let patterns: Vec<SourcePattern> = db
+        .prepare(
+            "SELECT domain_pattern, source_id FROM system_email_domains
+             WHERE source_id IS NOT NULL
+             ORDER BY domain_pattern",
+        )
+        .all()
+        .await?
+        .results()?;
+    let Some(source_id) = source_for_domain(&domain, &patterns) else {
+        return Ok(());
+    };
+
+    db.prepare("UPDATE jobs SET source_id = ? WHERE id = ? AND source_id IS NULL")
+        .bind(&[source_id.into(), job_id.into()])?
+        .run()
+        .await?;
+
+    Ok(())
+}
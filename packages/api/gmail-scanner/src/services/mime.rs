@@ -0,0 +1,149 @@
+//! RFC 2822 message composition for sending mail through Gmail
+//!
+//! Messages are plain text, UTF-8 and base64 encoded. Non-ASCII subjects use
+//! RFC 2047 encoded words. Gmail fills in From, Date and Message-ID itself.
+
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE},
+    Engine,
+};
+
+/// Maximum length of a base64 body line
+const BODY_LINE_LENGTH: usize = 76;
+
+/// Message to send
+#[derive(Debug, Clone, Default)]
+pub struct OutgoingMessage {
+    pub to: String,
+    pub cc: Option<String>,
+    pub subject: String,
+    pub body: String,
+    /// Message-ID of the message being replied to
+    pub in_reply_to: Option<String>,
+    /// Message-IDs of the thread, oldest first
+    pub references: Option<String>,
+}
+
+/// Remove line breaks so a value can't inject extra headers
+fn header_value(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c == '\r' || c == '\n' { ' ' } else { c })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Encode a header value as an RFC 2047 encoded word when it isn't ASCII
+pub fn encode_header(value: &str) -> String {
+    let value = header_value(value);
+    if value.is_ascii() {
+        value
+    } else {
+        format!("=?UTF-8?B?{}?=", STANDARD.encode(value.as_bytes()))
+    }
+}
+
+/// Subject of a reply: "Re: " unless the subject already starts with it
+pub fn reply_subject(subject: &str) -> String {
+    let subject = subject.trim();
+    let is_reply = subject
+        .get(..3)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("re:"));
+    if is_reply {
+        subject.to_string()
+    } else {
+        format!("Re: {}", subject)
+    }
+}
+
+/// References header of a reply: the parent's references followed by the
+/// parent's Message-ID, without duplicates
+pub fn build_references(parent_references: Option<&str>, parent_message_id: &str) -> String {
+    let mut ids: Vec<&str> = parent_references
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect();
+    let parent_message_id = parent_message_id.trim();
+    if !parent_message_id.is_empty() && !ids.contains(&parent_message_id) {
+        ids.push(parent_message_id);
+    }
+    ids.join(" ")
+}
+
+/// Email addresses in an address list ("Jane <jane@acme.com>, bob@beta.io"),
+/// lowercased. Returns `None` if any entry isn't a valid address.
+pub fn parse_address_list(field: &str) -> Option<Vec<String>> {
+    let mut entries = Vec::new();
+    let mut quoted = false;
+    let mut start = 0;
+    for (i, c) in field.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                entries.push(&field[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    entries.push(&field[start..]);
+
+    let mut addresses = Vec::new();
+    for entry in entries.into_iter().map(str::trim).filter(|e| !e.is_empty()) {
+        let address = match (entry.rfind('<'), entry.rfind('>')) {
+            (Some(open), Some(close)) if open < close => &entry[open + 1..close],
+            _ => entry,
+        };
+        let address = address.trim().to_lowercase();
+        let (local, domain) = address.split_once('@')?;
+        if local.is_empty() || !domain.contains('.') || address.contains(char::is_whitespace) {
+            return None;
+        }
+        addresses.push(address);
+    }
+    Some(addresses)
+}
+
+/// Build the RFC 2822 text of a message
+pub fn build_message(message: &OutgoingMessage) -> String {
+    let mut headers = vec![format!("To: {}", header_value(&message.to))];
+    if let Some(cc) = message.cc.as_deref().map(header_value) {
+        if !cc.is_empty() {
+            headers.push(format!("Cc: {}", cc));
+        }
+    }
+    headers.push(format!("Subject: {}", encode_header(&message.subject)));
+    if let Some(in_reply_to) = message.in_reply_to.as_deref().map(header_value) {
+        if !in_reply_to.is_empty() {
+            headers.push(format!("In-Reply-To: {}", in_reply_to));
+        }
+    }
+    if let Some(references) = message.references.as_deref().map(header_value) {
+        if !references.is_empty() {
+            headers.push(format!("References: {}", references));
+        }
+    }
+    headers.push("MIME-Version: 1.0".to_string());
+    headers.push("Content-Type: text/plain; charset=\"UTF-8\"".to_string());
+    headers.push("Content-Transfer-Encoding: base64".to_string());
+
+    let body = message.body.replace("\r\n", "\n").replace('\n', "\r\n");
+    let encoded = STANDARD.encode(body.as_bytes());
+    let body_lines: Vec<&str> = encoded
+        .as_bytes()
+        .chunks(BODY_LINE_LENGTH)
+        .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+        .collect();
+
+    format!(
+        "{}\r\n\r\n{}\r\n",
+        headers.join("\r\n"),
+        body_lines.join("\r\n")
+    )
+}
+
+/// Base64url encoding of a message, as expected by `messages.send`
+pub fn encode_raw(message: &str) -> String {
+    URL_SAFE.encode(message.as_bytes())
+}
@@ -1,2 +1,7 @@
 pub mod gmail_api;
+pub mod gmail_labels;
 pub mod gmail_oauth;
+pub mod gmail_tokens;
+pub mod job_sources;
+pub mod mime;
+pub mod scan_filters;
@@ -0,0 +1,198 @@
+//! Per-user pre-filters that narrow what a scan asks Gmail for
+//!
+//! Filters compose into the Gmail search (`q`) string after the date range:
+//! included labels and categories and allowed keywords each match any of
+//! their terms, excluded ones and blocked senders are negated one by one.
+
+use serde::{Deserialize, Serialize};
+
+/// Gmail's inbox categories
+pub const CATEGORIES: [&str; 5] = ["primary", "social", "promotions", "updates", "forums"];
+
+/// Most terms in one list
+pub const MAX_TERMS: usize = 50;
+
+/// Longest single term
+pub const MAX_TERM_LENGTH: usize = 100;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScanFilters {
+    /// Only messages with one of these labels
+    pub include_labels: Vec<String>,
+    pub exclude_labels: Vec<String>,
+    /// Only messages in one of these categories
+    pub include_categories: Vec<String>,
+    pub exclude_categories: Vec<String>,
+    /// Only messages containing one of these words or phrases
+    pub allow_keywords: Vec<String>,
+    pub deny_keywords: Vec<String>,
+    /// Addresses or domains whose mail is never fetched
+    pub blocked_senders: Vec<String>,
+}
+
+impl ScanFilters {
+    /// Trim every term, drop what Gmail would read as query syntax and
+    /// remove empty and repeated terms
+    pub fn normalized(&self) -> ScanFilters {
+        let lower =
+            |terms: &[String]| -> Vec<String> { terms.iter().map(|t| t.to_lowercase()).collect() };
+        ScanFilters {
+            include_labels: clean_terms(&self.include_labels),
+            exclude_labels: clean_terms(&self.exclude_labels),
+            include_categories: clean_terms(&lower(&self.include_categories)),
+            exclude_categories: clean_terms(&lower(&self.exclude_categories)),
+            allow_keywords: clean_terms(&self.allow_keywords),
+            deny_keywords: clean_terms(&self.deny_keywords),
+            blocked_senders: clean_terms(&lower(&self.blocked_senders)),
+        }
+    }
+
+    /// Check normalized filters before they're saved
+    pub fn validate(&self) -> Result<(), String> {
+        let lists = [
+            ("include_labels", &self.include_labels),
+            ("exclude_labels", &self.exclude_labels),
+            ("include_categories", &self.include_categories),
+            ("exclude_categories", &self.exclude_categories),
+            ("allow_keywords", &self.allow_keywords),
+            ("deny_keywords", &self.deny_keywords),
+            ("blocked_senders", &self.blocked_senders),
+        ];
+        for (name, terms) in lists {
+            if terms.len() > MAX_TERMS {
+                return Err(format!("{} has more than {} entries", name, MAX_TERMS));
+            }
+            if let Some(term) = terms.iter().find(|t| t.chars().count() > MAX_TERM_LENGTH) {
+                return Err(format!(
+                    "\"{}\" in {} is longer than {} characters",
+                    term, name, MAX_TERM_LENGTH
+                ));
+            }
+        }
+
+        for category in self
+            .include_categories
+            .iter()
+            .chain(&self.exclude_categories)
+        {
+            if !CATEGORIES.contains(&category.as_str()) {
+                return Err(format!(
+                    "Unknown category \"{}\", expected one of {}",
+                    category,
+                    CATEGORIES.join(", ")
+                ));
+            }
+        }
+
+        let both = |included: &[String], excluded: &[String]| {
+            included
+                .iter()
+                .find(|i| excluded.iter().any(|e| e.eq_ignore_ascii_case(i)))
+                .cloned()
+        };
+        if let Some(label) = both(&self.include_labels, &self.exclude_labels) {
+            return Err(format!("Label \"{}\" is both included and excluded", label));
+        }
+        if let Some(category) = both(&self.include_categories, &self.exclude_categories) {
+            return Err(format!(
+                "Category \"{}\" is both included and excluded",
+                category
+            ));
+        }
+        if let Some(keyword) = both(&self.allow_keywords, &self.deny_keywords) {
+            return Err(format!(
+                "Keyword \"{}\" is both allowed and denied",
+                keyword
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// The Gmail search terms of these filters, empty when there are none
+    pub fn to_query(&self) -> String {
+        let labels = |terms: &[String]| terms.iter().map(|t| label_term(t)).collect::<Vec<_>>();
+        let quoted = |terms: &[String]| terms.iter().map(|t| quote(t)).collect::<Vec<_>>();
+
+        let mut parts = Vec::new();
+        parts.extend(any_of("label:", &labels(&self.include_labels)));
+        parts.extend(any_of("category:", &self.include_categories));
+        parts.extend(any_of("", &quoted(&self.allow_keywords)));
+        parts.extend(none_of("label:", &labels(&self.exclude_labels)));
+        parts.extend(none_of("category:", &self.exclude_categories));
+        parts.extend(none_of("", &quoted(&self.deny_keywords)));
+        parts.extend(none_of("from:", &quoted(&self.blocked_senders)));
+        parts.join(" ")
+    }
+}
+
+/// The full search of a scan: its date range narrowed by the user's filters
+pub fn build_query(date_query: &str, filters: &ScanFilters) -> String {
+    let filter_query = filters.normalized().to_query();
+    if filter_query.is_empty() {
+        date_query.to_string()
+    } else {
+        format!("{} {}", date_query, filter_query)
+    }
+}
+
+fn clean_terms(terms: &[String]) -> Vec<String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for term in terms {
+        let term = clean(term);
+        if !term.is_empty() && !cleaned.iter().any(|c| c.eq_ignore_ascii_case(&term)) {
+            cleaned.push(term);
+        }
+    }
+    cleaned
+}
+
+/// Strip quotes, braces and parentheses and a leading `-` so a term can't
+/// change the structure of the query, and collapse whitespace
+fn clean(term: &str) -> String {
+    let stripped: String = term
+        .chars()
+        .filter(|c| !matches!(c, '"' | '{' | '}' | '(' | ')'))
+        .collect();
+    stripped
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_start_matches('-')
+        .trim()
+        .to_string()
+}
+
+/// Gmail searches labels by name with spaces and slashes as dashes
+fn label_term(label: &str) -> String {
+    label
+        .to_lowercase()
+        .replace(|c: char| c.is_whitespace() || c == '/', "-")
+}
+
+/// Quote a keyword or sender so Gmail matches it literally. Unquoted, terms
+/// like `OR`, `has:attachment` or `from:boss@x.com` would be read as operators.
+fn quote(term: &str) -> String {
+    format!("\"{}\"", term)
+}
+
+/// `prefix:a` for one term, `{prefix:a prefix:b}` matching either for more
+fn any_of(prefix: &str, terms: &[String]) -> Option<String> {
+    match terms {
+        [] => None,
+        [term] => Some(format!("{}{}", prefix, term)),
+        _ => Some(format!(
+            "{{{}}}",
+            terms
+                .iter()
+                .map(|t| format!("{}{}", prefix, t))
+                .collect::<Vec<_>>()
+                .join(" ")
+        )),
+    }
+}
+
+fn none_of(prefix: &str, terms: &[String]) -> Vec<String> {
+    terms.iter().map(|t| format!("-{}{}", prefix, t)).collect()
+}
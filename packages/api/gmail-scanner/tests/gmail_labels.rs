@@ -0,0 +1,36 @@
+//! Tests for the labels written back to Gmail
+
+use api_gmail_scanner::services::gmail_labels::{
+    label_change, APPLICATION_LABEL, INTERVIEW_LABEL, LINKED_LABEL, REJECTION_LABEL,
+};
+
+#[test]
+fn test_classification_and_assignment_are_labeled() {
+    let change = label_change(Some("interview"), true);
+    assert_eq!(change.add, vec![INTERVIEW_LABEL, LINKED_LABEL]);
+    assert_eq!(change.remove, vec![REJECTION_LABEL, APPLICATION_LABEL]);
+
+    let change = label_change(Some("application_sent"), false);
+    assert_eq!(change.add, vec![APPLICATION_LABEL]);
+    assert_eq!(
+        change.remove,
+        vec![INTERVIEW_LABEL, REJECTION_LABEL, LINKED_LABEL]
+    );
+}
+
+#[test]
+fn test_unassigned_unlabeled_categories_remove_everything() {
+    for category in [None, Some("other"), Some("new_job")] {
+        let change = label_change(category, false);
+        assert!(change.add.is_empty());
+        assert_eq!(
+            change.remove,
+            vec![
+                INTERVIEW_LABEL,
+                REJECTION_LABEL,
+                APPLICATION_LABEL,
+                LINKED_LABEL
+            ]
+        );
+    }
+}
@@ -0,0 +1,81 @@
+//! Tests for inferring job sources from email senders
+
+use api_gmail_scanner::services::job_sources::{
+    matches_source_pattern, sender_domain, source_for_domain, SourcePattern,
+};
+
+#[test]
+fn test_sender_domain() {
+    assert_eq!(
+        sender_domain("LinkedIn <jobs-noreply@LinkedIn.com>"),
+        Some("linkedin.com".to_string())
+    );
+    assert_eq!(
+        sender_domain("no-reply@us.greenhouse.io"),
+        Some("us.greenhouse.io".to_string())
+    );
+    assert_eq!(
+        sender_domain("\"Jane @ Acme\" <jane@acme.com>"),
+        Some("acme.com".to_string())
+    );
+    assert_eq!(sender_domain("not an email"), None);
+    assert_eq!(sender_domain("broken@"), None);
+}
+
+fn pattern(domain_pattern: &str, source_id: &str) -> SourcePattern {
+    SourcePattern {
+        domain_pattern: domain_pattern.to_string(),
+        source_id: source_id.to_string(),
+    }
+}
+
+#[test]
+fn test_source_pattern_matches_domain_and_subdomains() {
+    assert!(matches_source_pattern("*.greenhouse.io", "greenhouse.io"));
+    assert!(matches_source_pattern(
+        "*.greenhouse.io",
+        "us.greenhouse.io"
+    ));
+    assert!(matches_source_pattern(
+        "*.greenhouse.io",
+        "a.b.greenhouse.io"
+    ));
+
+    // Look-alike domains and bare patterns never match
+    assert!(!matches_source_pattern(
+        "*.greenhouse.io",
+        "evilgreenhouse.io"
+    ));
+    assert!(!matches_source_pattern(
+        "*.greenhouse.io",
+        "greenhouse.io.evil.com"
+    ));
+    assert!(!matches_source_pattern("greenhouse.io", "greenhouse.io"));
+}
+
+#[test]
+fn test_source_pattern_is_literal() {
+    // `_` and `%` are plain characters, not wildcards
+    assert!(!matches_source_pattern("*.green_ouse.io", "greenhouse.io"));
+    assert!(!matches_source_pattern("*.%.io", "greenhouse.io"));
+    assert!(matches_source_pattern("*.green_ouse.io", "green_ouse.io"));
+}
+
+#[test]
+fn test_source_for_domain() {
+    let patterns = vec![
+        pattern("*.linkedin.com", "linkedin"),
+        pattern("*.greenhouse.io", "company_website"),
+    ];
+
+    assert_eq!(
+        source_for_domain("linkedin.com", &patterns),
+        Some("linkedin")
+    );
+    assert_eq!(
+        source_for_domain("us.greenhouse.io", &patterns),
+        Some("company_website")
+    );
+    assert_eq!(source_for_domain("evilgreenhouse.io", &patterns), None);
+    assert_eq!(source_for_domain("acme.com", &patterns), None);
+}
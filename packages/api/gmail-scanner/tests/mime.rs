@@ -0,0 +1,84 @@
+//! Tests for composing outgoing messages
+
+use api_gmail_scanner::services::mime::{
+    build_message, build_references, encode_header, parse_address_list, reply_subject,
+    OutgoingMessage,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+fn decoded_body(message: &str) -> String {
+    let (_, body) = message.split_once("\r\n\r\n").unwrap();
+    let bytes = STANDARD.decode(body.replace("\r\n", "")).unwrap();
+    String::from_utf8(bytes).unwrap()
+}
+
+#[test]
+fn test_reply_headers() {
+    let message = build_message(&OutgoingMessage {
+        to: "Jane <jane@acme.com>".to_string(),
+        cc: Some("bob@acme.com".to_string()),
+        subject: reply_subject("Interview next week"),
+        body: "Thanks Jane!\nSee you then.".to_string(),
+        in_reply_to: Some("<b@mail.acme.com>".to_string()),
+        references: Some(build_references(Some("<a@mail.acme.com>"), "<b@mail.acme.com>")),
+    });
+
+    assert!(message.starts_with("To: Jane <jane@acme.com>\r\nCc: bob@acme.com\r\n"));
+    assert!(message.contains("\r\nSubject: Re: Interview next week\r\n"));
+    assert!(message.contains("\r\nIn-Reply-To: <b@mail.acme.com>\r\n"));
+    assert!(message.contains("\r\nReferences: <a@mail.acme.com> <b@mail.acme.com>\r\n"));
+    assert!(message.contains("\r\nContent-Type: text/plain; charset=\"UTF-8\"\r\n"));
+    assert_eq!(decoded_body(&message), "Thanks Jane!\r\nSee you then.");
+}
+
+#[test]
+fn test_new_message_has_no_threading_headers() {
+    let message = build_message(&OutgoingMessage {
+        to: "jane@acme.com".to_string(),
+        subject: "Following up".to_string(),
+        body: "Hi".to_string(),
+        ..Default::default()
+    });
+
+    assert!(!message.contains("In-Reply-To"));
+    assert!(!message.contains("References"));
+    assert!(!message.contains("Cc:"));
+}
+
+#[test]
+fn test_header_injection_and_encoding() {
+    let message = build_message(&OutgoingMessage {
+        to: "jane@acme.com\r\nBcc: evil@example.com".to_string(),
+        subject: "Merci beaucoup – entretien".to_string(),
+        body: "x".repeat(200),
+        ..Default::default()
+    });
+
+    assert!(!message.contains("\r\nBcc:"));
+    assert!(message.contains(&format!(
+        "Subject: {}\r\n",
+        encode_header("Merci beaucoup – entretien")
+    )));
+    assert!(encode_header("Merci beaucoup – entretien").starts_with("=?UTF-8?B?"));
+    let (_, body) = message.split_once("\r\n\r\n").unwrap();
+    assert!(body.split("\r\n").all(|line| line.len() <= 76));
+}
+
+#[test]
+fn test_reply_subject_and_references() {
+    assert_eq!(reply_subject("RE: Offer"), "RE: Offer");
+    assert_eq!(reply_subject(" Offer "), "Re: Offer");
+    assert_eq!(build_references(None, "<a@x>"), "<a@x>");
+    assert_eq!(build_references(Some("<a@x> <b@x>"), "<b@x>"), "<a@x> <b@x>");
+}
+
+#[test]
+fn test_parse_address_list() {
+    assert_eq!(
+        parse_address_list("\"Doe, Jane\" <Jane@Acme.com>, bob@beta.io"),
+        Some(vec!["jane@acme.com".to_string(), "bob@beta.io".to_string()])
+    );
+    assert_eq!(parse_address_list("not an address"), None);
+    assert_eq!(parse_address_list("jane@localhost"), None);
+    assert_eq!(parse_address_list(""), Some(vec![]));
+}
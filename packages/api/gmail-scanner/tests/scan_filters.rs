@@ -0,0 +1,97 @@
+//! Tests for composing scan pre-filters into Gmail searches
+
+use api_gmail_scanner::services::scan_filters::{build_query, ScanFilters, MAX_TERMS};
+
+fn terms(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+const DATES: &str = "after:1704067200 before:1704672000";
+
+#[test]
+fn test_no_filters_keep_the_date_query() {
+    assert_eq!(build_query(DATES, &ScanFilters::default()), DATES);
+}
+
+#[test]
+fn test_filters_compose_into_the_query() {
+    let filters = ScanFilters {
+        include_labels: terms(&["Jobs/Applications"]),
+        exclude_labels: terms(&["Receipts", "Old Stuff"]),
+        include_categories: terms(&["Primary", "updates"]),
+        exclude_categories: terms(&["promotions", "social"]),
+        allow_keywords: terms(&["interview", "job offer"]),
+        deny_keywords: terms(&["unsubscribe"]),
+        blocked_senders: terms(&["News@Medium.com", "linkedin.com"]),
+    };
+
+    assert_eq!(
+        build_query(DATES, &filters),
+        "after:1704067200 before:1704672000 label:jobs-applications \
+         {category:primary category:updates} {\"interview\" \"job offer\"} \
+         -label:receipts -label:old-stuff -category:promotions -category:social \
+         -\"unsubscribe\" -from:\"news@medium.com\" -from:\"linkedin.com\""
+    );
+}
+
+#[test]
+fn test_operator_terms_stay_literal() {
+    let filters = ScanFilters {
+        allow_keywords: terms(&["OR", "has:attachment"]),
+        deny_keywords: terms(&["AND", "from:boss@x.com"]),
+        blocked_senders: terms(&["is:unread"]),
+        ..ScanFilters::default()
+    };
+
+    assert_eq!(
+        build_query(DATES, &filters),
+        "after:1704067200 before:1704672000 {\"OR\" \"has:attachment\"} \
+         -\"AND\" -\"from:boss@x.com\" -from:\"is:unread\""
+    );
+}
+
+#[test]
+fn test_normalized_drops_query_syntax_and_repeats() {
+    let filters = ScanFilters {
+        allow_keywords: terms(&["  offer ", "Offer", "", "{remote}", "-(recruiter)"]),
+        ..ScanFilters::default()
+    }
+    .normalized();
+
+    assert_eq!(
+        filters.allow_keywords,
+        terms(&["offer", "remote", "recruiter"])
+    );
+}
+
+#[test]
+fn test_validate_rejects_bad_filters() {
+    let unknown = ScanFilters {
+        exclude_categories: terms(&["spam"]),
+        ..ScanFilters::default()
+    };
+    assert!(unknown.validate().unwrap_err().contains("Unknown category"));
+
+    let conflicting = ScanFilters {
+        include_labels: terms(&["Jobs"]),
+        exclude_labels: terms(&["jobs"]),
+        ..ScanFilters::default()
+    };
+    assert!(conflicting
+        .validate()
+        .unwrap_err()
+        .contains("both included and excluded"));
+
+    let too_many = ScanFilters {
+        blocked_senders: (0..=MAX_TERMS).map(|i| format!("s{}@x.com", i)).collect(),
+        ..ScanFilters::default()
+    };
+    assert!(too_many.validate().is_err());
+
+    let valid = ScanFilters {
+        exclude_categories: terms(&["promotions"]),
+        blocked_senders: terms(&["noreply@example.com"]),
+        ..ScanFilters::default()
+    };
+    assert!(valid.validate().is_ok());
+}
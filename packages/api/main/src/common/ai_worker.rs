@@ -0,0 +1,15 @@
+use worker::*;
+
+/// Pass on a user over their daily AI quota as a 429 with the worker's
+/// Retry-After, so the client knows when to try again
+pub async fn quota_exceeded(response: &mut Response) -> Result<Option<Response>> {
+    if response.status_code() != 429 {
+        return Ok(None);
+    }
+    let headers = Headers::new();
+    if let Some(retry_after) = response.headers().get("Retry-After")? {
+        headers.set("Retry-After", &retry_after)?;
+    }
+    let text = response.text().await?;
+    Ok(Some(Response::error(text, 429)?.with_headers(headers)))
+}
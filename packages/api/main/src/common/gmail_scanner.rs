@@ -0,0 +1,47 @@
+use api_shared::requests::gmail_scanner_request;
+use std::collections::BTreeMap;
+use worker::*;
+
+/// Have the scanner bring the Gmail labels of these emails in line with
+/// their current category and job link. It skips users who haven't turned
+/// labels on, and failures are only logged since labels never block changes.
+pub async fn sync_gmail_labels(env: &Env, user_id: &str, email_ids: &[String]) {
+    if email_ids.is_empty() {
+        return;
+    }
+    let result = async {
+        let body = serde_json::to_string(&serde_json::json!({
+            "user_id": user_id,
+            "email_ids": email_ids
+        }))?;
+        let request = gmail_scanner_request(env, "/labels/sync", &body)?;
+        let mut response = Fetch::Request(request).send().await?;
+        if response.status_code() != 200 {
+            let text = response.text().await?;
+            return Err(worker::Error::RustError(format!(
+                "Gmail scanner error: {}",
+                text
+            )));
+        }
+        Ok(())
+    }
+    .await;
+
+    if let Err(e) = result {
+        console_log!("Error syncing Gmail labels: {}", e);
+    }
+}
+
+/// Sync the labels of `(user_id, gmail_id)` pairs, one request per user
+pub async fn sync_email_labels(env: &Env, emails: &[(String, String)]) {
+    let mut by_user: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for (user_id, gmail_id) in emails {
+        by_user
+            .entry(user_id.as_str())
+            .or_default()
+            .push(gmail_id.clone());
+    }
+    for (user_id, email_ids) in by_user {
+        sync_gmail_labels(env, user_id, &email_ids).await;
+    }
+}
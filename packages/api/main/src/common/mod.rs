@@ -1,3 +1,5 @@
+pub mod ai_worker;
 pub mod auth;
 pub mod cors;
 pub mod db;
+pub mod gmail_scanner;
@@ -0,0 +1,37 @@
+//! Daily AI processing stats for the admin dashboard
+
+use crate::common::auth::require_admin;
+use crate::common::db::get_d1;
+use crate::services::ai_stats::summarize_days;
+use crate::services::db::ai_stats::list_ai_daily_stats;
+use serde_json::json;
+use worker::*;
+
+const DEFAULT_STATS_DAYS: u32 = 30;
+const MAX_STATS_DAYS: u32 = 365;
+
+/// Daily stats of the last `days` days (30 by default) with their totals
+pub async fn get_ai_stats(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let _admin_id = require_admin(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let url = req.url()?;
+    let days = url
+        .query_pairs()
+        .find(|(key, _)| key == "days")
+        .and_then(|(_, value)| value.parse::<u32>().ok())
+        .unwrap_or(DEFAULT_STATS_DAYS)
+        .clamp(1, MAX_STATS_DAYS);
+
+    let db = get_d1(&ctx.env)?;
+    let daily = list_ai_daily_stats(&db, days)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to get AI stats: {}", e)))?;
+    let totals = summarize_days(&daily);
+
+    Response::from_json(&json!({
+        "days": daily,
+        "totals": totals
+    }))
+}
@@ -0,0 +1,391 @@
+//! Prompt evaluation: labelled datasets and scored runs of candidate prompts
+
+use crate::common::auth::require_admin;
+use crate::common::db::get_d1;
+use crate::services::ai_eval::{
+    build_report, sample_evenly, EvalCase, CATEGORIES, MAX_EVAL_EXAMPLES,
+};
+use crate::services::db::ai_eval::{
+    add_example, create_dataset, dataset_exists, delete_example, get_run, list_datasets,
+    list_examples, list_runs, save_run, EvalExample, EvalRun, NewEvalExample,
+};
+use crate::services::db::ai_prompts::{get_active_prompt, get_prompt, AiPrompt};
+use crate::services::db::ai_reviews::list_reviews;
+use api_shared::requests::ai_worker_request;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use serde_json::{json, Map, Value};
+use worker::*;
+
+/// Stages whose prompts can be evaluated against labelled examples
+const EVAL_STAGES: [&str; 2] = ["classify", "extract"];
+
+/// Category recorded for examples the AI worker couldn't process
+const FAILED_PREDICTION: &str = "failed";
+
+/// Examples sent to the AI worker per request
+const EVAL_CHUNK_SIZE: usize = 10;
+
+/// Requests to the AI worker in flight at once for one prompt
+const EVAL_CONCURRENCY: usize = 3;
+
+pub async fn datasets(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let _admin_id = require_admin(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let db = get_d1(&ctx.env)?;
+
+    if req.method() == Method::Post {
+        #[derive(serde::Deserialize)]
+        struct CreateRequest {
+            name: String,
+            description: Option<String>,
+        }
+
+        let create_data: CreateRequest = match req.json().await {
+            Ok(body) => body,
+            Err(_) => return Response::error("name is required", 400),
+        };
+        if create_data.name.trim().is_empty() {
+            return Response::error("name is required", 400);
+        }
+
+        let id = create_dataset(
+            &db,
+            create_data.name.trim(),
+            create_data.description.as_deref(),
+        )
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to create dataset: {}", e)))?;
+
+        return Response::from_json(&json!({
+            "id": id,
+            "success": true
+        }));
+    }
+
+    let datasets = list_datasets(&db)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to list datasets: {}", e)))?;
+
+    Response::from_json(&datasets)
+}
+
+pub async fn examples(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let _admin_id = require_admin(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let dataset_id = ctx
+        .param("id")
+        .ok_or_else(|| worker::Error::RustError("Missing dataset id".to_string()))?;
+
+    let db = get_d1(&ctx.env)?;
+    if !dataset_exists(&db, dataset_id)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to get dataset: {}", e)))?
+    {
+        return Response::error("Dataset not found", 404);
+    }
+
+    match (req.method(), ctx.param("example_id")) {
+        (Method::Get, None) => {
+            let examples = list_examples(&db, dataset_id)
+                .await
+                .map_err(|e| worker::Error::RustError(format!("Failed to list examples: {}", e)))?;
+            Response::from_json(&examples)
+        }
+        (Method::Post, None) => {
+            let example: NewEvalExample = match req.json().await {
+                Ok(body) => body,
+                Err(_) => return Response::error("Invalid request body", 400),
+            };
+            if !CATEGORIES.contains(&example.expected_category.as_str()) {
+                return Response::error(
+                    format!(
+                        "expected_category must be one of: {}",
+                        CATEGORIES.join(", ")
+                    ),
+                    400,
+                );
+            }
+            if example.email_id.is_none() && example.subject.is_none() && example.body.is_none() {
+                return Response::error("email_id or the email content is required", 400);
+            }
+
+            match add_example(&db, dataset_id, example)
+                .await
+                .map_err(|e| worker::Error::RustError(format!("Failed to add example: {}", e)))?
+            {
+                Some(id) => Response::from_json(&json!({
+                    "id": id,
+                    "success": true
+                })),
+                None => Response::error("Email not found", 404),
+            }
+        }
+        (Method::Delete, Some(example_id)) => {
+            delete_example(&db, dataset_id, example_id)
+                .await
+                .map_err(|e| {
+                    worker::Error::RustError(format!("Failed to delete example: {}", e))
+                })?;
+            Response::from_json(&json!({
+                "success": true
+            }))
+        }
+        _ => Response::error("Method not allowed", 405),
+    }
+}
+
+/// Copy reviewed AI results into a dataset as labelled examples, skipping
+/// emails the dataset already has
+pub async fn import_reviews(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let _admin_id = require_admin(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let dataset_id = ctx
+        .param("id")
+        .ok_or_else(|| worker::Error::RustError("Missing dataset id".to_string()))?;
+
+    #[derive(serde::Deserialize, Default)]
+    struct ImportRequest {
+        #[serde(default)]
+        corrected_only: bool,
+    }
+
+    let import_data: ImportRequest = req.json().await.unwrap_or_default();
+
+    let db = get_d1(&ctx.env)?;
+    if !dataset_exists(&db, dataset_id)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to get dataset: {}", e)))?
+    {
+        return Response::error("Dataset not found", 404);
+    }
+
+    let existing: Vec<String> = list_examples(&db, dataset_id)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to list examples: {}", e)))?
+        .into_iter()
+        .filter_map(|example| example.email_id)
+        .collect();
+    let reviews = list_reviews(&db, import_data.corrected_only)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to list reviews: {}", e)))?;
+
+    let mut imported = 0;
+    for review in reviews {
+        if existing.contains(&review.email_id) {
+            continue;
+        }
+        let example = NewEvalExample {
+            email_id: Some(review.email_id),
+            from_email: review.from_email,
+            subject: review.subject,
+            body: review.body,
+            expected_category: review.category,
+            expected_fields: review.fields,
+        };
+        if add_example(&db, dataset_id, example)
+            .await
+            .map_err(|e| worker::Error::RustError(format!("Failed to add example: {}", e)))?
+            .is_some()
+        {
+            imported += 1;
+        }
+    }
+
+    Response::from_json(&json!({
+        "imported": imported,
+        "success": true
+    }))
+}
+
+pub async fn runs(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let _admin_id = require_admin(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let url = req.url()?;
+    let query_params = url
+        .query_pairs()
+        .collect::<std::collections::HashMap<_, _>>();
+    let Some(dataset_id) = query_params.get("dataset_id") else {
+        return Response::error("dataset_id is required", 400);
+    };
+
+    let db = get_d1(&ctx.env)?;
+    let runs = list_runs(&db, dataset_id)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to list runs: {}", e)))?;
+
+    Response::from_json(&runs)
+}
+
+/// Evaluate a candidate prompt against a dataset, together with the active
+/// prompt of the same stage so the two can be compared before activating
+pub async fn run(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let _admin_id = require_admin(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    #[derive(serde::Deserialize)]
+    struct RunRequest {
+        dataset_id: String,
+        prompt_id: String,
+    }
+
+    let run_data: RunRequest = match req.json().await {
+        Ok(body) => body,
+        Err(_) => return Response::error("dataset_id and prompt_id are required", 400),
+    };
+
+    let db = get_d1(&ctx.env)?;
+
+    let candidate = match get_prompt(&db, &run_data.prompt_id)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to get prompt: {}", e)))?
+    {
+        Some(prompt) => prompt,
+        None => return Response::error("Prompt not found", 404),
+    };
+    if !EVAL_STAGES.contains(&candidate.stage.as_str()) {
+        return Response::error(
+            format!(
+                "Only {} prompts can be evaluated",
+                EVAL_STAGES.join(" and ")
+            ),
+            400,
+        );
+    }
+
+    let examples = list_examples(&db, &run_data.dataset_id)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to list examples: {}", e)))?;
+    if examples.is_empty() {
+        return Response::error("Dataset has no examples", 400);
+    }
+
+    let examples = sample_evenly(&examples, MAX_EVAL_EXAMPLES);
+
+    let active = get_active_prompt(&db, &candidate.stage)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to get active prompt: {}", e)))?
+        .filter(|a| a.id != candidate.id);
+
+    // The candidate and the baseline are scored at the same time
+    let candidate_eval =
+        evaluate_prompt(&ctx.env, &db, &run_data.dataset_id, &candidate, &examples);
+    let baseline_eval = async {
+        match &active {
+            Some(active) => evaluate_prompt(&ctx.env, &db, &run_data.dataset_id, active, &examples)
+                .await
+                .map(Some),
+            None => Ok(None),
+        }
+    };
+    let (candidate_run, baseline_run) = futures::join!(candidate_eval, baseline_eval);
+    let (candidate_run, baseline_run) = (candidate_run?, baseline_run?);
+
+    Response::from_json(&json!({
+        "candidate": candidate_run,
+        "baseline": baseline_run
+    }))
+}
+
+/// Predictions of the AI worker for one chunk of examples
+async fn evaluate_chunk(
+    env: &Env,
+    prompt_key: &str,
+    prompt: &str,
+    examples: &[EvalExample],
+) -> Result<Vec<Value>> {
+    let emails: Vec<Value> = examples
+        .iter()
+        .map(|example| {
+            json!({
+                "id": example.id,
+                "from_email": example.from_email,
+                "subject": example.subject,
+                "body": example.body
+            })
+        })
+        .collect();
+    let request_body = json!({
+        prompt_key: prompt,
+        "emails": emails
+    });
+
+    let body = serde_json::to_string(&request_body)?;
+    let request = ai_worker_request(env, "/evaluate", &body)?;
+
+    let mut response = Fetch::Request(request).send().await?;
+    if response.status_code() != 200 {
+        let text = response.text().await?;
+        return Err(worker::Error::RustError(format!(
+            "AI worker error: {}",
+            text
+        )));
+    }
+    let result: Value = response.json().await?;
+    Ok(result
+        .get("predictions")
+        .and_then(|p| p.as_array())
+        .cloned()
+        .unwrap_or_default())
+}
+
+/// Run a prompt over the examples with the AI worker, score and save the run
+async fn evaluate_prompt(
+    env: &Env,
+    db: &D1Database,
+    dataset_id: &str,
+    prompt: &AiPrompt,
+    examples: &[EvalExample],
+) -> Result<EvalRun> {
+    // A few chunks at a time, so no single worker request runs too long
+    let prompt_key = format!("{}_prompt", prompt.stage);
+    let prompt_key = prompt_key.as_str();
+    let predictions: Vec<Value> = stream::iter(examples.chunks(EVAL_CHUNK_SIZE))
+        .map(|chunk| evaluate_chunk(env, prompt_key, &prompt.prompt, chunk))
+        .buffered(EVAL_CONCURRENCY)
+        .try_concat()
+        .await?;
+
+    let cases: Vec<EvalCase> = examples
+        .iter()
+        .map(|example| {
+            let prediction = predictions
+                .iter()
+                .find(|p| p.get("id").and_then(|id| id.as_str()) == Some(example.id.as_str()));
+            EvalCase {
+                expected_category: example.expected_category.clone(),
+                expected_fields: example.expected_fields.clone(),
+                // Emails the worker failed on show up as their own label
+                // instead of counting as "other"
+                predicted_category: prediction
+                    .and_then(|p| p.get("category"))
+                    .and_then(|c| c.as_str())
+                    .unwrap_or(FAILED_PREDICTION)
+                    .to_string(),
+                predicted_fields: prediction
+                    .and_then(|p| p.get("extraction"))
+                    .and_then(|e| e.as_object())
+                    .cloned()
+                    .unwrap_or_else(Map::new),
+            }
+        })
+        .collect();
+
+    let report = build_report(&cases);
+    let run_id = save_run(db, dataset_id, &prompt.id, &prompt.stage, &report)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to save run: {}", e)))?;
+
+    get_run(db, &run_id)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to get run: {}", e)))?
+        .ok_or_else(|| worker::Error::RustError("Run not found".to_string()))
+}
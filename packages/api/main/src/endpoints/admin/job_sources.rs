@@ -0,0 +1,36 @@
+use crate::common::auth::require_admin;
+use crate::common::db::get_d1;
+use crate::services::job_sources::{create_source, delete_source};
+use worker::*;
+
+pub async fn create(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let _admin_id = require_admin(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    #[derive(serde::Deserialize)]
+    struct CreateRequest {
+        name: String,
+    }
+
+    let create_data: CreateRequest = match req.json().await {
+        Ok(body) => body,
+        Err(_) => return Response::error("name is required", 400),
+    };
+
+    let db = get_d1(&ctx.env)?;
+    create_source(&db, &create_data.name).await
+}
+
+pub async fn delete(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let _admin_id = require_admin(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let source_id = ctx
+        .param("id")
+        .ok_or_else(|| worker::Error::RustError("Missing source id".to_string()))?;
+
+    let db = get_d1(&ctx.env)?;
+    delete_source(&db, source_id).await
+}
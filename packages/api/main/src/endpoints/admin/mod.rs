@@ -1,3 +1,7 @@
+pub mod ai_stats;
+pub mod eval;
+pub mod job_sources;
 pub mod prompts;
 pub mod stats;
+pub mod usage;
 pub mod users;
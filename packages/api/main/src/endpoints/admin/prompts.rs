@@ -1,6 +1,9 @@
 use crate::common::auth::require_admin;
 use crate::common::db::get_d1;
-use crate::services::db::ai_prompts::{activate_prompt, create_prompt, list_prompts};
+use crate::services::db::ai_prompts::{
+    activate_prompt, create_prompt, list_prompts, PromptModelSettings,
+};
+use api_shared::requests::ai_worker_request;
 use serde_json::json;
 use worker::*;
 
@@ -23,6 +26,34 @@ pub async fn list(req: Request, ctx: RouteContext<()>) -> Result<Response> {
     Response::from_json(&prompts)
 }
 
+/// Providers the AI worker can call. Model specs without one of these
+/// prefixes are Workers AI models.
+const MODEL_PROVIDERS: [&str; 2] = ["workers-ai", "openai"];
+
+/// Check the model settings of a new prompt, returning the problem if any
+fn validate_model_settings(settings: &PromptModelSettings) -> Option<String> {
+    if let Some(models) = &settings.models {
+        for spec in models {
+            let model = match spec.trim().split_once(':') {
+                Some((provider, model)) if MODEL_PROVIDERS.contains(&provider) => model,
+                _ => spec.as_str(),
+            };
+            if model.trim().is_empty() {
+                return Some(format!("Invalid model: \"{}\"", spec));
+            }
+        }
+    }
+    if let Some(temperature) = settings.temperature {
+        if !(0.0..=2.0).contains(&temperature) {
+            return Some("Temperature must be between 0 and 2".to_string());
+        }
+    }
+    if settings.max_tokens == Some(0) {
+        return Some("Max tokens must be greater than 0".to_string());
+    }
+    None
+}
+
 pub async fn create(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
     let _admin_id = require_admin(&req, &ctx.env)
         .await
@@ -33,16 +64,41 @@ pub async fn create(mut req: Request, ctx: RouteContext<()>) -> Result<Response>
         name: String,
         stage: String,
         prompt: String,
+        #[serde(default)]
+        models: Option<Vec<String>>,
+        #[serde(default)]
+        temperature: Option<f64>,
+        #[serde(default)]
+        max_tokens: Option<u32>,
     }
 
     let create_data: CreateRequest = req.json().await?;
 
+    let settings = PromptModelSettings {
+        // An empty list means the defaults, same as no list
+        models: create_data
+            .models
+            .map(|models| {
+                models
+                    .into_iter()
+                    .map(|m| m.trim().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|models| !models.is_empty()),
+        temperature: create_data.temperature,
+        max_tokens: create_data.max_tokens,
+    };
+    if let Some(problem) = validate_model_settings(&settings) {
+        return Response::error(problem, 400);
+    }
+
     let db = get_d1(&ctx.env)?;
     let id = create_prompt(
         &db,
         &create_data.name,
         &create_data.stage,
         &create_data.prompt,
+        &settings,
     )
     .await
     .map_err(|e| worker::Error::RustError(format!("Failed to create prompt: {}", e)))?;
@@ -79,6 +135,15 @@ pub async fn activate(mut req: Request, ctx: RouteContext<()>) -> Result<Respons
     }))
 }
 
+/// Most emails a sandbox test runs on, since every email is three AI calls
+const MAX_TEST_EMAILS: usize = 10;
+
+/// Stages a prompt test can take draft prompts for
+const TEST_STAGES: [&str; 3] = ["classify", "extract", "summarize"];
+
+/// Run draft prompts against real emails in the AI worker's sandbox, which
+/// returns the rendered prompt, raw output, parsed JSON and timing of each
+/// stage without saving results or touching the emails
 pub async fn test(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
     let _admin_id = require_admin(&req, &ctx.env)
         .await
@@ -87,32 +152,34 @@ pub async fn test(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
     #[derive(serde::Deserialize)]
     struct TestRequest {
         email_ids: Vec<String>,
-        user_id: String,
+        #[serde(default)]
+        prompts: std::collections::HashMap<String, String>,
     }
 
     let test_data: TestRequest = req.json().await?;
 
-    // Call AI worker to process emails
-    let ai_worker_url = ctx
-        .env
-        .var("AI_WORKER_URL")
-        .map(|v| v.to_string())
-        .unwrap_or_else(|_| "http://localhost:8002".to_string());
+    if test_data.email_ids.is_empty() || test_data.email_ids.len() > MAX_TEST_EMAILS {
+        return Response::error(
+            format!("Provide between 1 and {} email ids", MAX_TEST_EMAILS),
+            400,
+        );
+    }
+    if let Some(stage) = test_data
+        .prompts
+        .keys()
+        .find(|s| !TEST_STAGES.contains(&s.as_str()))
+    {
+        return Response::error(format!("Prompts can't be tested for stage: {}", stage), 400);
+    }
 
-    let url = format!("{}/process/batch", ai_worker_url);
+    // Call AI worker sandbox
     let request_body = json!({
         "email_ids": test_data.email_ids,
-        "user_id": test_data.user_id
+        "prompts": test_data.prompts
     });
 
     let body = serde_json::to_string(&request_body)?;
-    let mut init = RequestInit::new();
-    init.with_method(Method::Post);
-    init.with_body(Some(body.into()));
-    let headers = Headers::new();
-    headers.set("Content-Type", "application/json")?;
-    init.with_headers(headers);
-    let request = Request::new_with_init(&url, &init)?;
+    let request = ai_worker_request(&ctx.env, "/sandbox", &body)?;
 
     let mut response = Fetch::Request(request).send().await?;
     let status = response.status_code();
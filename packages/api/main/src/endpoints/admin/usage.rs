@@ -0,0 +1,92 @@
+//! Per-user usage of the daily limits, and the limits themselves
+
+use crate::common::auth::require_admin;
+use crate::common::db::get_d1;
+use crate::services::db::get_user_by_id;
+use crate::services::db::usage::{
+    list_limits, list_usage_today, remove_user_limit, set_limit, UsageLimit,
+};
+use api_shared::quota::{seconds_until_reset, QUOTA_KINDS};
+use api_shared::usage::now_secs;
+use serde_json::json;
+use worker::*;
+
+/// Today's usage of everyone who used anything or has limits of their own
+pub async fn get_usage(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let _admin_id = require_admin(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let db = get_d1(&ctx.env)?;
+    let users = list_usage_today(&db)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to get usage: {}", e)))?;
+    let limits = list_limits(&db)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to get limits: {}", e)))?;
+
+    Response::from_json(&json!({
+        "users": users,
+        "limits": limits,
+        "resets_in": seconds_until_reset(now_secs())
+    }))
+}
+
+/// Set a default limit, or a user's own one. A user's limit without a value
+/// is removed so the default applies again.
+pub async fn update_limit(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let _admin_id = require_admin(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    #[derive(serde::Deserialize)]
+    struct LimitRequest {
+        user_id: Option<String>,
+        kind: String,
+        daily_limit: Option<u32>,
+    }
+
+    let limit_data: LimitRequest = match req.json().await {
+        Ok(body) => body,
+        Err(_) => return Response::error("kind and daily_limit are required", 400),
+    };
+    if !QUOTA_KINDS.contains(&limit_data.kind.as_str()) {
+        return Response::error(
+            format!("kind must be one of: {}", QUOTA_KINDS.join(", ")),
+            400,
+        );
+    }
+
+    let db = get_d1(&ctx.env)?;
+    if let Some(user_id) = &limit_data.user_id {
+        let user = get_user_by_id(&db, user_id)
+            .await
+            .map_err(|e| worker::Error::RustError(format!("Failed to get user: {}", e)))?;
+        if user.is_none() {
+            return Response::error("User not found", 404);
+        }
+    }
+
+    match (limit_data.user_id, limit_data.daily_limit) {
+        (user_id, Some(daily_limit)) => {
+            let limit = UsageLimit {
+                user_id,
+                kind: limit_data.kind,
+                daily_limit,
+            };
+            set_limit(&db, &limit)
+                .await
+                .map_err(|e| worker::Error::RustError(format!("Failed to set limit: {}", e)))?;
+        }
+        (Some(user_id), None) => {
+            remove_user_limit(&db, &user_id, &limit_data.kind)
+                .await
+                .map_err(|e| worker::Error::RustError(format!("Failed to remove limit: {}", e)))?;
+        }
+        (None, None) => return Response::error("daily_limit is required", 400),
+    }
+
+    Response::from_json(&json!({
+        "success": true
+    }))
+}
@@ -0,0 +1,52 @@
+use crate::common::ai_worker::quota_exceeded;
+use crate::common::auth::require_auth;
+use api_shared::requests::ai_worker_request;
+use serde_json::json;
+use worker::*;
+
+/// Draft a thank-you or follow-up email for a job with the AI worker
+pub async fn handler(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let user_id = require_auth(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let job_id = ctx
+        .param("id")
+        .ok_or_else(|| worker::Error::RustError("Missing job id".to_string()))?;
+
+    #[derive(serde::Deserialize)]
+    struct DraftRequest {
+        kind: String,
+        reply_to_email_id: Option<String>,
+        instructions: Option<String>,
+    }
+
+    let draft_data: DraftRequest = match req.json().await {
+        Ok(data) => data,
+        Err(_) => return Response::error("Invalid request body", 400),
+    };
+
+    let request_body = json!({
+        "user_id": user_id,
+        "kind": draft_data.kind,
+        "reply_to_email_id": draft_data.reply_to_email_id,
+        "instructions": draft_data.instructions
+    });
+
+    let body = serde_json::to_string(&request_body)?;
+    let request = ai_worker_request(&ctx.env, &format!("/draft/{}", job_id), &body)?;
+
+    let mut response = Fetch::Request(request).send().await?;
+    if let Some(exceeded) = quota_exceeded(&mut response).await? {
+        return Ok(exceeded);
+    }
+    let status = response.status_code();
+
+    if status == 200 {
+        let result: serde_json::Value = response.json().await?;
+        Response::from_json(&result)
+    } else {
+        let text = response.text().await?;
+        Response::error(format!("AI worker error: {}", text), status)
+    }
+}
@@ -1 +1,3 @@
+pub mod drafts;
 pub mod results;
+pub mod reviews;
@@ -1,10 +1,13 @@
+use crate::common::ai_worker::quota_exceeded;
 use crate::common::auth::require_auth;
 use crate::common::db::get_d1;
-use crate::services::db::ai_results::get_ai_result;
+use crate::services::db::ai_results::{get_ai_result, user_owns_email};
+use api_shared::requests::ai_worker_request;
+use serde_json::json;
 use worker::*;
 
 pub async fn handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
-    let _user_id = require_auth(&req, &ctx.env)
+    let user_id = require_auth(&req, &ctx.env)
         .await
         .map_err(|e| {
             console_log!("Auth error in ai-results: {}", e);
@@ -21,7 +24,7 @@ pub async fn handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
     console_log!("Getting AI result for email_id: {}", email_id);
 
     let db = get_d1(&ctx.env)?;
-    let result = get_ai_result(&db, email_id)
+    let result = get_ai_result(&db, &user_id, email_id)
         .await
         .map_err(|e| {
             console_log!("Failed to get AI result: {}", e);
@@ -42,3 +45,61 @@ pub async fn handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
         }
     }
 }
+
+/// Run an email through the AI worker again with the current prompts. The new
+/// result becomes the latest version and the old ones move to its history.
+pub async fn reprocess(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let user_id = require_auth(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let email_id = ctx
+        .param("email_id")
+        .ok_or_else(|| worker::Error::RustError("Missing email_id".to_string()))?;
+
+    let db = get_d1(&ctx.env)?;
+    let owned = user_owns_email(&db, &user_id, email_id)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to get email: {}", e)))?;
+    if !owned {
+        return Response::error("Email not found", 404);
+    }
+
+    let previous_version = get_ai_result(&db, &user_id, email_id)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to get AI result: {}", e)))?
+        .map(|r| r.latest.version)
+        .unwrap_or(0);
+
+    // Reprocessing asks for a fresh run, so a cached result of the same
+    // content isn't reused
+    let body = serde_json::to_string(&json!({ "user_id": user_id, "bypass_cache": true }))?;
+    let request = ai_worker_request(&ctx.env, &format!("/process/{}", email_id), &body)?;
+
+    // A run that failed at a stage is stored as a version too, so it's
+    // returned like a successful one
+    let mut response = Fetch::Request(request).send().await?;
+    if let Some(exceeded) = quota_exceeded(&mut response).await? {
+        return Ok(exceeded);
+    }
+    let status = response.status_code();
+    let worker_error = if status == 200 {
+        None
+    } else {
+        Some(response.text().await?)
+    };
+
+    let result = get_ai_result(&db, &user_id, email_id)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to get AI result: {}", e)))?;
+    match result {
+        Some(result) if result.latest.version > previous_version => Response::from_json(&result),
+        _ => Response::error(
+            format!(
+                "AI worker error: {}",
+                worker_error.unwrap_or_else(|| "no result was saved".to_string())
+            ),
+            502,
+        ),
+    }
+}
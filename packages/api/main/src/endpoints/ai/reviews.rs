@@ -0,0 +1,86 @@
+//! Review queue for AI results with low confidence
+
+use crate::common::auth::require_auth;
+use crate::common::db::get_d1;
+use crate::common::gmail_scanner::sync_gmail_labels;
+use crate::services::ai_eval::CATEGORIES;
+use crate::services::ai_review::{diff_review, label_fields};
+use crate::services::db::ai_reviews::{get_review_item, list_review_queue, save_review};
+use serde_json::{json, Map, Value};
+use worker::*;
+
+/// List the user's emails flagged for review with their AI results
+pub async fn queue(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let user_id = require_auth(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let db = get_d1(&ctx.env)?;
+    let items = list_review_queue(&db, &user_id)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to list review queue: {}", e)))?;
+
+    Response::from_json(&items)
+}
+
+/// Accept or correct the AI result of an email. The reviewed values replace
+/// the AI result and are kept as ground truth.
+pub async fn review(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let user_id = require_auth(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let email_id = ctx
+        .param("email_id")
+        .ok_or_else(|| worker::Error::RustError("Missing email_id".to_string()))?
+        .to_string();
+
+    #[derive(serde::Deserialize)]
+    struct ReviewRequest {
+        category: String,
+        #[serde(default)]
+        fields: Map<String, Value>,
+    }
+
+    let review_data: ReviewRequest = match req.json().await {
+        Ok(body) => body,
+        Err(_) => return Response::error("category is required", 400),
+    };
+    if !CATEGORIES.contains(&review_data.category.as_str()) {
+        return Response::error(
+            format!("category must be one of: {}", CATEGORIES.join(", ")),
+            400,
+        );
+    }
+
+    let db = get_d1(&ctx.env)?;
+    let Some(item) = get_review_item(&db, &user_id, &email_id)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to get AI result: {}", e)))?
+    else {
+        return Response::error("AI result not found", 404);
+    };
+
+    let fields = label_fields(&review_data.fields);
+    let diff = diff_review(
+        item.category.as_deref(),
+        &item.fields,
+        &review_data.category,
+        &fields,
+    );
+
+    save_review(&db, &user_id, &item, &diff, &review_data.category, &fields)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to save review: {}", e)))?;
+
+    // The reviewed category replaces the AI one the label was based on
+    if diff.category_changed {
+        sync_gmail_labels(&ctx.env, &user_id, std::slice::from_ref(&item.email_id)).await;
+    }
+
+    Response::from_json(&json!({
+        "status": diff.status(),
+        "corrected_fields": diff.corrected_fields,
+        "success": true
+    }))
+}
@@ -0,0 +1,34 @@
+//! Companies endpoint handler
+
+use crate::common::auth::require_auth;
+use crate::common::db::get_d1;
+use crate::services::companies::{
+    get_company_details, list_companies, update_company, UpdateCompanyRequest,
+};
+use worker::*;
+
+/// GET /companies - companies with jobs or contacts
+/// GET /companies/:id - a company with its jobs, contacts, emails and outcomes
+/// PUT /companies/:id - update name, domain, website or notes
+pub async fn handler(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let db = get_d1(&ctx.env)?;
+    let user_id = require_auth(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let company_id = ctx.param("id").map(|s| s.to_string());
+
+    match (req.method(), company_id) {
+        (Method::Get, Some(id)) => get_company_details(&db, &user_id, &id).await,
+        (Method::Get, None) => list_companies(&db, &user_id).await,
+        (Method::Put, Some(id)) => {
+            let request: UpdateCompanyRequest = match req.json().await {
+                Ok(body) => body,
+                Err(e) => return Response::error(format!("Invalid company update: {}", e), 400),
+            };
+            update_company(&db, &id, request).await
+        }
+        (Method::Put, None) => Response::error("Company ID is required for update", 400),
+        _ => Response::error("Method not allowed", 405),
+    }
+}
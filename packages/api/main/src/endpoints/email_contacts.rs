@@ -1,9 +1,14 @@
 use crate::common::auth::require_auth;
 use crate::common::db::get_d1;
+use crate::services::contact_history::{
+    get_contact_history, list_contacts, list_stale_contacts, DEFAULT_STALE_DAYS,
+};
 use crate::services::db::email_contacts::{
     convert_to_user_contact, get_contact, get_contacts_for_job, update_contact,
+    update_contact_notes,
 };
 use crate::services::db::system_email_domains::is_system_email;
+use crate::services::vcard::{export_contacts, import_contacts, VCardVersion};
 use serde::{Deserialize, Serialize};
 use worker::*;
 
@@ -14,6 +19,11 @@ pub struct UpdateContactRequest {
     pub website: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateNotesRequest {
+    pub notes: Option<String>,
+}
+
 pub async fn handler(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
     let db = get_d1(&ctx.env)?;
     let user_id = require_auth(&req, &ctx.env)
@@ -54,6 +64,33 @@ pub async fn handler(mut req: Request, ctx: RouteContext<()>) -> Result<Response
                 }
             }
 
+            // GET /email-contacts?format=vcard&version={3.0|4.0} - Export all contacts
+            // GET /email-contacts/{email}?format=vcard - Export one contact
+            if query_params.get("format").map(|s| s.as_ref()) == Some("vcard") {
+                let version = match query_params.get("version") {
+                    Some(v) => match VCardVersion::parse(v) {
+                        Some(version) => version,
+                        None => return Response::error("version must be 3.0 or 4.0", 400),
+                    },
+                    None => VCardVersion::V3,
+                };
+                let email = ctx
+                    .param("email")
+                    .map(|e| e.replace("%40", "@").replace("%2E", "."));
+                return export_contacts(&db, &user_id, email.as_deref(), version).await;
+            }
+
+            // GET /email-contacts?stale_days={n} - Contacts waiting on a reply for n days
+            if let Some(days) = query_params.get("stale_days") {
+                return match days.parse::<i64>() {
+                    Ok(days) if days >= 0 => list_stale_contacts(&db, &user_id, days).await,
+                    _ => Response::error("stale_days must be a non-negative number", 400),
+                };
+            }
+            if query_params.get("stale").map(|s| s.as_ref()) == Some("true") {
+                return list_stale_contacts(&db, &user_id, DEFAULT_STALE_DAYS).await;
+            }
+
             // GET /email-contacts/{email} - Get specific contact
             // GET /email-contacts/{email}?check_system=true - Check if email is detected as system email
             if let Some(email) = ctx.param("email") {
@@ -76,6 +113,12 @@ pub async fn handler(mut req: Request, ctx: RouteContext<()>) -> Result<Response
                     }
                 }
 
+                // GET /email-contacts/{email}?include=history - Contact with emails, jobs and notes
+                if query_params.get("include").map(|s| s.as_ref()) == Some("history") {
+                    let decoded_email = email.replace("%40", "@").replace("%2E", ".");
+                    return get_contact_history(&db, &user_id, &decoded_email).await;
+                }
+
                 // Regular get contact
                 match get_contact(&db, email, &user_id).await {
                     Ok(Some(c)) => {
@@ -101,10 +144,8 @@ pub async fn handler(mut req: Request, ctx: RouteContext<()>) -> Result<Response
                 }
             }
 
-            Response::error(
-                "Missing job_id query parameter or email path parameter",
-                400,
-            )
+            // GET /email-contacts - All of the user's contacts
+            list_contacts(&db, &user_id).await
         }
         Method::Put => {
             // PUT /email-contacts/{email} - Update contact
@@ -143,6 +184,14 @@ pub async fn handler(mut req: Request, ctx: RouteContext<()>) -> Result<Response
             Response::error("Missing email path parameter", 400)
         }
         Method::Post => {
+            // POST /email-contacts?action=import - Import contacts from a vCard file
+            if ctx.param("email").is_none()
+                && query_params.get("action").map(|s| s.as_ref()) == Some("import")
+            {
+                let body = req.text().await?;
+                return import_contacts(&db, &user_id, &body).await;
+            }
+
             // POST /email-contacts/{email}?action=convert-to-user - Convert system email to user contact
             if let Some(email) = ctx.param("email") {
                 if query_params.get("action").map(|s| s.as_ref()) == Some("convert-to-user") {
@@ -174,3 +223,26 @@ pub async fn handler(mut req: Request, ctx: RouteContext<()>) -> Result<Response
         _ => Response::error("Method not allowed", 405),
     }
 }
+
+/// PUT /email-contacts/:email/notes - Replace the notes of a contact
+pub async fn notes_handler(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let db = get_d1(&ctx.env)?;
+    let user_id = require_auth(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let Some(email) = ctx.param("email") else {
+        return Response::error("Missing email path parameter", 400);
+    };
+    let decoded_email = email.replace("%40", "@").replace("%2E", ".");
+
+    let body: UpdateNotesRequest = match req.json().await {
+        Ok(body) => body,
+        Err(_) => return Response::error("notes is required", 400),
+    };
+
+    match update_contact_notes(&db, &decoded_email, &user_id, body.notes.as_deref()).await {
+        Ok(()) => get_contact_history(&db, &user_id, &decoded_email).await,
+        Err(e) => Response::error(format!("Failed to update notes: {}", e), 500),
+    }
+}
@@ -0,0 +1,40 @@
+//! Job analytics endpoint handler
+
+use crate::common::auth::require_auth;
+use crate::common::db::get_d1;
+use crate::services::jobs::analytics::is_valid_date;
+use crate::services::jobs::{get_funnel_analytics, AnalyticsFilter};
+use worker::*;
+
+/// GET /job-analytics?from=YYYY-MM-DD&to=YYYY-MM-DD&tag=remote - application funnel metrics
+pub async fn handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let db = get_d1(&ctx.env)?;
+    let user_id = require_auth(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let url = req.url()?;
+    let query_params = url
+        .query_pairs()
+        .collect::<std::collections::HashMap<_, _>>();
+    let param = |key: &str| {
+        query_params
+            .get(key)
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+
+    let filter = AnalyticsFilter {
+        from: param("from"),
+        to: param("to"),
+        tag: param("tag").map(|t| t.to_lowercase()),
+    };
+    for date in [&filter.from, &filter.to].into_iter().flatten() {
+        if !is_valid_date(date) {
+            return Response::error("Dates must be in YYYY-MM-DD format", 400);
+        }
+    }
+
+    let analytics = get_funnel_analytics(&db, &user_id, &filter).await?;
+    Response::from_json(&analytics)
+}
@@ -0,0 +1,207 @@
+//! Job attachments endpoint handler (documents stored in R2)
+
+use crate::common::auth::require_auth;
+use crate::common::db::get_d1;
+use crate::services::db::job_attachments::{
+    build_attachment_key, content_disposition, create_attachment, delete_attachment,
+    get_attachment, list_attachments_for_job, normalize_content_type, sanitize_file_name,
+    validate_attachment, JobAttachment, MAX_ATTACHMENT_SIZE,
+};
+use crate::services::password;
+use serde_json::Value;
+use worker::*;
+
+const ATTACHMENTS_BUCKET: &str = "ATTACHMENTS";
+
+pub async fn handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let db = get_d1(&ctx.env)?;
+    let user_id = require_auth(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let job_id = ctx
+        .param("id")
+        .ok_or_else(|| worker::Error::RustError("Job ID is required".to_string()))?
+        .to_string();
+    let attachment_id = ctx.param("attachment_id").map(|s| s.to_string());
+
+    match (req.method(), attachment_id) {
+        (Method::Get, None) => list_attachments(&db, &job_id, &user_id).await,
+        (Method::Post, None) => upload_attachment(&db, req, &ctx.env, &job_id, &user_id).await,
+        (Method::Get, Some(id)) => download_attachment(&db, &ctx.env, &id, &job_id, &user_id).await,
+        (Method::Delete, Some(id)) => {
+            remove_attachment(&db, &ctx.env, &id, &job_id, &user_id).await
+        }
+        _ => Response::error("Method not allowed", 405),
+    }
+}
+
+async fn list_attachments(db: &D1Database, job_id: &str, user_id: &str) -> Result<Response> {
+    match list_attachments_for_job(db, job_id, user_id).await {
+        Ok(attachments) => Response::from_json(&attachments),
+        Err(e) => Response::error(format!("Failed to fetch attachments: {}", e), 500),
+    }
+}
+
+/// Upload a file. The request body is the raw file content, the Content-Type
+/// header is the file's MIME type and the original name is passed as `?filename=`.
+async fn upload_attachment(
+    db: &D1Database,
+    mut req: Request,
+    env: &Env,
+    job_id: &str,
+    user_id: &str,
+) -> Result<Response> {
+    let job_exists = db
+        .prepare("SELECT id FROM jobs WHERE id = ?")
+        .bind(&[job_id.into()])?
+        .first::<Value>(None)
+        .await?;
+
+    if job_exists.is_none() {
+        return Response::error("Job not found", 404);
+    }
+
+    let content_type = req
+        .headers()
+        .get("Content-Type")?
+        .map(|ct| normalize_content_type(&ct))
+        .unwrap_or_default();
+
+    // Reject oversized uploads before reading the body when the client declares a length
+    if let Some(length) = req
+        .headers()
+        .get("Content-Length")?
+        .and_then(|l| l.parse::<u64>().ok())
+    {
+        if length > MAX_ATTACHMENT_SIZE {
+            return Response::error(
+                format!(
+                    "File is too large (max {} MB)",
+                    MAX_ATTACHMENT_SIZE / (1024 * 1024)
+                ),
+                413,
+            );
+        }
+    }
+
+    let url = req.url()?;
+    let file_name = url
+        .query_pairs()
+        .find(|(k, _)| k == "filename")
+        .map(|(_, v)| sanitize_file_name(&v))
+        .unwrap_or_else(|| "attachment".to_string());
+
+    let bytes = req.bytes().await?;
+    let size_bytes = bytes.len() as u64;
+
+    if let Err(message) = validate_attachment(&content_type, size_bytes) {
+        let status = if size_bytes > MAX_ATTACHMENT_SIZE {
+            413
+        } else {
+            400
+        };
+        return Response::error(message, status);
+    }
+
+    let attachment_id = password::generate_uuid()
+        .map_err(|e| worker::Error::RustError(format!("Failed to generate UUID: {}", e)))?;
+    let r2_key = build_attachment_key(user_id, job_id, &attachment_id);
+
+    let bucket = env.bucket(ATTACHMENTS_BUCKET)?;
+    bucket.put(r2_key.clone(), bytes).execute().await?;
+
+    let attachment = JobAttachment {
+        id: attachment_id,
+        job_id: job_id.to_string(),
+        user_id: user_id.to_string(),
+        file_name,
+        content_type,
+        size_bytes,
+        r2_key: r2_key.clone(),
+        created_at: String::new(),
+    };
+
+    if let Err(e) = create_attachment(db, &attachment).await {
+        // Don't leave an orphaned object behind if the metadata insert fails
+        let _ = bucket.delete(r2_key).await;
+        return Response::error(format!("Failed to save attachment: {}", e), 500);
+    }
+
+    match get_attachment(db, &attachment.id, job_id, user_id).await {
+        Ok(Some(saved)) => Ok(Response::from_json(&saved)?.with_status(201)),
+        _ => Ok(Response::from_json(&attachment)?.with_status(201)),
+    }
+}
+
+async fn download_attachment(
+    db: &D1Database,
+    env: &Env,
+    attachment_id: &str,
+    job_id: &str,
+    user_id: &str,
+) -> Result<Response> {
+    let attachment = match get_attachment(db, attachment_id, job_id, user_id).await {
+        Ok(Some(a)) => a,
+        Ok(None) => return Response::error("Attachment not found", 404),
+        Err(e) => return Response::error(format!("Failed to fetch attachment: {}", e), 500),
+    };
+
+    let bucket = env.bucket(ATTACHMENTS_BUCKET)?;
+    let object = match bucket.get(attachment.r2_key.clone()).execute().await? {
+        Some(object) => object,
+        None => return Response::error("Attachment file not found", 404),
+    };
+
+    let bytes = match object.body() {
+        Some(body) => body.bytes().await?,
+        None => return Response::error("Attachment file not found", 404),
+    };
+
+    let headers = Headers::new();
+    headers.set("Content-Type", &attachment.content_type)?;
+    headers.set(
+        "Content-Disposition",
+        &content_disposition(&attachment.file_name),
+    )?;
+    headers.set("Cache-Control", "private, no-store")?;
+
+    Ok(Response::from_bytes(bytes)?.with_headers(headers))
+}
+
+async fn remove_attachment(
+    db: &D1Database,
+    env: &Env,
+    attachment_id: &str,
+    job_id: &str,
+    user_id: &str,
+) -> Result<Response> {
+    let attachment = match get_attachment(db, attachment_id, job_id, user_id).await {
+        Ok(Some(a)) => a,
+        Ok(None) => return Response::error("Attachment not found", 404),
+        Err(e) => return Response::error(format!("Failed to fetch attachment: {}", e), 500),
+    };
+
+    let bucket = env.bucket(ATTACHMENTS_BUCKET)?;
+    bucket.delete(attachment.r2_key.clone()).await?;
+
+    if let Err(e) = delete_attachment(db, &attachment.id, user_id).await {
+        return Response::error(format!("Failed to delete attachment: {}", e), 500);
+    }
+
+    Response::ok("Attachment deleted successfully")
+}
+
+/// Delete stored objects for a job that is being removed. Metadata rows are
+/// removed by the foreign key cascade; failures here only leave orphaned objects.
+pub async fn delete_objects(env: &Env, keys: &[String]) {
+    if keys.is_empty() {
+        return;
+    }
+
+    if let Ok(bucket) = env.bucket(ATTACHMENTS_BUCKET) {
+        for key in keys {
+            let _ = bucket.delete(key.clone()).await;
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::common::auth::require_auth;
 use crate::common::db::get_d1;
+use crate::services::jobs::comment_edit_error;
 use crate::services::password;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -8,6 +9,12 @@ use worker::*;
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateCommentRequest {
     pub content: String,
+    pub parent_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateCommentRequest {
+    pub content: String,
 }
 
 pub async fn handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
@@ -16,10 +23,17 @@ pub async fn handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
     let job_id = ctx
         .param("id")
         .ok_or_else(|| worker::Error::RustError("Job ID is required".to_string()))?;
+    let comment_id = ctx.param("comment_id").map(|s| s.to_string());
 
-    match method {
-        Method::Get => list_comments(&db, job_id.to_string()).await,
-        Method::Post => create_comment(&db, req, &ctx.env, job_id.to_string()).await,
+    match (method, comment_id) {
+        (Method::Get, None) => list_comments(&db, job_id.to_string()).await,
+        (Method::Post, None) => create_comment(&db, req, &ctx.env, job_id.to_string()).await,
+        (Method::Put, Some(comment_id)) => {
+            update_comment(&db, req, &ctx.env, job_id.to_string(), comment_id).await
+        }
+        (Method::Delete, Some(comment_id)) => {
+            delete_comment(&db, req, &ctx.env, job_id.to_string(), comment_id).await
+        }
         _ => Response::error("Method not allowed", 405),
     }
 }
@@ -27,8 +41,8 @@ pub async fn handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
 async fn list_comments(db: &D1Database, job_id: String) -> Result<Response> {
     let result = db
         .prepare(
-            "SELECT jc.*, u.name, u.email, u.picture FROM job_comments jc 
-             LEFT JOIN users u ON jc.user_id = u.id 
+            "SELECT jc.*, u.name, u.email, u.picture FROM job_comments jc
+             LEFT JOIN users u ON jc.user_id = u.id
              WHERE jc.job_id = ? ORDER BY jc.created_at DESC",
         )
         .bind(&[job_id.into()])?
@@ -67,31 +81,44 @@ async fn create_comment(
         return Response::error("Comment content is required", 400);
     }
 
+    // Replies attach to the top-level comment so threads stay one level deep
+    let parent_id = match body.parent_id.filter(|p| !p.is_empty()) {
+        Some(parent_id) => match get_comment(db, &job_id, &parent_id).await? {
+            Some(parent) => {
+                if parent.get("deleted_at").and_then(|v| v.as_str()).is_some() {
+                    return Response::error("Cannot reply to a deleted comment", 400);
+                }
+                Some(
+                    parent
+                        .get("parent_id")
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string())
+                        .unwrap_or(parent_id),
+                )
+            }
+            None => return Response::error("Parent comment not found", 404),
+        },
+        None => None,
+    };
+
     let comment_id = password::generate_uuid()
         .map_err(|e| worker::Error::RustError(format!("Failed to generate UUID: {}", e)))?;
 
-    db.prepare("INSERT INTO job_comments (id, job_id, user_id, content) VALUES (?, ?, ?, ?)")
-        .bind(&[
-            comment_id.clone().into(),
-            job_id.into(),
-            user_id.into(),
-            body.content.into(),
-        ])?
-        .run()
-        .await?;
+    db.prepare(
+        "INSERT INTO job_comments (id, job_id, user_id, content, parent_id) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(&[
+        comment_id.clone().into(),
+        job_id.clone().into(),
+        user_id.into(),
+        body.content.into(),
+        parent_id.into(),
+    ])?
+    .run()
+    .await?;
 
     // Fetch the created comment with user info
-    let comment_result = db
-        .prepare(
-            "SELECT jc.*, u.name, u.email, u.picture FROM job_comments jc 
-             LEFT JOIN users u ON jc.user_id = u.id 
-             WHERE jc.id = ?",
-        )
-        .bind(&[comment_id.into()])?
-        .first::<Value>(None)
-        .await?;
-
-    match comment_result {
+    match get_comment(db, &job_id, &comment_id).await? {
         Some(comment) => {
             let resp = Response::from_json(&comment)?;
             Ok(resp.with_status(201))
@@ -99,3 +126,100 @@ async fn create_comment(
         None => Response::error("Failed to create comment", 500),
     }
 }
+
+async fn update_comment(
+    db: &D1Database,
+    mut req: Request,
+    env: &Env,
+    job_id: String,
+    comment_id: String,
+) -> Result<Response> {
+    let user_id = require_auth(&req, env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let comment = match get_comment(db, &job_id, &comment_id).await? {
+        Some(c) => c,
+        None => return Response::error("Comment not found", 404),
+    };
+
+    if let Some(response) = check_can_modify(&comment, &user_id)? {
+        return Ok(response);
+    }
+
+    let body: UpdateCommentRequest = req.json().await?;
+
+    if body.content.trim().is_empty() {
+        return Response::error("Comment content is required", 400);
+    }
+
+    db.prepare(
+        "UPDATE job_comments SET content = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ? AND user_id = ?",
+    )
+    .bind(&[
+        body.content.into(),
+        comment_id.clone().into(),
+        user_id.into(),
+    ])?
+    .run()
+    .await?;
+
+    match get_comment(db, &job_id, &comment_id).await? {
+        Some(comment) => Response::from_json(&comment),
+        None => Response::error("Comment not found", 404),
+    }
+}
+
+/// Soft-delete a comment. The row is kept (with its content cleared) so
+/// replies keep their thread and the deletion can be shown in the timeline.
+async fn delete_comment(
+    db: &D1Database,
+    req: Request,
+    env: &Env,
+    job_id: String,
+    comment_id: String,
+) -> Result<Response> {
+    let user_id = require_auth(&req, env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let comment = match get_comment(db, &job_id, &comment_id).await? {
+        Some(c) => c,
+        None => return Response::error("Comment not found", 404),
+    };
+
+    if let Some(response) = check_can_modify(&comment, &user_id)? {
+        return Ok(response);
+    }
+
+    db.prepare(
+        "UPDATE job_comments SET content = '', deleted_at = CURRENT_TIMESTAMP WHERE id = ? AND user_id = ?",
+    )
+    .bind(&[comment_id.clone().into(), user_id.into()])?
+    .run()
+    .await?;
+
+    match get_comment(db, &job_id, &comment_id).await? {
+        Some(comment) => Response::from_json(&comment),
+        None => Response::error("Comment not found", 404),
+    }
+}
+
+/// Only the author may change a comment, and deleted comments are final
+fn check_can_modify(comment: &Value, user_id: &str) -> Result<Option<Response>> {
+    match comment_edit_error(comment, user_id) {
+        Some((message, status)) => Ok(Some(Response::error(message, status)?)),
+        None => Ok(None),
+    }
+}
+
+async fn get_comment(db: &D1Database, job_id: &str, comment_id: &str) -> Result<Option<Value>> {
+    db.prepare(
+        "SELECT jc.*, u.name, u.email, u.picture FROM job_comments jc
+         LEFT JOIN users u ON jc.user_id = u.id
+         WHERE jc.id = ? AND jc.job_id = ?",
+    )
+    .bind(&[comment_id.into(), job_id.into()])?
+    .first::<Value>(None)
+    .await
+}
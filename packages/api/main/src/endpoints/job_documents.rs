@@ -0,0 +1,82 @@
+//! Job documents endpoint handler (AI-tailored cover letters and resume suggestions)
+
+use crate::common::ai_worker::quota_exceeded;
+use crate::common::auth::require_auth;
+use crate::common::db::get_d1;
+use crate::services::db::job_documents::list_documents_for_job;
+use api_shared::requests::ai_worker_request;
+use serde_json::{json, Value};
+use worker::*;
+
+pub async fn handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let db = get_d1(&ctx.env)?;
+    let user_id = require_auth(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let job_id = ctx
+        .param("id")
+        .ok_or_else(|| worker::Error::RustError("Job ID is required".to_string()))?
+        .to_string();
+
+    match req.method() {
+        Method::Get => list_documents(&db, &job_id, &user_id).await,
+        Method::Post => generate_documents(&db, req, &ctx.env, &job_id, &user_id).await,
+        _ => Response::error("Method not allowed", 405),
+    }
+}
+
+async fn list_documents(db: &D1Database, job_id: &str, user_id: &str) -> Result<Response> {
+    match list_documents_for_job(db, job_id, user_id).await {
+        Ok(documents) => Response::from_json(&documents),
+        Err(e) => Response::error(format!("Failed to fetch documents: {}", e), 500),
+    }
+}
+
+/// Generate a new version with the AI worker, which saves it against the job
+async fn generate_documents(
+    db: &D1Database,
+    mut req: Request,
+    env: &Env,
+    job_id: &str,
+    user_id: &str,
+) -> Result<Response> {
+    let job_exists = db
+        .prepare("SELECT id FROM jobs WHERE id = ? AND deleted_at IS NULL")
+        .bind(&[job_id.into()])?
+        .first::<Value>(None)
+        .await?;
+
+    if job_exists.is_none() {
+        return Response::error("Job not found", 404);
+    }
+
+    #[derive(serde::Deserialize, Default)]
+    struct GenerateRequest {
+        instructions: Option<String>,
+    }
+
+    let generate_data: GenerateRequest = req.json().await.unwrap_or_default();
+
+    let request_body = json!({
+        "user_id": user_id,
+        "instructions": generate_data.instructions
+    });
+
+    let body = serde_json::to_string(&request_body)?;
+    let request = ai_worker_request(env, &format!("/tailor/{}", job_id), &body)?;
+
+    let mut response = Fetch::Request(request).send().await?;
+    if let Some(exceeded) = quota_exceeded(&mut response).await? {
+        return Ok(exceeded);
+    }
+    let status = response.status_code();
+
+    if status == 200 {
+        let result: Value = response.json().await?;
+        Response::from_json(&result)
+    } else {
+        let text = response.text().await?;
+        Response::error(text, status)
+    }
+}
@@ -0,0 +1,43 @@
+use crate::common::auth::require_auth;
+use crate::common::db::get_d1;
+use crate::services::jobs::{find_duplicate_suggestions, merge_jobs};
+use serde::{Deserialize, Serialize};
+use worker::*;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MergeJobRequest {
+    pub duplicate_id: String,
+}
+
+/// GET /job-duplicates - all suggested duplicates
+/// GET /jobs/:id/duplicates - suggested duplicates of one job
+pub async fn list_handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let db = get_d1(&ctx.env)?;
+    let user_id = require_auth(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let suggestions =
+        find_duplicate_suggestions(&db, &user_id, ctx.param("id").map(|s| s.as_str())).await?;
+
+    Response::from_json(&suggestions)
+}
+
+/// POST /jobs/:id/merge - merge `duplicate_id` into this job
+pub async fn merge_handler(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let db = get_d1(&ctx.env)?;
+    let user_id = require_auth(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+    let job_id = ctx
+        .param("id")
+        .ok_or_else(|| worker::Error::RustError("Job ID is required".to_string()))?
+        .to_string();
+
+    let body: MergeJobRequest = match req.json().await {
+        Ok(body) => body,
+        Err(_) => return Response::error("duplicate_id is required", 400),
+    };
+
+    merge_jobs(&db, job_id, body.duplicate_id, &user_id).await
+}
@@ -1,8 +1,15 @@
 //! Jobs endpoint handler
 
+use crate::common::auth::require_auth;
 use crate::common::db::get_d1;
+use crate::common::gmail_scanner::sync_email_labels;
+use crate::endpoints::job_attachments;
+use crate::services::job_statuses::get_status_by_id;
 use crate::services::jobs::{
-    create_job, delete_job, get_job, get_job_details_data, list_jobs, update_job,
+    archive_job, bulk_update_jobs, create_job, delete_job, find_expired_trash, get_job,
+    get_job_details_data, list_jobs, purge_job, purge_job_statements, purge_target, restore_job,
+    trash_retention_days, update_job, update_job_source, validate_bulk_request, BulkAction,
+    BulkJobsRequest, JobListView, UpdateJobSourceRequest,
 };
 use worker::*;
 
@@ -19,6 +26,11 @@ pub async fn handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
         .get("include")
         .map(|s| s == "details")
         .unwrap_or(false);
+    let view = JobListView::from_query(query_params.get("view").map(|s| s.as_ref()));
+    let permanent = query_params
+        .get("permanent")
+        .map(|s| s == "true")
+        .unwrap_or(false);
 
     match method {
         Method::Get => {
@@ -29,7 +41,7 @@ pub async fn handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
                     get_job(&db, id).await
                 }
             } else {
-                list_jobs(&db).await
+                list_jobs(&db, view).await
             }
         }
         Method::Post => create_job(&db, req).await,
@@ -42,7 +54,17 @@ pub async fn handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
         }
         Method::Delete => {
             if let Some(id) = job_id {
-                delete_job(&db, id).await
+                if permanent {
+                    let target = purge_target(&db, &id).await;
+                    let response = purge_job(&db, id).await?;
+                    if response.status_code() == 200 {
+                        job_attachments::delete_objects(&ctx.env, &target.attachment_keys).await;
+                        sync_email_labels(&ctx.env, &target.emails).await;
+                    }
+                    Ok(response)
+                } else {
+                    delete_job(&db, id).await
+                }
             } else {
                 Response::error("Job ID is required for deletion", 400)
             }
@@ -70,3 +92,118 @@ async fn get_job_details_handler(
 
     Response::from_json(&response)
 }
+
+/// POST /job-bulk - apply one action to many jobs
+pub async fn bulk_handler(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let db = get_d1(&ctx.env)?;
+
+    let request: BulkJobsRequest = match req.json().await {
+        Ok(body) => body,
+        Err(e) => return Response::error(format!("Invalid bulk request: {}", e), 400),
+    };
+
+    let job_ids = match validate_bulk_request(&request) {
+        Ok(ids) => ids,
+        Err(msg) => return Response::error(msg, 400),
+    };
+
+    if let BulkAction::SetStatus { status_id } = &request.action {
+        if get_status_by_id(&db, *status_id).await?.is_none() {
+            return Response::error("Invalid status_id", 400);
+        }
+    }
+
+    let mut purge_targets = Vec::new();
+    if request.action == BulkAction::Purge {
+        for job_id in &job_ids {
+            purge_targets.push(purge_target(&db, job_id).await);
+        }
+    }
+
+    let results = bulk_update_jobs(&db, &job_ids, &request.action).await?;
+
+    let mut unlinked_emails = Vec::new();
+    for target in purge_targets {
+        let purged = results
+            .iter()
+            .any(|r| r.job_id == target.job_id && r.success);
+        if purged {
+            job_attachments::delete_objects(&ctx.env, &target.attachment_keys).await;
+            unlinked_emails.extend(target.emails);
+        }
+    }
+    sync_email_labels(&ctx.env, &unlinked_emails).await;
+
+    let succeeded = results.iter().filter(|r| r.success).count();
+    Response::from_json(&serde_json::json!({
+        "results": results,
+        "succeeded": succeeded,
+        "failed": results.len() - succeeded,
+    }))
+}
+
+/// PUT /jobs/:id/source - set where the application came from and who referred it
+pub async fn source_handler(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let db = get_d1(&ctx.env)?;
+    let user_id = require_auth(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+    let job_id = ctx
+        .param("id")
+        .ok_or_else(|| worker::Error::RustError("Job ID is required".to_string()))?
+        .to_string();
+
+    let request: UpdateJobSourceRequest = match req.json().await {
+        Ok(body) => body,
+        Err(e) => return Response::error(format!("Invalid source request: {}", e), 400),
+    };
+
+    update_job_source(&db, job_id, &user_id, request).await
+}
+
+/// POST /jobs/:id/archive
+pub async fn archive_handler(_req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let db = get_d1(&ctx.env)?;
+    match ctx.param("id") {
+        Some(id) => archive_job(&db, id.to_string()).await,
+        None => Response::error("Job ID is required", 400),
+    }
+}
+
+/// POST /jobs/:id/restore - bring a job back from the archive or the trash
+pub async fn restore_handler(_req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let db = get_d1(&ctx.env)?;
+    match ctx.param("id") {
+        Some(id) => restore_job(&db, id.to_string()).await,
+        None => Response::error("Job ID is required", 400),
+    }
+}
+
+/// The trash retention window configured for this environment
+pub fn trash_retention_days_of(env: &Env) -> u32 {
+    let value = env.var("TRASH_RETENTION_DAYS").ok().map(|v| v.to_string());
+    trash_retention_days(value.as_deref())
+}
+
+/// Permanently delete jobs that have been in the trash longer than the
+/// retention window (`TRASH_RETENTION_DAYS`, default 30). Returns the number purged.
+pub async fn purge_expired_jobs(env: &Env) -> Result<usize> {
+    let db = get_d1(env)?;
+    let retention_days = trash_retention_days_of(env);
+
+    let expired = find_expired_trash(&db, retention_days).await?;
+    let mut purged = 0;
+    for job_id in expired {
+        let target = purge_target(&db, &job_id).await;
+        match db.batch(purge_job_statements(&db, &job_id)?).await {
+            Ok(_) => {
+                job_attachments::delete_objects(env, &target.attachment_keys).await;
+                sync_email_labels(env, &target.emails).await;
+                purged += 1;
+            }
+            Err(e) => console_error!("Failed to purge job {}: {}", job_id, e),
+        }
+    }
+
+    Ok(purged)
+}
@@ -1,9 +1,15 @@
 pub mod admin;
 pub mod ai;
 pub mod auth;
+pub mod companies;
 pub mod email_contacts;
 pub mod health;
+pub mod job_analytics;
+pub mod job_attachments;
 pub mod job_comments;
+pub mod job_documents;
+pub mod job_duplicates;
+pub mod job_sources;
 pub mod job_statuses;
 pub mod jobs;
 pub mod root;
@@ -1 +1,4 @@
+pub mod resume;
 pub mod timezone;
+pub mod trash;
+pub mod usage;
@@ -0,0 +1,50 @@
+use crate::common::auth::require_auth;
+use crate::common::db::get_d1;
+use crate::services::db::job_documents::{clean_resume, get_user_resume, update_user_resume};
+use serde::Deserialize;
+use serde_json::json;
+use worker::*;
+
+#[derive(Debug, Deserialize)]
+struct ResumeRequest {
+    resume: Option<String>,
+}
+
+pub async fn handler(mut req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let user_id = match require_auth(&req, &ctx.env).await {
+        Ok(id) => id,
+        Err(e) => {
+            let error_message = format!("Unauthorized: {}", e);
+            return Response::error(error_message, 401);
+        }
+    };
+
+    let db = get_d1(&ctx.env)?;
+
+    match req.method() {
+        Method::Get => {
+            let resume = get_user_resume(&db, &user_id)
+                .await
+                .map_err(|e| worker::Error::RustError(format!("Failed to get resume: {}", e)))?;
+            Response::from_json(&json!({ "resume": resume }))
+        }
+        Method::Put => {
+            let body: ResumeRequest = req
+                .json()
+                .await
+                .map_err(|e| worker::Error::RustError(format!("Invalid JSON: {}", e)))?;
+
+            let resume = match clean_resume(body.resume) {
+                Ok(resume) => resume,
+                Err(message) => return Response::error(message, 400),
+            };
+
+            update_user_resume(&db, &user_id, resume.as_deref())
+                .await
+                .map_err(|e| worker::Error::RustError(format!("Failed to update resume: {}", e)))?;
+
+            Response::from_json(&json!({ "resume": resume }))
+        }
+        _ => Response::error("Method not allowed", 405),
+    }
+}
@@ -0,0 +1,16 @@
+//! How long trashed jobs are kept before they're purged
+
+use crate::common::auth::require_auth;
+use crate::endpoints::jobs::trash_retention_days_of;
+use serde_json::json;
+use worker::*;
+
+pub async fn handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    require_auth(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    Response::from_json(&json!({
+        "retention_days": trash_retention_days_of(&ctx.env)
+    }))
+}
@@ -0,0 +1,25 @@
+//! What the user has used today of their daily limits
+
+use crate::common::auth::require_auth;
+use crate::common::db::get_d1;
+use crate::services::db::usage::get_user_quotas;
+use api_shared::quota::seconds_until_reset;
+use api_shared::usage::now_secs;
+use serde_json::json;
+use worker::*;
+
+pub async fn handler(req: Request, ctx: RouteContext<()>) -> Result<Response> {
+    let user_id = require_auth(&req, &ctx.env)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Unauthorized: {}", e)))?;
+
+    let db = get_d1(&ctx.env)?;
+    let quotas = get_user_quotas(&db, &user_id)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to get usage: {}", e)))?;
+
+    Response::from_json(&json!({
+        "quotas": quotas,
+        "resets_in": seconds_until_reset(now_secs())
+    }))
+}
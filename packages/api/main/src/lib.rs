@@ -8,7 +8,8 @@ mod types;
 use common::auth::require_auth;
 use common::cors::get_cors;
 use endpoints::{
-    admin, ai, auth, email_contacts, health, job_comments, job_statuses, jobs, root, settings,
+    admin, ai, auth, companies, email_contacts, health, job_analytics, job_attachments,
+    job_comments, job_documents, job_duplicates, job_sources, job_statuses, jobs, root, settings,
 };
 
 #[event(fetch)]
@@ -32,6 +33,7 @@ async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
             | ("/auth/password-reset/request", Method::Post)
             | ("/auth/password-reset/confirm", Method::Post)
             | ("/job-statuses", Method::Get)
+            | ("/job-sources", Method::Get)
     );
 
     // OPTIONS requests (CORS preflight) should always be allowed through without authentication
@@ -135,11 +137,35 @@ async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
         })
         .options("/jobs", |_, _| Response::ok(""))
         .options("/jobs/:id", |_, _| Response::ok(""))
+        // Archive and trash
+        .post_async("/jobs/:id/archive", |req, ctx| async move {
+            jobs::archive_handler(req, ctx).await
+        })
+        .post_async("/jobs/:id/restore", |req, ctx| async move {
+            jobs::restore_handler(req, ctx).await
+        })
+        .options("/jobs/:id/archive", |_, _| Response::ok(""))
+        .options("/jobs/:id/restore", |_, _| Response::ok(""))
+        // Source and referrer
+        .put_async("/jobs/:id/source", |req, ctx| async move {
+            jobs::source_handler(req, ctx).await
+        })
+        .options("/jobs/:id/source", |_, _| Response::ok(""))
+        // Bulk job operations
+        .post_async("/job-bulk", |req, ctx| async move {
+            jobs::bulk_handler(req, ctx).await
+        })
+        .options("/job-bulk", |_, _| Response::ok(""))
         // Job statuses routes
         .get_async("/job-statuses", |req, ctx| async move {
             job_statuses::handler(req, ctx).await
         })
         .options("/job-statuses", |_, _| Response::ok(""))
+        // Job sources routes
+        .get_async("/job-sources", |req, ctx| async move {
+            job_sources::handler(req, ctx).await
+        })
+        .options("/job-sources", |_, _| Response::ok(""))
         // Job comments routes
         .get_async("/jobs/:id/comments", |req, ctx| async move {
             job_comments::handler(req, ctx).await
@@ -147,7 +173,64 @@ async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
         .post_async("/jobs/:id/comments", |req, ctx| async move {
             job_comments::handler(req, ctx).await
         })
+        .put_async("/jobs/:id/comments/:comment_id", |req, ctx| async move {
+            job_comments::handler(req, ctx).await
+        })
+        .delete_async("/jobs/:id/comments/:comment_id", |req, ctx| async move {
+            job_comments::handler(req, ctx).await
+        })
         .options("/jobs/:id/comments", |_, _| Response::ok(""))
+        .options("/jobs/:id/comments/:comment_id", |_, _| Response::ok(""))
+        // Job attachments routes
+        .get_async("/jobs/:id/attachments", |req, ctx| async move {
+            job_attachments::handler(req, ctx).await
+        })
+        .post_async("/jobs/:id/attachments", |req, ctx| async move {
+            job_attachments::handler(req, ctx).await
+        })
+        .get_async(
+            "/jobs/:id/attachments/:attachment_id",
+            |req, ctx| async move { job_attachments::handler(req, ctx).await },
+        )
+        .delete_async(
+            "/jobs/:id/attachments/:attachment_id",
+            |req, ctx| async move { job_attachments::handler(req, ctx).await },
+        )
+        .options("/jobs/:id/attachments", |_, _| Response::ok(""))
+        .options("/jobs/:id/attachments/:attachment_id", |_, _| {
+            Response::ok("")
+        })
+        // Job documents routes
+        .get_async("/jobs/:id/documents", |req, ctx| async move {
+            job_documents::handler(req, ctx).await
+        })
+        .post_async("/jobs/:id/documents", |req, ctx| async move {
+            job_documents::handler(req, ctx).await
+        })
+        .options("/jobs/:id/documents", |_, _| Response::ok(""))
+        // Job analytics routes
+        .get_async("/job-analytics", |req, ctx| async move {
+            job_analytics::handler(req, ctx).await
+        })
+        .options("/job-analytics", |_, _| Response::ok(""))
+        // Job duplicates routes
+        .get_async("/job-duplicates", |req, ctx| async move {
+            job_duplicates::list_handler(req, ctx).await
+        })
+        .get_async("/jobs/:id/duplicates", |req, ctx| async move {
+            job_duplicates::list_handler(req, ctx).await
+        })
+        .post_async("/jobs/:id/merge", |req, ctx| async move {
+            job_duplicates::merge_handler(req, ctx).await
+        })
+        .options("/job-duplicates", |_, _| Response::ok(""))
+        .options("/jobs/:id/duplicates", |_, _| Response::ok(""))
+        .options("/jobs/:id/merge", |_, _| Response::ok(""))
+        // AI drafting routes
+        .post_async("/jobs/:id/draft", |req, ctx| async move {
+            ai::drafts::handler(req, ctx).await
+        })
+        .options("/jobs/:id/draft", |_, _| Response::ok(""))
         // Email contacts routes
         .get_async("/email-contacts", |req, ctx| async move {
             email_contacts::handler(req, ctx).await
@@ -155,19 +238,53 @@ async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
         .get_async("/email-contacts/:email", |req, ctx| async move {
             email_contacts::handler(req, ctx).await
         })
+        .post_async("/email-contacts", |req, ctx| async move {
+            email_contacts::handler(req, ctx).await
+        })
         .put_async("/email-contacts/:email", |req, ctx| async move {
             email_contacts::handler(req, ctx).await
         })
         .post_async("/email-contacts/:email", |req, ctx| async move {
             email_contacts::handler(req, ctx).await
         })
+        .put_async("/email-contacts/:email/notes", |req, ctx| async move {
+            email_contacts::notes_handler(req, ctx).await
+        })
         .options("/email-contacts", |_, _| Response::ok(""))
         .options("/email-contacts/:email", |_, _| Response::ok(""))
+        .options("/email-contacts/:email/notes", |_, _| Response::ok(""))
+        // Companies routes
+        .get_async("/companies", |req, ctx| async move {
+            companies::handler(req, ctx).await
+        })
+        .get_async("/companies/:id", |req, ctx| async move {
+            companies::handler(req, ctx).await
+        })
+        .put_async("/companies/:id", |req, ctx| async move {
+            companies::handler(req, ctx).await
+        })
+        .options("/companies", |_, _| Response::ok(""))
+        .options("/companies/:id", |_, _| Response::ok(""))
         // Settings routes
         .put_async("/api/settings/timezone", |req, ctx| async move {
             settings::timezone::handler(req, ctx).await
         })
         .options("/api/settings/timezone", |_, _| Response::ok(""))
+        .get_async("/api/settings/resume", |req, ctx| async move {
+            settings::resume::handler(req, ctx).await
+        })
+        .put_async("/api/settings/resume", |req, ctx| async move {
+            settings::resume::handler(req, ctx).await
+        })
+        .options("/api/settings/resume", |_, _| Response::ok(""))
+        .get_async("/api/settings/usage", |req, ctx| async move {
+            settings::usage::handler(req, ctx).await
+        })
+        .options("/api/settings/usage", |_, _| Response::ok(""))
+        .get_async("/api/settings/trash", |req, ctx| async move {
+            settings::trash::handler(req, ctx).await
+        })
+        .options("/api/settings/trash", |_, _| Response::ok(""))
         // Admin routes
         .get_async("/api/admin/users", |req, ctx| async move {
             admin::users::list_users(req, ctx).await
@@ -178,6 +295,9 @@ async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
         .get_async("/api/admin/stats", |req, ctx| async move {
             admin::stats::get_stats(req, ctx).await
         })
+        .get_async("/api/admin/ai-stats", |req, ctx| async move {
+            admin::ai_stats::get_ai_stats(req, ctx).await
+        })
         .get_async("/api/admin/prompts", |req, ctx| async move {
             admin::prompts::list(req, ctx).await
         })
@@ -193,14 +313,84 @@ async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
         .options("/api/admin/users", |_, _| Response::ok(""))
         .options("/api/admin/users/:id/enabled", |_, _| Response::ok(""))
         .options("/api/admin/stats", |_, _| Response::ok(""))
+        .options("/api/admin/ai-stats", |_, _| Response::ok(""))
+        .get_async("/api/admin/usage", |req, ctx| async move {
+            admin::usage::get_usage(req, ctx).await
+        })
+        .put_async("/api/admin/usage/limits", |req, ctx| async move {
+            admin::usage::update_limit(req, ctx).await
+        })
+        .options("/api/admin/usage", |_, _| Response::ok(""))
+        .options("/api/admin/usage/limits", |_, _| Response::ok(""))
         .options("/api/admin/prompts", |_, _| Response::ok(""))
         .options("/api/admin/prompts/:id/activate", |_, _| Response::ok(""))
         .options("/api/admin/prompts/test", |_, _| Response::ok(""))
+        .get_async("/api/admin/eval/datasets", |req, ctx| async move {
+            admin::eval::datasets(req, ctx).await
+        })
+        .post_async("/api/admin/eval/datasets", |req, ctx| async move {
+            admin::eval::datasets(req, ctx).await
+        })
+        .get_async(
+            "/api/admin/eval/datasets/:id/examples",
+            |req, ctx| async move { admin::eval::examples(req, ctx).await },
+        )
+        .post_async(
+            "/api/admin/eval/datasets/:id/examples",
+            |req, ctx| async move { admin::eval::examples(req, ctx).await },
+        )
+        .delete_async(
+            "/api/admin/eval/datasets/:id/examples/:example_id",
+            |req, ctx| async move { admin::eval::examples(req, ctx).await },
+        )
+        .get_async("/api/admin/eval/runs", |req, ctx| async move {
+            admin::eval::runs(req, ctx).await
+        })
+        .post_async("/api/admin/eval/runs", |req, ctx| async move {
+            admin::eval::run(req, ctx).await
+        })
+        .options("/api/admin/eval/datasets", |_, _| Response::ok(""))
+        .options("/api/admin/eval/datasets/:id/examples", |_, _| {
+            Response::ok("")
+        })
+        .options(
+            "/api/admin/eval/datasets/:id/examples/:example_id",
+            |_, _| Response::ok(""),
+        )
+        .post_async(
+            "/api/admin/eval/datasets/:id/import-reviews",
+            |req, ctx| async move { admin::eval::import_reviews(req, ctx).await },
+        )
+        .options("/api/admin/eval/runs", |_, _| Response::ok(""))
+        .options("/api/admin/eval/datasets/:id/import-reviews", |_, _| {
+            Response::ok("")
+        })
+        .post_async("/api/admin/job-sources", |req, ctx| async move {
+            admin::job_sources::create(req, ctx).await
+        })
+        .delete_async("/api/admin/job-sources/:id", |req, ctx| async move {
+            admin::job_sources::delete(req, ctx).await
+        })
+        .options("/api/admin/job-sources", |_, _| Response::ok(""))
+        .options("/api/admin/job-sources/:id", |_, _| Response::ok(""))
         // AI results routes
         .get_async("/api/emails/:email_id/ai-results", |req, ctx| async move {
             ai::results::handler(req, ctx).await
         })
         .options("/api/emails/:email_id/ai-results", |_, _| Response::ok(""))
+        .post_async("/api/emails/:email_id/reprocess", |req, ctx| async move {
+            ai::results::reprocess(req, ctx).await
+        })
+        .options("/api/emails/:email_id/reprocess", |_, _| Response::ok(""))
+        // AI review queue routes
+        .get_async("/api/ai-reviews", |req, ctx| async move {
+            ai::reviews::queue(req, ctx).await
+        })
+        .post_async("/api/emails/:email_id/ai-review", |req, ctx| async move {
+            ai::reviews::review(req, ctx).await
+        })
+        .options("/api/ai-reviews", |_, _| Response::ok(""))
+        .options("/api/emails/:email_id/ai-review", |_, _| Response::ok(""))
         .run(req, env)
         .await?;
 
@@ -212,3 +402,39 @@ async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
 fn apply_cors(response: Response, cors: &Cors) -> Result<Response> {
     response.with_cors(cors)
 }
+
+#[event(scheduled)]
+async fn scheduled(_event: ScheduledEvent, env: Env, _ctx: ScheduleContext) {
+    // Purge jobs whose trash retention window has passed
+    match jobs::purge_expired_jobs(&env).await {
+        Ok(purged) => console_log!("Purged {} expired jobs from the trash", purged),
+        Err(e) => console_error!("Failed to purge expired jobs: {}", e),
+    }
+
+    // Link jobs created outside the API to companies and learn company domains
+    match common::db::get_d1(&env) {
+        Ok(db) => match services::companies::sync_companies(&db).await {
+            Ok(()) => console_log!("Synced companies"),
+            Err(e) => console_error!("Failed to sync companies: {}", e),
+        },
+        Err(e) => console_error!("Failed to sync companies: {}", e),
+    }
+
+    // Drop cached AI results the AI worker no longer reuses
+    match common::db::get_d1(&env) {
+        Ok(db) => match services::db::ai_results::prune_ai_result_cache(&db).await {
+            Ok(()) => console_log!("Pruned the AI result cache"),
+            Err(e) => console_error!("Failed to prune the AI result cache: {}", e),
+        },
+        Err(e) => console_error!("Failed to prune the AI result cache: {}", e),
+    }
+
+    // Roll AI results up into daily stats for the admin dashboard
+    match common::db::get_d1(&env) {
+        Ok(db) => match services::db::ai_stats::rollup_ai_daily_stats(&db).await {
+            Ok(days) => console_log!("Rolled up AI stats for {} days", days),
+            Err(e) => console_error!("Failed to roll up AI stats: {}", e),
+        },
+        Err(e) => console_error!("Failed to roll up AI stats: {}", e),
+    }
+}
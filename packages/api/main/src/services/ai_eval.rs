@@ -0,0 +1,168 @@
+//! Scoring of AI prompt evaluation runs against labelled examples
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// Categories the classify stage returns, in report order
+pub const CATEGORIES: [&str; 5] = [
+    "interview",
+    "rejection",
+    "new_job",
+    "application_sent",
+    "other",
+];
+
+/// Fields the extract stage returns
+pub const EXTRACTION_FIELDS: [&str; 7] = [
+    "company",
+    "job_title",
+    "recruiter_name",
+    "recruiter_email",
+    "interview_date",
+    "location",
+    "remote",
+];
+
+/// Most examples scored per prompt in a run, so runs on large datasets finish
+/// within the request time limit
+pub const MAX_EVAL_EXAMPLES: usize = 100;
+
+/// At most `max` items spread evenly over `items`, in their original order
+pub fn sample_evenly<T: Clone>(items: &[T], max: usize) -> Vec<T> {
+    if items.len() <= max {
+        return items.to_vec();
+    }
+    (0..max)
+        .map(|i| items[i * items.len() / max].clone())
+        .collect()
+}
+
+/// Expected and predicted results for one example
+#[derive(Debug, Clone, Default)]
+pub struct EvalCase {
+    pub expected_category: String,
+    /// Only the fields present here are scored; `null` means "should be empty"
+    pub expected_fields: Map<String, Value>,
+    pub predicted_category: String,
+    pub predicted_fields: Map<String, Value>,
+}
+
+/// Extraction counts and scores for one field. Precision and recall are
+/// `None` when there is nothing to divide by.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FieldMetrics {
+    pub true_positives: u32,
+    pub false_positives: u32,
+    pub false_negatives: u32,
+    pub precision: Option<f64>,
+    pub recall: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EvalReport {
+    pub total: u32,
+    pub correct: u32,
+    pub accuracy: f64,
+    /// Row and column labels of the confusion matrix
+    pub labels: Vec<String>,
+    /// `confusion_matrix[expected][predicted]` counts, indexed like `labels`
+    pub confusion_matrix: Vec<Vec<u32>>,
+    pub fields: BTreeMap<String, FieldMetrics>,
+}
+
+/// Normalize a field value for comparison: lowercase trimmed text, with
+/// empty strings and nulls treated as missing
+pub fn normalize_field(value: Option<&Value>) -> Option<String> {
+    let text = match value? {
+        Value::Null => return None,
+        Value::String(s) => s.trim().to_lowercase(),
+        Value::Bool(b) => b.to_string(),
+        other => other.to_string(),
+    };
+    if text.is_empty() || text == "null" {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+fn ratio(numerator: u32, denominator: u32) -> Option<f64> {
+    if denominator == 0 {
+        None
+    } else {
+        Some(f64::from(numerator) / f64::from(denominator))
+    }
+}
+
+/// Score a run: category accuracy and confusion matrix, and precision and
+/// recall for each labelled extraction field
+pub fn build_report(cases: &[EvalCase]) -> EvalReport {
+    let mut labels: Vec<String> = CATEGORIES.iter().map(|c| c.to_string()).collect();
+    for case in cases {
+        for category in [&case.expected_category, &case.predicted_category] {
+            if !labels.contains(category) {
+                labels.push(category.clone());
+            }
+        }
+    }
+
+    let mut confusion_matrix = vec![vec![0u32; labels.len()]; labels.len()];
+    let mut correct = 0;
+    let mut fields: BTreeMap<String, FieldMetrics> = BTreeMap::new();
+
+    for case in cases {
+        let expected = labels
+            .iter()
+            .position(|l| *l == case.expected_category)
+            .unwrap_or_default();
+        let predicted = labels
+            .iter()
+            .position(|l| *l == case.predicted_category)
+            .unwrap_or_default();
+        confusion_matrix[expected][predicted] += 1;
+        if expected == predicted {
+            correct += 1;
+        }
+
+        for field in EXTRACTION_FIELDS {
+            if !case.expected_fields.contains_key(field) {
+                continue;
+            }
+            let expected_value = normalize_field(case.expected_fields.get(field));
+            let predicted_value = normalize_field(case.predicted_fields.get(field));
+            let metrics = fields.entry(field.to_string()).or_default();
+            match (expected_value, predicted_value) {
+                (Some(e), Some(p)) if e == p => metrics.true_positives += 1,
+                (Some(_), Some(_)) => {
+                    metrics.false_positives += 1;
+                    metrics.false_negatives += 1;
+                }
+                (None, Some(_)) => metrics.false_positives += 1,
+                (Some(_), None) => metrics.false_negatives += 1,
+                (None, None) => {}
+            }
+        }
+    }
+
+    for metrics in fields.values_mut() {
+        metrics.precision = ratio(
+            metrics.true_positives,
+            metrics.true_positives + metrics.false_positives,
+        );
+        metrics.recall = ratio(
+            metrics.true_positives,
+            metrics.true_positives + metrics.false_negatives,
+        );
+    }
+
+    let total = cases.len() as u32;
+    EvalReport {
+        total,
+        correct,
+        accuracy: ratio(correct, total).unwrap_or(0.0),
+        labels,
+        confusion_matrix,
+        fields,
+    }
+}
@@ -0,0 +1,60 @@
+//! Comparing a reviewed AI result with what the model predicted
+
+use crate::services::ai_eval::{normalize_field, EXTRACTION_FIELDS};
+use serde_json::{Map, Value};
+
+/// What the user changed while reviewing an AI result
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReviewDiff {
+    pub category_changed: bool,
+    /// Extraction fields whose reviewed value differs from the prediction
+    pub corrected_fields: Vec<String>,
+}
+
+impl ReviewDiff {
+    /// `accepted` when the review matches the prediction, `corrected` otherwise
+    pub fn status(&self) -> &'static str {
+        if self.category_changed || !self.corrected_fields.is_empty() {
+            "corrected"
+        } else {
+            "accepted"
+        }
+    }
+}
+
+/// Compare reviewed values with predicted ones. Values are compared like in
+/// evaluation runs, so case, whitespace and empty strings don't count as changes.
+pub fn diff_review(
+    predicted_category: Option<&str>,
+    predicted_fields: &Map<String, Value>,
+    category: &str,
+    fields: &Map<String, Value>,
+) -> ReviewDiff {
+    let corrected_fields = EXTRACTION_FIELDS
+        .iter()
+        .filter(|field| {
+            normalize_field(predicted_fields.get(**field)) != normalize_field(fields.get(**field))
+        })
+        .map(|field| field.to_string())
+        .collect();
+
+    ReviewDiff {
+        category_changed: predicted_category != Some(category),
+        corrected_fields,
+    }
+}
+
+/// Reviewed fields with every extraction field present: unknown keys are
+/// dropped and empty values become `null`, so evaluation scores all of them
+pub fn label_fields(fields: &Map<String, Value>) -> Map<String, Value> {
+    EXTRACTION_FIELDS
+        .iter()
+        .map(|field| {
+            let value = match fields.get(*field) {
+                Some(value) if normalize_field(Some(value)).is_some() => value.clone(),
+                _ => Value::Null,
+            };
+            (field.to_string(), value)
+        })
+        .collect()
+}
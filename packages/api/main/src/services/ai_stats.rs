@@ -0,0 +1,92 @@
+//! Daily rollup of AI processing results
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Confidence below which `process_email` flags an email for review
+pub const REVIEW_CONFIDENCE_THRESHOLD: f64 = 0.7;
+
+/// AI results of one day and category, as aggregated by the database
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CategoryDayRow {
+    pub date: String,
+    pub category: Option<String>,
+    pub results: u32,
+    /// Sum and count of the non-null confidences
+    pub confidence_sum: Option<f64>,
+    pub confidence_count: u32,
+    pub needs_review: u32,
+}
+
+/// One row of `ai_daily_stats`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AiDailyStats {
+    pub date: String,
+    pub emails_processed: u32,
+    pub avg_confidence: Option<f64>,
+    pub needs_review_count: u32,
+    pub category_breakdown: BTreeMap<String, u32>,
+}
+
+/// Combine per-category rows into one row per day, ordered by date. Results
+/// without a category count as `other`.
+pub fn rollup_days(rows: &[CategoryDayRow]) -> Vec<AiDailyStats> {
+    let mut days: BTreeMap<String, (AiDailyStats, f64, u32)> = BTreeMap::new();
+
+    for row in rows {
+        let (day, confidence_sum, confidence_count) =
+            days.entry(row.date.clone()).or_insert_with(|| {
+                (
+                    AiDailyStats {
+                        date: row.date.clone(),
+                        ..Default::default()
+                    },
+                    0.0,
+                    0,
+                )
+            });
+        day.emails_processed += row.results;
+        day.needs_review_count += row.needs_review;
+        *day.category_breakdown
+            .entry(row.category.clone().unwrap_or_else(|| "other".to_string()))
+            .or_default() += row.results;
+        *confidence_sum += row.confidence_sum.unwrap_or(0.0);
+        *confidence_count += row.confidence_count;
+    }
+
+    days.into_values()
+        .map(|(mut day, confidence_sum, confidence_count)| {
+            if confidence_count > 0 {
+                day.avg_confidence = Some(confidence_sum / f64::from(confidence_count));
+            }
+            day
+        })
+        .collect()
+}
+
+/// Totals over a range of days, with the confidence averaged over all results
+pub fn summarize_days(days: &[AiDailyStats]) -> AiDailyStats {
+    let mut total = AiDailyStats::default();
+    let mut weighted_confidence = 0.0;
+    let mut weight = 0;
+
+    for day in days {
+        total.emails_processed += day.emails_processed;
+        total.needs_review_count += day.needs_review_count;
+        for (category, count) in &day.category_breakdown {
+            *total
+                .category_breakdown
+                .entry(category.clone())
+                .or_default() += count;
+        }
+        if let Some(avg) = day.avg_confidence {
+            weighted_confidence += avg * f64::from(day.emails_processed);
+            weight += day.emails_processed;
+        }
+    }
+    if weight > 0 {
+        total.avg_confidence = Some(weighted_confidence / f64::from(weight));
+    }
+
+    total
+}
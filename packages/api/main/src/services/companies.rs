@@ -0,0 +1,466 @@
+//! Company service
+//!
+//! Jobs store the company as free text. Companies group jobs whose company
+//! normalises to the same name ("Acme Inc." and "ACME"), and contacts whose
+//! email domain matches the company's domain. Jobs are linked when they're
+//! created or their company changes. The daily cron links jobs created
+//! elsewhere (such as by the scanner), learns company domains from linked
+//! emails and links contacts.
+
+use crate::services::db::system_email_domains::{
+    get_all_domains, is_system_address, SystemEmailDomain,
+};
+use crate::services::jobs::duplicates::normalize_company;
+use crate::services::password;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use worker::{D1Database, D1PreparedStatement, Response};
+
+/// Personal email providers, which never identify an employer
+pub const FREE_MAIL_DOMAINS: &[&str] = &[
+    "gmail.com",
+    "googlemail.com",
+    "yahoo.com",
+    "outlook.com",
+    "hotmail.com",
+    "live.com",
+    "msn.com",
+    "icloud.com",
+    "me.com",
+    "aol.com",
+    "proton.me",
+    "protonmail.com",
+];
+
+/// Fields of a company that can be edited. Fields that are not given are left
+/// unchanged; an empty string clears the field.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct UpdateCompanyRequest {
+    pub name: Option<String>,
+    pub domain: Option<String>,
+    pub website: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// Domain of an email address ("Name <user@example.com>" or a bare address)
+pub fn email_domain(address: &str) -> Option<String> {
+    let address = match (address.find('<'), address.find('>')) {
+        (Some(start), Some(end)) if start < end => &address[start + 1..end],
+        _ => address,
+    };
+    let domain = address.trim().rsplit_once('@')?.1.trim().to_lowercase();
+    if domain.contains('.') {
+        Some(domain)
+    } else {
+        None
+    }
+}
+
+/// Normalise a domain entered by the user: accepts a bare domain, a URL or an
+/// email address, e.g. "https://www.Acme.com/careers" -> "acme.com"
+pub fn normalize_domain(value: &str) -> Option<String> {
+    let value = value.trim().to_lowercase();
+    let value = value
+        .split_once("://")
+        .map(|(_, rest)| rest)
+        .unwrap_or(&value);
+    let value = value
+        .rsplit_once('@')
+        .map(|(_, rest)| rest)
+        .unwrap_or(value);
+    let host = value
+        .split(['/', '?', '#', ':'])
+        .next()
+        .unwrap_or("")
+        .trim_end_matches('.');
+    let host = host.strip_prefix("www.").unwrap_or(host);
+
+    let valid = host.contains('.')
+        && !host.starts_with('.')
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+    if valid {
+        Some(host.to_string())
+    } else {
+        None
+    }
+}
+
+/// Pick the domain a company sends email from: the most common sender domain
+/// that isn't a personal email provider or a system domain (ATS, job boards,
+/// no-reply addresses). Ties go to the alphabetically first domain.
+pub fn infer_company_domain(senders: &[&str], domains: &[SystemEmailDomain]) -> Option<String> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for sender in senders {
+        let Some(domain) = email_domain(sender) else {
+            continue;
+        };
+        if FREE_MAIL_DOMAINS.contains(&domain.as_str()) {
+            continue;
+        }
+        let address = match (sender.find('<'), sender.find('>')) {
+            (Some(start), Some(end)) if start < end => &sender[start + 1..end],
+            _ => sender,
+        };
+        if !is_system_address(&address.trim().to_lowercase(), domains) {
+            *counts.entry(domain).or_insert(0) += 1;
+        }
+    }
+
+    counts
+        .into_iter()
+        .fold(
+            None,
+            |best: Option<(String, usize)>, (domain, count)| match best {
+                Some((_, best_count)) if best_count >= count => best,
+                _ => Some((domain, count)),
+            },
+        )
+        .map(|(domain, _)| domain)
+}
+
+/// Find the company with the same normalised name, creating it if needed.
+/// Returns `None` when the name has nothing left after normalisation.
+async fn find_or_create_company(
+    db: &D1Database,
+    name: &str,
+) -> Result<Option<String>, worker::Error> {
+    let normalized = normalize_company(name);
+    if normalized.is_empty() {
+        return Ok(None);
+    }
+
+    let id = password::generate_uuid()
+        .map_err(|e| worker::Error::RustError(format!("Failed to generate UUID: {}", e)))?;
+    db.prepare("INSERT OR IGNORE INTO companies (id, name, normalized_name) VALUES (?, ?, ?)")
+        .bind(&[id.into(), name.trim().into(), normalized.clone().into()])?
+        .run()
+        .await?;
+
+    let row = db
+        .prepare("SELECT id FROM companies WHERE normalized_name = ?")
+        .bind(&[normalized.into()])?
+        .first::<Value>(None)
+        .await?;
+    Ok(row.and_then(|r| r.get("id").and_then(|v| v.as_str()).map(|s| s.to_string())))
+}
+
+/// Link a job to the company matching its company name
+pub async fn link_job_to_company(
+    db: &D1Database,
+    job_id: &str,
+    company: &str,
+) -> Result<(), worker::Error> {
+    let company_id = find_or_create_company(db, company).await?;
+    db.prepare("UPDATE jobs SET company_id = ? WHERE id = ?")
+        .bind(&[company_id.into(), job_id.into()])?
+        .run()
+        .await?;
+    Ok(())
+}
+
+/// Link contacts without a company to the company with their email domain
+fn link_contacts_statement(db: &D1Database) -> D1PreparedStatement {
+    db.prepare(
+        "UPDATE email_contacts SET company_id = (
+            SELECT c.id FROM companies c
+            WHERE c.domain = lower(substr(email_contacts.email, instr(email_contacts.email, '@') + 1))
+            ORDER BY c.created_at ASC
+            LIMIT 1
+        )
+        WHERE company_id IS NULL AND is_system = 0
+          AND EXISTS (
+            SELECT 1 FROM companies c
+            WHERE c.domain = lower(substr(email_contacts.email, instr(email_contacts.email, '@') + 1))
+          )",
+    )
+}
+
+/// Link unlinked jobs to companies, learn company domains from the emails of
+/// their jobs, and link contacts to the company with their domain.
+/// Run by the daily cron.
+pub async fn sync_companies(db: &D1Database) -> Result<(), worker::Error> {
+    // Jobs without a company
+    let result = db
+        .prepare("SELECT id, company FROM jobs WHERE company_id IS NULL")
+        .all()
+        .await?;
+    let rows: Vec<Value> = result.results()?;
+
+    let mut company_ids: HashMap<String, Option<String>> = HashMap::new();
+    let mut statements = Vec::new();
+    for row in &rows {
+        let (Some(job_id), Some(company)) = (
+            row.get("id").and_then(|v| v.as_str()),
+            row.get("company").and_then(|v| v.as_str()),
+        ) else {
+            continue;
+        };
+        let key = normalize_company(company);
+        if !company_ids.contains_key(&key) {
+            let id = find_or_create_company(db, company).await?;
+            company_ids.insert(key.clone(), id);
+        }
+        if let Some(Some(company_id)) = company_ids.get(&key) {
+            statements.push(
+                db.prepare("UPDATE jobs SET company_id = ? WHERE id = ?")
+                    .bind(&[company_id.as_str().into(), job_id.into()])?,
+            );
+        }
+    }
+
+    // Link jobs first so their emails count towards the domains below
+    if !statements.is_empty() {
+        db.batch(std::mem::take(&mut statements)).await?;
+    }
+
+    // Companies without a domain, from the senders of emails on their jobs
+    let result = db
+        .prepare(
+            "SELECT j.company_id, e.\"from\" as sender
+             FROM emails e
+             JOIN jobs j ON j.id = e.job_id
+             JOIN companies c ON c.id = j.company_id
+             WHERE c.domain IS NULL",
+        )
+        .all()
+        .await?;
+    let rows: Vec<Value> = result.results()?;
+
+    if !rows.is_empty() {
+        let mut senders: HashMap<&str, Vec<&str>> = HashMap::new();
+        for row in &rows {
+            if let (Some(company_id), Some(sender)) = (
+                row.get("company_id").and_then(|v| v.as_str()),
+                row.get("sender").and_then(|v| v.as_str()),
+            ) {
+                senders.entry(company_id).or_default().push(sender);
+            }
+        }
+
+        let domains = get_all_domains(db).await.map_err(|e| {
+            worker::Error::RustError(format!("Failed to load email domains: {}", e))
+        })?;
+        for (company_id, company_senders) in &senders {
+            if let Some(domain) = infer_company_domain(company_senders, &domains) {
+                statements.push(
+                    db.prepare(
+                        "UPDATE companies SET domain = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ? AND domain IS NULL",
+                    )
+                    .bind(&[domain.into(), (*company_id).into()])?,
+                );
+            }
+        }
+    }
+
+    // Contacts whose email domain is a company's domain
+    statements.push(link_contacts_statement(db));
+
+    db.batch(statements).await?;
+    Ok(())
+}
+
+/// List companies that have jobs or contacts, with counts
+pub async fn list_companies(db: &D1Database, user_id: &str) -> Result<Response, worker::Error> {
+    let result = db
+        .prepare(
+            "SELECT
+                c.id, c.name, c.domain, c.website,
+                (SELECT COUNT(*) FROM jobs j WHERE j.company_id = c.id AND j.deleted_at IS NULL) as job_count,
+                (SELECT COUNT(*) FROM jobs j WHERE j.company_id = c.id AND j.deleted_at IS NULL AND j.archived_at IS NULL) as active_job_count,
+                (SELECT COUNT(*) FROM email_contacts ec WHERE ec.company_id = c.id AND ec.user_id = ?) as contact_count,
+                (SELECT MAX(j.updated_at) FROM jobs j WHERE j.company_id = c.id AND j.deleted_at IS NULL) as last_activity
+            FROM companies c
+            WHERE EXISTS (SELECT 1 FROM jobs j WHERE j.company_id = c.id AND j.deleted_at IS NULL)
+               OR EXISTS (SELECT 1 FROM email_contacts ec WHERE ec.company_id = c.id AND ec.user_id = ?)
+            ORDER BY c.name COLLATE NOCASE ASC",
+        )
+        .bind(&[user_id.into(), user_id.into()])?
+        .all()
+        .await?;
+
+    let companies: Vec<Value> = result.results()?;
+    Response::from_json(&companies)
+}
+
+/// Get a company with its jobs, contacts, emails and outcomes
+pub async fn get_company_details(
+    db: &D1Database,
+    user_id: &str,
+    id: &str,
+) -> Result<Response, worker::Error> {
+    let company = match db
+        .prepare("SELECT id, name, domain, website, notes, created_at, updated_at FROM companies WHERE id = ?")
+        .bind(&[id.into()])?
+        .first::<Value>(None)
+        .await?
+    {
+        Some(company) => company,
+        None => return Response::error("Company not found", 404),
+    };
+
+    let result = db
+        .prepare(
+            "SELECT j.id, j.title, j.location, j.status_id, js.display_name as status_name,
+                    src.name as source_name, j.archived_at, j.created_at, j.updated_at
+             FROM jobs j
+             LEFT JOIN job_statuses js ON j.status_id = js.id
+             LEFT JOIN job_sources src ON j.source_id = src.id
+             WHERE j.company_id = ? AND j.deleted_at IS NULL
+             ORDER BY j.created_at DESC",
+        )
+        .bind(&[id.into()])?
+        .all()
+        .await?;
+    let jobs: Vec<Value> = result.results()?;
+
+    let result = db
+        .prepare(
+            "SELECT email, name, linkedin, website FROM email_contacts
+             WHERE company_id = ? AND user_id = ?
+             ORDER BY COALESCE(name, email) COLLATE NOCASE ASC",
+        )
+        .bind(&[id.into(), user_id.into()])?
+        .all()
+        .await?;
+    let contacts: Vec<Value> = result.results()?;
+
+    // Emails linked to the company's jobs or sent from its domain
+    let domain = company
+        .get("domain")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    let (bare_pattern, bracket_pattern) = if domain.is_empty() {
+        (String::new(), String::new())
+    } else {
+        (format!("%@{}", domain), format!("%@{}>", domain))
+    };
+    let result = db
+        .prepare(
+            "SELECT e.gmail_id, e.subject, e.\"from\", e.date, e.job_id, j.title as job_title
+             FROM emails e
+             LEFT JOIN jobs j ON j.id = e.job_id
+             WHERE e.user_id = ?
+               AND ((j.company_id = ? AND j.deleted_at IS NULL)
+                    OR (? != '' AND (lower(e.\"from\") LIKE ? OR lower(e.\"from\") LIKE ?)))
+             ORDER BY e.date DESC
+             LIMIT 100",
+        )
+        .bind(&[
+            user_id.into(),
+            id.into(),
+            domain.into(),
+            bare_pattern.into(),
+            bracket_pattern.into(),
+        ])?
+        .all()
+        .await?;
+    let emails: Vec<Value> = result.results()?;
+
+    // Current status of the company's jobs
+    let result = db
+        .prepare(
+            "SELECT js.id as status_id, js.display_name as status_name, COUNT(j.id) as count
+             FROM job_statuses js
+             JOIN jobs j ON j.status_id = js.id
+             WHERE j.company_id = ? AND j.deleted_at IS NULL
+             GROUP BY js.id
+             ORDER BY js.id ASC",
+        )
+        .bind(&[id.into()])?
+        .all()
+        .await?;
+    let outcomes: Vec<Value> = result.results()?;
+
+    Response::from_json(&serde_json::json!({
+        "company": company,
+        "jobs": jobs,
+        "contacts": contacts,
+        "emails": emails,
+        "outcomes": outcomes,
+    }))
+}
+
+/// Update a company. Renaming only changes the display name; jobs keep being
+/// matched on the normalised name the company was created with.
+pub async fn update_company(
+    db: &D1Database,
+    id: &str,
+    request: UpdateCompanyRequest,
+) -> Result<Response, worker::Error> {
+    let existing = db
+        .prepare("SELECT domain FROM companies WHERE id = ?")
+        .bind(&[id.into()])?
+        .first::<Value>(None)
+        .await?;
+    let Some(existing) = existing else {
+        return Response::error("Company not found", 404);
+    };
+
+    let mut sets: Vec<&str> = Vec::new();
+    let mut bindings: Vec<worker::wasm_bindgen::JsValue> = Vec::new();
+
+    if let Some(name) = &request.name {
+        let name = name.trim();
+        if name.is_empty() {
+            return Response::error("Company name is required", 400);
+        }
+        sets.push("name = ?");
+        bindings.push(name.into());
+    }
+
+    let mut domain_changed = false;
+    if let Some(domain) = &request.domain {
+        let domain = if domain.trim().is_empty() {
+            None
+        } else {
+            match normalize_domain(domain) {
+                Some(domain) => Some(domain),
+                None => return Response::error("Invalid domain", 400),
+            }
+        };
+        domain_changed = existing.get("domain").and_then(|v| v.as_str()) != domain.as_deref();
+        sets.push("domain = ?");
+        bindings.push(domain.into());
+    }
+
+    for (column, value) in [
+        ("website = ?", &request.website),
+        ("notes = ?", &request.notes),
+    ] {
+        if let Some(value) = value {
+            let value = Some(value.trim()).filter(|v| !v.is_empty());
+            sets.push(column);
+            bindings.push(value.into());
+        }
+    }
+
+    if !sets.is_empty() {
+        bindings.push(id.into());
+        let mut statements = vec![db
+            .prepare(format!(
+                "UPDATE companies SET {}, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+                sets.join(", ")
+            ))
+            .bind(&bindings)?];
+        // Relink contacts to the new domain
+        if domain_changed {
+            statements.push(
+                db.prepare("UPDATE email_contacts SET company_id = NULL WHERE company_id = ?")
+                    .bind(&[id.into()])?,
+            );
+            statements.push(link_contacts_statement(db));
+        }
+        db.batch(statements).await?;
+    }
+
+    let company = db
+        .prepare("SELECT id, name, domain, website, notes, created_at, updated_at FROM companies WHERE id = ?")
+        .bind(&[id.into()])?
+        .first::<Value>(None)
+        .await?;
+    Response::from_json(&company)
+}
@@ -0,0 +1,341 @@
+//! Contact relationship history
+//!
+//! The history of a contact is built from the emails exchanged with them.
+//! Stale contacts are people who emailed about a job and haven't had a reply
+//! from the user for a number of days.
+
+use crate::services::db::email_contacts::{extract_email_address, extract_emails_from_field};
+use crate::services::db::system_email_domains::{get_all_domains, is_system_address};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use worker::{D1Database, Response};
+
+/// Days without a reply before a contact is considered stale
+pub const DEFAULT_STALE_DAYS: i64 = 7;
+
+/// An email with the fields needed to tell who wrote to whom
+#[derive(Debug, Clone)]
+pub struct ContactMessage {
+    pub from: String,
+    pub to: String,
+    pub cc: String,
+    pub date: String,
+    /// Julian day of `date`
+    pub day: f64,
+    pub job_id: Option<String>,
+}
+
+/// A contact waiting on a reply
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct StaleContact {
+    pub email: String,
+    pub name: Option<String>,
+    pub company_name: Option<String>,
+    /// Date of the last email received from the contact
+    pub last_received: String,
+    /// Date of the last email sent to the contact, before `last_received`
+    pub last_replied: Option<String>,
+    pub days_waiting: i64,
+    /// Job the last email from the contact is linked to
+    pub job_id: Option<String>,
+}
+
+/// Find contacts whose last job-related email to the user is at least
+/// `stale_days` old and hasn't been answered since. Oldest first.
+pub fn find_stale_contacts(
+    messages: &[ContactMessage],
+    user_email: &str,
+    now_day: f64,
+    stale_days: i64,
+) -> Vec<StaleContact> {
+    let user_email = user_email.trim().to_lowercase();
+    let mut last_received: HashMap<String, &ContactMessage> = HashMap::new();
+    let mut last_sent: HashMap<String, &ContactMessage> = HashMap::new();
+
+    for message in messages {
+        let sender = extract_email_address(&message.from);
+        if sender.is_empty() {
+            continue;
+        }
+
+        if sender == user_email {
+            let recipients = extract_emails_from_field(&message.to)
+                .into_iter()
+                .chain(extract_emails_from_field(&message.cc));
+            for recipient in recipients {
+                let latest = last_sent.entry(recipient).or_insert(message);
+                if message.day > latest.day {
+                    *latest = message;
+                }
+            }
+        } else if message.job_id.is_some() {
+            let latest = last_received.entry(sender).or_insert(message);
+            if message.day > latest.day {
+                *latest = message;
+            }
+        }
+    }
+
+    let mut stale: Vec<StaleContact> = last_received
+        .into_iter()
+        .filter_map(|(email, received)| {
+            let days_waiting = (now_day - received.day).floor() as i64;
+            if days_waiting < stale_days {
+                return None;
+            }
+            let replied = last_sent.get(&email);
+            if replied.is_some_and(|sent| sent.day >= received.day) {
+                return None;
+            }
+            Some(StaleContact {
+                email,
+                name: None,
+                company_name: None,
+                last_received: received.date.clone(),
+                last_replied: replied.map(|sent| sent.date.clone()),
+                days_waiting,
+                job_id: received.job_id.clone(),
+            })
+        })
+        .collect();
+
+    stale.sort_by(|a, b| {
+        b.days_waiting
+            .cmp(&a.days_waiting)
+            .then_with(|| a.email.cmp(&b.email))
+    });
+    stale
+}
+
+/// SQL expression turning an address header column into a comma-bounded list
+/// of lowercased words, so `instr(list, ',' || address || ',')` matches only
+/// a whole address. "Jane <jane@acme.com>, bob@beta.io" becomes
+/// ",jane,,jane@acme.com,,,bob@beta.io,".
+fn address_list(column: &str) -> String {
+    let mut expression = format!("lower(COALESCE({}, ''))", column);
+    for delimiter in ["<", ">", " ", ";", "\""] {
+        expression = format!("replace({}, '{}', ',')", expression, delimiter);
+    }
+    format!("(',' || {} || ',')", expression)
+}
+
+fn text(row: &Value, key: &str) -> Option<String> {
+    row.get(key).and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+async fn get_user_email(db: &D1Database, user_id: &str) -> Result<String, worker::Error> {
+    let row = db
+        .prepare("SELECT email FROM users WHERE id = ?")
+        .bind(&[user_id.into()])?
+        .first::<Value>(None)
+        .await?;
+    Ok(row
+        .and_then(|row| text(&row, "email"))
+        .unwrap_or_default()
+        .to_lowercase())
+}
+
+/// List contacts waiting on a reply for at least `stale_days` days. System
+/// senders (ATS notifications, no-reply addresses) are left out.
+pub async fn list_stale_contacts(
+    db: &D1Database,
+    user_id: &str,
+    stale_days: i64,
+) -> Result<Response, worker::Error> {
+    let user_email = get_user_email(db, user_id).await?;
+
+    let result = db
+        .prepare(
+            "SELECT \"from\", \"to\", cc, date, julianday(date) as day, job_id
+             FROM emails
+             WHERE user_id = ? AND julianday(date) IS NOT NULL",
+        )
+        .bind(&[user_id.into()])?
+        .all()
+        .await?;
+    let rows: Vec<Value> = result.results()?;
+    let messages: Vec<ContactMessage> = rows
+        .iter()
+        .filter_map(|row| {
+            Some(ContactMessage {
+                from: text(row, "from")?,
+                to: text(row, "to").unwrap_or_default(),
+                cc: text(row, "cc").unwrap_or_default(),
+                date: text(row, "date")?,
+                day: row.get("day").and_then(|v| v.as_f64())?,
+                job_id: text(row, "job_id"),
+            })
+        })
+        .collect();
+
+    let now_day = js_sys::Date::now() / 86_400_000.0 + 2_440_587.5;
+    let mut stale = find_stale_contacts(&messages, &user_email, now_day, stale_days);
+    if stale.is_empty() {
+        return Response::from_json(&stale);
+    }
+
+    // Names, companies and saved system flags of the user's contacts
+    let result = db
+        .prepare(
+            "SELECT ec.email, ec.name, ec.is_system, c.name as company_name
+             FROM email_contacts ec
+             LEFT JOIN companies c ON c.id = ec.company_id
+             WHERE ec.user_id = ?",
+        )
+        .bind(&[user_id.into()])?
+        .all()
+        .await?;
+    let rows: Vec<Value> = result.results()?;
+    let contacts: HashMap<String, &Value> = rows
+        .iter()
+        .filter_map(|row| text(row, "email").map(|email| (email, row)))
+        .collect();
+
+    let domains = get_all_domains(db)
+        .await
+        .map_err(|e| worker::Error::RustError(format!("Failed to load email domains: {}", e)))?;
+
+    stale.retain(|s| !is_system_address(&s.email, &domains));
+    for contact in stale.iter_mut() {
+        if let Some(row) = contacts.get(&contact.email) {
+            contact.name = text(row, "name");
+            contact.company_name = text(row, "company_name");
+        }
+    }
+    stale.retain(|s| {
+        contacts
+            .get(&s.email)
+            .and_then(|row| row.get("is_system"))
+            .and_then(|v| v.as_i64())
+            .unwrap_or(0)
+            == 0
+    });
+
+    Response::from_json(&stale)
+}
+
+/// Get a contact with every email exchanged with them, the jobs they are
+/// involved in (through emails or as a referrer), first and last contact
+/// dates and notes
+pub async fn get_contact_history(
+    db: &D1Database,
+    user_id: &str,
+    email: &str,
+) -> Result<Response, worker::Error> {
+    let email = extract_email_address(email);
+    if email.is_empty() {
+        return Response::error("Invalid email address", 400);
+    }
+
+    let contact = db
+        .prepare(
+            "SELECT ec.email, ec.name, ec.linkedin, ec.website, ec.notes, ec.is_system,
+                    ec.company_id, c.name as company_name, ec.created_at
+             FROM email_contacts ec
+             LEFT JOIN companies c ON c.id = ec.company_id
+             WHERE ec.email = ? AND ec.user_id = ?",
+        )
+        .bind(&[email.clone().into(), user_id.into()])?
+        .first::<Value>(None)
+        .await?;
+    let Some(contact) = contact else {
+        return Response::error("Contact not found", 404);
+    };
+
+    // Emails with the address in a header, matched as a whole address
+    let involves_contact = format!(
+        "(instr({}, ?2) > 0 OR instr({}, ?2) > 0 OR instr({}, ?2) > 0)",
+        address_list("\"from\""),
+        address_list("\"to\""),
+        address_list("cc")
+    );
+    let bounded = format!(",{},", email);
+    let result = db
+        .prepare(format!(
+            "SELECT gmail_id, thread_id, subject, \"from\", \"to\", cc, snippet, date, job_id
+             FROM emails
+             WHERE user_id = ?1 AND {}
+             ORDER BY date DESC",
+            involves_contact
+        ))
+        .bind(&[user_id.into(), bounded.clone().into()])?
+        .all()
+        .await?;
+    let mut emails: Vec<Value> = result.results()?;
+
+    let mut received = 0;
+    for message in emails.iter_mut() {
+        let inbound = text(message, "from")
+            .map(|from| extract_email_address(&from) == email)
+            .unwrap_or(false);
+        if inbound {
+            received += 1;
+        }
+        message["inbound"] = Value::Bool(inbound);
+    }
+    let sent = emails.len() - received;
+
+    let result = db
+        .prepare(format!(
+            "SELECT j.id, j.title, j.company, j.company_id, js.display_name as status_name,
+                    (j.referrer_email = ?3) as referred
+             FROM jobs j
+             LEFT JOIN job_statuses js ON j.status_id = js.id
+             WHERE j.deleted_at IS NULL
+               AND (j.referrer_email = ?3
+                    OR j.id IN (
+                        SELECT job_id FROM emails
+                        WHERE user_id = ?1 AND job_id IS NOT NULL AND {}
+                    ))
+             ORDER BY j.created_at DESC",
+            involves_contact
+        ))
+        .bind(&[user_id.into(), bounded.into(), email.clone().into()])?
+        .all()
+        .await?;
+    let mut jobs: Vec<Value> = result.results()?;
+    for job in jobs.iter_mut() {
+        let referred = job.get("referred").and_then(|v| v.as_i64()).unwrap_or(0) != 0;
+        job["referred"] = Value::Bool(referred);
+    }
+
+    let last_contact = emails.first().and_then(|e| text(e, "date"));
+    let first_contact = emails.last().and_then(|e| text(e, "date"));
+
+    Response::from_json(&serde_json::json!({
+        "contact": contact,
+        "emails": emails,
+        "jobs": jobs,
+        "first_contact": first_contact,
+        "last_contact": last_contact,
+        "emails_sent": sent,
+        "emails_received": received,
+    }))
+}
+
+/// List the user's contacts, excluding saved system contacts, with their
+/// company and the date of the last email exchanged
+pub async fn list_contacts(db: &D1Database, user_id: &str) -> Result<Response, worker::Error> {
+    let result = db
+        .prepare(format!(
+            "SELECT ec.email, ec.name, ec.company_id, c.name as company_name,
+                    MAX(e.date) as last_contact
+             FROM email_contacts ec
+             LEFT JOIN companies c ON c.id = ec.company_id
+             LEFT JOIN emails e ON e.user_id = ec.user_id
+               AND (instr({}, ',' || lower(ec.email) || ',') > 0
+                    OR instr({}, ',' || lower(ec.email) || ',') > 0)
+             WHERE ec.user_id = ? AND ec.is_system = 0
+             GROUP BY ec.email
+             ORDER BY last_contact DESC",
+            address_list("e.\"from\""),
+            address_list("e.\"to\"")
+        ))
+        .bind(&[user_id.into()])?
+        .all()
+        .await?;
+
+    let contacts: Vec<Value> = result.results()?;
+    Response::from_json(&contacts)
+}
@@ -0,0 +1,260 @@
+use crate::services::ai_eval::EvalReport;
+use crate::services::password;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use worker::*;
+
+#[derive(Debug, Serialize)]
+pub struct EvalDataset {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub example_count: i64,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvalExample {
+    pub id: String,
+    pub dataset_id: String,
+    pub email_id: Option<String>,
+    pub from_email: Option<String>,
+    pub subject: Option<String>,
+    pub body: Option<String>,
+    pub expected_category: String,
+    pub expected_fields: Map<String, Value>,
+    pub created_at: String,
+}
+
+/// New example; the email content is copied from `email_id` when it is given
+#[derive(Debug, Deserialize)]
+pub struct NewEvalExample {
+    pub email_id: Option<String>,
+    pub from_email: Option<String>,
+    pub subject: Option<String>,
+    pub body: Option<String>,
+    pub expected_category: String,
+    #[serde(default)]
+    pub expected_fields: Map<String, Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EvalRun {
+    pub id: String,
+    pub dataset_id: String,
+    pub prompt_id: String,
+    pub prompt_name: Option<String>,
+    pub stage: String,
+    pub accuracy: Option<f64>,
+    pub report: EvalReport,
+    pub created_at: String,
+}
+
+fn text(row: &Value, key: &str) -> Option<String> {
+    row.get(key).and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+fn row_to_example(row: &Value) -> EvalExample {
+    EvalExample {
+        id: text(row, "id").unwrap_or_default(),
+        dataset_id: text(row, "dataset_id").unwrap_or_default(),
+        email_id: text(row, "email_id"),
+        from_email: text(row, "from_email"),
+        subject: text(row, "subject"),
+        body: text(row, "body"),
+        expected_category: text(row, "expected_category").unwrap_or_default(),
+        expected_fields: text(row, "expected_fields")
+            .and_then(|f| serde_json::from_str(&f).ok())
+            .unwrap_or_default(),
+        created_at: text(row, "created_at").unwrap_or_default(),
+    }
+}
+
+fn row_to_run(row: &Value) -> EvalRun {
+    EvalRun {
+        id: text(row, "id").unwrap_or_default(),
+        dataset_id: text(row, "dataset_id").unwrap_or_default(),
+        prompt_id: text(row, "prompt_id").unwrap_or_default(),
+        prompt_name: text(row, "prompt_name"),
+        stage: text(row, "stage").unwrap_or_default(),
+        accuracy: row.get("accuracy").and_then(|v| v.as_f64()),
+        report: text(row, "report")
+            .and_then(|r| serde_json::from_str(&r).ok())
+            .unwrap_or_default(),
+        created_at: text(row, "created_at").unwrap_or_default(),
+    }
+}
+
+pub async fn list_datasets(db: &D1Database) -> Result<Vec<EvalDataset>> {
+    let result = db
+        .prepare(
+            "SELECT d.id, d.name, d.description, d.created_at,
+                (SELECT COUNT(*) FROM ai_eval_examples e WHERE e.dataset_id = d.id) as example_count
+             FROM ai_eval_datasets d ORDER BY d.created_at DESC",
+        )
+        .all()
+        .await?;
+
+    let rows: Vec<Value> = result.results()?;
+    Ok(rows
+        .iter()
+        .map(|row| EvalDataset {
+            id: text(row, "id").unwrap_or_default(),
+            name: text(row, "name").unwrap_or_default(),
+            description: text(row, "description"),
+            example_count: row
+                .get("example_count")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(0),
+            created_at: text(row, "created_at").unwrap_or_default(),
+        })
+        .collect())
+}
+
+pub async fn create_dataset(
+    db: &D1Database,
+    name: &str,
+    description: Option<&str>,
+) -> Result<String> {
+    let id = password::generate_uuid()?;
+
+    db.prepare("INSERT INTO ai_eval_datasets (id, name, description) VALUES (?, ?, ?)")
+        .bind(&[id.clone().into(), name.into(), description.into()])?
+        .run()
+        .await?;
+
+    Ok(id)
+}
+
+pub async fn dataset_exists(db: &D1Database, dataset_id: &str) -> Result<bool> {
+    let result = db
+        .prepare("SELECT id FROM ai_eval_datasets WHERE id = ?")
+        .bind(&[dataset_id.into()])?
+        .first::<Value>(None)
+        .await?;
+
+    Ok(result.is_some())
+}
+
+pub async fn list_examples(db: &D1Database, dataset_id: &str) -> Result<Vec<EvalExample>> {
+    let result = db
+        .prepare("SELECT * FROM ai_eval_examples WHERE dataset_id = ? ORDER BY created_at")
+        .bind(&[dataset_id.into()])?
+        .all()
+        .await?;
+
+    let rows: Vec<Value> = result.results()?;
+    Ok(rows.iter().map(row_to_example).collect())
+}
+
+/// Add an example to a dataset. Returns `None` when `email_id` is given but
+/// the email doesn't exist.
+pub async fn add_example(
+    db: &D1Database,
+    dataset_id: &str,
+    example: NewEvalExample,
+) -> Result<Option<String>> {
+    let mut from_email = example.from_email;
+    let mut subject = example.subject;
+    let mut body = example.body;
+
+    if let Some(email_id) = example.email_id.as_deref() {
+        let email = db
+            .prepare("SELECT \"from\", subject, snippet FROM emails WHERE gmail_id = ?")
+            .bind(&[email_id.into()])?
+            .first::<Value>(None)
+            .await?;
+        let Some(email) = email else {
+            return Ok(None);
+        };
+        from_email = from_email.or_else(|| text(&email, "from"));
+        subject = subject.or_else(|| text(&email, "subject"));
+        body = body.or_else(|| text(&email, "snippet"));
+    }
+
+    let id = password::generate_uuid()?;
+    let expected_fields = serde_json::to_string(&example.expected_fields)?;
+
+    db.prepare(
+        "INSERT INTO ai_eval_examples (id, dataset_id, email_id, from_email, subject, body, expected_category, expected_fields) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&[
+        id.clone().into(),
+        dataset_id.into(),
+        example.email_id.as_deref().into(),
+        from_email.as_deref().into(),
+        subject.as_deref().into(),
+        body.as_deref().into(),
+        example.expected_category.into(),
+        expected_fields.into(),
+    ])?
+    .run()
+    .await?;
+
+    Ok(Some(id))
+}
+
+pub async fn delete_example(db: &D1Database, dataset_id: &str, example_id: &str) -> Result<()> {
+    db.prepare("DELETE FROM ai_eval_examples WHERE id = ? AND dataset_id = ?")
+        .bind(&[example_id.into(), dataset_id.into()])?
+        .run()
+        .await?;
+
+    Ok(())
+}
+
+pub async fn save_run(
+    db: &D1Database,
+    dataset_id: &str,
+    prompt_id: &str,
+    stage: &str,
+    report: &EvalReport,
+) -> Result<String> {
+    let id = password::generate_uuid()?;
+    let report_json = serde_json::to_string(report)?;
+
+    db.prepare(
+        "INSERT INTO ai_eval_runs (id, dataset_id, prompt_id, stage, accuracy, report) VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&[
+        id.clone().into(),
+        dataset_id.into(),
+        prompt_id.into(),
+        stage.into(),
+        report.accuracy.into(),
+        report_json.into(),
+    ])?
+    .run()
+    .await?;
+
+    Ok(id)
+}
+
+const RUN_QUERY: &str = "SELECT r.*, p.name as prompt_name FROM ai_eval_runs r
+     LEFT JOIN ai_prompts p ON p.id = r.prompt_id";
+
+pub async fn get_run(db: &D1Database, run_id: &str) -> Result<Option<EvalRun>> {
+    let result = db
+        .prepare(format!("{} WHERE r.id = ?", RUN_QUERY))
+        .bind(&[run_id.into()])?
+        .first::<Value>(None)
+        .await?;
+
+    Ok(result.as_ref().map(row_to_run))
+}
+
+/// Runs for a dataset, newest first
+pub async fn list_runs(db: &D1Database, dataset_id: &str) -> Result<Vec<EvalRun>> {
+    let result = db
+        .prepare(format!(
+            "{} WHERE r.dataset_id = ? ORDER BY r.created_at DESC",
+            RUN_QUERY
+        ))
+        .bind(&[dataset_id.into()])?
+        .all()
+        .await?;
+
+    let rows: Vec<Value> = result.results()?;
+    Ok(rows.iter().map(row_to_run).collect())
+}
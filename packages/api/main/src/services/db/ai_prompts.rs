@@ -10,24 +10,88 @@ pub struct AiPrompt {
     pub stage: String,
     pub prompt: String,
     pub is_active: bool,
+    /// Models tried in order, e.g. `openai:llama3.1:8b`. None uses the defaults.
+    pub models: Option<Vec<String>>,
+    pub temperature: Option<f64>,
+    pub max_tokens: Option<u32>,
     pub created_at: String,
     pub updated_at: String,
 }
 
+/// Model settings stored with a prompt
+#[derive(Debug, Default)]
+pub struct PromptModelSettings {
+    pub models: Option<Vec<String>>,
+    pub temperature: Option<f64>,
+    pub max_tokens: Option<u32>,
+}
+
+const PROMPT_COLUMNS: &str =
+    "id, name, stage, prompt, is_active, models, temperature, max_tokens, created_at, updated_at";
+
+fn text(row: &Value, key: &str) -> String {
+    row.get(key)
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn row_to_prompt(row: &Value) -> AiPrompt {
+    AiPrompt {
+        id: text(row, "id"),
+        name: text(row, "name"),
+        stage: text(row, "stage"),
+        prompt: text(row, "prompt"),
+        is_active: row
+            .get("is_active")
+            .and_then(|v| {
+                v.as_bool()
+                    .or_else(|| v.as_u64().map(|n| n != 0))
+                    .or_else(|| v.as_f64().map(|n| n != 0.0))
+            })
+            .unwrap_or(false),
+        models: row
+            .get("models")
+            .and_then(|v| v.as_str())
+            .and_then(|s| serde_json::from_str(s).ok()),
+        temperature: row.get("temperature").and_then(|v| v.as_f64()),
+        max_tokens: row
+            .get("max_tokens")
+            .and_then(|v| v.as_f64())
+            .map(|v| v as u32),
+        created_at: text(row, "created_at"),
+        updated_at: text(row, "updated_at"),
+    }
+}
+
 pub async fn create_prompt(
     db: &D1Database,
     name: &str,
     stage: &str,
     prompt: &str,
+    settings: &PromptModelSettings,
 ) -> Result<String> {
     use crate::services::password;
 
     let id = password::generate_uuid()?;
+    let models = match &settings.models {
+        Some(models) => Some(serde_json::to_string(models)?),
+        None => None,
+    };
 
     db.prepare(
-        "INSERT INTO ai_prompts (id, name, stage, prompt, is_active) VALUES (?, ?, ?, ?, false)",
+        "INSERT INTO ai_prompts (id, name, stage, prompt, is_active, models, temperature, max_tokens)
+         VALUES (?, ?, ?, ?, false, ?, ?, ?)",
     )
-    .bind(&[id.clone().into(), name.into(), stage.into(), prompt.into()])?
+    .bind(&[
+        id.clone().into(),
+        name.into(),
+        stage.into(),
+        prompt.into(),
+        models.into(),
+        settings.temperature.into(),
+        settings.max_tokens.map(|t| t as f64).into(),
+    ])?
     .run()
     .await?;
 
@@ -35,66 +99,44 @@ pub async fn create_prompt(
 }
 
 pub async fn get_active_prompt(db: &D1Database, stage: &str) -> Result<Option<AiPrompt>> {
+    let query = format!(
+        "SELECT {} FROM ai_prompts WHERE stage = ? AND is_active = true LIMIT 1",
+        PROMPT_COLUMNS
+    );
     let result = db
-        .prepare("SELECT id, name, stage, prompt, is_active, created_at, updated_at FROM ai_prompts WHERE stage = ? AND is_active = true LIMIT 1")
+        .prepare(&query)
         .bind(&[stage.into()])?
         .first::<Value>(None)
         .await?;
 
-    if let Some(row) = result {
-        Ok(Some(AiPrompt {
-            id: row
-                .get("id")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string(),
-            name: row
-                .get("name")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string(),
-            stage: row
-                .get("stage")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string(),
-            prompt: row
-                .get("prompt")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string(),
-            is_active: row
-                .get("is_active")
-                .and_then(|v| {
-                    v.as_bool()
-                        .or_else(|| v.as_u64().map(|n| n != 0))
-                        .or_else(|| v.as_f64().map(|n| n != 0.0))
-                })
-                .unwrap_or(false),
-            created_at: row
-                .get("created_at")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string(),
-            updated_at: row
-                .get("updated_at")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string(),
-        }))
-    } else {
-        Ok(None)
-    }
+    Ok(result.as_ref().map(row_to_prompt))
+}
+
+pub async fn get_prompt(db: &D1Database, prompt_id: &str) -> Result<Option<AiPrompt>> {
+    let query = format!("SELECT {} FROM ai_prompts WHERE id = ?", PROMPT_COLUMNS);
+    let result = db
+        .prepare(&query)
+        .bind(&[prompt_id.into()])?
+        .first::<Value>(None)
+        .await?;
+
+    Ok(result.as_ref().map(row_to_prompt))
 }
 
 pub async fn list_prompts(db: &D1Database, stage: Option<&str>) -> Result<Vec<AiPrompt>> {
-    let query = if let Some(_s) = stage {
-        "SELECT id, name, stage, prompt, is_active, created_at, updated_at FROM ai_prompts WHERE stage = ? ORDER BY created_at DESC"
+    let query = if stage.is_some() {
+        format!(
+            "SELECT {} FROM ai_prompts WHERE stage = ? ORDER BY created_at DESC",
+            PROMPT_COLUMNS
+        )
     } else {
-        "SELECT id, name, stage, prompt, is_active, created_at, updated_at FROM ai_prompts ORDER BY created_at DESC"
+        format!(
+            "SELECT {} FROM ai_prompts ORDER BY created_at DESC",
+            PROMPT_COLUMNS
+        )
     };
 
-    let mut stmt = db.prepare(query);
+    let mut stmt = db.prepare(&query);
     if let Some(s) = stage {
         stmt = stmt.bind(&[s.into()])?;
     }
@@ -102,51 +144,7 @@ pub async fn list_prompts(db: &D1Database, stage: Option<&str>) -> Result<Vec<Ai
     let result = stmt.all().await?;
     let rows: Vec<Value> = result.results()?;
 
-    let prompts: Vec<AiPrompt> = rows
-        .into_iter()
-        .map(|row| AiPrompt {
-            id: row
-                .get("id")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string(),
-            name: row
-                .get("name")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string(),
-            stage: row
-                .get("stage")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string(),
-            prompt: row
-                .get("prompt")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string(),
-            is_active: row
-                .get("is_active")
-                .and_then(|v| {
-                    v.as_bool()
-                        .or_else(|| v.as_u64().map(|n| n != 0))
-                        .or_else(|| v.as_f64().map(|n| n != 0.0))
-                })
-                .unwrap_or(false),
-            created_at: row
-                .get("created_at")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string(),
-            updated_at: row
-                .get("updated_at")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string(),
-        })
-        .collect();
-
-    Ok(prompts)
+    Ok(rows.iter().map(row_to_prompt).collect())
 }
 
 pub async fn activate_prompt(db: &D1Database, prompt_id: &str, stage: &str) -> Result<()> {
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use serde::Serialize;
-use serde_json::Value;
+use serde_json::{Map, Value};
 use worker::*;
 
 #[derive(Debug, Serialize)]
@@ -14,61 +14,117 @@ pub struct AiResult {
     pub job_title: Option<String>,
     pub summary: Option<String>,
     pub extracted_data: Option<String>,
+    /// "ok", or "failed" when a stage failed; failed results have no category or fields
+    pub status: String,
+    pub failed_stage: Option<String>,
+    pub error: Option<String>,
+    /// Number of the processing run of the email, starting at 1
+    pub version: u32,
+    /// Prompt IDs and models used, keyed by stage
+    pub prompt_ids: Map<String, Value>,
+    pub models: Map<String, Value>,
     pub created_at: String,
 }
 
-pub async fn get_ai_result(db: &D1Database, email_id: &str) -> Result<Option<AiResult>> {
+/// The latest AI result of an email with the earlier ones, newest first
+#[derive(Debug, Serialize)]
+pub struct AiResultWithHistory {
+    #[serde(flatten)]
+    pub latest: AiResult,
+    pub history: Vec<AiResult>,
+}
+
+fn text(row: &Value, key: &str) -> Option<String> {
+    row.get(key).and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+fn json_object(row: &Value, key: &str) -> Map<String, Value> {
+    text(row, key)
+        .and_then(|t| serde_json::from_str(&t).ok())
+        .unwrap_or_default()
+}
+
+fn row_to_result(row: &Value) -> AiResult {
+    AiResult {
+        id: text(row, "id").unwrap_or_default(),
+        email_id: text(row, "email_id").unwrap_or_default(),
+        user_id: text(row, "user_id").unwrap_or_default(),
+        category: text(row, "category"),
+        confidence: row.get("confidence").and_then(|v| v.as_f64()),
+        company: text(row, "company"),
+        job_title: text(row, "job_title"),
+        summary: text(row, "summary"),
+        extracted_data: text(row, "extracted_data"),
+        status: text(row, "status").unwrap_or_else(|| "ok".to_string()),
+        failed_stage: text(row, "failed_stage"),
+        error: text(row, "error"),
+        version: row
+            .get("version")
+            .and_then(|v| v.as_f64())
+            .map(|v| v as u32)
+            .unwrap_or(1),
+        prompt_ids: json_object(row, "prompt_ids"),
+        models: json_object(row, "models"),
+        created_at: text(row, "created_at").unwrap_or_default(),
+    }
+}
+
+/// All AI results of a user's email, newest version first
+pub async fn list_ai_results(
+    db: &D1Database,
+    user_id: &str,
+    email_id: &str,
+) -> Result<Vec<AiResult>> {
     let result = db
-        .prepare("SELECT id, email_id, user_id, category, confidence, company, job_title, summary, extracted_data, created_at FROM ai_results WHERE email_id = ? LIMIT 1")
-        .bind(&[email_id.into()])?
+        .prepare(
+            "SELECT id, email_id, user_id, category, confidence, company, job_title, summary, extracted_data,
+                status, failed_stage, error, version, prompt_ids, models, created_at
+             FROM ai_results WHERE email_id = ? AND user_id = ?
+             ORDER BY version DESC, created_at DESC",
+        )
+        .bind(&[email_id.into(), user_id.into()])?
+        .all()
+        .await?;
+    let rows = result.results::<Value>()?;
+
+    Ok(rows.iter().map(row_to_result).collect())
+}
+
+/// The latest AI result of a user's email along with its history
+pub async fn get_ai_result(
+    db: &D1Database,
+    user_id: &str,
+    email_id: &str,
+) -> Result<Option<AiResultWithHistory>> {
+    let mut results = list_ai_results(db, user_id, email_id).await?.into_iter();
+
+    Ok(results.next().map(|latest| AiResultWithHistory {
+        latest,
+        history: results.collect(),
+    }))
+}
+
+/// Whether the email belongs to the user
+pub async fn user_owns_email(db: &D1Database, user_id: &str, email_id: &str) -> Result<bool> {
+    let row = db
+        .prepare("SELECT gmail_id FROM emails WHERE gmail_id = ? AND user_id = ?")
+        .bind(&[email_id.into(), user_id.into()])?
         .first::<Value>(None)
         .await?;
 
-    if let Some(row) = result {
-        Ok(Some(AiResult {
-            id: row
-                .get("id")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string(),
-            email_id: row
-                .get("email_id")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string(),
-            user_id: row
-                .get("user_id")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string(),
-            category: row
-                .get("category")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string()),
-            confidence: row.get("confidence").and_then(|v| v.as_f64()),
-            company: row
-                .get("company")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string()),
-            job_title: row
-                .get("job_title")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string()),
-            summary: row
-                .get("summary")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string()),
-            extracted_data: row
-                .get("extracted_data")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string()),
-            created_at: row
-                .get("created_at")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .to_string(),
-        }))
-    } else {
-        Ok(None)
-    }
+    Ok(row.is_some())
+}
+
+/// Days a cached AI result is kept; the AI worker only reuses results this
+/// recent
+pub const AI_RESULT_CACHE_DAYS: u32 = 30;
+
+/// Delete cached AI results that are too old to be reused
+pub async fn prune_ai_result_cache(db: &D1Database) -> Result<()> {
+    db.prepare("DELETE FROM ai_result_cache WHERE created_at < datetime('now', ?)")
+        .bind(&[format!("-{} days", AI_RESULT_CACHE_DAYS).into()])?
+        .run()
+        .await?;
+
+    Ok(())
 }
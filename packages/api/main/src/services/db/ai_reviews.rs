@@ -0,0 +1,207 @@
+use crate::services::ai_review::ReviewDiff;
+use crate::services::password;
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use worker::*;
+
+/// Most emails returned by the review queue at once
+const REVIEW_QUEUE_LIMIT: u32 = 100;
+
+/// An email with its latest AI result, as shown in the review queue
+#[derive(Debug, Serialize)]
+pub struct ReviewItem {
+    pub email_id: String,
+    pub from: Option<String>,
+    pub subject: Option<String>,
+    pub snippet: Option<String>,
+    pub date: Option<String>,
+    pub ai_result_id: String,
+    pub category: Option<String>,
+    pub confidence: Option<f64>,
+    pub summary: Option<String>,
+    pub fields: Map<String, Value>,
+}
+
+/// A stored review, used as ground truth
+#[derive(Debug, Serialize)]
+pub struct AiReview {
+    pub id: String,
+    pub email_id: String,
+    pub status: String,
+    pub predicted_category: Option<String>,
+    pub category: String,
+    pub fields: Map<String, Value>,
+    pub corrected_fields: Vec<String>,
+    pub from_email: Option<String>,
+    pub subject: Option<String>,
+    pub body: Option<String>,
+    pub created_at: String,
+}
+
+fn text(row: &Value, key: &str) -> Option<String> {
+    row.get(key).and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+fn json_text<T: serde::de::DeserializeOwned + Default>(row: &Value, key: &str) -> T {
+    text(row, key)
+        .and_then(|t| serde_json::from_str(&t).ok())
+        .unwrap_or_default()
+}
+
+fn row_to_item(row: &Value) -> ReviewItem {
+    // Older results only have company and job title as columns
+    let mut fields: Map<String, Value> = json_text(row, "extracted_data");
+    for key in ["company", "job_title"] {
+        if !fields.contains_key(key) {
+            if let Some(value) = text(row, key) {
+                fields.insert(key.to_string(), Value::String(value));
+            }
+        }
+    }
+
+    ReviewItem {
+        email_id: text(row, "gmail_id").unwrap_or_default(),
+        from: text(row, "from"),
+        subject: text(row, "subject"),
+        snippet: text(row, "snippet"),
+        date: text(row, "date"),
+        ai_result_id: text(row, "ai_result_id").unwrap_or_default(),
+        category: text(row, "category"),
+        confidence: row.get("confidence").and_then(|v| v.as_f64()),
+        summary: text(row, "summary"),
+        fields,
+    }
+}
+
+fn row_to_review(row: &Value) -> AiReview {
+    AiReview {
+        id: text(row, "id").unwrap_or_default(),
+        email_id: text(row, "email_id").unwrap_or_default(),
+        status: text(row, "status").unwrap_or_default(),
+        predicted_category: text(row, "predicted_category"),
+        category: text(row, "category").unwrap_or_default(),
+        fields: json_text(row, "fields"),
+        corrected_fields: json_text(row, "corrected_fields"),
+        from_email: text(row, "from_email"),
+        subject: text(row, "subject"),
+        body: text(row, "body"),
+        created_at: text(row, "created_at").unwrap_or_default(),
+    }
+}
+
+const REVIEW_ITEM_SELECT: &str = "SELECT e.gmail_id, e.\"from\", e.subject, e.snippet, e.date,
+        r.id as ai_result_id, r.category, r.confidence, r.company, r.job_title, r.summary, r.extracted_data
+     FROM emails e
+     JOIN ai_results r ON r.id = (
+        SELECT id FROM ai_results WHERE email_id = e.gmail_id AND status = 'ok' ORDER BY version DESC LIMIT 1
+     )";
+
+/// Emails of a user flagged for review, newest first
+pub async fn list_review_queue(db: &D1Database, user_id: &str) -> Result<Vec<ReviewItem>> {
+    let query = format!(
+        "{} WHERE e.user_id = ? AND e.needs_review = 1 ORDER BY e.date DESC LIMIT {}",
+        REVIEW_ITEM_SELECT, REVIEW_QUEUE_LIMIT
+    );
+    let result = db.prepare(&query).bind(&[user_id.into()])?.all().await?;
+    let rows = result.results::<Value>()?;
+
+    Ok(rows.iter().map(row_to_item).collect())
+}
+
+/// An email of the user with its latest AI result, flagged or not
+pub async fn get_review_item(
+    db: &D1Database,
+    user_id: &str,
+    email_id: &str,
+) -> Result<Option<ReviewItem>> {
+    let query = format!(
+        "{} WHERE e.user_id = ? AND e.gmail_id = ?",
+        REVIEW_ITEM_SELECT
+    );
+    let row = db
+        .prepare(&query)
+        .bind(&[user_id.into(), email_id.into()])?
+        .first::<Value>(None)
+        .await?;
+
+    Ok(row.as_ref().map(row_to_item))
+}
+
+/// Store a review as ground truth, apply it to the AI result and take the
+/// email out of the queue. Reviewing the same email again replaces the review.
+pub async fn save_review(
+    db: &D1Database,
+    user_id: &str,
+    item: &ReviewItem,
+    diff: &ReviewDiff,
+    category: &str,
+    fields: &Map<String, Value>,
+) -> Result<()> {
+    let id = password::generate_uuid()?;
+    let fields_json = serde_json::to_string(fields)?;
+    let corrected_json = serde_json::to_string(&diff.corrected_fields)?;
+    let field = |key: &str| {
+        fields
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    };
+
+    let statements = vec![
+        db.prepare(
+            "INSERT INTO ai_reviews (id, email_id, user_id, ai_result_id, status, predicted_category, category, fields, corrected_fields, from_email, subject, body)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT (email_id, user_id) DO UPDATE SET
+                ai_result_id = excluded.ai_result_id,
+                status = excluded.status,
+                predicted_category = excluded.predicted_category,
+                category = excluded.category,
+                fields = excluded.fields,
+                corrected_fields = excluded.corrected_fields,
+                created_at = CURRENT_TIMESTAMP",
+        )
+        .bind(&[
+            id.into(),
+            item.email_id.clone().into(),
+            user_id.into(),
+            item.ai_result_id.clone().into(),
+            diff.status().into(),
+            item.category.as_deref().into(),
+            category.into(),
+            fields_json.clone().into(),
+            corrected_json.into(),
+            item.from.as_deref().into(),
+            item.subject.as_deref().into(),
+            item.snippet.as_deref().into(),
+        ])?,
+        db.prepare(
+            "UPDATE ai_results SET category = ?, company = ?, job_title = ?, extracted_data = ? WHERE id = ?",
+        )
+        .bind(&[
+            category.into(),
+            field("company").into(),
+            field("job_title").into(),
+            fields_json.into(),
+            item.ai_result_id.clone().into(),
+        ])?,
+        db.prepare("UPDATE emails SET needs_review = 0 WHERE gmail_id = ? AND user_id = ?")
+            .bind(&[item.email_id.clone().into(), user_id.into()])?,
+    ];
+
+    db.batch(statements).await?;
+    Ok(())
+}
+
+/// All reviews, newest first, for building evaluation datasets
+pub async fn list_reviews(db: &D1Database, corrected_only: bool) -> Result<Vec<AiReview>> {
+    let query = if corrected_only {
+        "SELECT * FROM ai_reviews WHERE status = 'corrected' ORDER BY created_at DESC"
+    } else {
+        "SELECT * FROM ai_reviews ORDER BY created_at DESC"
+    };
+    let result = db.prepare(query).all().await?;
+    let rows = result.results::<Value>()?;
+
+    Ok(rows.iter().map(row_to_review).collect())
+}
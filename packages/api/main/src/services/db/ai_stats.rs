@@ -0,0 +1,103 @@
+use crate::services::ai_stats::{
+    rollup_days, AiDailyStats, CategoryDayRow, REVIEW_CONFIDENCE_THRESHOLD,
+};
+use anyhow::Result;
+use serde_json::Value;
+use worker::*;
+
+/// Roll `ai_results` up into `ai_daily_stats`. Emails reprocessed on the same
+/// day are counted once. Only days from the latest
+/// stored day onwards are recomputed, since that day may have been rolled up
+/// before it ended; rows are replaced, so running it again changes nothing.
+/// Returns the number of days written.
+pub async fn rollup_ai_daily_stats(db: &D1Database) -> Result<usize> {
+    let latest = db
+        .prepare("SELECT MAX(date) as date FROM ai_daily_stats")
+        .first::<Value>(None)
+        .await?
+        .and_then(|row| {
+            row.get("date")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+        })
+        .unwrap_or_else(|| "0000-00-00".to_string());
+
+    let result = db
+        .prepare(
+            "SELECT date(created_at) as date, category,
+                COUNT(DISTINCT email_id) as results,
+                SUM(confidence) as confidence_sum,
+                COUNT(confidence) as confidence_count,
+                COUNT(DISTINCT CASE WHEN confidence < ? THEN email_id END) as needs_review
+             FROM ai_results
+             WHERE date(created_at) >= ? AND status = 'ok'
+             GROUP BY date(created_at), category",
+        )
+        .bind(&[REVIEW_CONFIDENCE_THRESHOLD.into(), latest.into()])?
+        .all()
+        .await?;
+    let rows = result.results::<CategoryDayRow>()?;
+    let days = rollup_days(&rows);
+    if days.is_empty() {
+        return Ok(0);
+    }
+
+    let mut statements = Vec::with_capacity(days.len());
+    for day in &days {
+        statements.push(
+            db.prepare(
+                "INSERT OR REPLACE INTO ai_daily_stats (date, emails_processed, avg_confidence, needs_review_count, category_breakdown)
+                 VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind(&[
+                day.date.clone().into(),
+                day.emails_processed.into(),
+                day.avg_confidence.into(),
+                day.needs_review_count.into(),
+                serde_json::to_string(&day.category_breakdown)?.into(),
+            ])?,
+        );
+    }
+    db.batch(statements).await?;
+
+    Ok(days.len())
+}
+
+/// Stored daily stats of the last `days` days, oldest first
+pub async fn list_ai_daily_stats(db: &D1Database, days: u32) -> Result<Vec<AiDailyStats>> {
+    let result = db
+        .prepare(
+            "SELECT * FROM ai_daily_stats
+             WHERE date >= date('now', ?)
+             ORDER BY date ASC",
+        )
+        .bind(&[format!("-{} days", days).into()])?
+        .all()
+        .await?;
+    let rows = result.results::<Value>()?;
+
+    Ok(rows
+        .iter()
+        .map(|row| AiDailyStats {
+            date: row
+                .get("date")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            emails_processed: row
+                .get("emails_processed")
+                .and_then(|v| v.as_u64())
+                .unwrap_or_default() as u32,
+            avg_confidence: row.get("avg_confidence").and_then(|v| v.as_f64()),
+            needs_review_count: row
+                .get("needs_review_count")
+                .and_then(|v| v.as_u64())
+                .unwrap_or_default() as u32,
+            category_breakdown: row
+                .get("category_breakdown")
+                .and_then(|v| v.as_str())
+                .and_then(|s| serde_json::from_str(s).ok())
+                .unwrap_or_default(),
+        })
+        .collect())
+}
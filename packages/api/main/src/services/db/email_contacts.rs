@@ -18,7 +18,7 @@ fn normalize_email(email: &str) -> String {
 }
 
 /// Extract email address from "Name <email@example.com>" format
-fn extract_email_address(email_string: &str) -> String {
+pub fn extract_email_address(email_string: &str) -> String {
     let email = if let Some(start) = email_string.find('<') {
         if let Some(end) = email_string.find('>') {
             normalize_email(&email_string[start + 1..end])
@@ -292,6 +292,26 @@ pub async fn update_contact(
         .ok_or_else(|| anyhow!("Contact not found after update"))
 }
 
+/// Update the notes of a contact, creating the contact if needed
+pub async fn update_contact_notes(
+    db: &D1Database,
+    email: &str,
+    user_id: &str,
+    notes: Option<&str>,
+) -> Result<()> {
+    let contact = get_or_create_contact(db, email, user_id).await?;
+    let notes = notes.map(str::trim).filter(|n| !n.is_empty());
+
+    db.prepare(
+        "UPDATE email_contacts SET notes = ?, updated_at = CURRENT_TIMESTAMP WHERE email = ? AND user_id = ?",
+    )
+    .bind(&[notes.into(), contact.email.into(), user_id.into()])?
+    .run()
+    .await?;
+
+    Ok(())
+}
+
 /// Get all contacts for a job (from emails linked to the job)
 pub async fn get_contacts_for_job(
     db: &D1Database,
@@ -373,7 +393,7 @@ pub async fn convert_to_user_contact(
 }
 
 /// Extract individual email addresses from a field that may contain multiple emails
-fn extract_emails_from_field(field_value: &str) -> Vec<String> {
+pub fn extract_emails_from_field(field_value: &str) -> Vec<String> {
     let mut emails = Vec::new();
 
     // Split by comma and process each part
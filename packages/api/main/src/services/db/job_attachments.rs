@@ -0,0 +1,253 @@
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+use worker::*;
+
+/// Maximum size of a single attachment (10 MB)
+pub const MAX_ATTACHMENT_SIZE: u64 = 10 * 1024 * 1024;
+
+/// MIME types accepted for job attachments
+pub const ALLOWED_CONTENT_TYPES: &[&str] = &[
+    "application/pdf",
+    "application/msword",
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    "application/vnd.oasis.opendocument.text",
+    "application/rtf",
+    "text/plain",
+    "text/markdown",
+    "image/png",
+    "image/jpeg",
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JobAttachment {
+    pub id: String,
+    pub job_id: String,
+    pub user_id: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: u64,
+    #[serde(skip_serializing)]
+    pub r2_key: String,
+    pub created_at: String,
+}
+
+/// Validate an upload against the allowed MIME types and size limit.
+/// Returns a user-facing error message when the upload is rejected.
+pub fn validate_attachment(content_type: &str, size_bytes: u64) -> Result<(), String> {
+    let mime = normalize_content_type(content_type);
+
+    if !ALLOWED_CONTENT_TYPES.contains(&mime.as_str()) {
+        return Err(format!("Unsupported file type: {}", mime));
+    }
+
+    if size_bytes == 0 {
+        return Err("File is empty".to_string());
+    }
+
+    if size_bytes > MAX_ATTACHMENT_SIZE {
+        return Err(format!(
+            "File is too large (max {} MB)",
+            MAX_ATTACHMENT_SIZE / (1024 * 1024)
+        ));
+    }
+
+    Ok(())
+}
+
+/// Strip parameters (e.g. "; charset=utf-8") and lowercase a Content-Type value
+pub fn normalize_content_type(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_lowercase()
+}
+
+/// Reduce a client-supplied file name to a safe display name.
+/// Path components are dropped and control characters and quotes are removed.
+pub fn sanitize_file_name(file_name: &str) -> String {
+    let base = file_name
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or("")
+        .chars()
+        .filter(|c| !c.is_control() && *c != '"')
+        .collect::<String>();
+    let trimmed = base.trim();
+
+    if trimmed.is_empty() || trimmed == "." || trimmed == ".." {
+        "attachment".to_string()
+    } else {
+        trimmed.chars().take(255).collect()
+    }
+}
+
+/// Content-Disposition value for downloading a file (RFC 6266). The quoted
+/// `filename` is an ASCII fallback with quotes, backslashes and anything
+/// outside printable ASCII replaced, and `filename*` carries the full UTF-8
+/// name percent-encoded.
+pub fn content_disposition(file_name: &str) -> String {
+    let fallback: String = file_name
+        .chars()
+        .map(|c| {
+            if c == ' ' || (c.is_ascii_graphic() && c != '"' && c != '\\') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let fallback = if fallback.trim().is_empty() {
+        "attachment".to_string()
+    } else {
+        fallback
+    };
+
+    let encoded: String = file_name
+        .bytes()
+        .map(|b| {
+            if b.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&b) {
+                (b as char).to_string()
+            } else {
+                format!("%{:02X}", b)
+            }
+        })
+        .collect();
+
+    format!(
+        "attachment; filename=\"{}\"; filename*=UTF-8''{}",
+        fallback, encoded
+    )
+}
+
+/// Build the R2 object key for an attachment. Keys are namespaced per user so
+/// objects from different users can never collide.
+pub fn build_attachment_key(user_id: &str, job_id: &str, attachment_id: &str) -> String {
+    format!("users/{}/jobs/{}/{}", user_id, job_id, attachment_id)
+}
+
+fn row_to_attachment(row: &Value) -> JobAttachment {
+    JobAttachment {
+        id: row
+            .get("id")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        job_id: row
+            .get("job_id")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        user_id: row
+            .get("user_id")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        file_name: row
+            .get("file_name")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        content_type: row
+            .get("content_type")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        size_bytes: row
+            .get("size_bytes")
+            .and_then(|v| v.as_u64().or_else(|| v.as_f64().map(|f| f as u64)))
+            .unwrap_or(0),
+        r2_key: row
+            .get("r2_key")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        created_at: row
+            .get("created_at")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+    }
+}
+
+/// Insert attachment metadata after the object has been stored in R2
+pub async fn create_attachment(db: &D1Database, attachment: &JobAttachment) -> Result<()> {
+    db.prepare(
+        "INSERT INTO job_attachments (id, job_id, user_id, file_name, content_type, size_bytes, r2_key) VALUES (?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&[
+        attachment.id.clone().into(),
+        attachment.job_id.clone().into(),
+        attachment.user_id.clone().into(),
+        attachment.file_name.clone().into(),
+        attachment.content_type.clone().into(),
+        (attachment.size_bytes as i32).into(),
+        attachment.r2_key.clone().into(),
+    ])?
+    .run()
+    .await?;
+
+    Ok(())
+}
+
+/// List a user's attachments for a job, newest first
+pub async fn list_attachments_for_job(
+    db: &D1Database,
+    job_id: &str,
+    user_id: &str,
+) -> Result<Vec<JobAttachment>> {
+    let result = db
+        .prepare(
+            "SELECT * FROM job_attachments WHERE job_id = ? AND user_id = ? ORDER BY created_at DESC",
+        )
+        .bind(&[job_id.into(), user_id.into()])?
+        .all()
+        .await?;
+
+    let rows: Vec<Value> = result.results()?;
+    Ok(rows.iter().map(row_to_attachment).collect())
+}
+
+/// Get a single attachment owned by the user
+pub async fn get_attachment(
+    db: &D1Database,
+    attachment_id: &str,
+    job_id: &str,
+    user_id: &str,
+) -> Result<Option<JobAttachment>> {
+    let result = db
+        .prepare("SELECT * FROM job_attachments WHERE id = ? AND job_id = ? AND user_id = ?")
+        .bind(&[attachment_id.into(), job_id.into(), user_id.into()])?
+        .first::<Value>(None)
+        .await?;
+
+    Ok(result.as_ref().map(row_to_attachment))
+}
+
+/// Delete attachment metadata
+pub async fn delete_attachment(db: &D1Database, attachment_id: &str, user_id: &str) -> Result<()> {
+    db.prepare("DELETE FROM job_attachments WHERE id = ? AND user_id = ?")
+        .bind(&[attachment_id.into(), user_id.into()])?
+        .run()
+        .await?;
+
+    Ok(())
+}
+
+/// Get the R2 keys of every attachment on a job (all users), used when the job is deleted
+pub async fn get_attachment_keys_for_job(db: &D1Database, job_id: &str) -> Result<Vec<String>> {
+    let result = db
+        .prepare("SELECT r2_key FROM job_attachments WHERE job_id = ?")
+        .bind(&[job_id.into()])?
+        .all()
+        .await?;
+
+    let rows: Vec<Value> = result.results()?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| row.get("r2_key").and_then(|v| v.as_str()))
+        .map(|s| s.to_string())
+        .collect())
+}
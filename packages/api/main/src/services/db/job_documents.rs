@@ -0,0 +1,101 @@
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+use worker::*;
+
+/// A cover letter and resume suggestions generated for a job
+#[derive(Debug, Clone, Serialize)]
+pub struct JobDocument {
+    pub id: String,
+    pub job_id: String,
+    pub version: i64,
+    pub cover_letter: String,
+    pub resume_suggestions: Vec<String>,
+    pub created_at: String,
+}
+
+fn row_to_document(row: &Value) -> JobDocument {
+    let text = |key: &str| {
+        row.get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    JobDocument {
+        id: text("id"),
+        job_id: text("job_id"),
+        version: row.get("version").and_then(|v| v.as_i64()).unwrap_or(1),
+        cover_letter: text("cover_letter"),
+        resume_suggestions: serde_json::from_str(&text("resume_suggestions")).unwrap_or_default(),
+        created_at: text("created_at"),
+    }
+}
+
+/// List a user's document versions for a job, newest first
+pub async fn list_documents_for_job(
+    db: &D1Database,
+    job_id: &str,
+    user_id: &str,
+) -> Result<Vec<JobDocument>> {
+    let result = db
+        .prepare(
+            "SELECT * FROM job_documents WHERE job_id = ? AND user_id = ? ORDER BY version DESC",
+        )
+        .bind(&[job_id.into(), user_id.into()])?
+        .all()
+        .await?;
+
+    let rows: Vec<Value> = result.results()?;
+    Ok(rows.iter().map(row_to_document).collect())
+}
+
+/// Maximum length of the resume text, in characters
+pub const MAX_RESUME_LENGTH: usize = 50_000;
+
+/// Trim a submitted resume, treating a blank one as removing it. Fails when it
+/// is longer than `MAX_RESUME_LENGTH` characters.
+pub fn clean_resume(resume: Option<String>) -> std::result::Result<Option<String>, String> {
+    let resume = resume
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty());
+    if resume
+        .as_ref()
+        .is_some_and(|r| r.chars().count() > MAX_RESUME_LENGTH)
+    {
+        return Err(format!(
+            "Resume is too long (max {} characters)",
+            MAX_RESUME_LENGTH
+        ));
+    }
+    Ok(resume)
+}
+
+/// Get the user's resume text
+pub async fn get_user_resume(db: &D1Database, user_id: &str) -> Result<Option<String>> {
+    let result = db
+        .prepare("SELECT resume FROM users WHERE id = ?")
+        .bind(&[user_id.into()])?
+        .first::<Value>(None)
+        .await?;
+
+    Ok(result.and_then(|row| {
+        row.get("resume")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    }))
+}
+
+/// Set or clear the user's resume text
+pub async fn update_user_resume(
+    db: &D1Database,
+    user_id: &str,
+    resume: Option<&str>,
+) -> Result<()> {
+    db.prepare("UPDATE users SET resume = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
+        .bind(&[resume.into(), user_id.into()])?
+        .run()
+        .await?;
+
+    Ok(())
+}
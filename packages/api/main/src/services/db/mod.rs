@@ -1,9 +1,15 @@
+pub mod ai_eval;
 pub mod ai_prompts;
 pub mod ai_results;
+pub mod ai_reviews;
+pub mod ai_stats;
 pub mod credentials;
 pub mod email_contacts;
+pub mod job_attachments;
+pub mod job_documents;
 pub mod providers;
 pub mod system_email_domains;
+pub mod usage;
 pub mod users;
 
 pub use credentials::{
@@ -7,13 +7,15 @@ pub struct SystemEmailDomain {
     pub id: String,
     pub domain_pattern: String,
     pub name: Option<String>,
+    /// Job source inferred for jobs whose first email comes from this domain
+    pub source_id: Option<String>,
 }
 
 /// Get all system email domain patterns
 pub async fn get_all_domains(db: &D1Database) -> Result<Vec<SystemEmailDomain>> {
     let result = db
         .prepare(
-            "SELECT id, domain_pattern, name FROM system_email_domains ORDER BY domain_pattern",
+            "SELECT id, domain_pattern, name, source_id FROM system_email_domains ORDER BY domain_pattern",
         )
         .all()
         .await?;
@@ -37,6 +39,10 @@ pub async fn get_all_domains(db: &D1Database) -> Result<Vec<SystemEmailDomain>>
                 .get("name")
                 .and_then(|v| v.as_str())
                 .map(|s| s.to_string()),
+            source_id: row
+                .get("source_id")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
         });
     }
 
@@ -72,6 +78,18 @@ pub async fn is_system_email(db: &D1Database, email: &str) -> Result<bool> {
     Ok(false)
 }
 
+/// Check if a bare email address matches any of the system domain patterns,
+/// on either its domain or its local part
+pub fn is_system_address(address: &str, domains: &[SystemEmailDomain]) -> bool {
+    let Some((local_part, domain)) = address.rsplit_once('@') else {
+        return false;
+    };
+    domains.iter().any(|d| {
+        matches_pattern_impl(&d.domain_pattern, domain)
+            || matches_pattern_impl(&d.domain_pattern, local_part)
+    })
+}
+
 /// Check if a domain matches a pattern (supports wildcard *)
 /// Exposed for testing
 pub fn matches_pattern(pattern: &str, domain: &str) -> bool {
@@ -0,0 +1,160 @@
+//! Per-user daily usage counters and the limits they're checked against
+
+use anyhow::Result;
+use api_shared::quota::{Quota, QUOTA_KINDS};
+use serde::{Deserialize, Serialize};
+use worker::*;
+
+/// A daily limit, the default for everyone when it has no user
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UsageLimit {
+    pub user_id: Option<String>,
+    pub kind: String,
+    pub daily_limit: u32,
+}
+
+/// A user's usage today against each of their limits
+#[derive(Debug, Serialize, Clone)]
+pub struct UserUsage {
+    pub user_id: String,
+    pub email: String,
+    pub name: Option<String>,
+    pub quotas: Vec<Quota>,
+}
+
+#[derive(Deserialize)]
+struct QuotaRow {
+    user_id: String,
+    email: String,
+    name: Option<String>,
+    kind: String,
+    daily_limit: u32,
+    used: u32,
+}
+
+fn kind_order(kind: &str) -> usize {
+    QUOTA_KINDS
+        .iter()
+        .position(|k| *k == kind)
+        .unwrap_or(QUOTA_KINDS.len())
+}
+
+/// Limits and today's usage of users matching `filter`, a condition on `u`
+async fn list_quotas(
+    db: &D1Database,
+    filter: &str,
+    binds: &[worker::wasm_bindgen::JsValue],
+) -> Result<Vec<UserUsage>> {
+    let query = format!(
+        "SELECT u.id AS user_id, u.email, u.name, l.kind,
+            COALESCE(o.daily_limit, l.daily_limit) AS daily_limit,
+            COALESCE(c.count, 0) AS used
+         FROM users u
+         CROSS JOIN usage_limits l
+         LEFT JOIN usage_limits o ON o.user_id = u.id AND o.kind = l.kind
+         LEFT JOIN usage_counters c ON c.user_id = u.id AND c.kind = l.kind AND c.day = date('now')
+         WHERE l.user_id = '' AND {}
+         ORDER BY u.email, u.id",
+        filter
+    );
+    let rows = db
+        .prepare(&query)
+        .bind(binds)?
+        .all()
+        .await?
+        .results::<QuotaRow>()?;
+
+    let mut users: Vec<UserUsage> = Vec::new();
+    for row in rows {
+        let quota = Quota {
+            kind: row.kind,
+            limit: row.daily_limit,
+            used: row.used,
+        };
+        match users.last_mut() {
+            Some(user) if user.user_id == row.user_id => user.quotas.push(quota),
+            _ => users.push(UserUsage {
+                user_id: row.user_id,
+                email: row.email,
+                name: row.name,
+                quotas: vec![quota],
+            }),
+        }
+    }
+    for user in &mut users {
+        user.quotas.sort_by_key(|q| kind_order(&q.kind));
+    }
+
+    Ok(users)
+}
+
+/// A user's limits and what they've used of them today
+pub async fn get_user_quotas(db: &D1Database, user_id: &str) -> Result<Vec<Quota>> {
+    Ok(list_quotas(db, "u.id = ?", &[user_id.into()])
+        .await?
+        .into_iter()
+        .next()
+        .map(|usage| usage.quotas)
+        .unwrap_or_default())
+}
+
+/// Usage of everyone who used anything today or has limits of their own
+pub async fn list_usage_today(db: &D1Database) -> Result<Vec<UserUsage>> {
+    list_quotas(
+        db,
+        "(EXISTS (SELECT 1 FROM usage_counters x WHERE x.user_id = u.id AND x.day = date('now'))
+          OR EXISTS (SELECT 1 FROM usage_limits y WHERE y.user_id = u.id))",
+        &[],
+    )
+    .await
+}
+
+/// Default limits first, then the ones of single users
+pub async fn list_limits(db: &D1Database) -> Result<Vec<UsageLimit>> {
+    #[derive(Deserialize)]
+    struct LimitRow {
+        user_id: String,
+        kind: String,
+        daily_limit: u32,
+    }
+
+    let rows = db
+        .prepare("SELECT user_id, kind, daily_limit FROM usage_limits ORDER BY user_id, kind")
+        .all()
+        .await?
+        .results::<LimitRow>()?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| UsageLimit {
+            user_id: Some(row.user_id).filter(|id| !id.is_empty()),
+            kind: row.kind,
+            daily_limit: row.daily_limit,
+        })
+        .collect())
+}
+
+/// Set a limit; without a user it's the default for everyone
+pub async fn set_limit(db: &D1Database, limit: &UsageLimit) -> Result<()> {
+    db.prepare(
+        "INSERT INTO usage_limits (user_id, kind, daily_limit) VALUES (?, ?, ?)
+         ON CONFLICT (user_id, kind) DO UPDATE SET daily_limit = excluded.daily_limit",
+    )
+    .bind(&[
+        limit.user_id.as_deref().unwrap_or("").into(),
+        limit.kind.as_str().into(),
+        limit.daily_limit.into(),
+    ])?
+    .run()
+    .await?;
+    Ok(())
+}
+
+/// Remove a user's own limit, so the default applies again
+pub async fn remove_user_limit(db: &D1Database, user_id: &str, kind: &str) -> Result<()> {
+    db.prepare("DELETE FROM usage_limits WHERE user_id = ? AND kind = ?")
+        .bind(&[user_id.into(), kind.into()])?
+        .run()
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,87 @@
+//! Job source service
+//!
+//! Sources record where an application came from (a job board, a referral,
+//! recruiter outreach...). The list is managed by admins.
+
+use serde_json::Value;
+use worker::{D1Database, Response};
+
+/// Maximum length of a source name
+pub const MAX_SOURCE_NAME_LENGTH: usize = 64;
+
+/// Build a source ID from its name, e.g. "Hacker News" -> "hacker_news"
+pub fn source_id_from_name(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// List all job sources ordered by name
+pub async fn list_sources(db: &D1Database) -> Result<Response, worker::Error> {
+    let result = db
+        .prepare("SELECT id, name FROM job_sources ORDER BY name ASC")
+        .all()
+        .await?;
+
+    let sources: Vec<Value> = result.results()?;
+    Response::from_json(&sources)
+}
+
+/// Check whether a source exists
+pub async fn source_exists(db: &D1Database, id: &str) -> Result<bool, worker::Error> {
+    let result = db
+        .prepare("SELECT id FROM job_sources WHERE id = ?")
+        .bind(&[id.into()])?
+        .first::<Value>(None)
+        .await?;
+
+    Ok(result.is_some())
+}
+
+/// Create a source from its display name
+pub async fn create_source(db: &D1Database, name: &str) -> Result<Response, worker::Error> {
+    let name = name.trim();
+    let id = source_id_from_name(name);
+    if id.is_empty() || name.chars().count() > MAX_SOURCE_NAME_LENGTH {
+        return Response::error(
+            format!(
+                "Source name must be 1-{} characters and contain a letter or digit",
+                MAX_SOURCE_NAME_LENGTH
+            ),
+            400,
+        );
+    }
+
+    let existing = db
+        .prepare("SELECT id FROM job_sources WHERE id = ? OR name = ?")
+        .bind(&[id.clone().into(), name.into()])?
+        .first::<Value>(None)
+        .await?;
+    if existing.is_some() {
+        return Response::error("A source with this name already exists", 409);
+    }
+
+    db.prepare("INSERT INTO job_sources (id, name) VALUES (?, ?)")
+        .bind(&[id.clone().into(), name.into()])?
+        .run()
+        .await?;
+
+    let resp = Response::from_json(&serde_json::json!({ "id": id, "name": name }))?;
+    Ok(resp.with_status(201))
+}
+
+/// Delete a source. Jobs and email domains using it are left without a source.
+pub async fn delete_source(db: &D1Database, id: &str) -> Result<Response, worker::Error> {
+    if !source_exists(db, id).await? {
+        return Response::error("Source not found", 404);
+    }
+
+    db.prepare("DELETE FROM job_sources WHERE id = ?")
+        .bind(&[id.into()])?
+        .run()
+        .await?;
+
+    Response::ok("Source deleted")
+}
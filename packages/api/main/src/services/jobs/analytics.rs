@@ -0,0 +1,539 @@
+//! Application funnel analytics
+//!
+//! Metrics are computed from the status history of each job and the emails
+//! linked to it. Timestamps are read from D1 as julian days so durations can
+//! be computed without a date library.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use worker::D1Database;
+
+/// Status a job has to reach to count as an application
+pub const APPLIED_STATUS_ID: i32 = 200;
+
+/// Status that ends the pipeline without an offer
+pub const REJECTED_STATUS_ID: i32 = 500;
+
+/// Filters for the analytics endpoint. Dates are inclusive `YYYY-MM-DD`
+/// bounds on the job's creation date.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AnalyticsFilter {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub tag: Option<String>,
+}
+
+/// A job in scope for the analytics
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalyticsJob {
+    pub id: String,
+    pub status_id: Option<i32>,
+    /// Name of the job's source, if known
+    pub source: Option<String>,
+    /// Creation time in julian days
+    pub created_at: f64,
+}
+
+/// A recorded status transition
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalyticsTransition {
+    pub job_id: String,
+    pub to_status_id: i32,
+    /// Transition time in julian days
+    pub changed_at: f64,
+}
+
+/// An email linked to a job
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalyticsEmail {
+    pub job_id: String,
+    /// Email date in julian days
+    pub date: f64,
+    /// Whether the email was received rather than sent by the user
+    pub inbound: bool,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct StageMetrics {
+    pub status_id: i32,
+    pub name: String,
+    pub display_name: String,
+    /// Jobs that reached this stage or a later one
+    pub reached: usize,
+    /// Share of the jobs that reached the previous stage and also reached this one
+    pub conversion_rate: Option<f64>,
+    /// Median days spent in this stage, for jobs that have moved on from it
+    pub median_days_in_stage: Option<f64>,
+}
+
+/// Label used for jobs without a source
+pub const UNKNOWN_SOURCE: &str = "Unknown";
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct SourceMetrics {
+    pub source: String,
+    pub jobs: usize,
+    pub applied: usize,
+    pub responded: usize,
+    pub response_rate: Option<f64>,
+    /// Jobs from this source that reached each stage, in the order of `stages`
+    pub stages_reached: Vec<usize>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct WeeklyVolume {
+    /// Monday of the week, `YYYY-MM-DD`
+    pub week_start: String,
+    pub applications: usize,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct FunnelAnalytics {
+    pub total_jobs: usize,
+    pub applied: usize,
+    pub rejected: usize,
+    pub stages: Vec<StageMetrics>,
+    /// Applied jobs that received at least one email after applying
+    pub responded: usize,
+    pub response_rate: Option<f64>,
+    pub median_days_to_first_response: Option<f64>,
+    pub weekly_volume: Vec<WeeklyVolume>,
+    /// Breakdown by source, largest first
+    pub by_source: Vec<SourceMetrics>,
+}
+
+/// Check that a date parameter is a `YYYY-MM-DD` date
+pub fn is_valid_date(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('-').collect();
+    if parts.len() != 3
+        || parts[0].len() != 4
+        || parts[1].len() != 2
+        || parts[2].len() != 2
+        || !parts.iter().all(|p| p.chars().all(|c| c.is_ascii_digit()))
+    {
+        return false;
+    }
+
+    let month: u32 = parts[1].parse().unwrap_or(0);
+    let day: u32 = parts[2].parse().unwrap_or(0);
+    (1..=12).contains(&month) && (1..=31).contains(&day)
+}
+
+/// Median of a list of values, `None` when empty
+pub fn median(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 1 {
+        Some(sorted[mid])
+    } else {
+        Some((sorted[mid - 1] + sorted[mid]) / 2.0)
+    }
+}
+
+/// Monday of the week containing `julian_day`, as `YYYY-MM-DD`
+pub fn week_start(julian_day: f64) -> String {
+    // Julian day 2440587.5 is 1970-01-01, a Thursday
+    let days = (julian_day - 2440587.5).floor() as i64;
+    let monday = days - (days + 3).rem_euclid(7);
+    let (year, month, day) = civil_from_days(monday);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Convert days since 1970-01-01 to a (year, month, day) civil date
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+fn rate(count: usize, total: usize) -> Option<f64> {
+    if total == 0 {
+        None
+    } else {
+        Some(round2(count as f64 / total as f64))
+    }
+}
+
+/// Compute the funnel metrics.
+///
+/// `stages` are the pipeline statuses in order as `(id, name, display_name)`;
+/// a job reaching a stage is counted as having reached every earlier stage too.
+pub fn compute_funnel_analytics(
+    stages: &[(i32, String, String)],
+    jobs: &[AnalyticsJob],
+    transitions: &[AnalyticsTransition],
+    emails: &[AnalyticsEmail],
+) -> FunnelAnalytics {
+    let stage_index: HashMap<i32, usize> = stages
+        .iter()
+        .enumerate()
+        .map(|(i, (id, _, _))| (*id, i))
+        .collect();
+    let applied_index = stage_index.get(&APPLIED_STATUS_ID).copied();
+
+    let mut history: HashMap<&str, Vec<&AnalyticsTransition>> = HashMap::new();
+    for transition in transitions {
+        history
+            .entry(transition.job_id.as_str())
+            .or_default()
+            .push(transition);
+    }
+
+    let mut emails_by_job: HashMap<&str, Vec<&AnalyticsEmail>> = HashMap::new();
+    for email in emails.iter().filter(|e| e.inbound) {
+        emails_by_job
+            .entry(email.job_id.as_str())
+            .or_default()
+            .push(email);
+    }
+
+    let mut reached = vec![0usize; stages.len()];
+    let mut durations: Vec<Vec<f64>> = vec![Vec::new(); stages.len()];
+    let mut applied = 0;
+    let mut rejected = 0;
+    let mut responded = 0;
+    let mut response_days = Vec::new();
+    let mut weekly: BTreeMap<String, usize> = BTreeMap::new();
+    let mut by_source: BTreeMap<String, SourceMetrics> = BTreeMap::new();
+
+    for job in jobs {
+        let source = by_source
+            .entry(
+                job.source
+                    .clone()
+                    .unwrap_or_else(|| UNKNOWN_SOURCE.to_string()),
+            )
+            .or_insert_with_key(|name| SourceMetrics {
+                source: name.clone(),
+                jobs: 0,
+                applied: 0,
+                responded: 0,
+                response_rate: None,
+                stages_reached: vec![0; stages.len()],
+            });
+        source.jobs += 1;
+
+        let mut job_history: Vec<(i32, f64)> = history
+            .get(job.id.as_str())
+            .map(|rows| {
+                rows.iter()
+                    .map(|t| (t.to_status_id, t.changed_at))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if job_history.is_empty() {
+            // Jobs without recorded history have been in their current status since creation
+            if let Some(status_id) = job.status_id {
+                job_history.push((status_id, job.created_at));
+            }
+        }
+        job_history.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        // Furthest pipeline stage reached
+        if let Some(furthest) = job_history
+            .iter()
+            .filter_map(|(status_id, _)| stage_index.get(status_id))
+            .max()
+        {
+            for count in reached.iter_mut().take(furthest + 1) {
+                *count += 1;
+            }
+            for count in source.stages_reached.iter_mut().take(furthest + 1) {
+                *count += 1;
+            }
+        }
+
+        if job_history
+            .iter()
+            .any(|(status_id, _)| *status_id == REJECTED_STATUS_ID)
+        {
+            rejected += 1;
+        }
+
+        // Time in each stage the job has moved on from
+        for pair in job_history.windows(2) {
+            if let Some(index) = stage_index.get(&pair[0].0) {
+                durations[*index].push(pair[1].1 - pair[0].1);
+            }
+        }
+
+        // Applied when the job first reached the applied stage or a later one
+        let applied_at = applied_index.and_then(|applied_index| {
+            job_history
+                .iter()
+                .find(|(status_id, _)| {
+                    stage_index
+                        .get(status_id)
+                        .map(|i| *i >= applied_index)
+                        .unwrap_or(false)
+                })
+                .map(|(_, at)| *at)
+        });
+
+        if let Some(applied_at) = applied_at {
+            applied += 1;
+            source.applied += 1;
+            *weekly.entry(week_start(applied_at)).or_insert(0) += 1;
+
+            let first_response = emails_by_job
+                .get(job.id.as_str())
+                .into_iter()
+                .flatten()
+                .map(|e| e.date)
+                .filter(|date| *date >= applied_at)
+                .fold(None, |first: Option<f64>, date| {
+                    Some(first.map_or(date, |f| f.min(date)))
+                });
+            if let Some(first_response) = first_response {
+                responded += 1;
+                source.responded += 1;
+                response_days.push(first_response - applied_at);
+            }
+        }
+    }
+
+    let stage_metrics = stages
+        .iter()
+        .enumerate()
+        .map(|(i, (id, name, display_name))| StageMetrics {
+            status_id: *id,
+            name: name.clone(),
+            display_name: display_name.clone(),
+            reached: reached[i],
+            conversion_rate: if i == 0 {
+                None
+            } else {
+                rate(reached[i], reached[i - 1])
+            },
+            median_days_in_stage: median(&durations[i]).map(round2),
+        })
+        .collect();
+
+    let mut by_source: Vec<SourceMetrics> = by_source
+        .into_values()
+        .map(|mut source| {
+            source.response_rate = rate(source.responded, source.applied);
+            source
+        })
+        .collect();
+    by_source.sort_by_key(|source| std::cmp::Reverse(source.jobs));
+
+    FunnelAnalytics {
+        total_jobs: jobs.len(),
+        applied,
+        rejected,
+        stages: stage_metrics,
+        responded,
+        response_rate: rate(responded, applied),
+        median_days_to_first_response: median(&response_days).map(round2),
+        weekly_volume: fill_weeks(weekly),
+        by_source,
+    }
+}
+
+/// Turn per-week counts into a continuous series, including empty weeks
+fn fill_weeks(weekly: BTreeMap<String, usize>) -> Vec<WeeklyVolume> {
+    let (Some(first), Some(last)) = (weekly.keys().next(), weekly.keys().next_back()) else {
+        return Vec::new();
+    };
+    let last = last.clone();
+
+    let mut volume = Vec::new();
+    let mut week = first.clone();
+    let mut day = days_from_civil(&week);
+    while week <= last {
+        volume.push(WeeklyVolume {
+            applications: weekly.get(&week).copied().unwrap_or(0),
+            week_start: week,
+        });
+        day += 7;
+        let (y, m, d) = civil_from_days(day);
+        week = format!("{:04}-{:02}-{:02}", y, m, d);
+    }
+    volume
+}
+
+/// Days since 1970-01-01 for a `YYYY-MM-DD` date
+fn days_from_civil(date: &str) -> i64 {
+    let mut parts = date.split('-').map(|p| p.parse::<i64>().unwrap_or(0));
+    let (year, month, day) = (
+        parts.next().unwrap_or(1970),
+        parts.next().unwrap_or(1),
+        parts.next().unwrap_or(1),
+    );
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// WHERE clause and bindings that restrict `jobs j` to the filter
+fn filter_clause(filter: &AnalyticsFilter) -> (String, Vec<worker::wasm_bindgen::JsValue>) {
+    let mut conditions = vec!["j.deleted_at IS NULL".to_string()];
+    let mut bindings: Vec<worker::wasm_bindgen::JsValue> = Vec::new();
+
+    if let Some(from) = &filter.from {
+        conditions.push("j.created_at >= ?".to_string());
+        bindings.push(from.as_str().into());
+    }
+    if let Some(to) = &filter.to {
+        conditions.push("j.created_at < date(?, '+1 day')".to_string());
+        bindings.push(to.as_str().into());
+    }
+    if let Some(tag) = &filter.tag {
+        conditions.push(
+            "EXISTS (SELECT 1 FROM job_tags jt WHERE jt.job_id = j.id AND jt.tag = ?)".to_string(),
+        );
+        bindings.push(tag.as_str().into());
+    }
+
+    (conditions.join(" AND "), bindings)
+}
+
+fn number(row: &Value, key: &str) -> Option<f64> {
+    row.get(key)
+        .and_then(|v| v.as_f64().or_else(|| v.as_i64().map(|i| i as f64)))
+}
+
+fn text(row: &Value, key: &str) -> Option<String> {
+    row.get(key).and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+/// Load the jobs, history and emails in scope and compute the funnel metrics
+pub async fn get_funnel_analytics(
+    db: &D1Database,
+    user_id: &str,
+    filter: &AnalyticsFilter,
+) -> Result<FunnelAnalytics, worker::Error> {
+    let (where_clause, bindings) = filter_clause(filter);
+
+    let statuses_result = db
+        .prepare("SELECT id, name, display_name FROM job_statuses WHERE id != ? ORDER BY id")
+        .bind(&[REJECTED_STATUS_ID.into()])?
+        .all()
+        .await?;
+    let status_rows: Vec<Value> = statuses_result.results()?;
+    let stages: Vec<(i32, String, String)> = status_rows
+        .iter()
+        .filter_map(|row| {
+            Some((
+                number(row, "id")? as i32,
+                text(row, "name").unwrap_or_default(),
+                text(row, "display_name").unwrap_or_default(),
+            ))
+        })
+        .collect();
+
+    let jobs_result = db
+        .prepare(format!(
+            "SELECT j.id, j.status_id, src.name as source_name,
+                julianday(j.created_at) as created_day
+             FROM jobs j
+             LEFT JOIN job_sources src ON j.source_id = src.id
+             WHERE {}",
+            where_clause
+        ))
+        .bind(&bindings)?
+        .all()
+        .await?;
+    let job_rows: Vec<Value> = jobs_result.results()?;
+    let jobs: Vec<AnalyticsJob> = job_rows
+        .iter()
+        .filter_map(|row| {
+            Some(AnalyticsJob {
+                id: text(row, "id")?,
+                status_id: number(row, "status_id").map(|v| v as i32),
+                source: text(row, "source_name"),
+                created_at: number(row, "created_day")?,
+            })
+        })
+        .collect();
+
+    let history_result = db
+        .prepare(format!(
+            "SELECT h.job_id, h.to_status_id, julianday(h.changed_at) as changed_day
+             FROM job_status_history h
+             JOIN jobs j ON j.id = h.job_id
+             WHERE {}",
+            where_clause
+        ))
+        .bind(&bindings)?
+        .all()
+        .await?;
+    let history_rows: Vec<Value> = history_result.results()?;
+    let transitions: Vec<AnalyticsTransition> = history_rows
+        .iter()
+        .filter_map(|row| {
+            Some(AnalyticsTransition {
+                job_id: text(row, "job_id")?,
+                to_status_id: number(row, "to_status_id")? as i32,
+                changed_at: number(row, "changed_day")?,
+            })
+        })
+        .collect();
+
+    // Emails sent by the user don't count as responses
+    let user_email = db
+        .prepare("SELECT email FROM users WHERE id = ?")
+        .bind(&[user_id.into()])?
+        .first::<Value>(None)
+        .await?
+        .and_then(|row| text(&row, "email"))
+        .map(|email| email.to_lowercase());
+
+    let mut email_bindings: Vec<worker::wasm_bindgen::JsValue> = vec![user_id.into()];
+    email_bindings.extend(bindings);
+    let emails_result = db
+        .prepare(format!(
+            "SELECT e.job_id, e.\"from\" as sender, julianday(e.date) as email_day
+             FROM emails e
+             JOIN jobs j ON j.id = e.job_id
+             WHERE e.user_id = ? AND e.date IS NOT NULL AND {}",
+            where_clause
+        ))
+        .bind(&email_bindings)?
+        .all()
+        .await?;
+    let email_rows: Vec<Value> = emails_result.results()?;
+    let emails: Vec<AnalyticsEmail> = email_rows
+        .iter()
+        .filter_map(|row| {
+            let sender = text(row, "sender").unwrap_or_default().to_lowercase();
+            Some(AnalyticsEmail {
+                job_id: text(row, "job_id")?,
+                date: number(row, "email_day")?,
+                inbound: user_email
+                    .as_ref()
+                    .map(|email| !sender.contains(email.as_str()))
+                    .unwrap_or(true),
+            })
+        })
+        .collect();
+
+    Ok(compute_funnel_analytics(
+        &stages,
+        &jobs,
+        &transitions,
+        &emails,
+    ))
+}
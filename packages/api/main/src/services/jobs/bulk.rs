@@ -0,0 +1,236 @@
+//! Bulk job operations
+
+use crate::services::jobs::{purge_job_statements, status_change_statement};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use worker::D1Database;
+
+/// Maximum number of jobs in a single bulk request
+pub const MAX_BULK_JOBS: usize = 100;
+
+/// Maximum length of a tag in characters
+pub const MAX_TAG_LENGTH: usize = 32;
+
+/// Action applied to every job in a bulk request
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum BulkAction {
+    SetStatus {
+        status_id: i32,
+    },
+    AddTags {
+        tags: Vec<String>,
+    },
+    RemoveTags {
+        tags: Vec<String>,
+    },
+    Archive,
+    /// Move to the trash
+    Delete,
+    /// Bring back from the archive or the trash
+    Restore,
+    /// Delete permanently
+    Purge,
+}
+
+/// Bulk request body: `{"job_ids": [...], "action": "set_status", "status_id": 200}`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BulkJobsRequest {
+    pub job_ids: Vec<String>,
+    #[serde(flatten)]
+    pub action: BulkAction,
+}
+
+/// Outcome for a single job in a bulk request
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct BulkItemResult {
+    pub job_id: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Normalise tags: trim, lowercase, collapse inner whitespace to `-`, cap the
+/// length and drop empties and duplicates (keeping the first occurrence)
+pub fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    tags.iter()
+        .map(|t| {
+            t.split_whitespace()
+                .collect::<Vec<_>>()
+                .join("-")
+                .to_lowercase()
+                .chars()
+                .take(MAX_TAG_LENGTH)
+                .collect::<String>()
+        })
+        .filter(|t| !t.is_empty() && seen.insert(t.clone()))
+        .collect()
+}
+
+/// Validate a bulk request and return the de-duplicated job IDs in request order
+pub fn validate_bulk_request(request: &BulkJobsRequest) -> Result<Vec<String>, String> {
+    let mut seen = HashSet::new();
+    let job_ids: Vec<String> = request
+        .job_ids
+        .iter()
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty() && seen.insert(id.clone()))
+        .collect();
+
+    if job_ids.is_empty() {
+        return Err("At least one job ID is required".to_string());
+    }
+    if job_ids.len() > MAX_BULK_JOBS {
+        return Err(format!(
+            "At most {} jobs can be changed at once",
+            MAX_BULK_JOBS
+        ));
+    }
+
+    match &request.action {
+        BulkAction::AddTags { tags } | BulkAction::RemoveTags { tags } => {
+            if normalize_tags(tags).is_empty() {
+                return Err("At least one tag is required".to_string());
+            }
+        }
+        BulkAction::SetStatus { .. }
+        | BulkAction::Archive
+        | BulkAction::Delete
+        | BulkAction::Restore
+        | BulkAction::Purge => {}
+    }
+
+    Ok(job_ids)
+}
+
+/// Apply `action` to `job_ids` in a single D1 batch.
+///
+/// Unknown jobs are reported individually; the remaining jobs are changed
+/// together, so either all of them succeed or all of them report the batch error.
+/// The status in a `SetStatus` action must be validated by the caller.
+pub async fn bulk_update_jobs(
+    db: &D1Database,
+    job_ids: &[String],
+    action: &BulkAction,
+) -> Result<Vec<BulkItemResult>, worker::Error> {
+    let placeholders = vec!["?"; job_ids.len()].join(", ");
+    let bindings: Vec<worker::wasm_bindgen::JsValue> =
+        job_ids.iter().map(|id| id.as_str().into()).collect();
+    let existing_result = db
+        .prepare(format!(
+            "SELECT id, status_id FROM jobs WHERE id IN ({})",
+            placeholders
+        ))
+        .bind(&bindings)?
+        .all()
+        .await?;
+    let existing_rows: Vec<Value> = existing_result.results()?;
+
+    // job_id -> current status_id
+    let existing: HashMap<String, Option<i32>> = existing_rows
+        .iter()
+        .filter_map(|row| {
+            let id = row.get("id").and_then(|v| v.as_str())?.to_string();
+            let status_id = row
+                .get("status_id")
+                .and_then(|v| v.as_i64().or_else(|| v.as_f64().map(|f| f as i64)))
+                .map(|v| v as i32);
+            Some((id, status_id))
+        })
+        .collect();
+
+    let mut statements = Vec::new();
+    for job_id in job_ids {
+        let Some(current_status_id) = existing.get(job_id) else {
+            continue;
+        };
+
+        match action {
+            BulkAction::SetStatus { status_id } => {
+                if *current_status_id == Some(*status_id) {
+                    continue;
+                }
+                statements.push(
+                    db.prepare(
+                        "UPDATE jobs SET status_id = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+                    )
+                    .bind(&[(*status_id).into(), job_id.as_str().into()])?,
+                );
+                statements.push(status_change_statement(
+                    db,
+                    job_id,
+                    *current_status_id,
+                    *status_id,
+                )?);
+            }
+            BulkAction::AddTags { tags } => {
+                for tag in normalize_tags(tags) {
+                    statements.push(
+                        db.prepare("INSERT OR IGNORE INTO job_tags (job_id, tag) VALUES (?, ?)")
+                            .bind(&[job_id.as_str().into(), tag.into()])?,
+                    );
+                }
+            }
+            BulkAction::RemoveTags { tags } => {
+                for tag in normalize_tags(tags) {
+                    statements.push(
+                        db.prepare("DELETE FROM job_tags WHERE job_id = ? AND tag = ?")
+                            .bind(&[job_id.as_str().into(), tag.into()])?,
+                    );
+                }
+            }
+            BulkAction::Archive => {
+                statements.push(
+                    db.prepare(
+                        "UPDATE jobs SET archived_at = COALESCE(archived_at, CURRENT_TIMESTAMP) WHERE id = ?",
+                    )
+                    .bind(&[job_id.as_str().into()])?,
+                );
+            }
+            BulkAction::Delete => {
+                statements.push(
+                    db.prepare(
+                        "UPDATE jobs SET deleted_at = COALESCE(deleted_at, CURRENT_TIMESTAMP) WHERE id = ?",
+                    )
+                    .bind(&[job_id.as_str().into()])?,
+                );
+            }
+            BulkAction::Restore => {
+                statements.push(
+                    db.prepare(
+                        "UPDATE jobs SET archived_at = NULL, deleted_at = NULL WHERE id = ?",
+                    )
+                    .bind(&[job_id.as_str().into()])?,
+                );
+            }
+            BulkAction::Purge => {
+                statements.extend(purge_job_statements(db, job_id)?);
+            }
+        }
+    }
+
+    // D1 runs a batch as a single transaction
+    let batch_error = if statements.is_empty() {
+        None
+    } else {
+        db.batch(statements).await.err().map(|e| e.to_string())
+    };
+
+    Ok(job_ids
+        .iter()
+        .map(|job_id| {
+            let error = if !existing.contains_key(job_id) {
+                Some("Job not found".to_string())
+            } else {
+                batch_error.clone()
+            };
+            BulkItemResult {
+                job_id: job_id.clone(),
+                success: error.is_none(),
+                error,
+            }
+        })
+        .collect())
+}
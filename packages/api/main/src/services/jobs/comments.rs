@@ -0,0 +1,22 @@
+//! Rules for changing job comments
+
+use serde_json::Value;
+
+/// Why a user may not change a comment, as a message and HTTP status.
+/// Only the author may change a comment, and deleted comments are final.
+pub fn comment_edit_error(comment: &Value, user_id: &str) -> Option<(&'static str, u16)> {
+    let author_id = comment
+        .get("user_id")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+
+    if author_id != user_id {
+        return Some(("Only the author can modify this comment", 403));
+    }
+
+    if comment.get("deleted_at").and_then(|v| v.as_str()).is_some() {
+        return Some(("Comment has been deleted", 400));
+    }
+
+    None
+}
@@ -1,7 +1,8 @@
 //! Job creation
 
+use crate::services::companies::link_job_to_company;
 use crate::services::job_statuses::get_status_by_id;
-use crate::services::jobs::Job;
+use crate::services::jobs::{record_status_change, Job};
 use crate::services::password;
 use worker::{D1Database, Request, Response};
 
@@ -81,6 +82,9 @@ pub async fn create_job(db: &D1Database, mut req: Request) -> Result<Response, w
         }
     }
 
+    record_status_change(db, &job_id, None, status_id).await?;
+    link_job_to_company(db, &job_id, &job.company).await?;
+
     let created_job = serde_json::json!({
         "id": job_id,
         "title": job.title,
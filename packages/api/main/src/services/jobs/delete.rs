@@ -1,24 +1,172 @@
-//! Job deletion
+//! Job deletion, archiving and restore
+//!
+//! Deleting a job moves it to the trash by setting `deleted_at`. Trashed jobs
+//! are purged permanently once they have been in the trash for longer than
+//! the retention window, or when they are deleted from the trash.
 
+use crate::services::db::job_attachments::get_attachment_keys_for_job;
 use serde_json::Value;
-use worker::{D1Database, Response};
+use worker::{D1Database, D1PreparedStatement, Response};
 
-/// Delete a job
-pub async fn delete_job(db: &D1Database, id: String) -> Result<Response, worker::Error> {
+/// Days a job stays in the trash before it is purged
+pub const DEFAULT_TRASH_RETENTION_DAYS: u32 = 30;
+
+/// The retention window from the `TRASH_RETENTION_DAYS` variable, falling
+/// back to the default when it's unset or not a positive number of days
+pub fn trash_retention_days(value: Option<&str>) -> u32 {
+    value
+        .and_then(|v| v.trim().parse::<u32>().ok())
+        .filter(|days| *days > 0)
+        .unwrap_or(DEFAULT_TRASH_RETENTION_DAYS)
+}
+
+/// SQLite `datetime('now', ?)` modifier for the start of the retention window
+pub fn trash_cutoff_modifier(retention_days: u32) -> String {
+    format!("-{} days", retention_days)
+}
+
+async fn job_exists(db: &D1Database, id: &str) -> Result<bool, worker::Error> {
     let exists = db
         .prepare("SELECT id FROM jobs WHERE id = ?")
-        .bind(&[id.clone().into()])?
+        .bind(&[id.into()])?
         .first::<Value>(None)
         .await?;
 
-    if exists.is_none() {
+    Ok(exists.is_some())
+}
+
+/// Move a job to the trash
+pub async fn delete_job(db: &D1Database, id: String) -> Result<Response, worker::Error> {
+    if !job_exists(db, &id).await? {
         return Response::error("Job not found", 404);
     }
 
-    db.prepare("DELETE FROM jobs WHERE id = ?")
+    db.prepare("UPDATE jobs SET deleted_at = COALESCE(deleted_at, CURRENT_TIMESTAMP) WHERE id = ?")
         .bind(&[id.into()])?
         .run()
         .await?;
 
-    Response::ok("Job deleted successfully")
+    Response::ok("Job moved to trash")
+}
+
+/// Archive a job, hiding it from the default listing
+pub async fn archive_job(db: &D1Database, id: String) -> Result<Response, worker::Error> {
+    if !job_exists(db, &id).await? {
+        return Response::error("Job not found", 404);
+    }
+
+    db.prepare(
+        "UPDATE jobs SET archived_at = COALESCE(archived_at, CURRENT_TIMESTAMP) WHERE id = ?",
+    )
+    .bind(&[id.into()])?
+    .run()
+    .await?;
+
+    Response::ok("Job archived")
+}
+
+/// Restore an archived or trashed job to the active list
+pub async fn restore_job(db: &D1Database, id: String) -> Result<Response, worker::Error> {
+    if !job_exists(db, &id).await? {
+        return Response::error("Job not found", 404);
+    }
+
+    db.prepare("UPDATE jobs SET archived_at = NULL, deleted_at = NULL WHERE id = ?")
+        .bind(&[id.into()])?
+        .run()
+        .await?;
+
+    Response::ok("Job restored")
+}
+
+/// Build the statements that permanently delete a job.
+/// Emails are unlinked rather than deleted since they belong to the mailbox,
+/// not the job; comments, attachments and history cascade with the job.
+pub fn purge_job_statements(
+    db: &D1Database,
+    id: &str,
+) -> Result<Vec<D1PreparedStatement>, worker::Error> {
+    Ok(vec![
+        db.prepare("UPDATE emails SET job_id = NULL WHERE job_id = ?")
+            .bind(&[id.into()])?,
+        db.prepare("DELETE FROM jobs WHERE id = ?")
+            .bind(&[id.into()])?,
+    ])
+}
+
+/// What has to be cleaned up outside the database once a job is purged
+#[derive(Debug, Default)]
+pub struct PurgeTarget {
+    pub job_id: String,
+    /// Stored files of the job's attachments
+    pub attachment_keys: Vec<String>,
+    /// Linked emails as `(user_id, gmail_id)` pairs, whose Gmail labels
+    /// change once they are unlinked
+    pub emails: Vec<(String, String)>,
+}
+
+/// Collect a job's stored files and linked emails before the purge removes
+/// the rows that point to them
+pub async fn purge_target(db: &D1Database, id: &str) -> PurgeTarget {
+    PurgeTarget {
+        job_id: id.to_string(),
+        attachment_keys: get_attachment_keys_for_job(db, id)
+            .await
+            .unwrap_or_default(),
+        emails: linked_emails(db, id).await.unwrap_or_default(),
+    }
+}
+
+async fn linked_emails(db: &D1Database, id: &str) -> Result<Vec<(String, String)>, worker::Error> {
+    let result = db
+        .prepare("SELECT user_id, gmail_id FROM emails WHERE job_id = ?")
+        .bind(&[id.into()])?
+        .all()
+        .await?;
+
+    let rows: Vec<Value> = result.results()?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            let user_id = row.get("user_id")?.as_str()?;
+            let gmail_id = row.get("gmail_id")?.as_str()?;
+            Some((user_id.to_string(), gmail_id.to_string()))
+        })
+        .collect())
+}
+
+/// Permanently delete a job
+pub async fn purge_job(db: &D1Database, id: String) -> Result<Response, worker::Error> {
+    if !job_exists(db, &id).await? {
+        return Response::error("Job not found", 404);
+    }
+
+    db.batch(purge_job_statements(db, &id)?).await?;
+
+    Response::ok("Job deleted permanently")
+}
+
+/// IDs of jobs that have been in the trash for more than `retention_days`
+pub async fn find_expired_trash(
+    db: &D1Database,
+    retention_days: u32,
+) -> Result<Vec<String>, worker::Error> {
+    let result = db
+        .prepare(
+            "SELECT id FROM jobs
+             WHERE deleted_at IS NOT NULL AND deleted_at < datetime('now', ?)",
+        )
+        .bind(&[trash_cutoff_modifier(retention_days).into()])?
+        .all()
+        .await?;
+
+    let rows: Vec<Value> = result.results()?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            row.get("id")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+        })
+        .collect())
 }
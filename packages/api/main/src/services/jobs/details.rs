@@ -2,7 +2,8 @@
 
 use crate::common::auth::require_auth;
 use crate::services::jobs::{
-    build_timeline_events, extract_people_from_emails, normalize_job_id, process_contacts_for_job,
+    build_timeline_events, extract_people_from_emails, get_job_events, get_status_history,
+    normalize_job_id, parse_job_tags, process_contacts_for_job,
 };
 use serde_json::Value;
 use worker::{D1Database, Env, Request};
@@ -34,12 +35,17 @@ pub async fn get_job_details_data(
             "SELECT 
                 j.*, 
                 j.status_id,
-                js.name as status_name
+                js.name as status_name,
+                src.name as source_name,
+                rc.name as referrer_name,
+                (SELECT json_group_array(jt.tag) FROM job_tags jt WHERE jt.job_id = j.id) as tags
             FROM jobs j
             LEFT JOIN job_statuses js ON j.status_id = js.id
+            LEFT JOIN job_sources src ON j.source_id = src.id
+            LEFT JOIN email_contacts rc ON rc.email = j.referrer_email AND rc.user_id = ?
             WHERE j.id = ?",
         )
-        .bind(&[id.clone().into()])?
+        .bind(&[user_id.clone().into(), id.clone().into()])?
         .first::<Value>(None)
         .await?;
 
@@ -48,6 +54,7 @@ pub async fn get_job_details_data(
         None => return Err(worker::Error::RustError("Job not found".to_string())),
     };
     normalize_job_id(&mut job);
+    parse_job_tags(&mut job);
 
     // Get emails linked to this job
     let emails_result = db
@@ -71,8 +78,12 @@ pub async fn get_job_details_data(
 
     let comments: Vec<Value> = comments_result.results()?;
 
+    // Get status transitions and stored events for this job
+    let status_history = get_status_history(db, &id).await?;
+    let events = get_job_events(db, &id).await?;
+
     // Build timeline events
-    let timeline_events = build_timeline_events(&job, &emails, &comments);
+    let timeline_events = build_timeline_events(&job, &emails, &comments, &status_history, &events);
 
     // Extract unique people from emails (legacy, keep for backward compatibility)
     let people = extract_people_from_emails(&emails);
@@ -0,0 +1,262 @@
+//! Duplicate job detection
+//!
+//! Jobs are compared on normalised company and title similarity, and jobs that
+//! have emails from the same Gmail thread linked to them are treated as strong
+//! duplicate candidates.
+
+use crate::services::jobs::normalize_job_id;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use worker::D1Database;
+
+/// Minimum score for a pair of jobs to be suggested as duplicates
+pub const DUPLICATE_THRESHOLD: f64 = 0.75;
+
+/// Legal suffixes and filler words that don't distinguish companies
+const COMPANY_STOP_WORDS: &[&str] = &[
+    "the",
+    "inc",
+    "incorporated",
+    "llc",
+    "ltd",
+    "limited",
+    "corp",
+    "corporation",
+    "co",
+    "company",
+    "gmbh",
+    "plc",
+    "sa",
+    "ag",
+    "bv",
+    "group",
+    "holdings",
+];
+
+/// Normalise a company name: lowercase, strip punctuation and legal suffixes
+pub fn normalize_company(company: &str) -> String {
+    tokenize(company)
+        .into_iter()
+        .filter(|t| !COMPANY_STOP_WORDS.contains(&t.as_str()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Normalise a job title: lowercase, strip punctuation and expand common abbreviations
+pub fn normalize_title(title: &str) -> String {
+    tokenize(title)
+        .into_iter()
+        .map(|t| match t.as_str() {
+            "sr" | "snr" => "senior".to_string(),
+            "jr" | "jnr" => "junior".to_string(),
+            "eng" | "engr" => "engineer".to_string(),
+            "dev" => "developer".to_string(),
+            "mgr" => "manager".to_string(),
+            "swe" | "sde" => "software engineer".to_string(),
+            "pm" => "product manager".to_string(),
+            "ii" => "2".to_string(),
+            "iii" => "3".to_string(),
+            _ => t,
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Similarity of two company names (0.0 - 1.0)
+pub fn company_similarity(a: &str, b: &str) -> f64 {
+    let a = normalize_company(a);
+    let b = normalize_company(b);
+
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    if a == b {
+        return 1.0;
+    }
+    if a.replace(' ', "") == b.replace(' ', "") {
+        return 0.95;
+    }
+
+    token_dice(&a, &b)
+}
+
+/// Similarity of two job titles (0.0 - 1.0)
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    let a = normalize_title(a);
+    let b = normalize_title(b);
+
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    if a == b {
+        return 1.0;
+    }
+
+    token_dice(&a, &b)
+}
+
+/// Combined duplicate score for two jobs (0.0 - 1.0). A shared email thread is
+/// strong evidence on its own; otherwise both company and title must match well.
+pub fn duplicate_score(
+    company_a: &str,
+    title_a: &str,
+    company_b: &str,
+    title_b: &str,
+    shared_threads: usize,
+) -> f64 {
+    let company = company_similarity(company_a, company_b);
+    let title = title_similarity(title_a, title_b);
+
+    // Different companies are never duplicates based on names alone
+    let name_score = if company < 0.6 {
+        0.0
+    } else {
+        company * 0.5 + title * 0.5
+    };
+
+    if shared_threads > 0 {
+        (name_score + 0.5).clamp(0.8, 1.0)
+    } else {
+        name_score
+    }
+}
+
+fn tokenize(value: &str) -> Vec<String> {
+    value
+        .to_lowercase()
+        .replace('&', " and ")
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_string())
+        .collect()
+}
+
+/// Sørensen–Dice coefficient over word tokens
+fn token_dice(a: &str, b: &str) -> f64 {
+    let a: HashSet<&str> = a.split(' ').collect();
+    let b: HashSet<&str> = b.split(' ').collect();
+    let shared = a.intersection(&b).count();
+
+    (2 * shared) as f64 / (a.len() + b.len()) as f64
+}
+
+/// Find suggested duplicates across all jobs, or only those involving `job_id`.
+/// Shared threads are computed from the user's linked emails.
+pub async fn find_duplicate_suggestions(
+    db: &D1Database,
+    user_id: &str,
+    job_id: Option<&str>,
+) -> Result<Vec<Value>, worker::Error> {
+    let jobs_result = db
+        .prepare(
+            "SELECT j.id, j.title, j.company, j.status_id, j.created_at, js.name as status_name
+             FROM jobs j
+             LEFT JOIN job_statuses js ON j.status_id = js.id
+             WHERE j.deleted_at IS NULL
+             ORDER BY j.created_at ASC",
+        )
+        .all()
+        .await?;
+    let mut jobs: Vec<Value> = jobs_result.results()?;
+    for job in &mut jobs {
+        normalize_job_id(job);
+    }
+
+    // thread_id -> jobs that have an email from that thread
+    let threads_result = db
+        .prepare(
+            "SELECT DISTINCT thread_id, job_id FROM emails
+             WHERE user_id = ? AND job_id IS NOT NULL AND thread_id IS NOT NULL",
+        )
+        .bind(&[user_id.into()])?
+        .all()
+        .await?;
+    let thread_rows: Vec<Value> = threads_result.results()?;
+    let mut jobs_by_thread: HashMap<String, HashSet<String>> = HashMap::new();
+    for row in thread_rows {
+        if let (Some(thread_id), Some(linked_job)) = (
+            row.get("thread_id").and_then(|v| v.as_str()),
+            row.get("job_id").and_then(|v| v.as_str()),
+        ) {
+            jobs_by_thread
+                .entry(thread_id.to_string())
+                .or_default()
+                .insert(linked_job.to_string());
+        }
+    }
+
+    let mut shared_threads: HashMap<(String, String), usize> = HashMap::new();
+    for linked_jobs in jobs_by_thread.values() {
+        let mut ids: Vec<&String> = linked_jobs.iter().collect();
+        ids.sort();
+        for (i, a) in ids.iter().enumerate() {
+            for b in ids.iter().skip(i + 1) {
+                *shared_threads
+                    .entry(((*a).clone(), (*b).clone()))
+                    .or_insert(0) += 1;
+            }
+        }
+    }
+
+    let field = |job: &Value, key: &str| -> String {
+        job.get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+
+    let mut suggestions = Vec::new();
+    for (i, a) in jobs.iter().enumerate() {
+        for b in jobs.iter().skip(i + 1) {
+            let (id_a, id_b) = (field(a, "id"), field(b, "id"));
+            if let Some(target) = job_id {
+                if id_a != target && id_b != target {
+                    continue;
+                }
+            }
+
+            let pair_key = if id_a < id_b {
+                (id_a.clone(), id_b.clone())
+            } else {
+                (id_b.clone(), id_a.clone())
+            };
+            let threads = shared_threads.get(&pair_key).copied().unwrap_or(0);
+
+            let (company_a, title_a) = (field(a, "company"), field(a, "title"));
+            let (company_b, title_b) = (field(b, "company"), field(b, "title"));
+            let score = duplicate_score(&company_a, &title_a, &company_b, &title_b, threads);
+
+            if score < DUPLICATE_THRESHOLD {
+                continue;
+            }
+
+            let mut reasons = Vec::new();
+            if company_similarity(&company_a, &company_b) >= 0.9 {
+                reasons.push("same_company");
+            }
+            if title_similarity(&title_a, &title_b) >= 0.7 {
+                reasons.push("similar_title");
+            }
+            if threads > 0 {
+                reasons.push("shared_thread");
+            }
+
+            // The older job is suggested as the one to keep
+            suggestions.push(serde_json::json!({
+                "job": a,
+                "duplicate": b,
+                "score": (score * 100.0).round() / 100.0,
+                "shared_threads": threads,
+                "reasons": reasons,
+            }));
+        }
+    }
+
+    suggestions.sort_by(|a, b| {
+        let sa = a.get("score").and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let sb = b.get("score").and_then(|v| v.as_f64()).unwrap_or(0.0);
+        sb.partial_cmp(&sa).unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    Ok(suggestions)
+}
@@ -0,0 +1,97 @@
+//! Job status history and stored timeline events
+
+use crate::services::password;
+use serde_json::Value;
+use worker::{D1Database, D1PreparedStatement};
+
+/// Build the statement that records a status transition.
+/// `from_status_id` is `None` for the initial status of a new job.
+pub fn status_change_statement(
+    db: &D1Database,
+    job_id: &str,
+    from_status_id: Option<i32>,
+    to_status_id: i32,
+) -> Result<D1PreparedStatement, worker::Error> {
+    let history_id = password::generate_uuid()
+        .map_err(|e| worker::Error::RustError(format!("Failed to generate UUID: {}", e)))?;
+
+    db.prepare(
+        "INSERT INTO job_status_history (id, job_id, from_status_id, to_status_id) VALUES (?, ?, ?, ?)",
+    )
+    .bind(&[
+        history_id.into(),
+        job_id.into(),
+        from_status_id.into(),
+        to_status_id.into(),
+    ])
+}
+
+/// Record a status transition for a job
+pub async fn record_status_change(
+    db: &D1Database,
+    job_id: &str,
+    from_status_id: Option<i32>,
+    to_status_id: i32,
+) -> Result<(), worker::Error> {
+    status_change_statement(db, job_id, from_status_id, to_status_id)?
+        .run()
+        .await?;
+    Ok(())
+}
+
+/// Build the statement that stores a timeline event for a job
+pub fn job_event_statement(
+    db: &D1Database,
+    job_id: &str,
+    user_id: Option<&str>,
+    event_type: &str,
+    data: &Value,
+) -> Result<D1PreparedStatement, worker::Error> {
+    let event_id = password::generate_uuid()
+        .map_err(|e| worker::Error::RustError(format!("Failed to generate UUID: {}", e)))?;
+
+    db.prepare(
+        "INSERT INTO job_events (id, job_id, user_id, event_type, data) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(&[
+        event_id.into(),
+        job_id.into(),
+        user_id.into(),
+        event_type.into(),
+        data.to_string().into(),
+    ])
+}
+
+/// Get the status history of a job (oldest first) with status display names
+pub async fn get_status_history(
+    db: &D1Database,
+    job_id: &str,
+) -> Result<Vec<Value>, worker::Error> {
+    let result = db
+        .prepare(
+            "SELECT h.*, js.display_name as status_name
+             FROM job_status_history h
+             LEFT JOIN job_statuses js ON h.to_status_id = js.id
+             WHERE h.job_id = ? ORDER BY h.changed_at ASC",
+        )
+        .bind(&[job_id.into()])?
+        .all()
+        .await?;
+
+    result.results()
+}
+
+/// Get stored timeline events of a job
+pub async fn get_job_events(db: &D1Database, job_id: &str) -> Result<Vec<Value>, worker::Error> {
+    let result = db
+        .prepare(
+            "SELECT je.*, u.name as user_name FROM job_events je
+             LEFT JOIN users u ON je.user_id = u.id
+             WHERE je.job_id = ? ORDER BY je.created_at ASC",
+        )
+        .bind(&[job_id.into()])?
+        .all()
+        .await?;
+
+    result.results()
+}
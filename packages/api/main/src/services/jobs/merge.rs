@@ -0,0 +1,131 @@
+//! Job merging
+
+use crate::services::jobs::{job_event_statement, normalize_job_id};
+use serde_json::Value;
+use worker::{D1Database, Response};
+
+/// Statements that move everything of the duplicate (`?2`) to the survivor
+/// (`?1`) and fill the survivor's empty fields from it. Anything left on the
+/// duplicate is lost when it is deleted, so every table with a `job_id`
+/// needs one here.
+pub const MERGE_STATEMENTS: &[&str] = &[
+    "UPDATE emails SET job_id = ?1 WHERE job_id = ?2",
+    "UPDATE job_comments SET job_id = ?1 WHERE job_id = ?2",
+    "UPDATE job_attachments SET job_id = ?1 WHERE job_id = ?2",
+    // The duplicate's initial status is not a transition of the survivor
+    "UPDATE job_status_history SET job_id = ?1 WHERE job_id = ?2 AND from_status_id IS NOT NULL",
+    "UPDATE job_events SET job_id = ?1 WHERE job_id = ?2",
+    "INSERT OR IGNORE INTO job_tags (job_id, tag, created_at)
+     SELECT ?1, tag, created_at FROM job_tags WHERE job_id = ?2",
+    // Documents are numbered after the survivor's own versions of each user.
+    // The new numbers are parked as negatives first so the renumbering never
+    // collides with a version the duplicate still holds.
+    "UPDATE job_documents SET version = -(version + COALESCE(
+        (SELECT MAX(version) FROM job_documents d WHERE d.job_id = ?1 AND d.user_id = job_documents.user_id),
+        0))
+     WHERE job_id = ?2",
+    "UPDATE job_documents SET job_id = ?1, version = -version WHERE job_id = ?2",
+    "UPDATE jobs SET
+        description = COALESCE(description, (SELECT description FROM jobs WHERE id = ?2)),
+        location = COALESCE(location, (SELECT location FROM jobs WHERE id = ?2)),
+        source_id = COALESCE(source_id, (SELECT source_id FROM jobs WHERE id = ?2)),
+        referrer_email = COALESCE(referrer_email, (SELECT referrer_email FROM jobs WHERE id = ?2)),
+        company_id = COALESCE(company_id, (SELECT company_id FROM jobs WHERE id = ?2)),
+        updated_at = CURRENT_TIMESTAMP
+     WHERE id = ?1",
+];
+
+/// Merge `duplicate_id` into `survivor_id`.
+///
+/// Emails, comments, attachments, status transitions, tags and documents of
+/// the duplicate are moved to the survivor, empty survivor fields (including
+/// the source, company and referrer) are filled from the duplicate, a
+/// `job_merged` event is recorded and the duplicate is deleted. Trashed jobs
+/// can't be merged. All writes run in a single batch so a failed merge leaves
+/// both jobs untouched.
+pub async fn merge_jobs(
+    db: &D1Database,
+    survivor_id: String,
+    duplicate_id: String,
+    user_id: &str,
+) -> Result<Response, worker::Error> {
+    if survivor_id == duplicate_id {
+        return Response::error("Cannot merge a job into itself", 400);
+    }
+
+    let survivor = match db
+        .prepare("SELECT id, deleted_at FROM jobs WHERE id = ?")
+        .bind(&[survivor_id.clone().into()])?
+        .first::<Value>(None)
+        .await?
+    {
+        Some(job) => job,
+        None => return Response::error("Job not found", 404),
+    };
+    if is_trashed(&survivor) {
+        return Response::error("Cannot merge into a trashed job", 400);
+    }
+
+    let duplicate = match db
+        .prepare("SELECT * FROM jobs WHERE id = ?")
+        .bind(&[duplicate_id.clone().into()])?
+        .first::<Value>(None)
+        .await?
+    {
+        Some(job) => job,
+        None => return Response::error("Duplicate job not found", 404),
+    };
+    if is_trashed(&duplicate) {
+        return Response::error("Cannot merge a trashed job", 400);
+    }
+
+    let event_data = serde_json::json!({
+        "merged_job_id": duplicate_id,
+        "title": duplicate.get("title").and_then(|v| v.as_str()).unwrap_or(""),
+        "company": duplicate.get("company").and_then(|v| v.as_str()).unwrap_or(""),
+    });
+
+    let ids: [worker::wasm_bindgen::JsValue; 2] =
+        [survivor_id.clone().into(), duplicate_id.clone().into()];
+
+    let mut statements = MERGE_STATEMENTS
+        .iter()
+        .map(|sql| db.prepare(*sql).bind(&ids))
+        .collect::<Result<Vec<_>, _>>()?;
+    statements.push(job_event_statement(
+        db,
+        &survivor_id,
+        Some(user_id),
+        "job_merged",
+        &event_data,
+    )?);
+    statements.push(
+        db.prepare("DELETE FROM jobs WHERE id = ?")
+            .bind(&[duplicate_id.clone().into()])?,
+    );
+
+    db.batch(statements).await?;
+
+    let merged = db
+        .prepare(
+            "SELECT j.*, js.name as status_name
+             FROM jobs j
+             LEFT JOIN job_statuses js ON j.status_id = js.id
+             WHERE j.id = ?",
+        )
+        .bind(&[survivor_id.into()])?
+        .first::<Value>(None)
+        .await?;
+
+    match merged {
+        Some(mut job) => {
+            normalize_job_id(&mut job);
+            Response::from_json(&job)
+        }
+        None => Response::error("Job not found", 404),
+    }
+}
+
+fn is_trashed(job: &Value) -> bool {
+    job.get("deleted_at").is_some_and(|v| !v.is_null())
+}
@@ -1,23 +1,44 @@
 //! Job-related services
 
+pub mod analytics;
+pub mod bulk;
+pub mod comments;
 pub mod contacts;
 pub mod create;
 pub mod delete;
 pub mod details;
+pub mod duplicates;
+pub mod history;
+pub mod merge;
 pub mod people;
 pub mod read;
+pub mod source;
 pub mod timeline;
 pub mod types;
 pub mod update;
 pub mod utils;
 
+pub use analytics::{get_funnel_analytics, AnalyticsFilter};
+pub use bulk::{bulk_update_jobs, validate_bulk_request, BulkAction, BulkJobsRequest};
+pub use comments::comment_edit_error;
 pub use contacts::process_contacts_for_job;
 pub use create::create_job;
-pub use delete::delete_job;
+pub use delete::{
+    archive_job, delete_job, find_expired_trash, purge_job, purge_job_statements, purge_target,
+    restore_job, trash_cutoff_modifier, trash_retention_days, PurgeTarget,
+    DEFAULT_TRASH_RETENTION_DAYS,
+};
 pub use details::get_job_details_data;
+pub use duplicates::find_duplicate_suggestions;
+pub use history::{
+    get_job_events, get_status_history, job_event_statement, record_status_change,
+    status_change_statement,
+};
+pub use merge::merge_jobs;
 pub use people::extract_people_from_emails;
-pub use read::{get_job, list_jobs};
+pub use read::{get_job, list_jobs, JobListView};
+pub use source::{update_job_source, UpdateJobSourceRequest};
 pub use timeline::build_timeline_events;
 pub use types::{Job, JobStatus};
 pub use update::update_job;
-pub use utils::normalize_job_id;
+pub use utils::{normalize_job_id, parse_job_tags};
@@ -1,27 +1,62 @@
 //! Job read operations
 
-use crate::services::jobs::normalize_job_id;
+use crate::services::jobs::{normalize_job_id, parse_job_tags};
 use serde_json::Value;
 use worker::{D1Database, Response};
 
-/// List all jobs
-pub async fn list_jobs(db: &D1Database) -> Result<Response, worker::Error> {
+/// Which jobs a listing returns
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JobListView {
+    /// Jobs that are neither archived nor in the trash
+    Active,
+    Archived,
+    Trash,
+}
+
+impl JobListView {
+    /// Parse the `view` query parameter, defaulting to the active list
+    pub fn from_query(value: Option<&str>) -> Self {
+        match value {
+            Some("archived") => JobListView::Archived,
+            Some("trash") => JobListView::Trash,
+            _ => JobListView::Active,
+        }
+    }
+
+    /// SQL condition selecting the jobs of this view
+    pub fn filter(&self) -> &'static str {
+        match self {
+            JobListView::Active => "j.archived_at IS NULL AND j.deleted_at IS NULL",
+            JobListView::Archived => "j.archived_at IS NOT NULL AND j.deleted_at IS NULL",
+            JobListView::Trash => "j.deleted_at IS NOT NULL",
+        }
+    }
+}
+
+/// List jobs in a view with their tags
+pub async fn list_jobs(db: &D1Database, view: JobListView) -> Result<Response, worker::Error> {
     let result = db
-        .prepare(
+        .prepare(format!(
             "SELECT 
                 j.*, 
                 j.status_id,
-                js.name as status_name
+                js.name as status_name,
+                src.name as source_name,
+                (SELECT json_group_array(jt.tag) FROM job_tags jt WHERE jt.job_id = j.id) as tags
             FROM jobs j
             LEFT JOIN job_statuses js ON j.status_id = js.id
+            LEFT JOIN job_sources src ON j.source_id = src.id
+            WHERE {}
             ORDER BY j.created_at DESC",
-        )
+            view.filter()
+        ))
         .all()
         .await?;
 
     let mut jobs: Vec<Value> = result.results()?;
     for job in &mut jobs {
         normalize_job_id(job);
+        parse_job_tags(job);
     }
     Response::from_json(&jobs)
 }
@@ -33,9 +68,12 @@ pub async fn get_job(db: &D1Database, id: String) -> Result<Response, worker::Er
             "SELECT 
                 j.*, 
                 j.status_id,
-                js.name as status_name
+                js.name as status_name,
+                src.name as source_name,
+                (SELECT json_group_array(jt.tag) FROM job_tags jt WHERE jt.job_id = j.id) as tags
             FROM jobs j
             LEFT JOIN job_statuses js ON j.status_id = js.id
+            LEFT JOIN job_sources src ON j.source_id = src.id
             WHERE j.id = ?",
         )
         .bind(&[id.into()])?
@@ -45,6 +83,7 @@ pub async fn get_job(db: &D1Database, id: String) -> Result<Response, worker::Er
     match result {
         Some(mut job) => {
             normalize_job_id(&mut job);
+            parse_job_tags(&mut job);
             Response::from_json(&job)
         }
         None => Response::error("Job not found", 404),
@@ -0,0 +1,69 @@
+//! Job source and referrer
+
+use crate::services::db::email_contacts::get_or_create_contact;
+use crate::services::job_sources::source_exists;
+use crate::services::jobs::get_job;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use worker::{D1Database, Response};
+
+/// Source used when a referrer is set without a source
+pub const REFERRAL_SOURCE_ID: &str = "referral";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UpdateJobSourceRequest {
+    pub source_id: Option<String>,
+    /// Email address of the contact who referred the user
+    pub referrer_email: Option<String>,
+}
+
+/// Set the source and referrer of a job. The referrer is linked to the user's
+/// email contact for that address, creating the contact if needed.
+pub async fn update_job_source(
+    db: &D1Database,
+    id: String,
+    user_id: &str,
+    request: UpdateJobSourceRequest,
+) -> Result<Response, worker::Error> {
+    let exists = db
+        .prepare("SELECT id FROM jobs WHERE id = ?")
+        .bind(&[id.clone().into()])?
+        .first::<Value>(None)
+        .await?;
+    if exists.is_none() {
+        return Response::error("Job not found", 404);
+    }
+
+    let referrer_email = match request
+        .referrer_email
+        .as_deref()
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+    {
+        Some(email) => match get_or_create_contact(db, email, user_id).await {
+            Ok(contact) => Some(contact.email),
+            Err(_) => return Response::error("Invalid referrer email", 400),
+        },
+        None => None,
+    };
+
+    let source_id = match request.source_id.filter(|s| !s.is_empty()) {
+        Some(source_id) => Some(source_id),
+        None if referrer_email.is_some() => Some(REFERRAL_SOURCE_ID.to_string()),
+        None => None,
+    };
+    if let Some(source_id) = &source_id {
+        if !source_exists(db, source_id).await? {
+            return Response::error("Invalid source_id", 400);
+        }
+    }
+
+    db.prepare(
+        "UPDATE jobs SET source_id = ?, referrer_email = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+    )
+    .bind(&[source_id.into(), referrer_email.into(), id.clone().into()])?
+    .run()
+    .await?;
+
+    get_job(db, id).await
+}
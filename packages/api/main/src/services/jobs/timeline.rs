@@ -2,8 +2,14 @@
 
 use serde_json::Value;
 
-/// Build timeline events from job, emails, and comments
-pub fn build_timeline_events(job: &Value, emails: &[Value], comments: &[Value]) -> Vec<Value> {
+/// Build timeline events from job, emails, comments, status history and stored events
+pub fn build_timeline_events(
+    job: &Value,
+    emails: &[Value],
+    comments: &[Value],
+    status_history: &[Value],
+    events: &[Value],
+) -> Vec<Value> {
     let mut timeline_events: Vec<Value> = Vec::new();
 
     // Job creation event
@@ -17,22 +23,51 @@ pub fn build_timeline_events(job: &Value, emails: &[Value], comments: &[Value])
         }));
     }
 
-    // Status change events (if updated_at differs from created_at, assume status changed)
-    if let (Some(created_at), Some(updated_at)) = (
-        job.get("created_at").and_then(|v| v.as_str()),
-        job.get("updated_at").and_then(|v| v.as_str()),
-    ) {
-        if created_at != updated_at {
+    // Status change events from recorded history
+    for entry in status_history {
+        // The initial status is already covered by the job creation event
+        if entry
+            .get("from_status_id")
+            .unwrap_or(&Value::Null)
+            .is_null()
+        {
+            continue;
+        }
+        if let Some(changed_at) = entry.get("changed_at").and_then(|v| v.as_str()) {
             timeline_events.push(serde_json::json!({
                 "type": "status_changed",
-                "timestamp": updated_at,
+                "timestamp": changed_at,
                 "data": {
-                    "status": job.get("status").and_then(|v| v.as_str()).unwrap_or(""),
+                    "status": entry.get("status_name").and_then(|v| v.as_str()).unwrap_or(""),
                 }
             }));
         }
     }
 
+    // Stored job events (e.g. merges)
+    for event in events {
+        let event_type = event.get("event_type").and_then(|v| v.as_str());
+        let created_at = event.get("created_at").and_then(|v| v.as_str());
+        if let (Some(event_type), Some(created_at)) = (event_type, created_at) {
+            let mut data: Value = event
+                .get("data")
+                .and_then(|v| v.as_str())
+                .and_then(|s| serde_json::from_str(s).ok())
+                .unwrap_or_else(|| serde_json::json!({}));
+            if let Some(obj) = data.as_object_mut() {
+                obj.insert(
+                    "user_name".to_string(),
+                    event.get("user_name").cloned().unwrap_or(Value::Null),
+                );
+            }
+            timeline_events.push(serde_json::json!({
+                "type": event_type,
+                "timestamp": created_at,
+                "data": data,
+            }));
+        }
+    }
+
     // Email events
     for email in emails {
         if let Some(date) = email.get("date").and_then(|v| v.as_str()) {
@@ -48,16 +83,49 @@ pub fn build_timeline_events(job: &Value, emails: &[Value], comments: &[Value])
         }
     }
 
-    // Comment events
+    // Comment events (added, edited and deleted)
     for comment in comments {
+        let comment_id = comment.get("id").and_then(|v| v.as_str()).unwrap_or("");
+        let user_name = comment.get("name").and_then(|v| v.as_str());
+        let user_picture = comment.get("picture").and_then(|v| v.as_str());
+        let parent_id = comment.get("parent_id").and_then(|v| v.as_str());
+        let updated_at = comment.get("updated_at").and_then(|v| v.as_str());
+        let deleted_at = comment.get("deleted_at").and_then(|v| v.as_str());
+
         if let Some(created_at) = comment.get("created_at").and_then(|v| v.as_str()) {
             timeline_events.push(serde_json::json!({
                 "type": "comment_added",
                 "timestamp": created_at,
                 "data": {
+                    "comment_id": comment_id,
+                    "parent_id": parent_id,
                     "content": comment.get("content").and_then(|v| v.as_str()).unwrap_or(""),
-                    "user_name": comment.get("name").and_then(|v| v.as_str()),
-                    "user_picture": comment.get("picture").and_then(|v| v.as_str()),
+                    "user_name": user_name,
+                    "user_picture": user_picture,
+                    "edited": updated_at.is_some(),
+                    "deleted": deleted_at.is_some(),
+                }
+            }));
+        }
+
+        if let (Some(updated_at), None) = (updated_at, deleted_at) {
+            timeline_events.push(serde_json::json!({
+                "type": "comment_edited",
+                "timestamp": updated_at,
+                "data": {
+                    "comment_id": comment_id,
+                    "user_name": user_name,
+                }
+            }));
+        }
+
+        if let Some(deleted_at) = deleted_at {
+            timeline_events.push(serde_json::json!({
+                "type": "comment_deleted",
+                "timestamp": deleted_at,
+                "data": {
+                    "comment_id": comment_id,
+                    "user_name": user_name,
                 }
             }));
         }
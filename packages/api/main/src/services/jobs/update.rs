@@ -1,7 +1,8 @@
 //! Job update operations
 
+use crate::services::companies::link_job_to_company;
 use crate::services::job_statuses::get_status_by_id;
-use crate::services::jobs::{normalize_job_id, Job};
+use crate::services::jobs::{get_job, record_status_change, Job};
 use serde_json::Value;
 use worker::{D1Database, Request, Response};
 
@@ -55,6 +56,20 @@ pub async fn update_job(
             return Response::error("Invalid status_id", 400);
         }
 
+        // Previous status, to record the transition in the status history
+        let previous_status_id = db
+            .prepare("SELECT status_id FROM jobs WHERE id = ?")
+            .bind(&[id.clone().into()])?
+            .first::<Value>(None)
+            .await?
+            .and_then(|row| {
+                row.get("status_id")
+                    .and_then(|v| v.as_i64().or_else(|| v.as_f64().map(|f| f as i64)))
+            })
+            .map(|v| v as i32);
+
+        let company = job.company.clone();
+
         match (&job.location, &job.description) {
             (Some(location), Some(description)) => {
                 db.prepare(
@@ -113,27 +128,15 @@ pub async fn update_job(
                 .await?;
             }
         }
-    }
 
-    let result = db
-        .prepare(
-            "SELECT 
-                j.*, 
-                j.status_id,
-                js.name as status_name
-            FROM jobs j
-            LEFT JOIN job_statuses js ON j.status_id = js.id
-            WHERE j.id = ?",
-        )
-        .bind(&[id.into()])?
-        .first::<Value>(None)
-        .await?;
+        link_job_to_company(db, &id, &company).await?;
 
-    match result {
-        Some(mut updated_job) => {
-            normalize_job_id(&mut updated_job);
-            Response::from_json(&updated_job)
+        if let Some(previous) = previous_status_id {
+            if previous != status_id {
+                record_status_change(db, &id, Some(previous), status_id).await?;
+            }
         }
-        None => Response::error("Job not found", 404),
     }
+
+    get_job(db, id).await
 }
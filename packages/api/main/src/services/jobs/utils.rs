@@ -15,3 +15,15 @@ pub fn normalize_job_id(job: &mut Value) {
         }
     }
 }
+
+/// Convert the `tags` column (a JSON array string from `json_group_array`) into an array
+pub fn parse_job_tags(job: &mut Value) {
+    if let Some(tags_val) = job.get_mut("tags") {
+        let tags = tags_val
+            .as_str()
+            .and_then(|s| serde_json::from_str::<Value>(s).ok())
+            .filter(|v| v.is_array())
+            .unwrap_or_else(|| Value::Array(Vec::new()));
+        *tags_val = tags;
+    }
+}
@@ -1,6 +1,13 @@
+pub mod ai_eval;
+pub mod ai_review;
+pub mod ai_stats;
+pub mod companies;
+pub mod contact_history;
 pub mod db;
+pub mod job_sources;
 pub mod job_statuses;
 pub mod jobs;
 pub mod oidc;
 pub mod password;
 pub mod session;
+pub mod vcard;
@@ -0,0 +1,481 @@
+//! vCard import and export of email contacts
+//!
+//! Contacts are exported as vCard 3.0 (RFC 2426) or 4.0 (RFC 6350). LinkedIn
+//! and website are written as URL properties, with `TYPE=linkedin` on the
+//! LinkedIn one. Imports accept either version and merge into the user's
+//! existing contacts, keyed by the same normalized address as everywhere else.
+
+use crate::services::db::email_contacts::extract_email_address;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use worker::{D1Database, Headers, Response};
+
+/// Largest vCard file accepted for import (1 MB)
+pub const MAX_IMPORT_SIZE: usize = 1024 * 1024;
+
+/// Maximum line length in octets before folding
+const LINE_LIMIT: usize = 75;
+
+/// vCard format version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VCardVersion {
+    V3,
+    V4,
+}
+
+impl VCardVersion {
+    /// Parse a version as given in a query parameter ("3.0", "4", ...)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "3" | "3.0" => Some(Self::V3),
+            "4" | "4.0" => Some(Self::V4),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::V3 => "3.0",
+            Self::V4 => "4.0",
+        }
+    }
+}
+
+/// Contact fields stored in and read from vCards
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContactFields {
+    pub name: Option<String>,
+    pub linkedin: Option<String>,
+    pub website: Option<String>,
+}
+
+/// Contact to export
+#[derive(Debug, Clone, Default)]
+pub struct VCardContact {
+    pub email: String,
+    pub fields: ContactFields,
+    pub company_name: Option<String>,
+}
+
+/// A parsed vCard. One card can carry several email addresses.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VCard {
+    pub emails: Vec<String>,
+    pub fields: ContactFields,
+}
+
+/// Changes an import makes to the user's contacts
+#[derive(Debug, Default, PartialEq)]
+pub struct ImportPlan {
+    pub created: Vec<(String, ContactFields)>,
+    pub updated: Vec<(String, ContactFields)>,
+    pub unchanged: usize,
+    /// Cards without a usable email address
+    pub skipped: usize,
+}
+
+/// Import result returned to the client
+#[derive(Debug, Serialize)]
+pub struct ImportSummary {
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub skipped: usize,
+}
+
+fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ',' => escaped.push_str("\\,"),
+            ';' => escaped.push_str("\\;"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// Append a content line, folding it at 75 octets without splitting a
+/// character
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > LINE_LIMIT {
+            out.push_str("\r\n ");
+            // The leading space counts towards the next line
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+/// Structured name ("Family;Given;;;") from a display name, taking the last
+/// word as the family name
+fn structured_name(name: &str) -> String {
+    let mut words: Vec<&str> = name.split_whitespace().collect();
+    let family = if words.len() > 1 {
+        words.pop().unwrap_or_default()
+    } else {
+        ""
+    };
+    format!(
+        "{};{};;;",
+        escape_text(family),
+        escape_text(&words.join(" "))
+    )
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// Write contacts as vCards of the given version
+pub fn write_vcards(contacts: &[VCardContact], version: VCardVersion) -> String {
+    let mut out = String::new();
+    for contact in contacts {
+        let name = non_empty(&contact.fields.name).unwrap_or(&contact.email);
+
+        push_line(&mut out, "BEGIN:VCARD");
+        push_line(&mut out, &format!("VERSION:{}", version.as_str()));
+        push_line(&mut out, &format!("FN:{}", escape_text(name)));
+        push_line(&mut out, &format!("N:{}", structured_name(name)));
+        match version {
+            VCardVersion::V3 => {
+                push_line(&mut out, &format!("EMAIL;TYPE=INTERNET:{}", contact.email))
+            }
+            VCardVersion::V4 => push_line(&mut out, &format!("EMAIL:{}", contact.email)),
+        }
+        if let Some(company) = non_empty(&contact.company_name) {
+            push_line(&mut out, &format!("ORG:{}", escape_text(company)));
+        }
+        if let Some(linkedin) = non_empty(&contact.fields.linkedin) {
+            push_line(&mut out, &format!("URL;TYPE=linkedin:{}", linkedin));
+        }
+        if let Some(website) = non_empty(&contact.fields.website) {
+            push_line(&mut out, &format!("URL:{}", website));
+        }
+        push_line(&mut out, "END:VCARD");
+    }
+    out
+}
+
+/// Split at the first occurrence of `separator` outside double quotes
+fn split_unquoted(value: &str, separator: char) -> Option<(&str, &str)> {
+    let mut quoted = false;
+    for (i, c) in value.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c == separator && !quoted => return Some((&value[..i], &value[i + 1..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn is_linkedin(params: &str, value: &str) -> bool {
+    params.to_lowercase().contains("linkedin") || value.to_lowercase().contains("linkedin.com")
+}
+
+/// Display name from a structured name ("Family;Given;Additional;Prefix;Suffix")
+fn name_from_structured(value: &str) -> Option<String> {
+    let parts: Vec<String> = value.split(';').map(unescape_text).collect();
+    let order = [3, 1, 2, 0, 4];
+    let name = order
+        .iter()
+        .filter_map(|&i| parts.get(i))
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!name.is_empty()).then_some(name)
+}
+
+/// Parse vCards of any version. Properties other than FN, N, EMAIL, URL and
+/// X-SOCIALPROFILE are ignored.
+pub fn parse_vcards(text: &str) -> Vec<VCard> {
+    // Unfold continuation lines
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.split('\n') {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        match (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ => lines.push(raw.to_string()),
+        }
+    }
+
+    let mut cards = Vec::new();
+    let mut current: Option<(VCard, Option<String>)> = None;
+    for line in &lines {
+        let Some((head, value)) = split_unquoted(line, ':') else {
+            continue;
+        };
+        let (name, params) = split_unquoted(head, ';').unwrap_or((head, ""));
+        // Drop the group prefix ("item1.EMAIL")
+        let name = name.rsplit('.').next().unwrap_or(name).to_uppercase();
+        let value = value.trim();
+
+        match name.as_str() {
+            "BEGIN" if value.eq_ignore_ascii_case("VCARD") => {
+                current = Some((VCard::default(), None));
+            }
+            "END" if value.eq_ignore_ascii_case("VCARD") => {
+                if let Some((mut card, structured)) = current.take() {
+                    if card.fields.name.is_none() {
+                        card.fields.name = structured;
+                    }
+                    cards.push(card);
+                }
+            }
+            _ => {
+                let Some((card, structured)) = current.as_mut() else {
+                    continue;
+                };
+                match name.as_str() {
+                    "FN" => {
+                        let full_name = unescape_text(value).trim().to_string();
+                        if !full_name.is_empty() {
+                            card.fields.name = Some(full_name);
+                        }
+                    }
+                    "N" => *structured = name_from_structured(value),
+                    "EMAIL" => {
+                        let address = value
+                            .strip_prefix("mailto:")
+                            .or_else(|| value.strip_prefix("MAILTO:"))
+                            .unwrap_or(value);
+                        let email = extract_email_address(address);
+                        if !email.is_empty() && !card.emails.contains(&email) {
+                            card.emails.push(email);
+                        }
+                    }
+                    "URL" | "X-SOCIALPROFILE" if !value.is_empty() => {
+                        let url = unescape_text(value);
+                        if is_linkedin(params, value) {
+                            card.fields.linkedin.get_or_insert(url);
+                        } else if name == "URL" {
+                            card.fields.website.get_or_insert(url);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    cards
+}
+
+/// Merge imported fields over an existing contact. Fields present in the
+/// vCard replace the stored ones; missing fields keep their current value.
+pub fn merge_contact(existing: &ContactFields, imported: &ContactFields) -> ContactFields {
+    let pick = |imported: &Option<String>, existing: &Option<String>| {
+        non_empty(imported)
+            .map(|v| v.to_string())
+            .or_else(|| existing.clone())
+    };
+    ContactFields {
+        name: pick(&imported.name, &existing.name),
+        linkedin: pick(&imported.linkedin, &existing.linkedin),
+        website: pick(&imported.website, &existing.website),
+    }
+}
+
+/// Work out which contacts an import creates and updates. When an address
+/// appears in several cards, later cards merge over earlier ones.
+pub fn plan_import(existing: &HashMap<String, ContactFields>, cards: &[VCard]) -> ImportPlan {
+    let mut plan = ImportPlan::default();
+    let mut merged: HashMap<String, ContactFields> = HashMap::new();
+    let mut order: Vec<String> = Vec::new();
+
+    for card in cards {
+        if card.emails.is_empty() {
+            plan.skipped += 1;
+            continue;
+        }
+        for email in &card.emails {
+            let current = match merged.get(email) {
+                Some(fields) => fields.clone(),
+                None => {
+                    order.push(email.clone());
+                    existing.get(email).cloned().unwrap_or_default()
+                }
+            };
+            merged.insert(email.clone(), merge_contact(&current, &card.fields));
+        }
+    }
+
+    for email in order {
+        let fields = merged.remove(&email).unwrap_or_default();
+        match existing.get(&email) {
+            None => plan.created.push((email, fields)),
+            Some(stored) if *stored == fields => plan.unchanged += 1,
+            Some(_) => plan.updated.push((email, fields)),
+        }
+    }
+    plan
+}
+
+fn text(row: &Value, key: &str) -> Option<String> {
+    row.get(key).and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+fn fields_from_row(row: &Value) -> ContactFields {
+    ContactFields {
+        name: text(row, "name"),
+        linkedin: text(row, "linkedin"),
+        website: text(row, "website"),
+    }
+}
+
+/// Download one contact, or all of the user's non-system contacts, as a
+/// .vcf file
+pub async fn export_contacts(
+    db: &D1Database,
+    user_id: &str,
+    email: Option<&str>,
+    version: VCardVersion,
+) -> Result<Response, worker::Error> {
+    let query = "SELECT ec.email, ec.name, ec.linkedin, ec.website, c.name as company_name
+                 FROM email_contacts ec
+                 LEFT JOIN companies c ON c.id = ec.company_id
+                 WHERE ec.user_id = ?";
+
+    let rows: Vec<Value> = match email {
+        Some(email) => {
+            let email = extract_email_address(email);
+            db.prepare(format!("{} AND ec.email = ?", query))
+                .bind(&[user_id.into(), email.into()])?
+                .all()
+                .await?
+                .results()?
+        }
+        None => db
+            .prepare(format!("{} AND ec.is_system = 0 ORDER BY ec.email", query))
+            .bind(&[user_id.into()])?
+            .all()
+            .await?
+            .results()?,
+    };
+    if email.is_some() && rows.is_empty() {
+        return Response::error("Contact not found", 404);
+    }
+
+    let contacts: Vec<VCardContact> = rows
+        .iter()
+        .filter_map(|row| {
+            Some(VCardContact {
+                email: text(row, "email")?,
+                fields: fields_from_row(row),
+                company_name: text(row, "company_name"),
+            })
+        })
+        .collect();
+
+    let file_name = match contacts.first() {
+        Some(contact) if email.is_some() => format!("{}.vcf", contact.email),
+        _ => "contacts.vcf".to_string(),
+    };
+
+    let headers = Headers::new();
+    headers.set("Content-Type", "text/vcard; charset=utf-8")?;
+    headers.set(
+        "Content-Disposition",
+        &format!("attachment; filename=\"{}\"", file_name),
+    )?;
+    headers.set("Cache-Control", "private, no-store")?;
+
+    Ok(Response::ok(write_vcards(&contacts, version))?.with_headers(headers))
+}
+
+/// Import a vCard file into the user's contacts
+pub async fn import_contacts(
+    db: &D1Database,
+    user_id: &str,
+    body: &str,
+) -> Result<Response, worker::Error> {
+    if body.len() > MAX_IMPORT_SIZE {
+        return Response::error("vCard file is larger than 1 MB", 413);
+    }
+
+    let cards = parse_vcards(body);
+    if cards.is_empty() {
+        return Response::error("No vCards found", 400);
+    }
+
+    let result = db
+        .prepare("SELECT email, name, linkedin, website FROM email_contacts WHERE user_id = ?")
+        .bind(&[user_id.into()])?
+        .all()
+        .await?;
+    let rows: Vec<Value> = result.results()?;
+    let existing: HashMap<String, ContactFields> = rows
+        .iter()
+        .filter_map(|row| Some((text(row, "email")?, fields_from_row(row))))
+        .collect();
+
+    let plan = plan_import(&existing, &cards);
+
+    let mut statements = Vec::new();
+    for (email, fields) in &plan.created {
+        statements.push(
+            db.prepare(
+                "INSERT INTO email_contacts (email, user_id, name, linkedin, website, is_system, created_at, updated_at)
+                 VALUES (?, ?, ?, ?, ?, 0, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)",
+            )
+            .bind(&[
+                email.as_str().into(),
+                user_id.into(),
+                fields.name.as_deref().into(),
+                fields.linkedin.as_deref().into(),
+                fields.website.as_deref().into(),
+            ])?,
+        );
+    }
+    for (email, fields) in &plan.updated {
+        statements.push(
+            db.prepare(
+                "UPDATE email_contacts SET name = ?, linkedin = ?, website = ?, updated_at = CURRENT_TIMESTAMP
+                 WHERE email = ? AND user_id = ?",
+            )
+            .bind(&[
+                fields.name.as_deref().into(),
+                fields.linkedin.as_deref().into(),
+                fields.website.as_deref().into(),
+                email.as_str().into(),
+                user_id.into(),
+            ])?,
+        );
+    }
+    if !statements.is_empty() {
+        db.batch(statements).await?;
+    }
+
+    Response::from_json(&ImportSummary {
+        created: plan.created.len(),
+        updated: plan.updated.len(),
+        unchanged: plan.unchanged,
+        skipped: plan.skipped,
+    })
+}
@@ -0,0 +1,132 @@
+//! Tests for prompt evaluation scoring
+
+use api_main::services::ai_eval::{build_report, normalize_field, sample_evenly, EvalCase};
+use serde_json::{json, Map, Value};
+
+fn fields(value: Value) -> Map<String, Value> {
+    value.as_object().cloned().unwrap_or_default()
+}
+
+fn case(expected: &str, predicted: &str) -> EvalCase {
+    EvalCase {
+        expected_category: expected.to_string(),
+        predicted_category: predicted.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_accuracy_and_confusion_matrix() {
+    let cases = vec![
+        case("interview", "interview"),
+        case("interview", "other"),
+        case("rejection", "rejection"),
+        case("new_job", "recruiter_spam"),
+    ];
+
+    let report = build_report(&cases);
+    assert_eq!(report.total, 4);
+    assert_eq!(report.correct, 2);
+    assert_eq!(report.accuracy, 0.5);
+
+    // Unknown categories are added after the known ones
+    assert_eq!(
+        report.labels.last().map(String::as_str),
+        Some("recruiter_spam")
+    );
+    let index = |label: &str| report.labels.iter().position(|l| l == label).unwrap();
+    assert_eq!(
+        report.confusion_matrix[index("interview")][index("interview")],
+        1
+    );
+    assert_eq!(
+        report.confusion_matrix[index("interview")][index("other")],
+        1
+    );
+    assert_eq!(
+        report.confusion_matrix[index("rejection")][index("rejection")],
+        1
+    );
+    assert_eq!(
+        report.confusion_matrix[index("new_job")][index("recruiter_spam")],
+        1
+    );
+    let total: u32 = report.confusion_matrix.iter().flatten().sum();
+    assert_eq!(total, 4);
+}
+
+#[test]
+fn test_field_precision_and_recall() {
+    let cases = vec![
+        EvalCase {
+            expected_fields: fields(json!({"company": "Acme", "location": null, "remote": true})),
+            predicted_fields: fields(
+                json!({"company": " acme ", "location": "Berlin", "remote": true}),
+            ),
+            ..case("interview", "interview")
+        },
+        EvalCase {
+            expected_fields: fields(json!({"company": "Beta", "remote": false})),
+            predicted_fields: fields(json!({"company": "Gamma", "remote": null})),
+            ..case("interview", "interview")
+        },
+        EvalCase {
+            // Unlabelled fields aren't scored
+            expected_fields: fields(json!({"company": "Delta"})),
+            predicted_fields: fields(json!({"company": "Delta", "job_title": "Engineer"})),
+            ..case("rejection", "rejection")
+        },
+    ];
+
+    let report = build_report(&cases);
+
+    let company = &report.fields["company"];
+    assert_eq!(company.true_positives, 2);
+    assert_eq!(company.false_positives, 1);
+    assert_eq!(company.false_negatives, 1);
+    assert_eq!(company.precision, Some(2.0 / 3.0));
+    assert_eq!(company.recall, Some(2.0 / 3.0));
+
+    let location = &report.fields["location"];
+    assert_eq!(location.false_positives, 1);
+    assert_eq!(location.precision, Some(0.0));
+    assert_eq!(location.recall, None);
+
+    let remote = &report.fields["remote"];
+    assert_eq!(remote.true_positives, 1);
+    assert_eq!(remote.false_negatives, 1);
+    assert_eq!(remote.recall, Some(0.5));
+
+    assert!(!report.fields.contains_key("job_title"));
+}
+
+#[test]
+fn test_empty_run_and_normalization() {
+    let report = build_report(&[]);
+    assert_eq!(report.total, 0);
+    assert_eq!(report.accuracy, 0.0);
+    assert!(report.fields.is_empty());
+
+    assert_eq!(
+        normalize_field(Some(&json!("  Acme Corp "))),
+        Some("acme corp".to_string())
+    );
+    assert_eq!(normalize_field(Some(&json!(""))), None);
+    assert_eq!(normalize_field(Some(&json!("null"))), None);
+    assert_eq!(normalize_field(Some(&Value::Null)), None);
+    assert_eq!(
+        normalize_field(Some(&json!(false))),
+        Some("false".to_string())
+    );
+    assert_eq!(normalize_field(None), None);
+}
+
+#[test]
+fn test_sample_spreads_over_the_dataset() {
+    let items: Vec<u32> = (0..10).collect();
+    assert_eq!(sample_evenly(&items, 20), items);
+    assert_eq!(sample_evenly(&items, 10), items);
+    assert_eq!(sample_evenly(&items, 5), vec![0, 2, 4, 6, 8]);
+    assert_eq!(sample_evenly(&items, 3), vec![0, 3, 6]);
+    assert!(sample_evenly(&items, 0).is_empty());
+}
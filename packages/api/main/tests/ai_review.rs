@@ -0,0 +1,50 @@
+//! Tests for comparing reviewed AI results with predictions
+
+use api_main::services::ai_review::{diff_review, label_fields};
+use serde_json::{json, Map, Value};
+
+fn fields(value: Value) -> Map<String, Value> {
+    value.as_object().cloned().unwrap_or_default()
+}
+
+#[test]
+fn test_unchanged_review_is_accepted() {
+    let predicted = fields(json!({"company": "Acme", "job_title": "Engineer", "location": ""}));
+    let reviewed = fields(json!({"company": " acme ", "job_title": "Engineer"}));
+
+    let diff = diff_review(Some("interview"), &predicted, "interview", &reviewed);
+    assert!(!diff.category_changed);
+    assert!(diff.corrected_fields.is_empty());
+    assert_eq!(diff.status(), "accepted");
+}
+
+#[test]
+fn test_changed_category_and_fields_are_corrections() {
+    let predicted = fields(json!({"company": "Acme", "remote": false}));
+    let reviewed = fields(json!({"company": "Globex", "remote": false, "location": "Berlin"}));
+
+    let diff = diff_review(Some("other"), &predicted, "interview", &reviewed);
+    assert!(diff.category_changed);
+    assert_eq!(diff.corrected_fields, vec!["company", "location"]);
+    assert_eq!(diff.status(), "corrected");
+
+    let diff = diff_review(None, &predicted, "other", &predicted);
+    assert_eq!(diff.status(), "corrected");
+}
+
+#[test]
+fn test_label_fields_covers_every_field() {
+    let labelled = label_fields(&fields(json!({
+        "company": "Acme",
+        "location": "  ",
+        "remote": true,
+        "salary": "100k"
+    })));
+
+    assert_eq!(labelled.len(), 7);
+    assert_eq!(labelled["company"], json!("Acme"));
+    assert_eq!(labelled["location"], Value::Null);
+    assert_eq!(labelled["remote"], json!(true));
+    assert_eq!(labelled["job_title"], Value::Null);
+    assert!(!labelled.contains_key("salary"));
+}
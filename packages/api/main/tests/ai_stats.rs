@@ -0,0 +1,85 @@
+//! Tests for the daily AI stats rollup
+
+use api_main::services::ai_stats::{rollup_days, summarize_days, AiDailyStats, CategoryDayRow};
+
+fn row(
+    date: &str,
+    category: Option<&str>,
+    results: u32,
+    confidence_sum: f64,
+    needs_review: u32,
+) -> CategoryDayRow {
+    CategoryDayRow {
+        date: date.to_string(),
+        category: category.map(|c| c.to_string()),
+        results,
+        confidence_sum: Some(confidence_sum),
+        confidence_count: results,
+        needs_review,
+    }
+}
+
+#[test]
+fn test_rollup_combines_categories_per_day() {
+    let rows = vec![
+        row("2026-10-02", Some("interview"), 2, 1.8, 0),
+        row("2026-10-01", Some("rejection"), 1, 0.5, 1),
+        row("2026-10-01", Some("interview"), 3, 2.7, 0),
+        row("2026-10-01", None, 1, 0.2, 1),
+    ];
+
+    let days = rollup_days(&rows);
+    assert_eq!(days.len(), 2);
+
+    let first = &days[0];
+    assert_eq!(first.date, "2026-10-01");
+    assert_eq!(first.emails_processed, 5);
+    assert_eq!(first.needs_review_count, 2);
+    assert_eq!(first.category_breakdown["interview"], 3);
+    assert_eq!(first.category_breakdown["rejection"], 1);
+    assert_eq!(first.category_breakdown["other"], 1);
+    assert!((first.avg_confidence.unwrap() - 0.68).abs() < 1e-9);
+
+    assert_eq!(days[1].date, "2026-10-02");
+    assert_eq!(days[1].emails_processed, 2);
+}
+
+#[test]
+fn test_rollup_without_confidences() {
+    let rows = vec![CategoryDayRow {
+        date: "2026-10-01".to_string(),
+        category: Some("other".to_string()),
+        results: 2,
+        ..Default::default()
+    }];
+
+    let days = rollup_days(&rows);
+    assert_eq!(days[0].avg_confidence, None);
+    assert!(rollup_days(&[]).is_empty());
+}
+
+#[test]
+fn test_summarize_weights_confidence_by_volume() {
+    let days = vec![
+        AiDailyStats {
+            date: "2026-10-01".to_string(),
+            emails_processed: 3,
+            avg_confidence: Some(0.9),
+            needs_review_count: 0,
+            category_breakdown: [("interview".to_string(), 3)].into_iter().collect(),
+        },
+        AiDailyStats {
+            date: "2026-10-02".to_string(),
+            emails_processed: 1,
+            avg_confidence: Some(0.5),
+            needs_review_count: 1,
+            category_breakdown: [("interview".to_string(), 1)].into_iter().collect(),
+        },
+    ];
+
+    let total = summarize_days(&days);
+    assert_eq!(total.emails_processed, 4);
+    assert_eq!(total.needs_review_count, 1);
+    assert_eq!(total.category_breakdown["interview"], 4);
+    assert!((total.avg_confidence.unwrap() - 0.8).abs() < 1e-9);
+}
@@ -0,0 +1,87 @@
+//! Tests for company domain detection
+
+use api_main::services::companies::{email_domain, infer_company_domain, normalize_domain};
+use api_main::services::db::system_email_domains::SystemEmailDomain;
+
+fn domain(pattern: &str) -> SystemEmailDomain {
+    SystemEmailDomain {
+        id: pattern.to_string(),
+        domain_pattern: pattern.to_string(),
+        name: None,
+        source_id: None,
+    }
+}
+
+#[test]
+fn test_email_domain() {
+    assert_eq!(
+        email_domain("Jane Doe <Jane@Acme.com>"),
+        Some("acme.com".to_string())
+    );
+    assert_eq!(
+        email_domain("jane@mail.acme.co.uk"),
+        Some("mail.acme.co.uk".to_string())
+    );
+    assert_eq!(email_domain("Jane Doe"), None);
+    assert_eq!(email_domain("jane@localhost"), None);
+}
+
+#[test]
+fn test_normalize_domain() {
+    assert_eq!(normalize_domain("acme.com"), Some("acme.com".to_string()));
+    assert_eq!(
+        normalize_domain("https://www.Acme.com/careers?ref=x"),
+        Some("acme.com".to_string())
+    );
+    assert_eq!(
+        normalize_domain("jobs@acme.com"),
+        Some("acme.com".to_string())
+    );
+    assert_eq!(
+        normalize_domain("acme.com:443/"),
+        Some("acme.com".to_string())
+    );
+    assert_eq!(normalize_domain("acme"), None);
+    assert_eq!(normalize_domain("not a domain.com"), None);
+    assert_eq!(normalize_domain(""), None);
+}
+
+#[test]
+fn test_infer_company_domain_picks_most_common() {
+    let senders = [
+        "Recruiter <jane@acme.com>",
+        "bob@acme.com",
+        "Someone <someone@partner.io>",
+    ];
+    assert_eq!(
+        infer_company_domain(&senders, &[]),
+        Some("acme.com".to_string())
+    );
+}
+
+#[test]
+fn test_infer_company_domain_skips_free_mail_and_system_domains() {
+    let domains = vec![domain("*.greenhouse.io"), domain("noreply.*")];
+    let senders = [
+        "Jane <jane@gmail.com>",
+        "Acme via Greenhouse <no-reply@us.greenhouse.io>",
+        "noreply@acme.com",
+        "Hiring <hiring@acme.com>",
+    ];
+    assert_eq!(
+        infer_company_domain(&senders, &domains),
+        Some("acme.com".to_string())
+    );
+
+    let senders = ["jane@gmail.com", "noreply@acme.com"];
+    assert_eq!(infer_company_domain(&senders, &domains), None);
+}
+
+#[test]
+fn test_infer_company_domain_breaks_ties_alphabetically() {
+    let senders = ["a@zeta.com", "b@acme.com"];
+    assert_eq!(
+        infer_company_domain(&senders, &[]),
+        Some("acme.com".to_string())
+    );
+}
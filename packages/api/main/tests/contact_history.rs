@@ -0,0 +1,83 @@
+//! Tests for stale contact detection
+
+use api_main::services::contact_history::{find_stale_contacts, ContactMessage};
+
+const USER: &str = "me@example.com";
+const NOW: f64 = 2_460_000.0;
+
+fn message(from: &str, to: &str, days_ago: f64, job_id: Option<&str>) -> ContactMessage {
+    ContactMessage {
+        from: from.to_string(),
+        to: to.to_string(),
+        cc: String::new(),
+        date: format!("{} days ago", days_ago),
+        day: NOW - days_ago,
+        job_id: job_id.map(|s| s.to_string()),
+    }
+}
+
+#[test]
+fn test_unanswered_recruiter_is_stale() {
+    let messages = vec![message(
+        "Jane Recruiter <Jane@Acme.com>",
+        USER,
+        10.0,
+        Some("job-1"),
+    )];
+
+    let stale = find_stale_contacts(&messages, USER, NOW, 7);
+    assert_eq!(stale.len(), 1);
+    assert_eq!(stale[0].email, "jane@acme.com");
+    assert_eq!(stale[0].days_waiting, 10);
+    assert_eq!(stale[0].job_id.as_deref(), Some("job-1"));
+    assert_eq!(stale[0].last_replied, None);
+}
+
+#[test]
+fn test_recent_or_answered_contacts_are_not_stale() {
+    let messages = vec![
+        // Too recent
+        message("bob@beta.io", USER, 3.0, Some("job-2")),
+        // Answered after their last email, through cc
+        message("jane@acme.com", USER, 10.0, Some("job-1")),
+        ContactMessage {
+            cc: "Jane <jane@acme.com>".to_string(),
+            ..message(USER, "someone@acme.com", 9.0, None)
+        },
+    ];
+
+    assert!(find_stale_contacts(&messages, USER, NOW, 7).is_empty());
+}
+
+#[test]
+fn test_reply_before_last_email_still_stale() {
+    let messages = vec![
+        message("jane@acme.com", USER, 30.0, Some("job-1")),
+        message("Me <ME@example.com>", "jane@acme.com", 20.0, Some("job-1")),
+        message("jane@acme.com", USER, 14.0, Some("job-1")),
+    ];
+
+    let stale = find_stale_contacts(&messages, USER, NOW, 7);
+    assert_eq!(stale.len(), 1);
+    assert_eq!(stale[0].days_waiting, 14);
+    assert_eq!(stale[0].last_replied.as_deref(), Some("20 days ago"));
+}
+
+#[test]
+fn test_emails_not_about_a_job_are_ignored() {
+    let messages = vec![message("friend@example.org", USER, 30.0, None)];
+
+    assert!(find_stale_contacts(&messages, USER, NOW, 7).is_empty());
+}
+
+#[test]
+fn test_stale_contacts_are_sorted_oldest_first() {
+    let messages = vec![
+        message("a@acme.com", USER, 8.0, Some("job-1")),
+        message("b@beta.io", USER, 20.0, Some("job-2")),
+    ];
+
+    let stale = find_stale_contacts(&messages, USER, NOW, 7);
+    let emails: Vec<&str> = stale.iter().map(|s| s.email.as_str()).collect();
+    assert_eq!(emails, vec!["b@beta.io", "a@acme.com"]);
+}
@@ -0,0 +1,199 @@
+//! Tests for application funnel analytics
+
+use api_main::services::jobs::analytics::{
+    compute_funnel_analytics, is_valid_date, median, week_start, AnalyticsEmail, AnalyticsJob,
+    AnalyticsTransition,
+};
+
+// 2024-01-01 00:00 UTC (a Monday) as a julian day
+const JAN_1: f64 = 2460310.5;
+
+fn stages() -> Vec<(i32, String, String)> {
+    [
+        (100, "open", "Open"),
+        (200, "applied", "Applied"),
+        (300, "interviewing", "Interviewing"),
+        (400, "offer", "Offer"),
+    ]
+    .iter()
+    .map(|(id, name, display)| (*id, name.to_string(), display.to_string()))
+    .collect()
+}
+
+fn job(id: &str, status_id: i32, created_at: f64) -> AnalyticsJob {
+    AnalyticsJob {
+        id: id.to_string(),
+        status_id: Some(status_id),
+        source: None,
+        created_at,
+    }
+}
+
+fn transition(job_id: &str, to_status_id: i32, changed_at: f64) -> AnalyticsTransition {
+    AnalyticsTransition {
+        job_id: job_id.to_string(),
+        to_status_id,
+        changed_at,
+    }
+}
+
+fn email(job_id: &str, date: f64, inbound: bool) -> AnalyticsEmail {
+    AnalyticsEmail {
+        job_id: job_id.to_string(),
+        date,
+        inbound,
+    }
+}
+
+#[test]
+fn test_is_valid_date() {
+    assert!(is_valid_date("2024-01-31"));
+    assert!(!is_valid_date("2024-13-01"));
+    assert!(!is_valid_date("2024-1-01"));
+    assert!(!is_valid_date("2024-01-01'; --"));
+    assert!(!is_valid_date(""));
+}
+
+#[test]
+fn test_median() {
+    assert_eq!(median(&[]), None);
+    assert_eq!(median(&[3.0, 1.0, 2.0]), Some(2.0));
+    assert_eq!(median(&[4.0, 1.0, 2.0, 3.0]), Some(2.5));
+}
+
+#[test]
+fn test_week_start() {
+    assert_eq!(week_start(JAN_1), "2024-01-01");
+    assert_eq!(week_start(JAN_1 + 6.9), "2024-01-01");
+    assert_eq!(week_start(JAN_1 + 7.0), "2024-01-08");
+    assert_eq!(week_start(JAN_1 - 0.25), "2023-12-25");
+}
+
+#[test]
+fn test_funnel_conversion_and_time_in_stage() {
+    let jobs = vec![
+        job("a", 300, JAN_1),
+        job("b", 500, JAN_1),
+        job("c", 100, JAN_1),
+        job("d", 400, JAN_1),
+    ];
+    let transitions = vec![
+        // a: open -> applied after 2 days -> interviewing after 4 more
+        transition("a", 100, JAN_1),
+        transition("a", 200, JAN_1 + 2.0),
+        transition("a", 300, JAN_1 + 6.0),
+        // b: applied on creation, rejected after 10 days
+        transition("b", 200, JAN_1),
+        transition("b", 500, JAN_1 + 10.0),
+        // c: never applied
+        transition("c", 100, JAN_1),
+        // d: created straight into the offer stage
+        transition("d", 400, JAN_1),
+    ];
+
+    let analytics = compute_funnel_analytics(&stages(), &jobs, &transitions, &[]);
+
+    assert_eq!(analytics.total_jobs, 4);
+    assert_eq!(analytics.applied, 3);
+    assert_eq!(analytics.rejected, 1);
+
+    let reached: Vec<usize> = analytics.stages.iter().map(|s| s.reached).collect();
+    assert_eq!(reached, vec![4, 3, 2, 1]);
+    assert_eq!(analytics.stages[0].conversion_rate, None);
+    assert_eq!(analytics.stages[1].conversion_rate, Some(0.75));
+    assert_eq!(analytics.stages[2].conversion_rate, Some(0.67));
+
+    assert_eq!(analytics.stages[0].median_days_in_stage, Some(2.0));
+    assert_eq!(analytics.stages[1].median_days_in_stage, Some(7.0));
+    assert_eq!(analytics.stages[2].median_days_in_stage, None);
+}
+
+#[test]
+fn test_response_rate_and_weekly_volume() {
+    let jobs = vec![
+        job("a", 200, JAN_1),
+        job("b", 200, JAN_1 + 14.0),
+        job("c", 100, JAN_1),
+    ];
+    let transitions = vec![
+        transition("a", 200, JAN_1),
+        transition("b", 200, JAN_1 + 14.0),
+        transition("c", 100, JAN_1),
+    ];
+    let emails = vec![
+        // Sent by the user, not a response
+        email("a", JAN_1 + 1.0, false),
+        email("a", JAN_1 + 3.0, true),
+        email("a", JAN_1 + 5.0, true),
+        // Received before applying
+        email("b", JAN_1 + 13.0, true),
+        // Job was never applied to
+        email("c", JAN_1 + 1.0, true),
+    ];
+
+    let analytics = compute_funnel_analytics(&stages(), &jobs, &transitions, &emails);
+
+    assert_eq!(analytics.applied, 2);
+    assert_eq!(analytics.responded, 1);
+    assert_eq!(analytics.response_rate, Some(0.5));
+    assert_eq!(analytics.median_days_to_first_response, Some(3.0));
+
+    let weeks: Vec<(&str, usize)> = analytics
+        .weekly_volume
+        .iter()
+        .map(|w| (w.week_start.as_str(), w.applications))
+        .collect();
+    assert_eq!(
+        weeks,
+        vec![("2024-01-01", 1), ("2024-01-08", 0), ("2024-01-15", 1)]
+    );
+}
+
+#[test]
+fn test_empty_analytics() {
+    let analytics = compute_funnel_analytics(&stages(), &[], &[], &[]);
+    assert_eq!(analytics.total_jobs, 0);
+    assert_eq!(analytics.response_rate, None);
+    assert!(analytics.weekly_volume.is_empty());
+    assert!(analytics.stages.iter().all(|s| s.conversion_rate.is_none()));
+}
+
+#[test]
+fn test_breakdown_by_source() {
+    let mut jobs = vec![
+        job("a", 300, JAN_1),
+        job("b", 200, JAN_1),
+        job("c", 200, JAN_1),
+    ];
+    jobs[0].source = Some("Referral".to_string());
+    jobs[1].source = Some("LinkedIn".to_string());
+    let transitions = vec![
+        transition("a", 200, JAN_1),
+        transition("a", 300, JAN_1 + 3.0),
+        transition("b", 200, JAN_1),
+        transition("c", 200, JAN_1),
+    ];
+    let emails = vec![email("a", JAN_1 + 2.0, true)];
+
+    let analytics = compute_funnel_analytics(&stages(), &jobs, &transitions, &emails);
+
+    assert_eq!(analytics.by_source.len(), 3);
+    let referral = analytics
+        .by_source
+        .iter()
+        .find(|s| s.source == "Referral")
+        .unwrap();
+    assert_eq!(referral.jobs, 1);
+    assert_eq!(referral.response_rate, Some(1.0));
+    assert_eq!(referral.stages_reached, vec![1, 1, 1, 0]);
+
+    let linkedin = analytics
+        .by_source
+        .iter()
+        .find(|s| s.source == "LinkedIn")
+        .unwrap();
+    assert_eq!(linkedin.response_rate, Some(0.0));
+    assert_eq!(linkedin.stages_reached, vec![1, 1, 0, 0]);
+
+    assert!(analytics.by_source.iter().any(|s| s.source == "Unknown"));
+}
@@ -0,0 +1,67 @@
+//! Tests for job attachment validation and key building
+
+use api_main::services::db::job_attachments::{
+    build_attachment_key, content_disposition, sanitize_file_name, validate_attachment,
+    MAX_ATTACHMENT_SIZE,
+};
+
+#[test]
+fn test_allowed_content_types() {
+    assert!(validate_attachment("application/pdf", 1024).is_ok());
+    assert!(validate_attachment("Application/PDF; charset=binary", 1024).is_ok());
+    assert!(validate_attachment("text/plain; charset=utf-8", 10).is_ok());
+    assert!(validate_attachment("application/x-msdownload", 1024).is_err());
+    assert!(validate_attachment("", 1024).is_err());
+}
+
+#[test]
+fn test_size_limits() {
+    assert!(validate_attachment("application/pdf", 0).is_err());
+    assert!(validate_attachment("application/pdf", MAX_ATTACHMENT_SIZE).is_ok());
+    assert!(validate_attachment("application/pdf", MAX_ATTACHMENT_SIZE + 1).is_err());
+}
+
+#[test]
+fn test_sanitize_file_name() {
+    assert_eq!(sanitize_file_name("resume.pdf"), "resume.pdf");
+    assert_eq!(sanitize_file_name("../../etc/passwd"), "passwd");
+    assert_eq!(
+        sanitize_file_name("C:\\Users\\me\\cover.docx"),
+        "cover.docx"
+    );
+    assert_eq!(
+        sanitize_file_name("my \"best\" resume.pdf"),
+        "my best resume.pdf"
+    );
+    assert_eq!(sanitize_file_name(""), "attachment");
+    assert_eq!(sanitize_file_name(".."), "attachment");
+}
+
+#[test]
+fn test_attachment_keys_are_namespaced_per_user() {
+    let key = build_attachment_key("user-1", "job-1", "att-1");
+    assert_eq!(key, "users/user-1/jobs/job-1/att-1");
+    assert_ne!(key, build_attachment_key("user-2", "job-1", "att-1"));
+}
+
+#[test]
+fn test_content_disposition() {
+    assert_eq!(
+        content_disposition("resume.pdf"),
+        "attachment; filename=\"resume.pdf\"; filename*=UTF-8''resume.pdf"
+    );
+    // Quotes and line breaks can't end the header value or start a new header
+    assert_eq!(
+        content_disposition("a\"b\r\nSet-Cookie: x.pdf"),
+        "attachment; filename=\"a_b__Set-Cookie: x.pdf\"; filename*=UTF-8''a%22b%0D%0ASet-Cookie%3A%20x.pdf"
+    );
+    // Non-ASCII names keep their full name in filename*
+    assert_eq!(
+        content_disposition("Lebenslauf Müller.pdf"),
+        "attachment; filename=\"Lebenslauf M_ller.pdf\"; filename*=UTF-8''Lebenslauf%20M%C3%BCller.pdf"
+    );
+    assert_eq!(
+        content_disposition("履歴書"),
+        "attachment; filename=\"___\"; filename*=UTF-8''%E5%B1%A5%E6%AD%B4%E6%9B%B8"
+    );
+}
@@ -0,0 +1,93 @@
+//! Tests for bulk job request parsing and validation
+
+use api_main::services::jobs::bulk::{
+    normalize_tags, validate_bulk_request, BulkAction, BulkJobsRequest, MAX_BULK_JOBS,
+    MAX_TAG_LENGTH,
+};
+
+fn parse(json: &str) -> BulkJobsRequest {
+    serde_json::from_str(json).expect("valid bulk request")
+}
+
+#[test]
+fn test_parse_actions() {
+    let request = parse(r#"{"job_ids": ["a"], "action": "set_status", "status_id": 200}"#);
+    assert_eq!(request.action, BulkAction::SetStatus { status_id: 200 });
+
+    let request = parse(r#"{"job_ids": ["a"], "action": "add_tags", "tags": ["remote"]}"#);
+    assert_eq!(
+        request.action,
+        BulkAction::AddTags {
+            tags: vec!["remote".to_string()]
+        }
+    );
+
+    assert_eq!(
+        parse(r#"{"job_ids": ["a"], "action": "archive"}"#).action,
+        BulkAction::Archive
+    );
+    assert_eq!(
+        parse(r#"{"job_ids": ["a"], "action": "delete"}"#).action,
+        BulkAction::Delete
+    );
+    assert_eq!(
+        parse(r#"{"job_ids": ["a"], "action": "restore"}"#).action,
+        BulkAction::Restore
+    );
+    assert_eq!(
+        parse(r#"{"job_ids": ["a"], "action": "purge"}"#).action,
+        BulkAction::Purge
+    );
+
+    assert!(
+        serde_json::from_str::<BulkJobsRequest>(r#"{"job_ids": ["a"], "action": "explode"}"#)
+            .is_err()
+    );
+    assert!(serde_json::from_str::<BulkJobsRequest>(
+        r#"{"job_ids": ["a"], "action": "set_status"}"#
+    )
+    .is_err());
+}
+
+#[test]
+fn test_validate_job_ids() {
+    let request = parse(r#"{"job_ids": ["a", " b ", "a", ""], "action": "archive"}"#);
+    assert_eq!(
+        validate_bulk_request(&request).unwrap(),
+        vec!["a".to_string(), "b".to_string()]
+    );
+
+    let request = parse(r#"{"job_ids": [], "action": "archive"}"#);
+    assert!(validate_bulk_request(&request).is_err());
+
+    let request = BulkJobsRequest {
+        job_ids: (0..=MAX_BULK_JOBS).map(|i| i.to_string()).collect(),
+        action: BulkAction::Delete,
+    };
+    assert!(validate_bulk_request(&request).is_err());
+}
+
+#[test]
+fn test_validate_tags_required() {
+    let request = parse(r#"{"job_ids": ["a"], "action": "remove_tags", "tags": ["  "]}"#);
+    assert!(validate_bulk_request(&request).is_err());
+}
+
+#[test]
+fn test_normalize_tags() {
+    let tags = vec![
+        " Remote ".to_string(),
+        "remote".to_string(),
+        "Dream  Job".to_string(),
+        "".to_string(),
+        "x".repeat(MAX_TAG_LENGTH + 10),
+    ];
+    assert_eq!(
+        normalize_tags(&tags),
+        vec![
+            "remote".to_string(),
+            "dream-job".to_string(),
+            "x".repeat(MAX_TAG_LENGTH),
+        ]
+    );
+}
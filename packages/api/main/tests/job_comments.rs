@@ -0,0 +1,37 @@
+//! Tests for who may edit and delete job comments
+
+use api_main::services::jobs::comment_edit_error;
+use serde_json::json;
+
+#[test]
+fn test_author_can_modify_own_comment() {
+    let comment = json!({ "id": "c1", "user_id": "alice", "deleted_at": null });
+    assert_eq!(comment_edit_error(&comment, "alice"), None);
+}
+
+#[test]
+fn test_other_users_cannot_modify_comment() {
+    let comment = json!({ "id": "c1", "user_id": "alice", "deleted_at": null });
+    assert_eq!(
+        comment_edit_error(&comment, "bob"),
+        Some(("Only the author can modify this comment", 403))
+    );
+
+    let orphaned = json!({ "id": "c2" });
+    assert_eq!(comment_edit_error(&orphaned, "bob").map(|e| e.1), Some(403));
+}
+
+#[test]
+fn test_deleted_comment_cannot_be_modified() {
+    let comment = json!({
+        "id": "c1",
+        "user_id": "alice",
+        "deleted_at": "2024-01-01 00:00:00"
+    });
+    assert_eq!(
+        comment_edit_error(&comment, "alice"),
+        Some(("Comment has been deleted", 400))
+    );
+    // Authorship is checked first so others can't learn the comment was deleted
+    assert_eq!(comment_edit_error(&comment, "bob").map(|e| e.1), Some(403));
+}
@@ -0,0 +1,24 @@
+//! Tests for the resume saved in account settings
+
+use api_main::services::db::job_documents::{clean_resume, MAX_RESUME_LENGTH};
+
+#[test]
+fn test_resume_is_trimmed_and_blank_clears_it() {
+    assert_eq!(
+        clean_resume(Some("  Ten years of Rust \n".to_string())),
+        Ok(Some("Ten years of Rust".to_string()))
+    );
+    assert_eq!(clean_resume(Some(" \n ".to_string())), Ok(None));
+    assert_eq!(clean_resume(None), Ok(None));
+}
+
+#[test]
+fn test_resume_length_counts_characters() {
+    // Multi-byte characters count once each
+    let at_limit = "é".repeat(MAX_RESUME_LENGTH);
+    assert!(at_limit.len() > MAX_RESUME_LENGTH);
+    assert_eq!(clean_resume(Some(at_limit.clone())), Ok(Some(at_limit)));
+
+    let too_long = "a".repeat(MAX_RESUME_LENGTH + 1);
+    assert!(clean_resume(Some(too_long)).is_err());
+}
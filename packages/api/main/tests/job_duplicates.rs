@@ -0,0 +1,78 @@
+//! Tests for duplicate job scoring
+
+use api_main::services::jobs::duplicates::{
+    company_similarity, duplicate_score, normalize_company, normalize_title, title_similarity,
+    DUPLICATE_THRESHOLD,
+};
+
+#[test]
+fn test_normalize_company() {
+    assert_eq!(normalize_company("Acme, Inc."), "acme");
+    assert_eq!(normalize_company("The Acme Corporation"), "acme");
+    assert_eq!(
+        normalize_company("Johnson & Johnson"),
+        "johnson and johnson"
+    );
+    assert_eq!(normalize_company("Inc."), "");
+}
+
+#[test]
+fn test_normalize_title() {
+    assert_eq!(
+        normalize_title("Sr. Software Eng"),
+        "senior software engineer"
+    );
+    assert_eq!(normalize_title("SWE II"), "software engineer 2");
+    assert_eq!(
+        normalize_title("Backend Developer (Remote)"),
+        "backend developer remote"
+    );
+}
+
+#[test]
+fn test_company_similarity() {
+    assert_eq!(company_similarity("Acme Inc", "ACME, LLC"), 1.0);
+    assert!(company_similarity("Open AI", "OpenAI") > 0.9);
+    assert!(company_similarity("Acme", "Globex") < 0.1);
+    assert_eq!(company_similarity("", "Acme"), 0.0);
+}
+
+#[test]
+fn test_title_similarity() {
+    assert_eq!(
+        title_similarity("Sr Software Engineer", "Senior Software Engineer"),
+        1.0
+    );
+    assert!(title_similarity("Software Engineer", "Senior Software Engineer") > 0.7);
+    assert!(title_similarity("Software Engineer", "Product Designer") < 0.1);
+}
+
+#[test]
+fn test_duplicate_score() {
+    // Same company and equivalent title
+    assert!(
+        duplicate_score("Acme Inc", "Sr. SWE", "Acme", "Senior Software Engineer", 0)
+            >= DUPLICATE_THRESHOLD
+    );
+    // Same company, different role
+    assert!(
+        duplicate_score("Acme", "Software Engineer", "Acme", "Sales Director", 0)
+            < DUPLICATE_THRESHOLD
+    );
+    // Different company, same role
+    assert_eq!(
+        duplicate_score(
+            "Acme",
+            "Software Engineer",
+            "Globex",
+            "Software Engineer",
+            0
+        ),
+        0.0
+    );
+    // A shared email thread is enough on its own
+    assert!(
+        duplicate_score("Acme", "Software Engineer", "Globex", "Recruiter", 1)
+            >= DUPLICATE_THRESHOLD
+    );
+}
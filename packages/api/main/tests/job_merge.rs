@@ -0,0 +1,92 @@
+//! Tests for merging duplicate jobs
+
+use api_main::services::jobs::merge::MERGE_STATEMENTS;
+use std::path::Path;
+
+fn statements_for(table: &str) -> Vec<&'static str> {
+    MERGE_STATEMENTS
+        .iter()
+        .copied()
+        .filter(|sql| sql.split_whitespace().any(|word| word == table))
+        .collect()
+}
+
+/// Tables that get a `job_id` column in the migrations
+fn tables_with_job_id() -> Vec<String> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../migrations");
+    let mut tables = Vec::new();
+    for entry in std::fs::read_dir(dir).expect("migrations directory") {
+        let sql = std::fs::read_to_string(entry.expect("migration").path()).expect("migration");
+        let mut current = None;
+        for line in sql.lines().map(str::trim) {
+            if let Some(rest) = line.strip_prefix("CREATE TABLE IF NOT EXISTS ") {
+                current = rest.split_whitespace().next().map(str::to_string);
+            } else if let Some(rest) = line.strip_prefix("ALTER TABLE ") {
+                let mut words = rest.split_whitespace();
+                let table = words.next().map(str::to_string);
+                if words.any(|w| w == "job_id") {
+                    tables.extend(table);
+                }
+            } else if line.starts_with("job_id ") {
+                tables.extend(current.clone());
+            } else if line.starts_with(");") {
+                current = None;
+            }
+        }
+    }
+    tables.sort();
+    tables.dedup();
+    tables
+}
+
+#[test]
+fn test_merge_moves_every_job_table() {
+    let tables = tables_with_job_id();
+    for table in ["emails", "job_tags", "job_documents"] {
+        assert!(tables.contains(&table.to_string()), "{} not found", table);
+    }
+    for table in tables {
+        assert!(
+            !statements_for(&table).is_empty(),
+            "merging loses the duplicate's {}",
+            table
+        );
+    }
+}
+
+#[test]
+fn test_merge_moves_tags_and_documents() {
+    // Tags the survivor already has are kept once
+    let tags = statements_for("job_tags");
+    assert_eq!(tags.len(), 1);
+    assert!(tags[0].starts_with("INSERT OR IGNORE INTO job_tags"));
+    assert!(tags[0].contains("SELECT ?1, tag"));
+
+    // Documents are renumbered after the survivor's versions before moving
+    let documents = statements_for("job_documents");
+    assert_eq!(documents.len(), 2);
+    assert!(documents[0].contains("MAX(version)"));
+    assert!(documents[0].contains("d.job_id = ?1"));
+    assert!(documents[1].contains("SET job_id = ?1, version = -version"));
+}
+
+#[test]
+fn test_merge_fills_empty_job_fields() {
+    let jobs = statements_for("jobs");
+    assert_eq!(jobs.len(), 1);
+    for field in [
+        "description",
+        "location",
+        "source_id",
+        "referrer_email",
+        "company_id",
+    ] {
+        assert!(
+            jobs[0].contains(&format!(
+                "{field} = COALESCE({field}, (SELECT {field} FROM jobs WHERE id = ?2))"
+            )),
+            "{} is not filled from the duplicate",
+            field
+        );
+    }
+}
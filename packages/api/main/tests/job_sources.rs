@@ -0,0 +1,14 @@
+//! Tests for job source naming
+
+use api_main::services::job_sources::source_id_from_name;
+
+#[test]
+fn test_source_id_from_name() {
+    assert_eq!(source_id_from_name("LinkedIn"), "linkedin");
+    assert_eq!(source_id_from_name("Hacker News"), "hacker_news");
+    assert_eq!(
+        source_id_from_name("  Wellfound (AngelList) "),
+        "wellfound_angellist"
+    );
+    assert_eq!(source_id_from_name("!!!"), "");
+}
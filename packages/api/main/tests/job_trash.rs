@@ -0,0 +1,32 @@
+//! Tests for archiving, trashing, restoring and purging jobs
+
+use api_main::services::jobs::{
+    trash_cutoff_modifier, trash_retention_days, JobListView, DEFAULT_TRASH_RETENTION_DAYS,
+};
+
+#[test]
+fn test_view_query_defaults_to_active() {
+    assert_eq!(JobListView::from_query(None), JobListView::Active);
+    assert_eq!(JobListView::from_query(Some("active")), JobListView::Active);
+    assert_eq!(JobListView::from_query(Some("bogus")), JobListView::Active);
+    assert_eq!(
+        JobListView::from_query(Some("archived")),
+        JobListView::Archived
+    );
+    assert_eq!(JobListView::from_query(Some("trash")), JobListView::Trash);
+}
+
+#[test]
+fn test_retention_days_fall_back_to_default() {
+    assert_eq!(trash_retention_days(Some("7")), 7);
+    assert_eq!(trash_retention_days(Some(" 90 ")), 90);
+    for value in [None, Some(""), Some("0"), Some("-5"), Some("a week")] {
+        assert_eq!(trash_retention_days(value), DEFAULT_TRASH_RETENTION_DAYS);
+    }
+}
+
+#[test]
+fn test_expiry_cutoff_is_retention_days_ago() {
+    assert_eq!(trash_cutoff_modifier(30), "-30 days");
+    assert_eq!(trash_cutoff_modifier(1), "-1 days");
+}
@@ -0,0 +1,134 @@
+//! Tests for vCard import and export
+
+use api_main::services::vcard::{
+    parse_vcards, plan_import, write_vcards, ContactFields, VCardContact, VCardVersion,
+};
+use std::collections::HashMap;
+
+fn fields(name: Option<&str>, linkedin: Option<&str>, website: Option<&str>) -> ContactFields {
+    ContactFields {
+        name: name.map(|s| s.to_string()),
+        linkedin: linkedin.map(|s| s.to_string()),
+        website: website.map(|s| s.to_string()),
+    }
+}
+
+#[test]
+fn test_export_round_trips_in_both_versions() {
+    let contacts = vec![VCardContact {
+        email: "jane@acme.com".to_string(),
+        fields: fields(
+            Some("Jane Doe, PhD"),
+            Some("https://www.linkedin.com/in/janedoe"),
+            Some("https://janedoe.dev"),
+        ),
+        company_name: Some("Acme".to_string()),
+    }];
+
+    for version in [VCardVersion::V3, VCardVersion::V4] {
+        let text = write_vcards(&contacts, version);
+        assert!(text.starts_with("BEGIN:VCARD\r\n"));
+        assert!(text.contains("FN:Jane Doe\\, PhD\r\n"));
+        assert!(text.contains("URL;TYPE=linkedin:https://www.linkedin.com/in/janedoe\r\n"));
+        assert!(text.contains("ORG:Acme\r\n"));
+
+        let cards = parse_vcards(&text);
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0].emails, vec!["jane@acme.com"]);
+        assert_eq!(cards[0].fields, contacts[0].fields);
+    }
+
+    let v3 = write_vcards(&contacts, VCardVersion::V3);
+    assert!(v3.contains("VERSION:3.0\r\n"));
+    assert!(v3.contains("EMAIL;TYPE=INTERNET:jane@acme.com\r\n"));
+    let v4 = write_vcards(&contacts, VCardVersion::V4);
+    assert!(v4.contains("VERSION:4.0\r\n"));
+    assert!(v4.contains("EMAIL:jane@acme.com\r\n"));
+}
+
+#[test]
+fn test_long_lines_are_folded_and_unfolded() {
+    let long_name = "Ééé ".repeat(30).trim().to_string();
+    let contacts = vec![VCardContact {
+        email: "long@example.com".to_string(),
+        fields: fields(Some(&long_name), None, None),
+        company_name: None,
+    }];
+
+    let text = write_vcards(&contacts, VCardVersion::V4);
+    assert!(text.split("\r\n").all(|line| line.len() <= 75));
+
+    let cards = parse_vcards(&text);
+    assert_eq!(cards[0].fields.name.as_deref(), Some(long_name.as_str()));
+}
+
+#[test]
+fn test_parse_other_clients_vcards() {
+    let text = "BEGIN:VCARD\n\
+                VERSION:3.0\n\
+                N:Smith;Bob;;Dr.;\n\
+                item1.EMAIL;type=INTERNET;type=pref:Bob Smith <BOB@Beta.IO>\n\
+                item1.X-ABLabel:work\n\
+                EMAIL;type=HOME:bob@gmail.com\n\
+                X-SOCIALPROFILE;type=linkedin:https://linkedin.com/in/bob\n\
+                URL;type=WORK:https://beta.io\n\
+                END:VCARD\n\
+                BEGIN:VCARD\n\
+                VERSION:4.0\n\
+                FN:No Email\n\
+                END:VCARD\n";
+
+    let cards = parse_vcards(text);
+    assert_eq!(cards.len(), 2);
+    assert_eq!(cards[0].emails, vec!["bob@beta.io", "bob@gmail.com"]);
+    assert_eq!(
+        cards[0].fields,
+        fields(
+            Some("Dr. Bob Smith"),
+            Some("https://linkedin.com/in/bob"),
+            Some("https://beta.io")
+        )
+    );
+    assert!(cards[1].emails.is_empty());
+}
+
+#[test]
+fn test_import_merges_into_existing_contacts() {
+    let mut existing = HashMap::new();
+    existing.insert(
+        "jane@acme.com".to_string(),
+        fields(Some("Jane"), None, Some("https://janedoe.dev")),
+    );
+    existing.insert(
+        "same@acme.com".to_string(),
+        fields(Some("Same"), None, None),
+    );
+
+    let text = "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Jane Doe\r\nEMAIL:Jane@Acme.com\r\n\
+                URL;TYPE=linkedin:https://linkedin.com/in/jane\r\nEND:VCARD\r\n\
+                BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Same\r\nEMAIL:same@acme.com\r\nEND:VCARD\r\n\
+                BEGIN:VCARD\r\nVERSION:3.0\r\nFN:New Person\r\nEMAIL:new@acme.com\r\nEND:VCARD\r\n\
+                BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Nobody\r\nEND:VCARD\r\n";
+
+    let plan = plan_import(&existing, &parse_vcards(text));
+    assert_eq!(
+        plan.updated,
+        vec![(
+            "jane@acme.com".to_string(),
+            fields(
+                Some("Jane Doe"),
+                Some("https://linkedin.com/in/jane"),
+                Some("https://janedoe.dev")
+            )
+        )]
+    );
+    assert_eq!(
+        plan.created,
+        vec![(
+            "new@acme.com".to_string(),
+            fields(Some("New Person"), None, None)
+        )]
+    );
+    assert_eq!(plan.unchanged, 1);
+    assert_eq!(plan.skipped, 1);
+}
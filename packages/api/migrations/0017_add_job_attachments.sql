@@ -0,0 +1,17 @@
+-- Create job_attachments table for documents stored in R2 (resumes, cover letters, offer letters)
+CREATE TABLE IF NOT EXISTS job_attachments (
+  id TEXT PRIMARY KEY,
+  job_id TEXT NOT NULL,
+  user_id TEXT NOT NULL,
+  file_name TEXT NOT NULL,
+  content_type TEXT NOT NULL,
+  size_bytes INTEGER NOT NULL,
+  r2_key TEXT NOT NULL UNIQUE,
+  created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE,
+  FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Indexes for efficient queries
+CREATE INDEX IF NOT EXISTS idx_job_attachments_job_id ON job_attachments(job_id);
+CREATE INDEX IF NOT EXISTS idx_job_attachments_user_id ON job_attachments(user_id);
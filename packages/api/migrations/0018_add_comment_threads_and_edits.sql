@@ -0,0 +1,7 @@
+-- Add reply threading, edit tracking and soft deletion to job comments
+ALTER TABLE job_comments ADD COLUMN parent_id TEXT;
+ALTER TABLE job_comments ADD COLUMN updated_at DATETIME;
+ALTER TABLE job_comments ADD COLUMN deleted_at DATETIME;
+
+-- Index for loading replies of a comment
+CREATE INDEX IF NOT EXISTS idx_job_comments_parent_id ON job_comments(parent_id);
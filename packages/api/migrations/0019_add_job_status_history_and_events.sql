@@ -0,0 +1,30 @@
+-- Create job_status_history table recording every status transition of a job
+CREATE TABLE IF NOT EXISTS job_status_history (
+  id TEXT PRIMARY KEY,
+  job_id TEXT NOT NULL,
+  from_status_id INTEGER, -- NULL for the initial status when the job is created
+  to_status_id INTEGER NOT NULL,
+  changed_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_job_status_history_job_id ON job_status_history(job_id);
+CREATE INDEX IF NOT EXISTS idx_job_status_history_changed_at ON job_status_history(changed_at);
+
+-- Seed history with the current status of existing jobs
+INSERT INTO job_status_history (id, job_id, from_status_id, to_status_id, changed_at)
+SELECT lower(hex(randomblob(16))), id, NULL, COALESCE(status_id, 100), created_at FROM jobs;
+
+-- Create job_events table for timeline events that aren't derived from other tables (e.g. merges)
+CREATE TABLE IF NOT EXISTS job_events (
+  id TEXT PRIMARY KEY,
+  job_id TEXT NOT NULL,
+  user_id TEXT,
+  event_type TEXT NOT NULL, -- 'job_merged'
+  data TEXT, -- JSON payload for the event
+  created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE,
+  FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_job_events_job_id ON job_events(job_id);
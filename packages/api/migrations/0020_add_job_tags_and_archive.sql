@@ -0,0 +1,15 @@
+-- Add archived_at to jobs so they can be hidden from the default listing
+ALTER TABLE jobs ADD COLUMN archived_at DATETIME;
+
+CREATE INDEX IF NOT EXISTS idx_jobs_archived_at ON jobs(archived_at);
+
+-- Create job_tags table for free-form labels on jobs
+CREATE TABLE IF NOT EXISTS job_tags (
+  job_id TEXT NOT NULL,
+  tag TEXT NOT NULL,
+  created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (job_id, tag),
+  FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_job_tags_tag ON job_tags(tag);
@@ -0,0 +1,9 @@
+-- Add deleted_at to jobs so deleted jobs go to the trash and can be restored
+-- until they are purged after the retention window
+ALTER TABLE jobs ADD COLUMN deleted_at DATETIME;
+
+CREATE INDEX IF NOT EXISTS idx_jobs_deleted_at ON jobs(deleted_at);
+
+-- Unlink emails still pointing at jobs that were hard-deleted before this migration
+UPDATE emails SET job_id = NULL
+WHERE job_id IS NOT NULL AND job_id NOT IN (SELECT id FROM jobs);
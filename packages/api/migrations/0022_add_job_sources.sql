@@ -0,0 +1,54 @@
+-- Create job_sources table listing where applications come from
+CREATE TABLE IF NOT EXISTS job_sources (
+  id TEXT PRIMARY KEY,
+  name TEXT NOT NULL UNIQUE,
+  created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT OR IGNORE INTO job_sources (id, name) VALUES
+('linkedin', 'LinkedIn'),
+('indeed', 'Indeed'),
+('glassdoor', 'Glassdoor'),
+('ziprecruiter', 'ZipRecruiter'),
+('job_board', 'Other job board'),
+('company_website', 'Company website'),
+('referral', 'Referral'),
+('recruiter', 'Recruiter outreach'),
+('other', 'Other');
+
+-- Source and referrer on jobs. The referrer is an email_contacts entry of the
+-- user who set it (email_contacts is keyed by email and user).
+ALTER TABLE jobs ADD COLUMN source_id TEXT REFERENCES job_sources(id) ON DELETE SET NULL;
+ALTER TABLE jobs ADD COLUMN referrer_email TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_jobs_source_id ON jobs(source_id);
+
+-- Map system email domains to the source a job is inferred to come from when
+-- its first linked email is sent from that domain
+ALTER TABLE system_email_domains ADD COLUMN source_id TEXT REFERENCES job_sources(id) ON DELETE SET NULL;
+
+-- Matched on the pattern rather than the row id, which differs between
+-- databases where domains were added in another order
+
+-- Job boards
+UPDATE system_email_domains SET source_id = 'linkedin' WHERE domain_pattern = '*.linkedin.com';
+UPDATE system_email_domains SET source_id = 'indeed' WHERE domain_pattern = '*.indeed.com';
+UPDATE system_email_domains SET source_id = 'glassdoor' WHERE domain_pattern = '*.glassdoor.com';
+UPDATE system_email_domains SET source_id = 'ziprecruiter' WHERE domain_pattern = '*.ziprecruiter.com';
+UPDATE system_email_domains SET source_id = 'job_board'
+WHERE domain_pattern IN (
+  '*.hired.com', '*.monster.com', '*.careerbuilder.com', '*.dice.com', '*.simplyhired.com',
+  '*.snagajob.com', '*.theladders.com', '*.angel.co', '*.stackoverflow.com', '*.github.com'
+);
+
+-- Applicant tracking systems used by employers' own careers pages
+UPDATE system_email_domains SET source_id = 'company_website'
+WHERE domain_pattern IN (
+  '*.greenhouse.io', '*.lever.co', '*.workday.com', '*.smartrecruiters.com', '*.jobvite.com',
+  '*.taleo.net', '*.icims.com', '*.bamboohr.com', '*.jazzhr.com', '*.recruitee.com',
+  '*.comeet.co', '*.workable.com', '*.personio.com', '*.zoho.com'
+);
+
+-- Applicant tracking systems used by staffing agencies
+UPDATE system_email_domains SET source_id = 'recruiter'
+WHERE domain_pattern IN ('*.bullhorn.com', '*.jobdiva.com', '*.ceipal.com', '*.jobadder.com');
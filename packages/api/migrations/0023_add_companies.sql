@@ -0,0 +1,23 @@
+-- Create companies table so jobs and contacts at the same employer are grouped
+-- together. normalized_name is the company name lowercased with punctuation
+-- and legal suffixes removed ("Acme Inc." and "ACME" are the same company).
+CREATE TABLE IF NOT EXISTS companies (
+  id TEXT PRIMARY KEY,
+  name TEXT NOT NULL,
+  normalized_name TEXT NOT NULL UNIQUE,
+  domain TEXT,
+  website TEXT,
+  notes TEXT,
+  created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+  updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_companies_domain ON companies(domain);
+
+-- Jobs keep their free-text company; company_id is filled in by the API
+ALTER TABLE jobs ADD COLUMN company_id TEXT REFERENCES companies(id) ON DELETE SET NULL;
+CREATE INDEX IF NOT EXISTS idx_jobs_company_id ON jobs(company_id);
+
+-- Contacts are linked to the company whose domain matches their email address
+ALTER TABLE email_contacts ADD COLUMN company_id TEXT REFERENCES companies(id) ON DELETE SET NULL;
+CREATE INDEX IF NOT EXISTS idx_email_contacts_company_id ON email_contacts(company_id);
@@ -0,0 +1,2 @@
+-- Free-form notes on a contact
+ALTER TABLE email_contacts ADD COLUMN notes TEXT;
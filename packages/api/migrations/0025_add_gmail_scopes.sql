@@ -0,0 +1,3 @@
+-- Scopes granted to the stored Gmail token (space-separated). Send access is
+-- requested incrementally, so tokens stored before this have read access only.
+ALTER TABLE gmail_tokens ADD COLUMN scopes TEXT;
@@ -0,0 +1,43 @@
+-- Resume or profile text the user tailors for each job
+ALTER TABLE users ADD COLUMN resume TEXT;
+
+-- Cover letters and resume suggestions generated for a job, one row per version
+CREATE TABLE IF NOT EXISTS job_documents (
+  id TEXT PRIMARY KEY,
+  job_id TEXT NOT NULL,
+  user_id TEXT NOT NULL,
+  version INTEGER NOT NULL,
+  cover_letter TEXT NOT NULL,
+  resume_suggestions TEXT NOT NULL, -- JSON array of resume bullet suggestions
+  created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE,
+  FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+  UNIQUE (job_id, user_id, version)
+);
+
+CREATE INDEX IF NOT EXISTS idx_job_documents_job_user ON job_documents(job_id, user_id);
+
+-- Seed the prompt for the 'tailor' stage
+INSERT INTO ai_prompts (id, name, stage, prompt, is_active) VALUES
+('tailor-v1', 'Tailoring v1', 'tailor', 'You help {{user_name}} apply for a job.
+
+Job: {{job_title}} at {{company}}
+Posting:
+{{description}}
+
+Resume of {{user_name}}:
+{{resume}}
+
+Extra instructions: {{instructions}}
+
+Return JSON only:
+{
+  "cover_letter": "plain text cover letter",
+  "resume_bullets": ["resume bullet", "resume bullet"]
+}
+
+Rules:
+- The cover letter is under 300 words, addressed to the hiring team at {{company}} and signed by {{user_name}}
+- Connect the requirements of the posting to experience from the resume
+- Give 3 to 6 resume bullets, rewritten from the resume to match the posting, starting with a verb
+- Never invent experience, employers, skills or numbers that are not in the resume', true);
@@ -0,0 +1,39 @@
+-- Labelled datasets for evaluating AI prompts before activating them
+CREATE TABLE IF NOT EXISTS ai_eval_datasets (
+  id TEXT PRIMARY KEY,
+  name TEXT NOT NULL,
+  description TEXT,
+  created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Emails in a dataset with their expected results. The email content is copied
+-- so the dataset stays the same when the original email changes or is deleted.
+CREATE TABLE IF NOT EXISTS ai_eval_examples (
+  id TEXT PRIMARY KEY,
+  dataset_id TEXT NOT NULL,
+  email_id TEXT, -- gmail_id the example was copied from, if any
+  from_email TEXT,
+  subject TEXT,
+  body TEXT,
+  expected_category TEXT NOT NULL,
+  expected_fields TEXT, -- JSON object of expected extraction fields; missing keys aren't scored
+  created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY (dataset_id) REFERENCES ai_eval_datasets(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_ai_eval_examples_dataset_id ON ai_eval_examples(dataset_id);
+
+-- Results of running a prompt against a dataset
+CREATE TABLE IF NOT EXISTS ai_eval_runs (
+  id TEXT PRIMARY KEY,
+  dataset_id TEXT NOT NULL,
+  prompt_id TEXT NOT NULL,
+  stage TEXT NOT NULL,
+  accuracy REAL,
+  report TEXT NOT NULL, -- JSON: accuracy, confusion matrix and per-field precision/recall
+  created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY (dataset_id) REFERENCES ai_eval_datasets(id) ON DELETE CASCADE,
+  FOREIGN KEY (prompt_id) REFERENCES ai_prompts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_ai_eval_runs_dataset_id ON ai_eval_runs(dataset_id, created_at);
@@ -0,0 +1,24 @@
+-- Reviews of AI results flagged with needs_review. The reviewed category and
+-- fields are ground truth for evaluation datasets and few-shot examples, so the
+-- email content is copied like in ai_eval_examples.
+CREATE TABLE IF NOT EXISTS ai_reviews (
+  id TEXT PRIMARY KEY,
+  email_id TEXT NOT NULL,
+  user_id TEXT NOT NULL,
+  ai_result_id TEXT,
+  status TEXT NOT NULL, -- 'accepted' or 'corrected'
+  predicted_category TEXT,
+  category TEXT NOT NULL,
+  fields TEXT, -- JSON object of reviewed extraction fields
+  corrected_fields TEXT, -- JSON array of the field names the user changed
+  from_email TEXT,
+  subject TEXT,
+  body TEXT,
+  created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY (email_id) REFERENCES emails(gmail_id) ON DELETE CASCADE,
+  FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+  FOREIGN KEY (ai_result_id) REFERENCES ai_results(id) ON DELETE SET NULL,
+  UNIQUE (email_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_ai_reviews_user_status ON ai_reviews(user_id, status, created_at);
@@ -0,0 +1,6 @@
+-- Model settings per prompt. models is a JSON array of model specs such as
+-- ["workers-ai:@cf/meta/llama-3.1-8b-instruct-fast", "openai:llama3.1:8b"],
+-- tried in order until one succeeds. NULL uses the worker's defaults.
+ALTER TABLE ai_prompts ADD COLUMN models TEXT;
+ALTER TABLE ai_prompts ADD COLUMN temperature REAL;
+ALTER TABLE ai_prompts ADD COLUMN max_tokens INTEGER;
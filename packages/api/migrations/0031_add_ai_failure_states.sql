@@ -0,0 +1,12 @@
+-- Failed AI processing is stored instead of replaced with made-up results.
+-- ai_results rows have status 'ok' or 'failed'; failed rows name the stage
+-- and error and have no category or fields.
+ALTER TABLE ai_results ADD COLUMN status TEXT NOT NULL DEFAULT 'ok';
+ALTER TABLE ai_results ADD COLUMN failed_stage TEXT;
+ALTER TABLE ai_results ADD COLUMN error TEXT;
+
+-- ai_status is 'processed' or 'failed' once the email went through the AI
+ALTER TABLE emails ADD COLUMN ai_status TEXT;
+ALTER TABLE emails ADD COLUMN ai_error TEXT;
+
+UPDATE emails SET ai_status = 'processed' WHERE ai_processed = 1;
@@ -0,0 +1,49 @@
+-- Prompt for processing an email in one call instead of three. It's used
+-- when the AI worker runs with AI_PROCESSING_MODE = "combined".
+INSERT INTO ai_prompts (id, name, stage, prompt, is_active) VALUES
+('combined-v1', 'Combined v1', 'combined', 'You categorize job search emails, extract their details and summarize them.
+
+Email from: {{from_email}}
+Subject: {{subject}}
+Body: {{body}}
+
+Return JSON only:
+{
+  "category": "interview|rejection|new_job|application_sent|other",
+  "confidence": 0.0-1.0,
+  "extraction": {
+    "company": "company name or null",
+    "job_title": "title or null",
+    "recruiter_name": "name or null",
+    "recruiter_email": "email or null",
+    "interview_date": "ISO8601 or null",
+    "location": "string or null",
+    "remote": true/false/null
+  },
+  "summary": "one sentence here"
+}
+
+Rules:
+- Interview invites/confirmations → "interview"
+- "Unfortunately" or "not moving forward" → "rejection"
+- New opportunities being shared → "new_job"
+- Application received confirmations → "application_sent"
+- If unsure → "other" with confidence < 0.6
+- Use null for details you don''t find
+- The summary says what the user needs to know and do', true);
+
+-- Results of processing, keyed by a hash of the normalised email content and
+-- the versions of the prompts used, so identical emails skip the AI
+CREATE TABLE IF NOT EXISTS ai_result_cache (
+  user_id TEXT NOT NULL,
+  content_hash TEXT NOT NULL,
+  category TEXT NOT NULL,
+  confidence REAL NOT NULL,
+  extracted_data TEXT NOT NULL,
+  summary TEXT NOT NULL,
+  created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (user_id, content_hash),
+  FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_ai_result_cache_created_at ON ai_result_cache(created_at);
@@ -0,0 +1,18 @@
+-- Every processing run of an email is kept as a numbered version with the
+-- prompts and models that produced it. prompt_ids and models are JSON objects
+-- keyed by stage, e.g. {"classify": "classify-v1"}.
+ALTER TABLE ai_results ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE ai_results ADD COLUMN prompt_ids TEXT;
+ALTER TABLE ai_results ADD COLUMN models TEXT;
+
+-- Number existing results of each email by age
+UPDATE ai_results SET version = (
+  SELECT COUNT(*) FROM ai_results earlier
+  WHERE earlier.email_id = ai_results.email_id
+    AND (earlier.created_at < ai_results.created_at
+      OR (earlier.created_at = ai_results.created_at AND earlier.id <= ai_results.id))
+);
+
+CREATE INDEX IF NOT EXISTS idx_ai_results_email_version ON ai_results(email_id, version);
+
+ALTER TABLE ai_result_cache ADD COLUMN models TEXT;
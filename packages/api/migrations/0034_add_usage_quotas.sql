@@ -0,0 +1,25 @@
+-- Daily limits on scans, fetched Gmail messages and AI calls. The row with an
+-- empty user_id is the default, other rows override it for one user.
+CREATE TABLE IF NOT EXISTS usage_limits (
+  user_id TEXT NOT NULL DEFAULT '',
+  kind TEXT NOT NULL,
+  daily_limit INTEGER NOT NULL,
+  PRIMARY KEY (user_id, kind)
+);
+
+INSERT OR IGNORE INTO usage_limits (user_id, kind, daily_limit) VALUES
+  ('', 'scans', 10),
+  ('', 'messages', 2000),
+  ('', 'ai_calls', 1500);
+
+-- What each user has used per UTC day
+CREATE TABLE IF NOT EXISTS usage_counters (
+  user_id TEXT NOT NULL,
+  day DATE NOT NULL,
+  kind TEXT NOT NULL,
+  count INTEGER NOT NULL DEFAULT 0,
+  PRIMARY KEY (user_id, day, kind),
+  FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_usage_counters_day ON usage_counters(day);
@@ -0,0 +1,7 @@
+-- Per-user pre-filters added to the Gmail search of every scan
+CREATE TABLE IF NOT EXISTS scan_filters (
+  user_id TEXT PRIMARY KEY,
+  filters TEXT NOT NULL, -- JSON: labels, categories, keywords and blocked senders
+  updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
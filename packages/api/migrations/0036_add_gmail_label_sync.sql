@@ -0,0 +1,3 @@
+-- Opt-in writing of "ApplyMonitor" labels back to Gmail. Kept on users since
+-- gmail_tokens rows are replaced whenever Gmail is reconnected.
+ALTER TABLE users ADD COLUMN gmail_label_sync BOOLEAN NOT NULL DEFAULT 0;
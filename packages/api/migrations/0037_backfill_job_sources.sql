@@ -0,0 +1,19 @@
+-- Fill in the source of existing jobs from the sender of their first linked
+-- email. New links infer the source when the email is assigned.
+UPDATE jobs SET source_id = (
+  SELECT d.source_id
+  FROM system_email_domains d
+  JOIN (
+    SELECT lower(trim(substr(e."from", instr(e."from", '@') + 1), ' >')) AS domain
+    FROM emails e
+    WHERE e.job_id = jobs.id
+    ORDER BY COALESCE(e.date, e.created_at) ASC
+    LIMIT 1
+  ) first_email
+    ON first_email.domain = substr(d.domain_pattern, 3)
+    OR first_email.domain LIKE '%' || substr(d.domain_pattern, 2)
+  WHERE d.source_id IS NOT NULL AND d.domain_pattern LIKE '*.%'
+  ORDER BY d.domain_pattern
+  LIMIT 1
+)
+WHERE source_id IS NULL AND deleted_at IS NULL;
@@ -0,0 +1,16 @@
+-- Result versions are numbered MAX(version) + 1 in the insert, and a unique
+-- index makes a concurrent insert that took the same number fail and retry.
+
+-- Renumber emails whose concurrent runs already share a version, by age
+UPDATE ai_results SET version = (
+  SELECT COUNT(*) FROM ai_results earlier
+  WHERE earlier.email_id = ai_results.email_id
+    AND (earlier.created_at < ai_results.created_at
+      OR (earlier.created_at = ai_results.created_at AND earlier.id <= ai_results.id))
+)
+WHERE email_id IN (
+  SELECT email_id FROM ai_results GROUP BY email_id, version HAVING COUNT(*) > 1
+);
+
+DROP INDEX IF EXISTS idx_ai_results_email_version;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_ai_results_email_version ON ai_results(email_id, version);
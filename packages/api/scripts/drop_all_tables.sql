@@ -6,6 +6,10 @@
 DROP TABLE IF EXISTS d1_migrations;
 
 -- Drop tables with foreign keys (child tables) first
+DROP TABLE IF EXISTS job_attachments;
+DROP TABLE IF EXISTS job_tags;
+DROP TABLE IF EXISTS job_events;
+DROP TABLE IF EXISTS job_status_history;
 DROP TABLE IF EXISTS job_comments;
 DROP TABLE IF EXISTS emails;
 DROP TABLE IF EXISTS email_contacts;
@@ -20,5 +24,7 @@ DROP TABLE IF EXISTS system_email_domains;
 
 -- Drop parent tables (referenced by others) last
 DROP TABLE IF EXISTS jobs;
+DROP TABLE IF EXISTS job_sources;
+DROP TABLE IF EXISTS companies;
 DROP TABLE IF EXISTS users;
 
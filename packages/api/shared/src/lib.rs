@@ -0,0 +1,7 @@
+//! Code shared by the API workers: signing, sending and checking the requests they make
+//! to each other, and users' daily quotas
+
+pub mod quota;
+pub mod requests;
+pub mod signing;
+pub mod usage;
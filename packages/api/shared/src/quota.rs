@@ -0,0 +1,80 @@
+//! Per-user daily limits on scans, fetched Gmail messages and AI calls, so one
+//! user can't use up the shared Gmail quota or the AI budget
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+pub const SCANS: &str = "scans";
+pub const MESSAGES: &str = "messages";
+pub const AI_CALLS: &str = "ai_calls";
+
+/// Everything usage is counted for
+pub const QUOTA_KINDS: [&str; 3] = [SCANS, MESSAGES, AI_CALLS];
+
+const SECS_PER_DAY: u64 = 86_400;
+
+/// Seconds until the daily counters start over at midnight UTC
+pub fn seconds_until_reset(now_secs: u64) -> u64 {
+    SECS_PER_DAY - now_secs % SECS_PER_DAY
+}
+
+/// How a kind of usage is named in messages
+pub fn describe_kind(kind: &str) -> &str {
+    match kind {
+        SCANS => "scans",
+        MESSAGES => "fetched messages",
+        AI_CALLS => "AI calls",
+        other => other,
+    }
+}
+
+/// A user's limit of one kind of usage and what they've used of it today
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Quota {
+    pub kind: String,
+    pub limit: u32,
+    pub used: u32,
+}
+
+impl Quota {
+    pub fn remaining(&self) -> u32 {
+        self.limit.saturating_sub(self.used)
+    }
+
+    /// Check that `amount` more still fits in today's limit
+    pub fn check(&self, amount: u32, now_secs: u64) -> Result<(), QuotaExceeded> {
+        if self.used.saturating_add(amount) > self.limit {
+            Err(QuotaExceeded {
+                kind: self.kind.clone(),
+                limit: self.limit,
+                retry_after: seconds_until_reset(now_secs),
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A request that would go over a daily limit
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuotaExceeded {
+    pub kind: String,
+    pub limit: u32,
+    /// Seconds until the limit resets
+    pub retry_after: u64,
+}
+
+impl fmt::Display for QuotaExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Daily limit of {} {} reached, try again in {}h {}m",
+            self.limit,
+            describe_kind(&self.kind),
+            self.retry_after / 3600,
+            self.retry_after % 3600 / 60
+        )
+    }
+}
+
+impl std::error::Error for QuotaExceeded {}
@@ -0,0 +1,94 @@
+//! Signed requests from one API worker to another
+
+use crate::signing::{sign, verify, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use crate::usage::now_secs;
+use anyhow::anyhow;
+use worker::*;
+
+/// The method of every request between the workers
+const SIGNED_METHOD: Method = Method::Post;
+
+/// A POST of a JSON body to the worker at `base_url`, signed with the shared
+/// AI_WORKER_SECRET so the receiver knows it comes from one of the workers
+fn signed_request(env: &Env, base_url: &str, path: &str, body: &str) -> Result<Request> {
+    let secret = env
+        .secret("AI_WORKER_SECRET")
+        .map_err(|_| worker::Error::RustError("AI_WORKER_SECRET secret not found".to_string()))?
+        .to_string();
+
+    let timestamp = now_secs() as i64;
+    let mut init = RequestInit::new();
+    init.with_method(SIGNED_METHOD);
+    init.with_body(Some(body.into()));
+    let headers = Headers::new();
+    headers.set("Content-Type", "application/json")?;
+    headers.set(TIMESTAMP_HEADER, &timestamp.to_string())?;
+    headers.set(
+        SIGNATURE_HEADER,
+        &sign(&secret, timestamp, SIGNED_METHOD.as_ref(), path, body),
+    )?;
+    init.with_headers(headers);
+
+    Request::new_with_init(
+        &format!("{}{}", base_url.trim_end_matches('/'), path),
+        &init,
+    )
+}
+
+fn worker_url(env: &Env, var: &str, default: &str) -> String {
+    env.var(var)
+        .map(|v| v.to_string())
+        .unwrap_or_else(|_| default.to_string())
+}
+
+/// A signed POST of a JSON body to the AI worker at AI_WORKER_URL
+pub fn ai_worker_request(env: &Env, path: &str, body: &str) -> Result<Request> {
+    let url = worker_url(env, "AI_WORKER_URL", "http://localhost:8002");
+    signed_request(env, &url, path, body)
+}
+
+/// A signed POST of a JSON body to the Gmail scanner at GMAIL_SCANNER_URL
+pub fn gmail_scanner_request(env: &Env, path: &str, body: &str) -> Result<Request> {
+    let url = worker_url(env, "GMAIL_SCANNER_URL", "http://localhost:8001");
+    signed_request(env, &url, path, body)
+}
+
+/// Check that a request from another worker is signed with the shared
+/// AI_WORKER_SECRET. The body is read from a copy so the handler can still
+/// parse it.
+pub async fn require_signature(req: &Request, env: &Env) -> anyhow::Result<()> {
+    let secret = env
+        .secret("AI_WORKER_SECRET")
+        .map_err(|_| anyhow!("AI_WORKER_SECRET secret not found"))?
+        .to_string();
+
+    let headers = req.headers();
+    let timestamp = headers
+        .get(TIMESTAMP_HEADER)
+        .ok()
+        .flatten()
+        .ok_or_else(|| anyhow!("Missing {} header", TIMESTAMP_HEADER))?;
+    let signature = headers
+        .get(SIGNATURE_HEADER)
+        .ok()
+        .flatten()
+        .ok_or_else(|| anyhow!("Missing {} header", SIGNATURE_HEADER))?;
+
+    let body = req
+        .clone()
+        .map_err(|e| anyhow!("Failed to copy request: {}", e))?
+        .text()
+        .await
+        .map_err(|e| anyhow!("Failed to read request body: {}", e))?;
+
+    verify(
+        &secret,
+        &timestamp,
+        &signature,
+        req.method().as_ref(),
+        &req.path(),
+        &body,
+        now_secs() as i64,
+    )
+    .map_err(|e| anyhow!(e))
+}
@@ -0,0 +1,97 @@
+//! Reading and counting users' daily usage in D1
+
+use crate::quota::{seconds_until_reset, Quota, QuotaExceeded};
+use anyhow::Result;
+use worker::*;
+
+/// The user's own daily limit of a kind, or the default one
+const DAILY_LIMIT: &str = "COALESCE(
+       (SELECT daily_limit FROM usage_limits WHERE user_id = ?1 AND kind = ?2),
+       (SELECT daily_limit FROM usage_limits WHERE user_id = '' AND kind = ?2),
+       0
+     )";
+
+/// A user's daily limit of one kind of usage, with what they've used today
+pub async fn get_quota(env: &Env, user_id: &str, kind: &str) -> Result<Quota> {
+    let db = env.d1("DB")?;
+
+    #[derive(serde::Deserialize)]
+    struct QuotaRow {
+        daily_limit: u32,
+        used: u32,
+    }
+
+    let row = db
+        .prepare(format!(
+            "SELECT {} AS daily_limit,
+               COALESCE(
+                 (SELECT count FROM usage_counters WHERE user_id = ?1 AND kind = ?2 AND day = date('now')),
+                 0
+               ) AS used",
+            DAILY_LIMIT
+        ))
+        .bind(&[user_id.into(), kind.into()])?
+        .first::<QuotaRow>(None)
+        .await?;
+
+    Ok(Quota {
+        kind: kind.to_string(),
+        limit: row.as_ref().map(|r| r.daily_limit).unwrap_or(0),
+        used: row.as_ref().map(|r| r.used).unwrap_or(0),
+    })
+}
+
+/// Add to what a user has used today
+pub async fn record_usage(env: &Env, user_id: &str, kind: &str, amount: u32) -> Result<()> {
+    let db = env.d1("DB")?;
+    db.prepare(
+        "INSERT INTO usage_counters (user_id, day, kind, count) VALUES (?, date('now'), ?, ?)
+         ON CONFLICT (user_id, day, kind) DO UPDATE SET count = count + excluded.count",
+    )
+    .bind(&[user_id.into(), kind.into(), amount.into()])?
+    .run()
+    .await?;
+    Ok(())
+}
+
+/// Count `amount` more as used when it fits in the user's limit. Checking and
+/// counting is one conditional upsert, so concurrent requests can't both take
+/// the last of the quota. Going over the limit fails with a `QuotaExceeded`.
+pub async fn use_quota(env: &Env, user_id: &str, kind: &str, amount: u32) -> Result<()> {
+    let db = env.d1("DB")?;
+    let counted = db
+        .prepare(format!(
+            "INSERT INTO usage_counters (user_id, day, kind, count)
+             SELECT ?1, date('now'), ?2, ?3 WHERE ?3 <= {limit}
+             ON CONFLICT (user_id, day, kind) DO UPDATE SET count = count + excluded.count
+             WHERE usage_counters.count + excluded.count <= {limit}
+             RETURNING count",
+            limit = DAILY_LIMIT
+        ))
+        .bind(&[user_id.into(), kind.into(), amount.into()])?
+        .first::<serde::de::IgnoredAny>(None)
+        .await?;
+    if counted.is_some() {
+        return Ok(());
+    }
+
+    let quota = get_quota(env, user_id, kind).await?;
+    Err(QuotaExceeded {
+        kind: kind.to_string(),
+        limit: quota.limit,
+        retry_after: seconds_until_reset(now_secs()),
+    }
+    .into())
+}
+
+/// Current Unix time in seconds, for working out when quotas reset
+pub fn now_secs() -> u64 {
+    Date::now().as_millis() / 1000
+}
+
+/// 429 response for a request over a limit, saying when to retry
+pub fn too_many_requests(e: &QuotaExceeded) -> worker::Result<Response> {
+    let headers = Headers::new();
+    headers.set("Retry-After", &e.retry_after.to_string())?;
+    Ok(Response::error(e.to_string(), 429)?.with_headers(headers))
+}
@@ -0,0 +1,48 @@
+//! Tests for per-user daily quotas
+
+use api_shared::quota::{seconds_until_reset, Quota, AI_CALLS, MESSAGES};
+
+/// 2024-01-01T00:00:00Z
+const MIDNIGHT: u64 = 1_704_067_200;
+
+fn quota(kind: &str, limit: u32, used: u32) -> Quota {
+    Quota {
+        kind: kind.to_string(),
+        limit,
+        used,
+    }
+}
+
+#[test]
+fn test_seconds_until_reset_counts_to_next_midnight_utc() {
+    assert_eq!(seconds_until_reset(MIDNIGHT), 86_400);
+    assert_eq!(seconds_until_reset(MIDNIGHT + 1), 86_399);
+    assert_eq!(seconds_until_reset(MIDNIGHT + 23 * 3600 + 59 * 60 + 59), 1);
+}
+
+#[test]
+fn test_check_allows_usage_up_to_the_limit() {
+    let ai = quota(AI_CALLS, 10, 7);
+    assert_eq!(ai.remaining(), 3);
+    assert!(ai.check(3, MIDNIGHT).is_ok());
+    assert!(ai.check(4, MIDNIGHT).is_err());
+
+    // Limits lowered below today's usage leave nothing
+    let over = quota(MESSAGES, 100, 150);
+    assert_eq!(over.remaining(), 0);
+    assert!(over.check(1, MIDNIGHT).is_err());
+    assert!(over.check(0, MIDNIGHT).is_err());
+}
+
+#[test]
+fn test_exceeded_quota_says_when_to_retry() {
+    let err = quota(AI_CALLS, 10, 10)
+        .check(1, MIDNIGHT + 22 * 3600 + 30 * 60)
+        .unwrap_err();
+    assert_eq!(err.retry_after, 5400);
+    assert_eq!(err.limit, 10);
+    assert_eq!(
+        err.to_string(),
+        "Daily limit of 10 AI calls reached, try again in 1h 30m"
+    );
+}
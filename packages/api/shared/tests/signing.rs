@@ -0,0 +1,110 @@
+//! Tests for signed requests between the workers
+
+use api_shared::signing::{sign, verify, MAX_CLOCK_SKEW_SECS};
+
+const SECRET: &str = "secret";
+const NOW: i64 = 1_700_000_000;
+const BODY: &str = r#"{"user_id":"u1"}"#;
+
+#[test]
+fn test_sign_is_hmac_sha256_of_the_request() {
+    assert_eq!(
+        sign(SECRET, NOW, "POST", "/process/batch", BODY),
+        "915dd66195574c2c3be19294b57c2e054be07ec707e479193b496c784c158fd8"
+    );
+    // Methods are compared case-insensitively
+    assert_eq!(
+        sign(SECRET, NOW, "post", "/process/batch", BODY),
+        sign(SECRET, NOW, "POST", "/process/batch", BODY)
+    );
+}
+
+#[test]
+fn test_verify_accepts_a_fresh_signature() {
+    let signature = sign(SECRET, NOW, "POST", "/process/batch", BODY);
+    let timestamp = NOW.to_string();
+    assert_eq!(
+        verify(
+            SECRET,
+            &timestamp,
+            &signature,
+            "POST",
+            "/process/batch",
+            BODY,
+            NOW + 10
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        verify(
+            SECRET,
+            &timestamp,
+            &signature.to_uppercase(),
+            "POST",
+            "/process/batch",
+            BODY,
+            NOW - MAX_CLOCK_SKEW_SECS
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_verify_rejects_changed_requests() {
+    let signature = sign(SECRET, NOW, "POST", "/process/e1", BODY);
+    let timestamp = NOW.to_string();
+    let check = |secret: &str, path: &str, body: &str| {
+        verify(secret, &timestamp, &signature, "POST", path, body, NOW)
+    };
+
+    assert!(check(SECRET, "/process/e1", BODY).is_ok());
+    assert!(check("other", "/process/e1", BODY).is_err());
+    assert!(check(SECRET, "/process/e2", BODY).is_err());
+    assert!(check(SECRET, "/process/e1", r#"{"user_id":"u2"}"#).is_err());
+    assert!(verify(
+        SECRET,
+        &timestamp,
+        &signature,
+        "GET",
+        "/process/e1",
+        BODY,
+        NOW
+    )
+    .is_err());
+}
+
+#[test]
+fn test_verify_rejects_expired_or_malformed_headers() {
+    let signature = sign(SECRET, NOW, "POST", "/sandbox", BODY);
+
+    assert_eq!(
+        verify(
+            SECRET,
+            &NOW.to_string(),
+            &signature,
+            "POST",
+            "/sandbox",
+            BODY,
+            NOW + MAX_CLOCK_SKEW_SECS + 1
+        ),
+        Err("Signature has expired".to_string())
+    );
+    assert_eq!(
+        verify(
+            SECRET,
+            "yesterday",
+            &signature,
+            "POST",
+            "/sandbox",
+            BODY,
+            NOW
+        ),
+        Err("Invalid signature timestamp".to_string())
+    );
+    for bad in ["", "abc", "zz", &signature[..62]] {
+        assert_eq!(
+            verify(SECRET, &NOW.to_string(), bad, "POST", "/sandbox", BODY, NOW),
+            Err("Invalid signature".to_string())
+        );
+    }
+}
@@ -1,5 +1,6 @@
 //! Comment form component for adding and displaying comments
 
+use crate::components::markdown::Markdown;
 use crate::services::comments_service::Comment;
 use crate::state::{use_auth, use_comments};
 use crate::utils::format_relative_time;
@@ -25,7 +26,7 @@ pub fn CommentForm(job_id: String) -> Element {
             }
 
             let job_id_for_spawn = job_id_clone.clone();
-            comments.add_comment(job_id_for_spawn, text.clone());
+            comments.add_comment(job_id_for_spawn, text.clone(), None);
             *comment_text_signal.write() = String::new();
         }
     };
@@ -36,6 +37,22 @@ pub fn CommentForm(job_id: String) -> Element {
         .as_ref()
         .map(|e| format!("Failed: {}", e));
 
+    // Top-level comments (newest first) with their replies (oldest first)
+    let all_comments = comments_state.comments.read().clone();
+    let threads: Vec<(Comment, Vec<Comment>)> = all_comments
+        .iter()
+        .filter(|c| c.parent_id.is_none())
+        .map(|parent| {
+            let mut replies: Vec<Comment> = all_comments
+                .iter()
+                .filter(|c| c.parent_id.as_deref() == Some(parent.id.as_str()))
+                .cloned()
+                .collect();
+            replies.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+            (parent.clone(), replies)
+        })
+        .collect();
+
     rsx! {
         div {
             class: "space-y-6",
@@ -60,7 +77,7 @@ pub fn CommentForm(job_id: String) -> Element {
                             id: "comment",
                             name: "comment",
                             rows: "2",
-                            placeholder: "Add your comment...",
+                            placeholder: "Add your comment... (Markdown supported)",
                             class: "block w-full resize-none bg-transparent px-3 py-1.5 text-base text-gray-900 placeholder:text-gray-400 focus:outline focus:outline-0 sm:text-sm/6 dark:text-white dark:placeholder:text-gray-500",
                             value: "{comment_text}",
                             oninput: move |e: Event<FormData>| {
@@ -96,13 +113,33 @@ pub fn CommentForm(job_id: String) -> Element {
             }
 
             // Comments list
-            if !comments_state.comments.read().is_empty() {
+            if !threads.is_empty() {
                 ul {
                     role: "list",
                     class: "space-y-6",
-                    for comment in comments_state.comments.read().iter() {
+                    for (comment, replies) in threads.into_iter() {
                         CommentItem {
+                            key: "{comment.id}",
+                            job_id: job_id.clone(),
                             comment: comment.clone(),
+                            is_reply: false,
+                        }
+                        if !replies.is_empty() {
+                            li {
+                                class: "ml-10",
+                                ul {
+                                    role: "list",
+                                    class: "space-y-4",
+                                    for reply in replies.into_iter() {
+                                        CommentItem {
+                                            key: "{reply.id}",
+                                            job_id: job_id.clone(),
+                                            comment: reply.clone(),
+                                            is_reply: true,
+                                        }
+                                    }
+                                }
+                            }
                         }
                     }
                 }
@@ -113,17 +150,31 @@ pub fn CommentForm(job_id: String) -> Element {
 
 /// Individual comment item component
 #[component]
-fn CommentItem(comment: Comment) -> Element {
+fn CommentItem(job_id: String, comment: Comment, is_reply: bool) -> Element {
     let auth = use_auth();
+    let comments_state = use_comments();
+    let mut editing = use_signal(|| false);
+    let mut edit_text = use_signal(String::new);
+    let mut replying = use_signal(|| false);
+    let mut reply_text = use_signal(String::new);
+
     let user_name = comment.name.as_deref().unwrap_or("Unknown").to_string();
     let user_picture = comment.picture.clone();
     let content = comment.content.clone();
     let created_at = comment.created_at.clone();
+    let is_deleted = comment.deleted_at.is_some();
+    let is_edited = comment.updated_at.is_some() && !is_deleted;
 
     // Format relative time
     let user = auth.user.read();
     let timezone = user.as_ref().and_then(|u| u.timezone.as_deref());
     let relative_time = format_relative_time(&created_at, timezone);
+    let is_author = user
+        .as_ref()
+        .map(|u| u.id == comment.user_id)
+        .unwrap_or(false);
+
+    let action_class = "text-xs font-medium text-gray-500 hover:text-gray-700 dark:text-gray-400 dark:hover:text-gray-200";
 
     rsx! {
         li {
@@ -149,7 +200,17 @@ fn CommentItem(comment: Comment) -> Element {
                             class: "font-medium text-gray-900 dark:text-white",
                             {user_name}
                         }
-                        " commented"
+                        if is_reply {
+                            " replied"
+                        } else {
+                            " commented"
+                        }
+                        if is_edited {
+                            span {
+                                class: "ml-1 italic",
+                                "(edited)"
+                            }
+                        }
                     }
                     time {
                         datetime: created_at,
@@ -157,10 +218,131 @@ fn CommentItem(comment: Comment) -> Element {
                         {relative_time}
                     }
                 }
-                        p {
-                            class: "text-sm/6 text-gray-500 dark:text-gray-400",
-                            {content}
+                if is_deleted {
+                    p {
+                        class: "text-sm/6 italic text-gray-400 dark:text-gray-500",
+                        "This comment was deleted"
+                    }
+                } else if editing() {
+                    div {
+                        class: "space-y-2",
+                        textarea {
+                            class: "block w-full resize-none rounded-md bg-transparent px-3 py-1.5 text-sm/6 text-gray-900 outline outline-1 -outline-offset-1 outline-gray-300 dark:text-white dark:outline-white/10",
+                            rows: "3",
+                            value: "{edit_text}",
+                            oninput: move |e: Event<FormData>| {
+                                *edit_text.write() = e.value();
+                            },
+                        }
+                        div {
+                            class: "flex gap-x-3",
+                            button {
+                                class: action_class,
+                                disabled: edit_text().trim().is_empty(),
+                                onclick: {
+                                    let job_id = job_id.clone();
+                                    let comment_id = comment.id.clone();
+                                    move |_| {
+                                        let text = edit_text().trim().to_string();
+                                        if text.is_empty() {
+                                            return;
+                                        }
+                                        comments_state.edit_comment(job_id.clone(), comment_id.clone(), text);
+                                        *editing.write() = false;
+                                    }
+                                },
+                                "Save"
+                            }
+                            button {
+                                class: action_class,
+                                onclick: move |_| *editing.write() = false,
+                                "Cancel"
+                            }
+                        }
+                    }
+                } else {
+                    Markdown {
+                        content: content.clone(),
+                        class: "text-sm/6 text-gray-500 dark:text-gray-400",
+                    }
+                    div {
+                        class: "mt-2 flex gap-x-3",
+                        if !is_reply {
+                            button {
+                                class: action_class,
+                                onclick: move |_| {
+                                    let open = replying();
+                                    *replying.write() = !open;
+                                },
+                                "Reply"
+                            }
+                        }
+                        if is_author {
+                            button {
+                                class: action_class,
+                                onclick: {
+                                    let content = content.clone();
+                                    move |_| {
+                                        *edit_text.write() = content.clone();
+                                        *editing.write() = true;
+                                    }
+                                },
+                                "Edit"
+                            }
+                            button {
+                                class: "text-xs font-medium text-red-600 hover:text-red-500 dark:text-red-400",
+                                onclick: {
+                                    let job_id = job_id.clone();
+                                    let comment_id = comment.id.clone();
+                                    move |_| {
+                                        comments_state.delete_comment(job_id.clone(), comment_id.clone());
+                                    }
+                                },
+                                "Delete"
+                            }
+                        }
+                    }
+                }
+                if replying() && !is_deleted {
+                    div {
+                        class: "mt-3 space-y-2",
+                        textarea {
+                            class: "block w-full resize-none rounded-md bg-transparent px-3 py-1.5 text-sm/6 text-gray-900 outline outline-1 -outline-offset-1 outline-gray-300 placeholder:text-gray-400 dark:text-white dark:outline-white/10 dark:placeholder:text-gray-500",
+                            rows: "2",
+                            placeholder: "Write a reply...",
+                            value: "{reply_text}",
+                            oninput: move |e: Event<FormData>| {
+                                *reply_text.write() = e.value();
+                            },
                         }
+                        div {
+                            class: "flex gap-x-3",
+                            button {
+                                class: action_class,
+                                disabled: reply_text().trim().is_empty(),
+                                onclick: {
+                                    let job_id = job_id.clone();
+                                    let parent_id = comment.id.clone();
+                                    move |_| {
+                                        let text = reply_text().trim().to_string();
+                                        if text.is_empty() {
+                                            return;
+                                        }
+                                        comments_state.add_comment(job_id.clone(), text, Some(parent_id.clone()));
+                                        *reply_text.write() = String::new();
+                                        *replying.write() = false;
+                                    }
+                                },
+                                "Reply"
+                            }
+                            button {
+                                class: action_class,
+                                onclick: move |_| *replying.write() = false,
+                                "Cancel"
+                            }
+                        }
+                    }
+                }
             }
         }
     }
@@ -0,0 +1,169 @@
+//! Companies list component
+
+use crate::services::companies_service::{CompaniesService, Company};
+use crate::state::use_auth;
+use crate::utils::format_date;
+use dioxus::prelude::*;
+use dioxus_router::Link;
+
+/// Companies list component
+#[component]
+pub fn CompaniesList() -> Element {
+    let auth = use_auth();
+    let companies = use_signal(Vec::<Company>::new);
+    let loading = use_signal(|| true);
+    let error = use_signal(|| None::<String>);
+    let mut search = use_signal(String::new);
+
+    let user = auth.user.read();
+    let timezone = user.as_ref().and_then(|u| u.timezone.as_deref());
+
+    // Fetch companies on mount
+    use_effect(move || {
+        let mut companies = companies;
+        let mut loading = loading;
+        let mut error = error;
+        spawn(async move {
+            *loading.write() = true;
+            match CompaniesService::fetch_companies().await {
+                Ok(list) => {
+                    *companies.write() = list;
+                    *error.write() = None;
+                }
+                Err(e) => *error.write() = Some(format!("Failed to load companies: {}", e)),
+            }
+            *loading.write() = false;
+        });
+    });
+
+    let query = search().trim().to_lowercase();
+    let visible: Vec<Company> = companies()
+        .into_iter()
+        .filter(|c| {
+            query.is_empty()
+                || c.name.to_lowercase().contains(&query)
+                || c.domain
+                    .as_deref()
+                    .is_some_and(|d| d.contains(query.as_str()))
+        })
+        .collect();
+
+    rsx! {
+        div {
+            class: "px-4 sm:px-6 lg:px-8 py-6",
+            div {
+                class: "sm:flex sm:items-center",
+                div {
+                    class: "sm:flex-auto",
+                    h1 {
+                        class: "text-2xl font-semibold text-gray-900 dark:text-white",
+                        "Companies"
+                    }
+                    p {
+                        class: "mt-2 text-sm text-gray-700 dark:text-gray-300",
+                        "Every employer you have applied to or been in touch with."
+                    }
+                }
+                div {
+                    class: "mt-4 sm:ml-16 sm:mt-0 sm:flex-none",
+                    input {
+                        r#type: "search",
+                        placeholder: "Search companies",
+                        class: "block w-64 rounded-md bg-white px-3 py-1.5 text-sm text-gray-900 outline outline-1 -outline-offset-1 outline-gray-300 placeholder:text-gray-400 dark:bg-white/5 dark:text-white dark:outline-white/10",
+                        value: "{search}",
+                        oninput: move |e: Event<FormData>| *search.write() = e.value(),
+                    }
+                }
+            }
+
+            if let Some(err) = error() {
+                div {
+                    class: "mt-6 rounded-md bg-red-50 dark:bg-red-900/20 p-4",
+                    p {
+                        class: "text-sm text-red-800 dark:text-red-200",
+                        {err}
+                    }
+                }
+            }
+
+            if loading() {
+                div {
+                    class: "mt-8 text-center text-gray-500 dark:text-gray-400",
+                    "Loading companies..."
+                }
+            } else if visible.is_empty() {
+                div {
+                    class: "mt-8 text-center text-gray-500 dark:text-gray-400",
+                    if query.is_empty() {
+                        "No companies yet. Companies are created from the jobs you add."
+                    } else {
+                        "No companies match your search."
+                    }
+                }
+            } else {
+                div {
+                    class: "mt-8 overflow-x-auto",
+                    table {
+                        class: "min-w-full divide-y divide-gray-300 dark:divide-white/15",
+                        thead {
+                            tr {
+                                th {
+                                    class: "py-3.5 pl-4 pr-3 text-left text-sm font-semibold text-gray-900 dark:text-white sm:pl-0",
+                                    "Company"
+                                }
+                                th {
+                                    class: "px-3 py-3.5 text-left text-sm font-semibold text-gray-900 dark:text-white",
+                                    "Domain"
+                                }
+                                th {
+                                    class: "px-3 py-3.5 text-right text-sm font-semibold text-gray-900 dark:text-white",
+                                    "Jobs"
+                                }
+                                th {
+                                    class: "px-3 py-3.5 text-right text-sm font-semibold text-gray-900 dark:text-white",
+                                    "Contacts"
+                                }
+                                th {
+                                    class: "px-3 py-3.5 text-left text-sm font-semibold text-gray-900 dark:text-white",
+                                    "Last activity"
+                                }
+                            }
+                        }
+                        tbody {
+                            class: "divide-y divide-gray-200 dark:divide-white/10",
+                            for company in visible.iter() {
+                                tr {
+                                    key: "{company.id}",
+                                    td {
+                                        class: "whitespace-nowrap py-4 pl-4 pr-3 text-sm font-medium sm:pl-0",
+                                        Link {
+                                            to: format!("/companies/{}", company.id),
+                                            class: "text-gray-900 dark:text-white hover:text-indigo-600 dark:hover:text-indigo-400",
+                                            {company.name.clone()}
+                                        }
+                                    }
+                                    td {
+                                        class: "whitespace-nowrap px-3 py-4 text-sm text-gray-500 dark:text-gray-400",
+                                        {company.domain.clone().unwrap_or_default()}
+                                    }
+                                    td {
+                                        class: "whitespace-nowrap px-3 py-4 text-right text-sm text-gray-500 dark:text-gray-400",
+                                        {format!("{} active / {}", company.active_job_count, company.job_count)}
+                                    }
+                                    td {
+                                        class: "whitespace-nowrap px-3 py-4 text-right text-sm text-gray-500 dark:text-gray-400",
+                                        "{company.contact_count}"
+                                    }
+                                    td {
+                                        class: "whitespace-nowrap px-3 py-4 text-sm text-gray-500 dark:text-gray-400",
+                                        {company.last_activity.as_deref().map(|d| format_date(d, timezone)).unwrap_or_default()}
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,415 @@
+//! Company details component
+
+use crate::components::button::{Button, ButtonVariant};
+use crate::services::companies_service::{
+    CompaniesService, CompanyDetails as CompanyDetailsData, UpdateCompanyRequest,
+};
+use crate::state::use_auth;
+use crate::utils::{format_date, parse_email_address};
+use dioxus::prelude::*;
+use dioxus_router::Link;
+
+const INPUT_CLASS: &str = "block w-full rounded-md bg-white px-3 py-1.5 text-sm text-gray-900 outline outline-1 -outline-offset-1 outline-gray-300 placeholder:text-gray-400 dark:bg-white/5 dark:text-white dark:outline-white/10";
+
+/// Company page: details, outcomes, jobs, contacts and emails for one employer
+#[component]
+pub fn CompanyDetails(company_id: String) -> Element {
+    let auth = use_auth();
+    let details = use_signal(|| None::<CompanyDetailsData>);
+    let loading = use_signal(|| true);
+    let error = use_signal(|| None::<String>);
+    let mut editing = use_signal(|| false);
+    let mut saving = use_signal(|| false);
+    let mut edit_name = use_signal(String::new);
+    let mut edit_domain = use_signal(String::new);
+    let mut edit_website = use_signal(String::new);
+    let mut edit_notes = use_signal(String::new);
+
+    let user = auth.user.read();
+    let timezone = user.as_ref().and_then(|u| u.timezone.as_deref());
+
+    let load = {
+        let company_id = company_id.clone();
+        let mut details = details;
+        let mut loading = loading;
+        let mut error = error;
+        move || {
+            let company_id = company_id.clone();
+            spawn(async move {
+                *loading.write() = true;
+                match CompaniesService::fetch_company(company_id).await {
+                    Ok(data) => {
+                        *details.write() = Some(data);
+                        *error.write() = None;
+                    }
+                    Err(e) => *error.write() = Some(format!("Failed to load company: {}", e)),
+                }
+                *loading.write() = false;
+            });
+        }
+    };
+
+    // Fetch company on mount
+    use_effect({
+        let load = load.clone();
+        move || load()
+    });
+
+    let Some(data) = details() else {
+        return rsx! {
+            div {
+                class: "px-4 sm:px-6 lg:px-8 py-6 text-center text-gray-500 dark:text-gray-400",
+                if loading() {
+                    "Loading company..."
+                } else {
+                    {error().unwrap_or_else(|| "Company not found".to_string())}
+                }
+            }
+        };
+    };
+
+    let company = data.company.clone();
+    let total_jobs: i64 = data.outcomes.iter().map(|o| o.count).sum();
+
+    rsx! {
+        div {
+            class: "px-4 sm:px-6 lg:px-8 py-6 space-y-6",
+            Link {
+                to: "/companies",
+                class: "text-sm font-medium text-indigo-600 dark:text-indigo-400 hover:text-indigo-500",
+                "← All companies"
+            }
+
+            // Company header and editable details
+            div {
+                class: "rounded-lg bg-white dark:bg-gray-800 p-5 ring-1 ring-inset ring-gray-200 dark:ring-white/15",
+                if editing() {
+                    div {
+                        class: "grid grid-cols-1 gap-4 sm:grid-cols-2",
+                        label {
+                            class: "block text-sm font-medium text-gray-700 dark:text-gray-300",
+                            "Name"
+                            input {
+                                r#type: "text",
+                                class: "mt-1 {INPUT_CLASS}",
+                                value: "{edit_name}",
+                                oninput: move |e: Event<FormData>| *edit_name.write() = e.value(),
+                            }
+                        }
+                        label {
+                            class: "block text-sm font-medium text-gray-700 dark:text-gray-300",
+                            "Email domain"
+                            input {
+                                r#type: "text",
+                                placeholder: "acme.com",
+                                class: "mt-1 {INPUT_CLASS}",
+                                value: "{edit_domain}",
+                                oninput: move |e: Event<FormData>| *edit_domain.write() = e.value(),
+                            }
+                        }
+                        label {
+                            class: "block text-sm font-medium text-gray-700 dark:text-gray-300 sm:col-span-2",
+                            "Website"
+                            input {
+                                r#type: "url",
+                                placeholder: "https://acme.com",
+                                class: "mt-1 {INPUT_CLASS}",
+                                value: "{edit_website}",
+                                oninput: move |e: Event<FormData>| *edit_website.write() = e.value(),
+                            }
+                        }
+                        label {
+                            class: "block text-sm font-medium text-gray-700 dark:text-gray-300 sm:col-span-2",
+                            "Notes"
+                            textarea {
+                                rows: 4,
+                                class: "mt-1 {INPUT_CLASS}",
+                                value: "{edit_notes}",
+                                oninput: move |e: Event<FormData>| *edit_notes.write() = e.value(),
+                            }
+                        }
+                        div {
+                            class: "flex gap-x-2 sm:col-span-2",
+                            Button {
+                                variant: ButtonVariant::Primary,
+                                disabled: saving(),
+                                onclick: {
+                                    let company_id = company_id.clone();
+                                    let load = load.clone();
+                                    let mut error = error;
+                                    move |_| {
+                                        let company_id = company_id.clone();
+                                        let load = load.clone();
+                                        let request = UpdateCompanyRequest {
+                                            name: Some(edit_name()),
+                                            domain: Some(edit_domain()),
+                                            website: Some(edit_website()),
+                                            notes: Some(edit_notes()),
+                                        };
+                                        spawn(async move {
+                                            *saving.write() = true;
+                                            match CompaniesService::update_company(company_id, request).await {
+                                                Ok(_) => {
+                                                    *editing.write() = false;
+                                                    load();
+                                                }
+                                                Err(e) => *error.write() = Some(format!("Failed to save company: {}", e)),
+                                            }
+                                            *saving.write() = false;
+                                        });
+                                    }
+                                },
+                                if saving() {
+                                    "Saving..."
+                                } else {
+                                    "Save"
+                                }
+                            }
+                            Button {
+                                variant: ButtonVariant::Secondary,
+                                onclick: move |_| *editing.write() = false,
+                                "Cancel"
+                            }
+                        }
+                    }
+                } else {
+                    div {
+                        class: "flex items-start justify-between gap-x-4",
+                        div {
+                            class: "min-w-0",
+                            h1 {
+                                class: "text-2xl font-semibold text-gray-900 dark:text-white",
+                                {company.name.clone()}
+                            }
+                            div {
+                                class: "mt-1 flex flex-wrap gap-x-4 text-sm text-gray-500 dark:text-gray-400",
+                                if let Some(domain) = company.domain.clone() {
+                                    span { "@{domain}" }
+                                }
+                                if let Some(website) = company.website.clone() {
+                                    a {
+                                        href: "{website}",
+                                        target: "_blank",
+                                        rel: "noopener noreferrer",
+                                        class: "hover:text-indigo-600 dark:hover:text-indigo-400",
+                                        {website.clone()}
+                                    }
+                                }
+                            }
+                            if let Some(notes) = company.notes.clone() {
+                                p {
+                                    class: "mt-3 whitespace-pre-line text-sm text-gray-700 dark:text-gray-300",
+                                    {notes}
+                                }
+                            }
+                        }
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            onclick: {
+                                let company = company.clone();
+                                move |_| {
+                                    *edit_name.write() = company.name.clone();
+                                    *edit_domain.write() = company.domain.clone().unwrap_or_default();
+                                    *edit_website.write() = company.website.clone().unwrap_or_default();
+                                    *edit_notes.write() = company.notes.clone().unwrap_or_default();
+                                    *editing.write() = true;
+                                }
+                            },
+                            "Edit"
+                        }
+                    }
+                }
+                if let Some(err) = error() {
+                    p {
+                        class: "mt-3 text-sm text-red-600 dark:text-red-400",
+                        {err}
+                    }
+                }
+            }
+
+            // Outcomes: current status of the company's jobs
+            if !data.outcomes.is_empty() {
+                div {
+                    class: "grid grid-cols-2 gap-4 sm:grid-cols-3 lg:grid-cols-6",
+                    div {
+                        class: "rounded-lg bg-white dark:bg-gray-800 p-4 ring-1 ring-inset ring-gray-200 dark:ring-white/15",
+                        p {
+                            class: "text-sm text-gray-500 dark:text-gray-400",
+                            "Total"
+                        }
+                        p {
+                            class: "mt-1 text-2xl font-semibold text-gray-900 dark:text-white",
+                            "{total_jobs}"
+                        }
+                    }
+                    for outcome in data.outcomes.iter() {
+                        div {
+                            key: "{outcome.status_id}",
+                            class: "rounded-lg bg-white dark:bg-gray-800 p-4 ring-1 ring-inset ring-gray-200 dark:ring-white/15",
+                            p {
+                                class: "text-sm text-gray-500 dark:text-gray-400",
+                                {outcome.status_name.clone()}
+                            }
+                            p {
+                                class: "mt-1 text-2xl font-semibold text-gray-900 dark:text-white",
+                                "{outcome.count}"
+                            }
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "grid grid-cols-1 gap-6 lg:grid-cols-3",
+                // Jobs and emails
+                div {
+                    class: "space-y-6 lg:col-span-2",
+                    div {
+                        class: "rounded-lg bg-white dark:bg-gray-800 p-5 ring-1 ring-inset ring-gray-200 dark:ring-white/15",
+                        h2 {
+                            class: "text-lg font-semibold text-gray-900 dark:text-white mb-3",
+                            "Jobs"
+                        }
+                        if data.jobs.is_empty() {
+                            p {
+                                class: "text-sm text-gray-500 dark:text-gray-400",
+                                "No jobs at this company."
+                            }
+                        }
+                        ul {
+                            role: "list",
+                            class: "divide-y divide-gray-100 dark:divide-white/5",
+                            for job in data.jobs.iter() {
+                                li {
+                                    key: "{job.id}",
+                                    class: "flex items-center justify-between gap-x-4 py-3",
+                                    div {
+                                        class: "min-w-0",
+                                        Link {
+                                            to: format!("/jobs/{}", job.id),
+                                            class: "block truncate text-sm font-medium text-gray-900 dark:text-white hover:text-indigo-600 dark:hover:text-indigo-400",
+                                            {job.title.clone()}
+                                        }
+                                        p {
+                                            class: "text-xs text-gray-500 dark:text-gray-400",
+                                            {[
+                                                job.location.clone(),
+                                                job.source_name.clone(),
+                                                job.created_at.as_deref().map(|d| format_date(d, timezone)),
+                                                job.archived_at.as_ref().map(|_| "Archived".to_string()),
+                                            ]
+                                            .into_iter()
+                                            .flatten()
+                                            .collect::<Vec<_>>()
+                                            .join(" · ")}
+                                        }
+                                    }
+                                    span {
+                                        class: "flex-none rounded-full bg-gray-100 dark:bg-white/10 px-2 py-0.5 text-xs font-medium text-gray-700 dark:text-gray-300",
+                                        {job.status_name.clone().unwrap_or_default()}
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    div {
+                        class: "rounded-lg bg-white dark:bg-gray-800 p-5 ring-1 ring-inset ring-gray-200 dark:ring-white/15",
+                        h2 {
+                            class: "text-lg font-semibold text-gray-900 dark:text-white mb-3",
+                            "Emails"
+                        }
+                        if data.emails.is_empty() {
+                            p {
+                                class: "text-sm text-gray-500 dark:text-gray-400",
+                                "No emails from this company."
+                            }
+                        }
+                        ul {
+                            role: "list",
+                            class: "divide-y divide-gray-100 dark:divide-white/5",
+                            for email in data.emails.iter() {
+                                li {
+                                    key: "{email.gmail_id}",
+                                    class: "py-3",
+                                    div {
+                                        class: "flex justify-between gap-x-4",
+                                        p {
+                                            class: "truncate text-sm font-medium text-gray-900 dark:text-white",
+                                            {email.subject.clone().unwrap_or_else(|| "(no subject)".to_string())}
+                                        }
+                                        p {
+                                            class: "flex-none text-xs text-gray-500 dark:text-gray-400",
+                                            {email.date.as_deref().map(|d| format_date(d, timezone)).unwrap_or_default()}
+                                        }
+                                    }
+                                    div {
+                                        class: "flex justify-between gap-x-4 text-xs text-gray-500 dark:text-gray-400",
+                                        span {
+                                            class: "truncate",
+                                            {email.from.as_deref().map(|f| {
+                                                let (name, address) = parse_email_address(f);
+                                                name.unwrap_or(address)
+                                            }).unwrap_or_default()}
+                                        }
+                                        if let (Some(job_id), Some(job_title)) = (email.job_id.clone(), email.job_title.clone()) {
+                                            Link {
+                                                to: format!("/jobs/{}", job_id),
+                                                class: "flex-none truncate hover:text-indigo-600 dark:hover:text-indigo-400",
+                                                {job_title}
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // Contacts
+                div {
+                    class: "rounded-lg bg-white dark:bg-gray-800 p-5 ring-1 ring-inset ring-gray-200 dark:ring-white/15 h-fit",
+                    h2 {
+                        class: "text-lg font-semibold text-gray-900 dark:text-white mb-1",
+                        "Contacts"
+                    }
+                    p {
+                        class: "text-xs text-gray-500 dark:text-gray-400 mb-3",
+                        if let Some(domain) = company.domain.clone() {
+                            "People you know with an @{domain} address."
+                        } else {
+                            "Set the company's email domain to link contacts."
+                        }
+                    }
+                    ul {
+                        role: "list",
+                        class: "space-y-3",
+                        for contact in data.contacts.iter() {
+                            li {
+                                key: "{contact.email}",
+                                p {
+                                    class: "text-sm font-medium text-gray-900 dark:text-white",
+                                    {contact.name.clone().unwrap_or_else(|| contact.email.clone())}
+                                }
+                                a {
+                                    href: "mailto:{contact.email}",
+                                    class: "text-xs text-gray-500 dark:text-gray-400 hover:text-indigo-600 dark:hover:text-indigo-400",
+                                    {contact.email.clone()}
+                                }
+                                if let Some(linkedin) = contact.linkedin.clone() {
+                                    a {
+                                        href: "{linkedin}",
+                                        target: "_blank",
+                                        rel: "noopener noreferrer",
+                                        class: "ml-2 text-xs text-indigo-600 dark:text-indigo-400",
+                                        "LinkedIn"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -37,6 +37,22 @@ pub fn AccountLinking() -> Element {
         .as_ref()
         .map(|s| s.connected)
         .unwrap_or(false);
+    let gmail_can_send = gmail_status
+        .read()
+        .as_ref()
+        .map(|s| s.can_send)
+        .unwrap_or(false);
+    let gmail_can_modify = gmail_status
+        .read()
+        .as_ref()
+        .map(|s| s.can_modify)
+        .unwrap_or(false);
+    let gmail_label_sync = gmail_status
+        .read()
+        .as_ref()
+        .map(|s| s.label_sync)
+        .unwrap_or(false);
+    let label_error = use_signal(|| None::<String>);
 
     rsx! {
         div {
@@ -215,6 +231,14 @@ pub fn AccountLinking() -> Element {
                                             }
                                             "Connected"
                                         }
+                                        div {
+                                            class: "text-xs/5 text-gray-500 dark:text-gray-400",
+                                            if gmail_can_send {
+                                                "Reading and sending email"
+                                            } else {
+                                                "Reading email only"
+                                            }
+                                        }
                                     } else {
                                         div {
                                             class: "mt-1 text-xs/5 text-gray-500 dark:text-gray-400",
@@ -238,6 +262,89 @@ pub fn AccountLinking() -> Element {
                                     },
                                     "Connect"
                                 }
+                            } else if !gmail_can_send {
+                                Button {
+                                    variant: ButtonVariant::Ghost,
+                                    class: "text-sm font-semibold text-indigo-600 hover:text-indigo-700 dark:text-indigo-400 dark:hover:text-indigo-300",
+                                    onclick: move |_| {
+                                        #[cfg(target_arch = "wasm32")]
+                                        {
+                                            let auth_url = GmailScannerService::get_gmail_send_auth_url();
+                                            let window = web_sys::window().expect("no global `window` exists");
+                                            let location = window.location();
+                                            let _ = location.set_href(&auth_url);
+                                        }
+                                    },
+                                    "Allow sending"
+                                }
+                            }
+                        }
+                    }
+
+                    if gmail_connected {
+                        div {
+                            class: "mt-4 rounded-lg border border-gray-200 dark:border-white/10 bg-white dark:bg-white/5 p-4",
+                            div {
+                                class: "flex items-center justify-between gap-4",
+                                div {
+                                    div {
+                                        class: "text-sm/6 font-semibold text-gray-900 dark:text-white",
+                                        "Label emails in Gmail"
+                                    }
+                                    div {
+                                        class: "mt-1 text-xs/5 text-gray-500 dark:text-gray-400",
+                                        "Adds ApplyMonitor labels (Interview, Rejection, Application, Linked) to emails once they are classified or assigned to a job."
+                                    }
+                                }
+                                if !gmail_can_modify {
+                                    Button {
+                                        variant: ButtonVariant::Ghost,
+                                        class: "text-sm font-semibold text-indigo-600 hover:text-indigo-700 dark:text-indigo-400 dark:hover:text-indigo-300",
+                                        onclick: move |_| {
+                                            #[cfg(target_arch = "wasm32")]
+                                            {
+                                                let auth_url = GmailScannerService::get_gmail_modify_auth_url();
+                                                let window = web_sys::window().expect("no global `window` exists");
+                                                let location = window.location();
+                                                let _ = location.set_href(&auth_url);
+                                            }
+                                        },
+                                        "Allow labeling"
+                                    }
+                                } else {
+                                    Button {
+                                        variant: if gmail_label_sync { ButtonVariant::Secondary } else { ButtonVariant::Primary },
+                                        onclick: move |_| {
+                                            let enabled = !gmail_label_sync;
+                                            let mut status = gmail_status;
+                                            let mut error = label_error;
+                                            spawn(async move {
+                                                match GmailScannerService::set_label_sync(enabled).await {
+                                                    Ok(_) => {
+                                                        if let Some(s) = status.write().as_mut() {
+                                                            s.label_sync = enabled;
+                                                        }
+                                                        *error.write() = None;
+                                                    }
+                                                    Err(e) => {
+                                                        *error.write() = Some(format!("Failed to update labeling: {}", e));
+                                                    }
+                                                }
+                                            });
+                                        },
+                                        if gmail_label_sync {
+                                            "Turn off"
+                                        } else {
+                                            "Turn on"
+                                        }
+                                    }
+                                }
+                            }
+                            if let Some(err) = label_error() {
+                                p {
+                                    class: "mt-2 text-sm text-red-600 dark:text-red-400",
+                                    {err}
+                                }
                             }
                         }
                     }
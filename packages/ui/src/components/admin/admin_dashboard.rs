@@ -3,7 +3,10 @@
 use crate::services::admin_service::{AdminService, AdminStats};
 use dioxus::prelude::*;
 
-use super::{ai_metrics::AiMetrics, ai_prompts::AiPromptsEditor, users_list::UsersList};
+use super::{
+    ai_eval::AiEvaluation, ai_metrics::AiMetrics, ai_prompts::AiPromptsEditor,
+    job_sources::JobSourcesEditor, usage_limits::UsageLimitsEditor, users_list::UsersList,
+};
 
 #[component]
 pub fn AdminDashboard() -> Element {
@@ -59,10 +62,16 @@ pub fn AdminDashboard() -> Element {
                             onclick: move |_| *active_tab.write() = "ai-prompts".to_string(),
                         }
                         AdminTabButton {
-                            label: "AI Metrics",
-                            tab: "ai-metrics",
-                            active: active_tab() == "ai-metrics",
-                            onclick: move |_| *active_tab.write() = "ai-metrics".to_string(),
+                            label: "AI Evaluation",
+                            tab: "ai-eval",
+                            active: active_tab() == "ai-eval",
+                            onclick: move |_| *active_tab.write() = "ai-eval".to_string(),
+                        }
+                        AdminTabButton {
+                            label: "Job Sources",
+                            tab: "job-sources",
+                            active: active_tab() == "job-sources",
+                            onclick: move |_| *active_tab.write() = "job-sources".to_string(),
                         }
                     }
                 }
@@ -110,6 +119,8 @@ pub fn AdminDashboard() -> Element {
                                         color: "purple",
                                     }
                                 }
+                                AiMetrics {}
+                                UsageLimitsEditor {}
                                 UsersList {}
                             }
                         }
@@ -117,8 +128,11 @@ pub fn AdminDashboard() -> Element {
                     "ai-prompts" => rsx! {
                         AiPromptsEditor {}
                     },
-                    "ai-metrics" => rsx! {
-                        AiMetrics {}
+                    "ai-eval" => rsx! {
+                        AiEvaluation {}
+                    },
+                    "job-sources" => rsx! {
+                        JobSourcesEditor {}
                     },
                     _ => rsx! {
                         div { "Unknown tab" }
@@ -0,0 +1,526 @@
+//! AI prompt evaluation component
+
+use crate::services::ai_service::{
+    AiPrompt, AiService, EvalComparison, EvalDataset, EvalExample, EvalReport, EvalRun,
+    NewEvalExample,
+};
+use dioxus::prelude::*;
+
+const CATEGORIES: [&str; 5] = [
+    "interview",
+    "rejection",
+    "new_job",
+    "application_sent",
+    "other",
+];
+
+const INPUT_CLASS: &str = "w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-md bg-white dark:bg-gray-700 text-gray-900 dark:text-gray-100 text-sm";
+
+fn percent(value: Option<f64>) -> String {
+    value
+        .map(|v| format!("{:.0}%", v * 100.0))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Labelled datasets and runs comparing a candidate prompt with the active one
+#[component]
+pub fn AiEvaluation() -> Element {
+    let datasets = use_signal(Vec::<EvalDataset>::new);
+    let mut selected_dataset = use_signal(|| None::<String>);
+    let examples = use_signal(Vec::<EvalExample>::new);
+    let runs = use_signal(Vec::<EvalRun>::new);
+    let prompts = use_signal(Vec::<AiPrompt>::new);
+    let mut selected_prompt = use_signal(String::new);
+    let comparison = use_signal(|| None::<EvalComparison>);
+    let running = use_signal(|| false);
+    let error = use_signal(|| None::<String>);
+
+    let mut new_dataset_name = use_signal(String::new);
+    let mut example_email_id = use_signal(String::new);
+    let mut example_subject = use_signal(String::new);
+    let mut example_body = use_signal(String::new);
+    let mut example_category = use_signal(|| CATEGORIES[0].to_string());
+    let mut example_fields = use_signal(String::new);
+
+    let load_datasets = {
+        let mut datasets = datasets;
+        let mut error = error;
+        move || {
+            spawn(async move {
+                match AiService::list_eval_datasets().await {
+                    Ok(loaded) => *datasets.write() = loaded,
+                    Err(e) => *error.write() = Some(format!("Failed to load datasets: {:?}", e)),
+                }
+            });
+        }
+    };
+
+    let load_dataset = {
+        let mut examples = examples;
+        let mut runs = runs;
+        let mut error = error;
+        move |dataset_id: String| {
+            spawn(async move {
+                match AiService::list_eval_examples(&dataset_id).await {
+                    Ok(loaded) => *examples.write() = loaded,
+                    Err(e) => *error.write() = Some(format!("Failed to load examples: {:?}", e)),
+                }
+                match AiService::list_eval_runs(&dataset_id).await {
+                    Ok(loaded) => *runs.write() = loaded,
+                    Err(e) => *error.write() = Some(format!("Failed to load runs: {:?}", e)),
+                }
+            });
+        }
+    };
+
+    use_effect(move || {
+        load_datasets();
+        let mut prompts = prompts;
+        spawn(async move {
+            let mut all = Vec::new();
+            for stage in ["classify", "extract"] {
+                if let Ok(loaded) = AiService::list_prompts(Some(stage)).await {
+                    all.extend(loaded);
+                }
+            }
+            *prompts.write() = all;
+        });
+    });
+
+    rsx! {
+        div {
+            class: "space-y-6",
+
+            if let Some(err) = error() {
+                div {
+                    class: "p-4 bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 rounded-md",
+                    p {
+                        class: "text-sm text-red-800 dark:text-red-200",
+                        {err}
+                    }
+                }
+            }
+
+            // Datasets
+            div {
+                class: "p-5 bg-white dark:bg-gray-800 rounded-lg border border-gray-200 dark:border-gray-700",
+                h3 {
+                    class: "text-lg font-medium text-gray-900 dark:text-gray-100 mb-4",
+                    "Datasets"
+                }
+                div {
+                    class: "flex gap-3 mb-4",
+                    select {
+                        class: INPUT_CLASS,
+                        onchange: move |e: Event<FormData>| {
+                            let id = e.value();
+                            if id.is_empty() {
+                                *selected_dataset.write() = None;
+                            } else {
+                                *selected_dataset.write() = Some(id.clone());
+                                load_dataset(id);
+                            }
+                        },
+                        option { value: "", "Select a dataset" }
+                        for dataset in datasets().iter() {
+                            option {
+                                value: "{dataset.id}",
+                                selected: selected_dataset().as_deref() == Some(dataset.id.as_str()),
+                                "{dataset.name} ({dataset.example_count} examples)"
+                            }
+                        }
+                    }
+                    input {
+                        class: INPUT_CLASS,
+                        r#type: "text",
+                        placeholder: "New dataset name",
+                        value: new_dataset_name(),
+                        oninput: move |e| *new_dataset_name.write() = e.value(),
+                    }
+                    button {
+                        class: "px-4 py-2 bg-blue-600 text-white rounded-md hover:bg-blue-700 disabled:opacity-50 whitespace-nowrap",
+                        disabled: new_dataset_name().trim().is_empty(),
+                        onclick: move |_| {
+                            let name = new_dataset_name();
+                            let mut error = error;
+                            spawn(async move {
+                                match AiService::create_eval_dataset(name.trim()).await {
+                                    Ok(id) => {
+                                        *new_dataset_name.write() = String::new();
+                                        *selected_dataset.write() = Some(id.clone());
+                                        load_datasets();
+                                        load_dataset(id);
+                                    }
+                                    Err(e) => *error.write() = Some(format!("Failed to create dataset: {:?}", e)),
+                                }
+                            });
+                        },
+                        "Create"
+                    }
+                }
+            }
+
+            if let Some(dataset_id) = selected_dataset() {
+                // Examples
+                div {
+                    class: "p-5 bg-white dark:bg-gray-800 rounded-lg border border-gray-200 dark:border-gray-700",
+                    h3 {
+                        class: "text-lg font-medium text-gray-900 dark:text-gray-100 mb-4",
+                        "Labelled Examples"
+                    }
+                    div {
+                        class: "space-y-2 mb-6",
+                        if examples().is_empty() {
+                            p {
+                                class: "text-sm text-gray-500 dark:text-gray-400",
+                                "No examples yet."
+                            }
+                        }
+                        for example in examples().iter() {
+                            div {
+                                key: "{example.id}",
+                                class: "flex items-center justify-between gap-4 p-3 bg-gray-50 dark:bg-gray-900 rounded-md",
+                                div {
+                                    class: "min-w-0",
+                                    div {
+                                        class: "truncate font-medium text-gray-900 dark:text-gray-100",
+                                        {example.subject.clone().unwrap_or_else(|| "No subject".to_string())}
+                                    }
+                                    div {
+                                        class: "text-sm text-gray-500 dark:text-gray-400 font-mono",
+                                        {format!("{} {}", example.expected_category, serde_json::Value::Object(example.expected_fields.clone()))}
+                                    }
+                                }
+                                button {
+                                    class: "px-3 py-1 text-sm text-red-600 hover:text-red-700 dark:text-red-400",
+                                    onclick: {
+                                        let dataset_id = dataset_id.clone();
+                                        let example_id = example.id.clone();
+                                        let mut error = error;
+                                        move |_| {
+                                            let dataset_id = dataset_id.clone();
+                                            let example_id = example_id.clone();
+                                            spawn(async move {
+                                                match AiService::delete_eval_example(&dataset_id, &example_id).await {
+                                                    Ok(_) => load_dataset(dataset_id),
+                                                    Err(e) => *error.write() = Some(format!("Failed to delete example: {:?}", e)),
+                                                }
+                                            });
+                                        }
+                                    },
+                                    "Remove"
+                                }
+                            }
+                        }
+                    }
+
+                    div {
+                        class: "grid grid-cols-1 gap-3 md:grid-cols-2",
+                        input {
+                            class: INPUT_CLASS,
+                            r#type: "text",
+                            placeholder: "Gmail ID to copy the email from (optional)",
+                            value: example_email_id(),
+                            oninput: move |e| *example_email_id.write() = e.value(),
+                        }
+                        select {
+                            class: INPUT_CLASS,
+                            onchange: move |e: Event<FormData>| *example_category.write() = e.value(),
+                            for category in CATEGORIES {
+                                option {
+                                    value: category,
+                                    selected: example_category() == category,
+                                    {category}
+                                }
+                            }
+                        }
+                        input {
+                            class: INPUT_CLASS,
+                            r#type: "text",
+                            placeholder: "Subject",
+                            value: example_subject(),
+                            oninput: move |e| *example_subject.write() = e.value(),
+                        }
+                        input {
+                            class: "{INPUT_CLASS} font-mono",
+                            r#type: "text",
+                            placeholder: "Expected fields, e.g. {{\"company\": \"Acme\", \"location\": null}}",
+                            value: example_fields(),
+                            oninput: move |e| *example_fields.write() = e.value(),
+                        }
+                        textarea {
+                            class: "{INPUT_CLASS} md:col-span-2",
+                            rows: 4,
+                            placeholder: "Body",
+                            value: example_body(),
+                            oninput: move |e| *example_body.write() = e.value(),
+                        }
+                    }
+                    div {
+                        class: "mt-3 flex gap-3",
+                        button {
+                            class: "px-4 py-2 bg-blue-600 text-white rounded-md hover:bg-blue-700",
+                            onclick: {
+                                let dataset_id = dataset_id.clone();
+                                let mut error = error;
+                                move |_| {
+                                    let fields_text = example_fields();
+                                    let expected_fields = if fields_text.trim().is_empty() {
+                                        serde_json::Map::new()
+                                    } else {
+                                        match serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&fields_text) {
+                                            Ok(fields) => fields,
+                                            Err(e) => {
+                                                *error.write() = Some(format!("Expected fields must be a JSON object: {}", e));
+                                                return;
+                                            }
+                                        }
+                                    };
+                                    let non_empty = |s: String| Some(s).filter(|s| !s.trim().is_empty());
+                                    let example = NewEvalExample {
+                                        email_id: non_empty(example_email_id()),
+                                        subject: non_empty(example_subject()),
+                                        body: non_empty(example_body()),
+                                        expected_category: example_category(),
+                                        expected_fields,
+                                    };
+                                    let dataset_id = dataset_id.clone();
+                                    spawn(async move {
+                                        *error.write() = None;
+                                        match AiService::add_eval_example(&dataset_id, example).await {
+                                            Ok(_) => {
+                                                *example_email_id.write() = String::new();
+                                                *example_subject.write() = String::new();
+                                                *example_body.write() = String::new();
+                                                *example_fields.write() = String::new();
+                                                load_datasets();
+                                                load_dataset(dataset_id);
+                                            }
+                                            Err(e) => *error.write() = Some(format!("Failed to add example: {:?}", e)),
+                                        }
+                                    });
+                                }
+                            },
+                            "Add Example"
+                        }
+                        button {
+                            class: "px-4 py-2 bg-white dark:bg-gray-700 text-gray-900 dark:text-gray-100 border border-gray-300 dark:border-gray-600 rounded-md hover:bg-gray-50 dark:hover:bg-gray-600",
+                            onclick: {
+                                let dataset_id = dataset_id.clone();
+                                let mut error = error;
+                                move |_| {
+                                    let dataset_id = dataset_id.clone();
+                                    spawn(async move {
+                                        *error.write() = None;
+                                        match AiService::import_eval_reviews(&dataset_id, false).await {
+                                            Ok(_) => {
+                                                load_datasets();
+                                                load_dataset(dataset_id);
+                                            }
+                                            Err(e) => *error.write() = Some(format!("Failed to import reviews: {:?}", e)),
+                                        }
+                                    });
+                                }
+                            },
+                            "Import Reviewed Emails"
+                        }
+                    }
+                }
+
+                // Run
+                div {
+                    class: "p-5 bg-white dark:bg-gray-800 rounded-lg border border-gray-200 dark:border-gray-700",
+                    h3 {
+                        class: "text-lg font-medium text-gray-900 dark:text-gray-100 mb-4",
+                        "Evaluate a Prompt"
+                    }
+                    div {
+                        class: "flex gap-3",
+                        select {
+                            class: INPUT_CLASS,
+                            onchange: move |e: Event<FormData>| *selected_prompt.write() = e.value(),
+                            option { value: "", "Select a prompt" }
+                            for prompt in prompts().iter() {
+                                option {
+                                    value: "{prompt.id}",
+                                    selected: selected_prompt() == prompt.id,
+                                    if prompt.is_active {
+                                        "{prompt.stage}: {prompt.name} (active)"
+                                    } else {
+                                        "{prompt.stage}: {prompt.name}"
+                                    }
+                                }
+                            }
+                        }
+                        button {
+                            class: "px-4 py-2 bg-green-600 text-white rounded-md hover:bg-green-700 disabled:opacity-50 whitespace-nowrap",
+                            disabled: running() || selected_prompt().is_empty() || examples().is_empty(),
+                            onclick: {
+                                let dataset_id = dataset_id.clone();
+                                let mut error = error;
+                                let mut running = running;
+                                let mut comparison = comparison;
+                                move |_| {
+                                    let dataset_id = dataset_id.clone();
+                                    let prompt_id = selected_prompt();
+                                    spawn(async move {
+                                        *running.write() = true;
+                                        *error.write() = None;
+                                        match AiService::run_eval(&dataset_id, &prompt_id).await {
+                                            Ok(result) => {
+                                                *comparison.write() = Some(result);
+                                                load_dataset(dataset_id);
+                                            }
+                                            Err(e) => *error.write() = Some(format!("Evaluation failed: {:?}", e)),
+                                        }
+                                        *running.write() = false;
+                                    });
+                                }
+                            },
+                            if running() {
+                                "Running..."
+                            } else {
+                                "Run Evaluation"
+                            }
+                        }
+                    }
+
+                    if let Some(result) = comparison() {
+                        div {
+                            class: "mt-6 grid grid-cols-1 gap-6 lg:grid-cols-2",
+                            RunReport {
+                                title: format!("Candidate: {}", result.candidate.prompt_name.clone().unwrap_or_default()),
+                                report: result.candidate.report.clone(),
+                            }
+                            if let Some(baseline) = result.baseline {
+                                RunReport {
+                                    title: format!("Active: {}", baseline.prompt_name.clone().unwrap_or_default()),
+                                    report: baseline.report.clone(),
+                                }
+                            } else {
+                                p {
+                                    class: "text-sm text-gray-500 dark:text-gray-400",
+                                    "This is the active prompt, so there is nothing to compare against."
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // History
+                div {
+                    class: "p-5 bg-white dark:bg-gray-800 rounded-lg border border-gray-200 dark:border-gray-700",
+                    h3 {
+                        class: "text-lg font-medium text-gray-900 dark:text-gray-100 mb-4",
+                        "Run History"
+                    }
+                    div {
+                        class: "space-y-2",
+                        for run in runs().iter() {
+                            div {
+                                key: "{run.id}",
+                                class: "flex items-center justify-between p-3 bg-gray-50 dark:bg-gray-900 rounded-md text-sm",
+                                div {
+                                    div {
+                                        class: "font-medium text-gray-900 dark:text-gray-100",
+                                        "{run.stage}: {run.prompt_name.clone().unwrap_or_default()}"
+                                    }
+                                    div {
+                                        class: "text-gray-500 dark:text-gray-400",
+                                        {run.created_at.clone()}
+                                    }
+                                }
+                                span {
+                                    class: "font-medium text-gray-900 dark:text-gray-100",
+                                    {percent(run.accuracy)}
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Accuracy, confusion matrix and per-field precision/recall of one run
+#[component]
+fn RunReport(title: String, report: EvalReport) -> Element {
+    rsx! {
+        div {
+            class: "space-y-4",
+            div {
+                h4 {
+                    class: "font-medium text-gray-900 dark:text-gray-100",
+                    {title}
+                }
+                p {
+                    class: "text-sm text-gray-500 dark:text-gray-400",
+                    "Accuracy {percent(Some(report.accuracy))} ({report.correct} of {report.total})"
+                }
+            }
+            div {
+                class: "overflow-x-auto",
+                table {
+                    class: "text-xs text-gray-700 dark:text-gray-300",
+                    thead {
+                        tr {
+                            th { class: "px-2 py-1 text-left", "expected \\ predicted" }
+                            for label in report.labels.iter() {
+                                th { class: "px-2 py-1", {label.clone()} }
+                            }
+                        }
+                    }
+                    tbody {
+                        for (row_index, row) in report.confusion_matrix.iter().enumerate() {
+                            tr {
+                                th {
+                                    class: "px-2 py-1 text-left",
+                                    {report.labels.get(row_index).cloned().unwrap_or_default()}
+                                }
+                                for (col_index, count) in row.iter().enumerate() {
+                                    td {
+                                        class: if row_index == col_index && *count > 0 {
+                                            "px-2 py-1 text-center font-semibold text-green-700 dark:text-green-400"
+                                        } else if *count > 0 {
+                                            "px-2 py-1 text-center font-semibold text-red-700 dark:text-red-400"
+                                        } else {
+                                            "px-2 py-1 text-center text-gray-400"
+                                        },
+                                        "{count}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            if !report.fields.is_empty() {
+                table {
+                    class: "w-full text-xs text-gray-700 dark:text-gray-300",
+                    thead {
+                        tr {
+                            th { class: "px-2 py-1 text-left", "Field" }
+                            th { class: "px-2 py-1 text-right", "Precision" }
+                            th { class: "px-2 py-1 text-right", "Recall" }
+                            th { class: "px-2 py-1 text-right", "TP / FP / FN" }
+                        }
+                    }
+                    tbody {
+                        for (field, metrics) in report.fields.iter() {
+                            tr {
+                                td { class: "px-2 py-1", {field.clone()} }
+                                td { class: "px-2 py-1 text-right", {percent(metrics.precision)} }
+                                td { class: "px-2 py-1 text-right", {percent(metrics.recall)} }
+                                td {
+                                    class: "px-2 py-1 text-right",
+                                    "{metrics.true_positives} / {metrics.false_positives} / {metrics.false_negatives}"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
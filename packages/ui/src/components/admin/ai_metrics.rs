@@ -1,21 +1,234 @@
 //! AI Metrics dashboard component
 
+use crate::services::admin_service::{AdminService, AiDailyStats, AiStats};
 use dioxus::prelude::*;
 
+/// Day ranges offered for the charts
+const DAY_OPTIONS: [u32; 3] = [7, 30, 90];
+
+fn percent(value: Option<f64>) -> String {
+    value
+        .map(|v| format!("{:.0}%", v * 100.0))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Processing volume, confidence and categories from the daily AI stats rollup
 #[component]
 pub fn AiMetrics() -> Element {
+    let mut days = use_signal(|| DAY_OPTIONS[1]);
+    let stats = use_signal(|| None::<AiStats>);
+    let error = use_signal(|| None::<String>);
+
+    use_effect(move || {
+        let range = days();
+        let mut stats = stats;
+        let mut error = error;
+        spawn(async move {
+            match AdminService::fetch_ai_stats(range).await {
+                Ok(loaded) => {
+                    *stats.write() = Some(loaded);
+                    *error.write() = None;
+                }
+                Err(e) => *error.write() = Some(format!("Failed to load AI stats: {:?}", e)),
+            }
+        });
+    });
+
     rsx! {
         div {
             class: "space-y-6",
-            h2 {
-                class: "text-2xl font-bold text-gray-900 dark:text-gray-100",
-                "AI Processing Metrics"
-            }
             div {
-                class: "p-5 bg-white dark:bg-gray-800 rounded-lg border border-gray-200 dark:border-gray-700",
+                class: "flex items-center justify-between",
+                div {
+                    h2 {
+                        class: "text-lg font-medium text-gray-900 dark:text-gray-100",
+                        "AI Processing"
+                    }
+                    p {
+                        class: "text-sm text-gray-500 dark:text-gray-400",
+                        "Rolled up daily from AI results."
+                    }
+                }
+                select {
+                    class: "px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-md bg-white dark:bg-gray-700 text-gray-900 dark:text-gray-100 text-sm",
+                    onchange: move |e: Event<FormData>| {
+                        if let Ok(value) = e.value().parse() {
+                            *days.write() = value;
+                        }
+                    },
+                    for option_days in DAY_OPTIONS {
+                        option {
+                            value: "{option_days}",
+                            selected: days() == option_days,
+                            "Last {option_days} days"
+                        }
+                    }
+                }
+            }
+
+            if let Some(err) = error() {
+                p {
+                    class: "text-sm text-red-600 dark:text-red-400",
+                    {err}
+                }
+            }
+
+            if let Some(data) = stats() {
+                div {
+                    class: "grid grid-cols-1 gap-5 sm:grid-cols-3",
+                    MetricCard {
+                        title: "Emails Processed",
+                        value: data.totals.emails_processed.to_string(),
+                    }
+                    MetricCard {
+                        title: "Average Confidence",
+                        value: percent(data.totals.avg_confidence),
+                    }
+                    MetricCard {
+                        title: "Flagged for Review",
+                        value: data.totals.needs_review_count.to_string(),
+                    }
+                }
+                div {
+                    class: "grid grid-cols-1 gap-5 lg:grid-cols-2",
+                    DailyVolumeChart { days: data.days.clone() }
+                    CategoryBreakdown { totals: data.totals.clone() }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn MetricCard(title: String, value: String) -> Element {
+    rsx! {
+        div {
+            class: "overflow-hidden rounded-lg border bg-gray-50 dark:bg-gray-800 border-gray-200 dark:border-gray-700 px-4 py-5 sm:p-6",
+            dt {
+                class: "truncate text-sm font-medium text-gray-500 dark:text-gray-400",
+                {title}
+            }
+            dd {
+                class: "mt-1 text-3xl font-semibold text-gray-900 dark:text-gray-100",
+                {value}
+            }
+        }
+    }
+}
+
+/// Column chart of emails processed per day, with the flagged share stacked on top
+#[component]
+fn DailyVolumeChart(days: Vec<AiDailyStats>) -> Element {
+    let max = days
+        .iter()
+        .map(|d| d.emails_processed)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    rsx! {
+        div {
+            class: "p-5 bg-white dark:bg-gray-800 rounded-lg border border-gray-200 dark:border-gray-700",
+            h3 {
+                class: "text-base font-semibold text-gray-900 dark:text-white mb-4",
+                "Emails per day"
+            }
+            if days.is_empty() {
+                p {
+                    class: "text-sm text-gray-500 dark:text-gray-400",
+                    "No emails processed in this period."
+                }
+            } else {
+                div {
+                    class: "flex h-40 items-end gap-1",
+                    for day in days.iter() {
+                        div {
+                            key: "{day.date}",
+                            class: "flex h-full flex-1 flex-col justify-end",
+                            title: format!(
+                                "{}: {} processed, {} flagged, confidence {}",
+                                day.date,
+                                day.emails_processed,
+                                day.needs_review_count,
+                                percent(day.avg_confidence)
+                            ),
+                            div {
+                                class: "w-full rounded-t bg-blue-500",
+                                style: format!(
+                                    "height: {:.1}%",
+                                    day.emails_processed.saturating_sub(day.needs_review_count) as f64 / max as f64 * 100.0
+                                ),
+                            }
+                            div {
+                                class: "w-full bg-yellow-500",
+                                style: format!("height: {:.1}%", day.needs_review_count as f64 / max as f64 * 100.0),
+                            }
+                        }
+                    }
+                }
+                div {
+                    class: "mt-2 flex justify-between text-xs text-gray-500 dark:text-gray-400",
+                    span {
+                        {days.first().map(|d| d.date.clone()).unwrap_or_default()}
+                    }
+                    span {
+                        {days.last().map(|d| d.date.clone()).unwrap_or_default()}
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Horizontal bars of results per category
+#[component]
+fn CategoryBreakdown(totals: AiDailyStats) -> Element {
+    let max = totals
+        .category_breakdown
+        .values()
+        .copied()
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let mut categories: Vec<(String, u32)> = totals.category_breakdown.into_iter().collect();
+    categories.sort_by(|a, b| b.1.cmp(&a.1));
+
+    rsx! {
+        div {
+            class: "p-5 bg-white dark:bg-gray-800 rounded-lg border border-gray-200 dark:border-gray-700",
+            h3 {
+                class: "text-base font-semibold text-gray-900 dark:text-white mb-4",
+                "Categories"
+            }
+            if categories.is_empty() {
                 p {
-                    class: "text-gray-600 dark:text-gray-400",
-                    "Metrics dashboard coming soon. This will show processing statistics, category breakdown, and confidence metrics."
+                    class: "text-sm text-gray-500 dark:text-gray-400",
+                    "No emails processed in this period."
+                }
+            } else {
+                div {
+                    class: "space-y-2",
+                    for (category, count) in categories.iter() {
+                        div {
+                            key: "{category}",
+                            class: "flex items-center gap-3 text-sm",
+                            span {
+                                class: "w-32 shrink-0 text-gray-700 dark:text-gray-300",
+                                {category.clone()}
+                            }
+                            div {
+                                class: "h-3 flex-1 rounded bg-gray-100 dark:bg-gray-700",
+                                div {
+                                    class: "h-3 rounded bg-blue-500",
+                                    style: format!("width: {:.1}%", *count as f64 / max as f64 * 100.0),
+                                }
+                            }
+                            span {
+                                class: "w-10 shrink-0 text-right text-gray-900 dark:text-gray-100",
+                                "{count}"
+                            }
+                        }
+                    }
                 }
             }
         }
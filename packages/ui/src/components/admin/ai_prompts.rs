@@ -1,8 +1,66 @@
 //! AI Prompts editor component
 
-use crate::services::ai_service::{AiPrompt, AiService};
+use crate::services::ai_service::{AiPrompt, AiService, PromptModelSettings, SandboxResult};
 use dioxus::prelude::*;
 
+/// Stages the sandbox can test a draft prompt for
+const TESTABLE_STAGES: [&str; 3] = ["classify", "extract", "summarize"];
+
+/// Model settings from the editor inputs. Blank inputs keep the defaults.
+fn parse_model_settings(
+    models: &str,
+    temperature: &str,
+    max_tokens: &str,
+) -> Result<PromptModelSettings, String> {
+    let models: Vec<String> = models
+        .split([',', '\n'])
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+        .collect();
+    let temperature = match temperature.trim() {
+        "" => None,
+        t => Some(
+            t.parse::<f64>()
+                .map_err(|_| "Temperature must be a number".to_string())?,
+        ),
+    };
+    let max_tokens = match max_tokens.trim() {
+        "" => None,
+        t => Some(
+            t.parse::<u32>()
+                .map_err(|_| "Max tokens must be a whole number".to_string())?,
+        ),
+    };
+
+    Ok(PromptModelSettings {
+        models: if models.is_empty() {
+            None
+        } else {
+            Some(models)
+        },
+        temperature,
+        max_tokens,
+    })
+}
+
+/// One-line description of a prompt's model settings
+fn describe_model_settings(prompt: &AiPrompt) -> String {
+    let models = prompt
+        .models
+        .as_ref()
+        .map(|m| m.join(" → "))
+        .unwrap_or_else(|| "Default model".to_string());
+    let temperature = prompt
+        .temperature
+        .map(|t| format!("temperature {}", t))
+        .unwrap_or_else(|| "default temperature".to_string());
+    let max_tokens = prompt
+        .max_tokens
+        .map(|t| format!("{} max tokens", t))
+        .unwrap_or_else(|| "default max tokens".to_string());
+    format!("{} · {} · {}", models, temperature, max_tokens)
+}
+
 #[component]
 pub fn AiPromptsEditor() -> Element {
     let mut active_tab = use_signal(|| "classify".to_string());
@@ -11,6 +69,12 @@ pub fn AiPromptsEditor() -> Element {
     let error = use_signal(|| None::<String>);
     let mut prompt_text = use_signal(String::new);
     let mut prompt_name = use_signal(String::new);
+    let mut prompt_models = use_signal(String::new);
+    let mut prompt_temperature = use_signal(String::new);
+    let mut prompt_max_tokens = use_signal(String::new);
+    let mut test_email_ids = use_signal(String::new);
+    let test_results = use_signal(Vec::<SandboxResult>::new);
+    let testing = use_signal(|| false);
 
     let load_prompts = {
         let mut loading = loading;
@@ -78,6 +142,33 @@ pub fn AiPromptsEditor() -> Element {
                             load_prompts("summarize".to_string());
                         }
                     }
+                    TabButton {
+                        label: "Combined",
+                        stage: "combined",
+                        active: active_tab() == "combined",
+                        onclick: move |_| {
+                            *active_tab.write() = "combined".to_string();
+                            load_prompts("combined".to_string());
+                        }
+                    }
+                    TabButton {
+                        label: "Draft",
+                        stage: "draft",
+                        active: active_tab() == "draft",
+                        onclick: move |_| {
+                            *active_tab.write() = "draft".to_string();
+                            load_prompts("draft".to_string());
+                        }
+                    }
+                    TabButton {
+                        label: "Tailor",
+                        stage: "tailor",
+                        active: active_tab() == "tailor",
+                        onclick: move |_| {
+                            *active_tab.write() = "tailor".to_string();
+                            load_prompts("tailor".to_string());
+                        }
+                    }
                 }
             }
 
@@ -114,6 +205,10 @@ pub fn AiPromptsEditor() -> Element {
                                         class: "text-sm text-gray-500 dark:text-gray-400",
                                         "Active version"
                                     }
+                                    p {
+                                        class: "text-xs text-gray-500 dark:text-gray-400 font-mono",
+                                        {describe_model_settings(active)}
+                                    }
                                 }
                                 span {
                                     class: "px-3 py-1 text-xs font-medium bg-green-100 dark:bg-green-900/20 text-green-800 dark:text-green-200 rounded-full",
@@ -162,6 +257,53 @@ pub fn AiPromptsEditor() -> Element {
                                     oninput: move |e| *prompt_text.write() = e.value(),
                                 }
                             }
+                            div {
+                                class: "grid grid-cols-1 gap-4 sm:grid-cols-4",
+                                div {
+                                    class: "sm:col-span-2",
+                                    label {
+                                        class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2",
+                                        "Models"
+                                    }
+                                    textarea {
+                                        class: "w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-md bg-white dark:bg-gray-700 text-gray-900 dark:text-gray-100 font-mono text-sm",
+                                        rows: 3,
+                                        placeholder: "One per line, tried in order, e.g.\n@cf/meta/llama-3.1-8b-instruct-fast\nopenai:llama3.1:8b",
+                                        value: prompt_models(),
+                                        oninput: move |e| *prompt_models.write() = e.value(),
+                                    }
+                                }
+                                div {
+                                    label {
+                                        class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2",
+                                        "Temperature"
+                                    }
+                                    input {
+                                        class: "w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-md bg-white dark:bg-gray-700 text-gray-900 dark:text-gray-100",
+                                        r#type: "number",
+                                        min: "0",
+                                        max: "2",
+                                        step: "0.1",
+                                        placeholder: "Default",
+                                        value: prompt_temperature(),
+                                        oninput: move |e| *prompt_temperature.write() = e.value(),
+                                    }
+                                }
+                                div {
+                                    label {
+                                        class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2",
+                                        "Max Tokens"
+                                    }
+                                    input {
+                                        class: "w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-md bg-white dark:bg-gray-700 text-gray-900 dark:text-gray-100",
+                                        r#type: "number",
+                                        min: "1",
+                                        placeholder: "Default",
+                                        value: prompt_max_tokens(),
+                                        oninput: move |e| *prompt_max_tokens.write() = e.value(),
+                                    }
+                                }
+                            }
                             div {
                                 class: "flex gap-3",
                                 button {
@@ -177,16 +319,31 @@ pub fn AiPromptsEditor() -> Element {
                                             let name = prompt_name();
                                             let text = prompt_text();
                                             let stage = active_tab();
+                                            let settings = match parse_model_settings(
+                                                &prompt_models(),
+                                                &prompt_temperature(),
+                                                &prompt_max_tokens(),
+                                            ) {
+                                                Ok(settings) => settings,
+                                                Err(e) => {
+                                                    *error.write() = Some(e);
+                                                    return;
+                                                }
+                                            };
                                             spawn(async move {
                                                 *error.write() = None;
                                                 match AiService::create_prompt(
                                                     &name,
                                                     &stage,
                                                     &text,
+                                                    settings,
                                                 ).await {
                                                     Ok(_) => {
                                                         *prompt_name.write() = String::new();
                                                         *prompt_text.write() = String::new();
+                                                        *prompt_models.write() = String::new();
+                                                        *prompt_temperature.write() = String::new();
+                                                        *prompt_max_tokens.write() = String::new();
                                                         *loading.write() = true;
                                                         *error.write() = None;
                                                         match AiService::list_prompts(Some(&stage)).await {
@@ -249,6 +406,71 @@ pub fn AiPromptsEditor() -> Element {
                         }
                     }
 
+                    // Sandbox test
+                    if TESTABLE_STAGES.contains(&active_tab().as_str()) {
+                        div {
+                            class: "p-5 bg-white dark:bg-gray-800 rounded-lg border border-gray-200 dark:border-gray-700",
+                            h3 {
+                                class: "text-lg font-medium text-gray-900 dark:text-gray-100 mb-1",
+                                "Test Draft"
+                            }
+                            p {
+                                class: "text-sm text-gray-500 dark:text-gray-400 mb-4",
+                                "Runs the prompt text above on real emails without saving results or changing the emails. Other stages use their active prompts."
+                            }
+                            div {
+                                class: "flex gap-3",
+                                input {
+                                    class: "w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-md bg-white dark:bg-gray-700 text-gray-900 dark:text-gray-100 text-sm",
+                                    r#type: "text",
+                                    placeholder: "Gmail IDs, separated by commas",
+                                    value: test_email_ids(),
+                                    oninput: move |e| *test_email_ids.write() = e.value(),
+                                }
+                                button {
+                                    class: "px-4 py-2 bg-blue-600 text-white rounded-md hover:bg-blue-700 disabled:opacity-50 whitespace-nowrap",
+                                    disabled: testing() || prompt_text().trim().is_empty() || test_email_ids().trim().is_empty(),
+                                    onclick: {
+                                        let mut error = error;
+                                        let mut testing = testing;
+                                        let mut test_results = test_results;
+                                        move |_| {
+                                            let email_ids: Vec<String> = test_email_ids()
+                                                .split(',')
+                                                .map(|id| id.trim().to_string())
+                                                .filter(|id| !id.is_empty())
+                                                .collect();
+                                            let stage = active_tab();
+                                            let text = prompt_text();
+                                            spawn(async move {
+                                                *testing.write() = true;
+                                                *error.write() = None;
+                                                match AiService::test_prompt(email_ids, &stage, &text).await {
+                                                    Ok(results) => *test_results.write() = results,
+                                                    Err(e) => {
+                                                        *error.write() = Some(format!("Failed to test prompt: {:?}", e));
+                                                    }
+                                                }
+                                                *testing.write() = false;
+                                            });
+                                        }
+                                    },
+                                    if testing() {
+                                        "Testing..."
+                                    } else {
+                                        "Run Test"
+                                    }
+                                }
+                            }
+                            for result in test_results().iter() {
+                                SandboxResultView {
+                                    key: "{result.email_id}",
+                                    result: result.clone(),
+                                }
+                            }
+                        }
+                    }
+
                     // Version history
                     div {
                         class: "p-5 bg-white dark:bg-gray-800 rounded-lg border border-gray-200 dark:border-gray-700",
@@ -323,6 +545,84 @@ pub fn AiPromptsEditor() -> Element {
     }
 }
 
+/// The stages of one email in a sandbox test, side by side
+#[component]
+fn SandboxResultView(result: SandboxResult) -> Element {
+    rsx! {
+        div {
+            class: "mt-6",
+            h4 {
+                class: "font-medium text-gray-900 dark:text-gray-100 mb-2",
+                {result.subject.clone().unwrap_or_else(|| result.email_id.clone())}
+            }
+            if let Some(err) = result.error.clone() {
+                p {
+                    class: "text-sm text-red-600 dark:text-red-400",
+                    {err}
+                }
+            }
+            div {
+                class: "grid grid-cols-1 gap-4 lg:grid-cols-3",
+                for trace in result.stages.iter() {
+                    div {
+                        key: "{trace.stage}",
+                        class: "p-3 bg-gray-50 dark:bg-gray-900 rounded-md text-xs space-y-2 min-w-0",
+                        div {
+                            class: "flex items-center justify-between text-sm",
+                            span {
+                                class: "font-medium text-gray-900 dark:text-gray-100",
+                                {trace.stage.clone()}
+                            }
+                            span {
+                                class: "text-gray-500 dark:text-gray-400",
+                                "{trace.duration_ms} ms"
+                            }
+                        }
+                        if let Some(err) = trace.error.clone() {
+                            p {
+                                class: "text-red-600 dark:text-red-400",
+                                {err}
+                            }
+                        }
+                        details {
+                            summary {
+                                class: "cursor-pointer text-gray-700 dark:text-gray-300",
+                                "Rendered prompt"
+                            }
+                            pre {
+                                class: "mt-1 whitespace-pre-wrap font-mono text-gray-800 dark:text-gray-200",
+                                {trace.rendered_prompt.clone()}
+                            }
+                        }
+                        div {
+                            p {
+                                class: "text-gray-700 dark:text-gray-300",
+                                "Raw output"
+                            }
+                            pre {
+                                class: "mt-1 whitespace-pre-wrap font-mono text-gray-800 dark:text-gray-200",
+                                {trace.raw_output.clone().unwrap_or_default()}
+                            }
+                        }
+                        if let Some(parsed) = trace.parsed.clone() {
+                            div {
+                                p {
+                                    class: "text-gray-700 dark:text-gray-300",
+                                    "Parsed JSON"
+                                }
+                                pre {
+                                    class: "mt-1 whitespace-pre-wrap font-mono text-gray-800 dark:text-gray-200",
+                                    {serde_json::to_string_pretty(&parsed).unwrap_or_default()}
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[component]
 fn TabButton(label: String, stage: String, active: bool, onclick: EventHandler) -> Element {
     let border_class = if active {
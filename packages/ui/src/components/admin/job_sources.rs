@@ -0,0 +1,136 @@
+//! Job sources admin component
+
+use crate::services::admin_service::AdminService;
+use crate::services::jobs_service::{JobSource, JobsService};
+use dioxus::prelude::*;
+
+#[component]
+pub fn JobSourcesEditor() -> Element {
+    let sources = use_signal(Vec::<JobSource>::new);
+    let loading = use_signal(|| true);
+    let error = use_signal(|| None::<String>);
+    let mut new_name = use_signal(String::new);
+
+    let load_sources = {
+        let mut loading = loading;
+        let mut error = error;
+        let mut sources = sources;
+        move || {
+            spawn(async move {
+                *loading.write() = true;
+                match JobsService::fetch_job_sources().await {
+                    Ok(loaded) => {
+                        *sources.write() = loaded;
+                        *error.write() = None;
+                    }
+                    Err(e) => {
+                        *error.write() = Some(format!("Failed to load sources: {:?}", e));
+                    }
+                }
+                *loading.write() = false;
+            });
+        }
+    };
+
+    use_effect(move || {
+        load_sources();
+    });
+
+    rsx! {
+        div {
+            class: "space-y-6",
+            if let Some(err) = error() {
+                div {
+                    class: "p-4 bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 rounded-md",
+                    p {
+                        class: "text-sm text-red-800 dark:text-red-200",
+                        {err}
+                    }
+                }
+            }
+
+            // Add a source
+            form {
+                class: "flex gap-x-3",
+                onsubmit: move |e| {
+                    e.prevent_default();
+                    let name = new_name().trim().to_string();
+                    if name.is_empty() {
+                        return;
+                    }
+                    let mut error = error;
+                    spawn(async move {
+                        match AdminService::create_job_source(&name).await {
+                            Ok(_) => {
+                                *new_name.write() = String::new();
+                                load_sources();
+                            }
+                            Err(e) => {
+                                *error.write() = Some(format!("Failed to add source: {:?}", e));
+                            }
+                        }
+                    });
+                },
+                input {
+                    r#type: "text",
+                    placeholder: "New source name",
+                    class: "block w-full max-w-sm rounded-md border border-gray-300 dark:border-gray-600 bg-white dark:bg-gray-700 px-3 py-2 text-sm text-gray-900 dark:text-gray-100",
+                    value: "{new_name}",
+                    oninput: move |e| *new_name.write() = e.value(),
+                }
+                button {
+                    r#type: "submit",
+                    class: "px-4 py-2 text-sm font-medium text-white bg-indigo-600 rounded-md hover:bg-indigo-700",
+                    "Add source"
+                }
+            }
+
+            if loading() {
+                div {
+                    class: "text-center py-8",
+                    "Loading sources..."
+                }
+            } else {
+                ul {
+                    role: "list",
+                    class: "divide-y divide-gray-200 dark:divide-gray-700 bg-white dark:bg-gray-800 rounded-lg border border-gray-200 dark:border-gray-700",
+                    for source in sources().into_iter() {
+                        li {
+                            key: "{source.id}",
+                            class: "flex items-center justify-between px-5 py-3",
+                            div {
+                                p {
+                                    class: "text-sm font-medium text-gray-900 dark:text-gray-100",
+                                    {source.name.clone()}
+                                }
+                                p {
+                                    class: "text-xs text-gray-500 dark:text-gray-400",
+                                    {source.id.clone()}
+                                }
+                            }
+                            button {
+                                class: "text-sm font-medium text-red-600 hover:text-red-500 dark:text-red-400",
+                                onclick: {
+                                    let id = source.id.clone();
+                                    move |_| {
+                                        let id = id.clone();
+                                        let mut error = error;
+                                        spawn(async move {
+                                            match AdminService::delete_job_source(&id).await {
+                                                Ok(_) => load_sources(),
+                                                Err(e) => {
+                                                    *error.write() = Some(format!("Failed to delete source: {:?}", e));
+                                                }
+                                            }
+                                        });
+                                    }
+                                },
+                                "Delete"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,9 +1,15 @@
 pub mod admin_dashboard;
+pub mod ai_eval;
 pub mod ai_metrics;
 pub mod ai_prompts;
+pub mod job_sources;
+pub mod usage_limits;
 pub mod users_list;
 
 pub use admin_dashboard::AdminDashboard;
+pub use ai_eval::AiEvaluation;
 pub use ai_metrics::AiMetrics;
 pub use ai_prompts::AiPromptsEditor;
+pub use job_sources::JobSourcesEditor;
+pub use usage_limits::UsageLimitsEditor;
 pub use users_list::UsersList;
@@ -0,0 +1,266 @@
+//! Daily usage limits admin component: today's usage per user, the default
+//! limits and the limits of single users
+
+use crate::services::admin_service::{AdminService, UsageOverview};
+use crate::services::auth_service::User;
+use crate::services::usage_service::{describe_kind, format_resets_in};
+use dioxus::prelude::*;
+
+const QUOTA_KINDS: [&str; 3] = ["scans", "messages", "ai_calls"];
+
+const INPUT_CLASS: &str = "w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-md bg-white dark:bg-gray-700 text-gray-900 dark:text-gray-100 text-sm";
+
+#[component]
+pub fn UsageLimitsEditor() -> Element {
+    let overview = use_signal(|| None::<UsageOverview>);
+    let mut users = use_signal(Vec::<User>::new);
+    let error = use_signal(|| None::<String>);
+    let mut defaults = use_signal(|| vec![String::new(); QUOTA_KINDS.len()]);
+    let mut user_id = use_signal(String::new);
+    let mut user_kind = use_signal(|| QUOTA_KINDS[0].to_string());
+    let mut user_limit = use_signal(String::new);
+
+    let load_usage = {
+        let mut overview = overview;
+        let mut error = error;
+        move || {
+            spawn(async move {
+                match AdminService::fetch_usage().await {
+                    Ok(loaded) => {
+                        *defaults.write() = QUOTA_KINDS
+                            .iter()
+                            .map(|kind| {
+                                loaded
+                                    .limits
+                                    .iter()
+                                    .find(|l| l.user_id.is_none() && l.kind == *kind)
+                                    .map(|l| l.daily_limit.to_string())
+                                    .unwrap_or_default()
+                            })
+                            .collect();
+                        *overview.write() = Some(loaded);
+                        *error.write() = None;
+                    }
+                    Err(e) => {
+                        *error.write() = Some(format!("Failed to load usage: {:?}", e));
+                    }
+                }
+            });
+        }
+    };
+
+    use_effect(move || {
+        load_usage();
+        spawn(async move {
+            if let Ok(loaded) = AdminService::list_users().await {
+                *users.write() = loaded;
+            }
+        });
+    });
+
+    // Save a limit, then reload everything it changes
+    let save_limit = move |user: Option<String>, kind: String, value: String| {
+        let mut error = error;
+        let daily_limit = value.trim().parse::<u32>().ok();
+        if user.is_none() && daily_limit.is_none() {
+            *error.write() = Some(format!(
+                "The default limit of {} must be a number",
+                describe_kind(&kind)
+            ));
+            return;
+        }
+        spawn(async move {
+            match AdminService::set_usage_limit(user.as_deref(), &kind, daily_limit).await {
+                Ok(_) => load_usage(),
+                Err(e) => {
+                    *error.write() = Some(format!("Failed to save limit: {:?}", e));
+                }
+            }
+        });
+    };
+
+    rsx! {
+        div {
+            class: "bg-white dark:bg-gray-800 shadow rounded-lg p-6 space-y-6",
+            div {
+                h2 {
+                    class: "text-lg font-semibold text-gray-900 dark:text-gray-100",
+                    "Daily usage"
+                }
+                if let Some(data) = overview() {
+                    p {
+                        class: "text-sm text-gray-500 dark:text-gray-400",
+                        {format!("Counters reset in {}", format_resets_in(data.resets_in))}
+                    }
+                }
+            }
+
+            if let Some(err) = error() {
+                div {
+                    class: "p-4 bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 rounded-md",
+                    p {
+                        class: "text-sm text-red-800 dark:text-red-200",
+                        {err}
+                    }
+                }
+            }
+
+            // Default limits
+            div {
+                class: "grid grid-cols-1 gap-4 sm:grid-cols-3",
+                for (index, kind) in QUOTA_KINDS.into_iter().enumerate() {
+                    div {
+                        key: "{kind}",
+                        label {
+                            class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1",
+                            {format!("{} per day", describe_kind(kind))}
+                        }
+                        div {
+                            class: "flex gap-x-2",
+                            input {
+                                r#type: "number",
+                                min: "0",
+                                class: INPUT_CLASS,
+                                value: defaults()[index].clone(),
+                                oninput: move |e: Event<FormData>| defaults.write()[index] = e.value(),
+                            }
+                            button {
+                                class: "px-3 py-2 text-sm font-medium text-white bg-blue-600 rounded-md hover:bg-blue-700",
+                                onclick: move |_| {
+                                    save_limit(None, kind.to_string(), defaults()[index].clone());
+                                },
+                                "Save"
+                            }
+                        }
+                    }
+                }
+            }
+
+            // A single user's limit; an empty limit goes back to the default
+            form {
+                class: "flex flex-wrap items-end gap-3",
+                onsubmit: move |e| {
+                    e.prevent_default();
+                    if user_id().is_empty() {
+                        return;
+                    }
+                    save_limit(Some(user_id()), user_kind(), user_limit());
+                    *user_limit.write() = String::new();
+                },
+                div {
+                    class: "min-w-48",
+                    label {
+                        class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1",
+                        "User"
+                    }
+                    select {
+                        class: INPUT_CLASS,
+                        onchange: move |e: Event<FormData>| *user_id.write() = e.value(),
+                        option { value: "", "Select a user" }
+                        for user in users().iter() {
+                            option {
+                                value: "{user.id}",
+                                selected: user_id() == user.id,
+                                {user.email.clone().unwrap_or_else(|| user.id.clone())}
+                            }
+                        }
+                    }
+                }
+                div {
+                    label {
+                        class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1",
+                        "Limit"
+                    }
+                    select {
+                        class: INPUT_CLASS,
+                        onchange: move |e: Event<FormData>| *user_kind.write() = e.value(),
+                        for kind in QUOTA_KINDS {
+                            option {
+                                value: kind,
+                                selected: user_kind() == kind,
+                                {describe_kind(kind).to_string()}
+                            }
+                        }
+                    }
+                }
+                div {
+                    label {
+                        class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1",
+                        "Per day"
+                    }
+                    input {
+                        r#type: "number",
+                        min: "0",
+                        placeholder: "Default",
+                        class: INPUT_CLASS,
+                        value: "{user_limit}",
+                        oninput: move |e: Event<FormData>| *user_limit.write() = e.value(),
+                    }
+                }
+                button {
+                    r#type: "submit",
+                    class: "px-3 py-2 text-sm font-medium text-white bg-blue-600 rounded-md hover:bg-blue-700",
+                    "Set user limit"
+                }
+            }
+
+            // Today's usage
+            if let Some(data) = overview() {
+                if data.users.is_empty() {
+                    p {
+                        class: "text-sm text-gray-500 dark:text-gray-400",
+                        "Nobody has used anything today."
+                    }
+                } else {
+                    table {
+                        class: "min-w-full divide-y divide-gray-300 dark:divide-gray-700 text-sm",
+                        thead {
+                            tr {
+                                th {
+                                    class: "py-2 text-left font-medium text-gray-500 dark:text-gray-400",
+                                    "User"
+                                }
+                                for kind in QUOTA_KINDS {
+                                    th {
+                                        class: "py-2 text-right font-medium text-gray-500 dark:text-gray-400",
+                                        {describe_kind(kind).to_string()}
+                                    }
+                                }
+                            }
+                        }
+                        tbody {
+                            class: "divide-y divide-gray-200 dark:divide-gray-700",
+                            for usage in data.users.iter() {
+                                tr {
+                                    key: "{usage.user_id}",
+                                    td {
+                                        class: "py-2 text-gray-900 dark:text-gray-100",
+                                        {usage.email.clone()}
+                                    }
+                                    for quota in usage.quotas.iter() {
+                                        td {
+                                            class: if quota.used >= quota.limit {
+                                                "py-2 text-right font-semibold text-red-600 dark:text-red-400"
+                                            } else {
+                                                "py-2 text-right text-gray-700 dark:text-gray-300"
+                                            },
+                                            title: if data.limits.iter().any(|l| {
+                                                l.user_id.as_deref() == Some(usage.user_id.as_str())
+                                                    && l.kind == quota.kind
+                                            }) {
+                                                "Limit of this user"
+                                            } else {
+                                                "Default limit"
+                                            },
+                                            {format!("{} / {}", quota.used, quota.limit)}
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
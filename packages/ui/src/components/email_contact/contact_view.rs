@@ -1,8 +1,10 @@
 //! Contact view mode component
 
 use crate::components::button::{Button, ButtonVariant};
+use crate::services::email_contacts_service::EmailContactsService;
 use crate::services::jobs_service::EmailContact;
 use dioxus::prelude::*;
+use dioxus_router::Link;
 
 /// Contact view mode component
 #[component]
@@ -68,12 +70,23 @@ pub fn ContactView(contact: EmailContact, on_edit: EventHandler) -> Element {
                 }
             }
             div {
-                class: "pt-4",
+                class: "pt-4 flex items-center gap-x-4",
                 Button {
                     variant: ButtonVariant::Primary,
                     onclick: move |_| on_edit.call(()),
                     "Edit"
                 }
+                Link {
+                    to: format!("/contacts/{}", contact.email),
+                    class: "text-sm font-medium text-indigo-600 dark:text-indigo-400 hover:text-indigo-500",
+                    "View history"
+                }
+                a {
+                    href: EmailContactsService::vcard_url(Some(&contact.email)),
+                    download: "{contact.email}.vcf",
+                    class: "text-sm font-medium text-indigo-600 dark:text-indigo-400 hover:text-indigo-500",
+                    "Download vCard"
+                }
             }
         }
     }
@@ -0,0 +1,119 @@
+//! Markdown rendering component
+
+use crate::utils::markdown::{parse_markdown, MarkdownBlock, MarkdownInline};
+use dioxus::prelude::*;
+
+/// Render user-written Markdown as elements (raw HTML is shown as text)
+#[component]
+pub fn Markdown(content: String, class: Option<String>) -> Element {
+    let blocks = parse_markdown(&content);
+    let class = class.unwrap_or_default();
+
+    rsx! {
+        div {
+            class: "space-y-2 break-words {class}",
+            for block in blocks.iter() {
+                {render_block(block)}
+            }
+        }
+    }
+}
+
+fn render_block(block: &MarkdownBlock) -> Element {
+    match block {
+        MarkdownBlock::Paragraph(lines) => rsx! {
+            p {
+                for (idx, line) in lines.iter().enumerate() {
+                    if idx > 0 {
+                        br {}
+                    }
+                    {render_inlines(line)}
+                }
+            }
+        },
+        MarkdownBlock::Heading(level, inlines) => {
+            let heading_class = match level {
+                1 => "text-base font-semibold text-gray-900 dark:text-white",
+                2 => "text-sm font-semibold text-gray-900 dark:text-white",
+                _ => "text-sm font-medium text-gray-900 dark:text-white",
+            };
+            rsx! {
+                p {
+                    class: heading_class,
+                    {render_inlines(inlines)}
+                }
+            }
+        }
+        MarkdownBlock::List { ordered, items } => {
+            if *ordered {
+                rsx! {
+                    ol {
+                        class: "list-decimal pl-5 space-y-1",
+                        for item in items.iter() {
+                            li { {render_inlines(item)} }
+                        }
+                    }
+                }
+            } else {
+                rsx! {
+                    ul {
+                        class: "list-disc pl-5 space-y-1",
+                        for item in items.iter() {
+                            li { {render_inlines(item)} }
+                        }
+                    }
+                }
+            }
+        }
+        MarkdownBlock::Quote(inlines) => rsx! {
+            blockquote {
+                class: "border-l-2 border-gray-300 dark:border-gray-600 pl-3 italic",
+                {render_inlines(inlines)}
+            }
+        },
+        MarkdownBlock::CodeBlock(code) => rsx! {
+            pre {
+                class: "overflow-x-auto rounded bg-gray-50 dark:bg-gray-800 p-2 text-xs font-mono text-gray-800 dark:text-gray-200",
+                {code.clone()}
+            }
+        },
+    }
+}
+
+fn render_inlines(inlines: &[MarkdownInline]) -> Element {
+    rsx! {
+        for inline in inlines.iter() {
+            {render_inline(inline)}
+        }
+    }
+}
+
+fn render_inline(inline: &MarkdownInline) -> Element {
+    match inline {
+        MarkdownInline::Text(text) => rsx! { {text.clone()} },
+        MarkdownInline::Bold(text) => rsx! {
+            strong {
+                class: "font-semibold text-gray-900 dark:text-white",
+                {text.clone()}
+            }
+        },
+        MarkdownInline::Italic(text) => rsx! {
+            em { {text.clone()} }
+        },
+        MarkdownInline::Code(text) => rsx! {
+            code {
+                class: "rounded bg-gray-100 dark:bg-gray-800 px-1 py-0.5 text-xs font-mono",
+                {text.clone()}
+            }
+        },
+        MarkdownInline::Link { text, url } => rsx! {
+            a {
+                href: url.clone(),
+                target: "_blank",
+                rel: "noopener noreferrer",
+                class: "text-indigo-600 dark:text-indigo-400 hover:underline",
+                {text.clone()}
+            }
+        },
+    }
+}
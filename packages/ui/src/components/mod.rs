@@ -11,8 +11,11 @@ pub mod input;
 pub mod label;
 pub mod login_button;
 pub mod login_page;
+pub mod markdown;
 pub mod navbar;
 pub mod popover;
+pub mod resume_settings;
+pub mod scan_filter_settings;
 pub mod scroll_area;
 pub mod select;
 pub mod separator;
@@ -23,4 +26,5 @@ pub mod status_change_dialog;
 pub mod status_stepper;
 pub mod timezone_settings;
 pub mod toast;
+pub mod usage_settings;
 pub mod user_profile;
@@ -0,0 +1,82 @@
+//! Resume settings component
+
+use crate::components::button::{Button, ButtonVariant};
+use crate::services::job_documents_service::JobDocumentsService;
+use dioxus::prelude::*;
+
+/// Resume or profile text used to tailor cover letters and resume suggestions
+#[component]
+pub fn ResumeSettings() -> Element {
+    let mut resume = use_signal(String::new);
+    let mut saving = use_signal(|| false);
+    let mut message = use_signal(|| None::<String>);
+
+    use_effect(move || {
+        spawn(async move {
+            if let Ok(Some(saved)) = JobDocumentsService::fetch_resume().await {
+                *resume.write() = saved;
+            }
+        });
+    });
+
+    rsx! {
+        div {
+            class: "px-4 sm:px-6 lg:px-8 py-6",
+            div {
+                class: "mb-6",
+                h2 {
+                    class: "text-base font-semibold text-gray-900 dark:text-white",
+                    "Resume"
+                }
+                p {
+                    class: "mt-1 text-sm text-gray-500 dark:text-gray-400",
+                    "Paste your resume or a profile summary. It is used to tailor cover letters and resume suggestions for each job."
+                }
+            }
+
+            div {
+                class: "space-y-4",
+                textarea {
+                    rows: 12,
+                    class: "block w-full rounded-md bg-white px-3 py-1.5 text-sm text-gray-900 outline outline-1 -outline-offset-1 outline-gray-300 placeholder:text-gray-400 dark:bg-white/5 dark:text-white dark:outline-white/10",
+                    placeholder: "Experience, skills, education...",
+                    value: "{resume}",
+                    oninput: move |e: Event<FormData>| {
+                        *resume.write() = e.value();
+                        *message.write() = None;
+                    },
+                }
+                div {
+                    class: "flex items-center gap-x-4",
+                    Button {
+                        variant: ButtonVariant::Primary,
+                        disabled: saving(),
+                        onclick: move |_| {
+                            let text = resume();
+                            spawn(async move {
+                                *saving.write() = true;
+                                let value = Some(text).filter(|t| !t.trim().is_empty());
+                                *message.write() = Some(match JobDocumentsService::update_resume(value).await {
+                                    Ok(_) => "Resume saved".to_string(),
+                                    Err(e) => format!("Failed to save resume: {}", e),
+                                });
+                                *saving.write() = false;
+                            });
+                        },
+                        if saving() {
+                            "Saving..."
+                        } else {
+                            "Save"
+                        }
+                    }
+                    if let Some(msg) = message() {
+                        p {
+                            class: "text-sm text-gray-500 dark:text-gray-400",
+                            {msg}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod component;
+
+pub use component::ResumeSettings;
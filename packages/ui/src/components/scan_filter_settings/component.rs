@@ -0,0 +1,273 @@
+//! Scan filter settings component
+
+use crate::components::button::{Button, ButtonVariant};
+use crate::services::gmail_scanner_service::{FilterPreview, GmailScannerService, ScanFilters};
+use dioxus::prelude::*;
+
+/// Gmail's inbox categories, with their names in the UI
+const CATEGORIES: [(&str, &str); 5] = [
+    ("primary", "Primary"),
+    ("social", "Social"),
+    ("promotions", "Promotions"),
+    ("updates", "Updates"),
+    ("forums", "Forums"),
+];
+
+const TEXTAREA_CLASS: &str = "block w-full rounded-md bg-white px-3 py-1.5 text-sm text-gray-900 outline outline-1 -outline-offset-1 outline-gray-300 placeholder:text-gray-400 dark:bg-white/5 dark:text-white dark:outline-white/10";
+
+/// One term per line
+fn to_lines(terms: &[String]) -> String {
+    terms.join("\n")
+}
+
+fn from_lines(text: &str) -> Vec<String> {
+    text.lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// Labels, categories, keywords and senders that narrow what scans fetch
+#[component]
+pub fn ScanFilterSettings() -> Element {
+    let mut include_labels = use_signal(String::new);
+    let mut exclude_labels = use_signal(String::new);
+    let mut allow_keywords = use_signal(String::new);
+    let mut deny_keywords = use_signal(String::new);
+    let mut blocked_senders = use_signal(String::new);
+    // "include", "exclude" or "" for each category
+    let mut categories = use_signal(|| vec![String::new(); CATEGORIES.len()]);
+    let mut preview = use_signal(|| None::<FilterPreview>);
+    let mut busy = use_signal(|| false);
+    let mut message = use_signal(|| None::<String>);
+
+    let mut show_filters = move |filters: &ScanFilters| {
+        *include_labels.write() = to_lines(&filters.include_labels);
+        *exclude_labels.write() = to_lines(&filters.exclude_labels);
+        *allow_keywords.write() = to_lines(&filters.allow_keywords);
+        *deny_keywords.write() = to_lines(&filters.deny_keywords);
+        *blocked_senders.write() = to_lines(&filters.blocked_senders);
+        *categories.write() = CATEGORIES
+            .iter()
+            .map(|(category, _)| {
+                let is = |list: &[String]| list.iter().any(|c| c.as_str() == *category);
+                if is(&filters.include_categories) {
+                    "include".to_string()
+                } else if is(&filters.exclude_categories) {
+                    "exclude".to_string()
+                } else {
+                    String::new()
+                }
+            })
+            .collect();
+    };
+
+    use_effect(move || {
+        spawn(async move {
+            if let Ok(saved) = GmailScannerService::get_scan_filters().await {
+                show_filters(&saved);
+            }
+        });
+    });
+
+    let current_filters = move || {
+        let with_mode = |mode: &str| -> Vec<String> {
+            CATEGORIES
+                .iter()
+                .zip(categories().iter())
+                .filter(|(_, m)| m.as_str() == mode)
+                .map(|((category, _), _)| category.to_string())
+                .collect()
+        };
+        ScanFilters {
+            include_labels: from_lines(&include_labels()),
+            exclude_labels: from_lines(&exclude_labels()),
+            include_categories: with_mode("include"),
+            exclude_categories: with_mode("exclude"),
+            allow_keywords: from_lines(&allow_keywords()),
+            deny_keywords: from_lines(&deny_keywords()),
+            blocked_senders: from_lines(&blocked_senders()),
+        }
+    };
+
+    rsx! {
+        div {
+            class: "px-4 sm:px-6 lg:px-8 py-6",
+            div {
+                class: "mb-6",
+                h2 {
+                    class: "text-base font-semibold text-gray-900 dark:text-white",
+                    "Scan filters"
+                }
+                p {
+                    class: "mt-1 text-sm text-gray-500 dark:text-gray-400",
+                    "Keep newsletters, receipts and personal mail out of your scans. Enter one label, keyword or sender per line."
+                }
+            }
+
+            div {
+                class: "space-y-6",
+                div {
+                    class: "grid grid-cols-1 gap-4 sm:grid-cols-5",
+                    for (index, (_, name)) in CATEGORIES.into_iter().enumerate() {
+                        div {
+                            key: "{name}",
+                            label {
+                                class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1",
+                                "{name}"
+                            }
+                            select {
+                                class: "block w-full rounded-md bg-white px-3 py-1.5 text-sm text-gray-900 outline outline-1 -outline-offset-1 outline-gray-300 dark:bg-white/5 dark:text-white dark:outline-white/10",
+                                onchange: move |e: Event<FormData>| {
+                                    categories.write()[index] = e.value();
+                                    *preview.write() = None;
+                                },
+                                option { value: "", selected: categories()[index].is_empty(), "Scan" }
+                                option { value: "include", selected: categories()[index] == "include", "Only these" }
+                                option { value: "exclude", selected: categories()[index] == "exclude", "Skip" }
+                            }
+                        }
+                    }
+                }
+
+                div {
+                    class: "grid grid-cols-1 gap-4 sm:grid-cols-2",
+                    FilterTerms {
+                        label: "Only labels",
+                        placeholder: "Jobs",
+                        value: include_labels(),
+                        onchange: move |value| {
+                            *include_labels.write() = value;
+                            *preview.write() = None;
+                        },
+                    }
+                    FilterTerms {
+                        label: "Skip labels",
+                        placeholder: "Receipts",
+                        value: exclude_labels(),
+                        onchange: move |value| {
+                            *exclude_labels.write() = value;
+                            *preview.write() = None;
+                        },
+                    }
+                    FilterTerms {
+                        label: "Only mail containing",
+                        placeholder: "interview",
+                        value: allow_keywords(),
+                        onchange: move |value| {
+                            *allow_keywords.write() = value;
+                            *preview.write() = None;
+                        },
+                    }
+                    FilterTerms {
+                        label: "Skip mail containing",
+                        placeholder: "unsubscribe",
+                        value: deny_keywords(),
+                        onchange: move |value| {
+                            *deny_keywords.write() = value;
+                            *preview.write() = None;
+                        },
+                    }
+                    FilterTerms {
+                        label: "Blocked senders",
+                        placeholder: "newsletter@example.com",
+                        value: blocked_senders(),
+                        onchange: move |value| {
+                            *blocked_senders.write() = value;
+                            *preview.write() = None;
+                        },
+                    }
+                }
+
+                if let Some(result) = preview() {
+                    div {
+                        class: "rounded-md bg-gray-50 dark:bg-white/5 p-4 space-y-1",
+                        p {
+                            class: "text-sm text-gray-700 dark:text-gray-300",
+                            {format!("About {} messages in the last 7 days match", result.estimated_count)}
+                        }
+                        p {
+                            class: "font-mono text-xs text-gray-500 dark:text-gray-400 break-all",
+                            {result.query}
+                        }
+                    }
+                }
+
+                div {
+                    class: "flex items-center gap-x-4",
+                    Button {
+                        variant: ButtonVariant::Primary,
+                        disabled: busy(),
+                        onclick: move |_| {
+                            let filters = current_filters();
+                            spawn(async move {
+                                *busy.write() = true;
+                                match GmailScannerService::update_scan_filters(&filters).await {
+                                    Ok(saved) => {
+                                        show_filters(&saved);
+                                        *message.write() = Some("Scan filters saved".to_string());
+                                    }
+                                    Err(e) => {
+                                        *message.write() = Some(format!("Failed to save scan filters: {}", e));
+                                    }
+                                }
+                                *busy.write() = false;
+                            });
+                        },
+                        "Save"
+                    }
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        disabled: busy(),
+                        onclick: move |_| {
+                            let filters = current_filters();
+                            spawn(async move {
+                                *busy.write() = true;
+                                *message.write() = None;
+                                match GmailScannerService::preview_scan_filters(&filters).await {
+                                    Ok(result) => *preview.write() = Some(result),
+                                    Err(e) => {
+                                        *message.write() = Some(format!("Failed to preview scan filters: {}", e));
+                                    }
+                                }
+                                *busy.write() = false;
+                            });
+                        },
+                        "Preview"
+                    }
+                    if let Some(msg) = message() {
+                        p {
+                            class: "text-sm text-gray-500 dark:text-gray-400",
+                            {msg}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A list of filter terms, one per line
+#[component]
+fn FilterTerms(
+    label: &'static str,
+    placeholder: &'static str,
+    value: String,
+    onchange: EventHandler<String>,
+) -> Element {
+    rsx! {
+        div {
+            label {
+                class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1",
+                "{label}"
+            }
+            textarea {
+                rows: 3,
+                class: TEXTAREA_CLASS,
+                placeholder: "{placeholder}",
+                value: "{value}",
+                oninput: move |e: Event<FormData>| onchange.call(e.value()),
+            }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Usage settings component
+
+use crate::services::usage_service::{describe_kind, format_resets_in, UsageService, UsageSummary};
+use dioxus::prelude::*;
+
+/// What the user has used today of their daily limits
+#[component]
+pub fn UsageSettings() -> Element {
+    let mut usage = use_signal(|| None::<UsageSummary>);
+    let mut error = use_signal(|| None::<String>);
+
+    use_effect(move || {
+        spawn(async move {
+            match UsageService::fetch_usage().await {
+                Ok(summary) => *usage.write() = Some(summary),
+                Err(e) => *error.write() = Some(format!("Failed to load usage: {}", e)),
+            }
+        });
+    });
+
+    rsx! {
+        div {
+            class: "px-4 sm:px-6 lg:px-8 py-6",
+            div {
+                class: "mb-6",
+                h2 {
+                    class: "text-base font-semibold text-gray-900 dark:text-white",
+                    "Daily usage"
+                }
+                p {
+                    class: "mt-1 text-sm text-gray-500 dark:text-gray-400",
+                    "Gmail scans, fetched messages and AI calls are limited per day."
+                }
+            }
+
+            if let Some(err) = error() {
+                p {
+                    class: "text-sm text-red-600 dark:text-red-400",
+                    {err}
+                }
+            }
+
+            if let Some(summary) = usage() {
+                div {
+                    class: "space-y-4 max-w-xl",
+                    for quota in summary.quotas.iter() {
+                        div {
+                            key: "{quota.kind}",
+                            div {
+                                class: "flex justify-between text-sm",
+                                span {
+                                    class: "text-gray-700 dark:text-gray-300",
+                                    {describe_kind(&quota.kind).to_string()}
+                                }
+                                span {
+                                    class: "text-gray-500 dark:text-gray-400",
+                                    {format!("{} / {}", quota.used, quota.limit)}
+                                }
+                            }
+                            div {
+                                class: "mt-1 h-2 w-full rounded-full bg-gray-200 dark:bg-gray-700",
+                                div {
+                                    class: if quota.used >= quota.limit {
+                                        "h-2 rounded-full bg-red-500"
+                                    } else {
+                                        "h-2 rounded-full bg-blue-500"
+                                    },
+                                    style: format!("width: {}%", usage_percent(quota.used, quota.limit)),
+                                }
+                            }
+                        }
+                    }
+                    p {
+                        class: "text-xs text-gray-500 dark:text-gray-400",
+                        {format!("Resets in {}", format_resets_in(summary.resets_in))}
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn usage_percent(used: u32, limit: u32) -> u32 {
+    if limit == 0 {
+        100
+    } else {
+        (used.saturating_mul(100) / limit).min(100)
+    }
+}
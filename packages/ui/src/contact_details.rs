@@ -0,0 +1,322 @@
+//! Contact details component
+
+use crate::components::button::{Button, ButtonVariant};
+use crate::services::email_contacts_service::{ContactHistory, EmailContactsService};
+use crate::state::use_auth;
+use crate::utils::format_date;
+use dioxus::prelude::*;
+use dioxus_router::Link;
+
+/// Contact page: relationship history, jobs, emails and notes for one person
+#[component]
+pub fn ContactDetails(email: String) -> Element {
+    let auth = use_auth();
+    let mut history = use_signal(|| None::<ContactHistory>);
+    let loading = use_signal(|| true);
+    let error = use_signal(|| None::<String>);
+    let mut notes = use_signal(String::new);
+    let mut saving = use_signal(|| false);
+    let mut saved = use_signal(|| false);
+
+    let user = auth.user.read();
+    let timezone = user.as_ref().and_then(|u| u.timezone.as_deref());
+
+    // Fetch contact history on mount
+    use_effect({
+        let email = email.clone();
+        let mut loading = loading;
+        let mut error = error;
+        move || {
+            let email = email.clone();
+            spawn(async move {
+                *loading.write() = true;
+                match EmailContactsService::fetch_contact_history(&email).await {
+                    Ok(data) => {
+                        *notes.write() = data.contact.notes.clone().unwrap_or_default();
+                        *history.write() = Some(data);
+                        *error.write() = None;
+                    }
+                    Err(e) => *error.write() = Some(format!("Failed to load contact: {}", e)),
+                }
+                *loading.write() = false;
+            });
+        }
+    });
+
+    let Some(data) = history() else {
+        return rsx! {
+            div {
+                class: "px-4 sm:px-6 lg:px-8 py-6 text-center text-gray-500 dark:text-gray-400",
+                if loading() {
+                    "Loading contact..."
+                } else {
+                    {error().unwrap_or_else(|| "Contact not found".to_string())}
+                }
+            }
+        };
+    };
+
+    let contact = data.contact.clone();
+
+    rsx! {
+        div {
+            class: "px-4 sm:px-6 lg:px-8 py-6 space-y-6",
+            Link {
+                to: "/contacts",
+                class: "text-sm font-medium text-indigo-600 dark:text-indigo-400 hover:text-indigo-500",
+                "← All contacts"
+            }
+
+            // Contact header
+            div {
+                class: "rounded-lg bg-white dark:bg-gray-800 p-5 ring-1 ring-inset ring-gray-200 dark:ring-white/15",
+                h1 {
+                    class: "text-2xl font-semibold text-gray-900 dark:text-white",
+                    {contact.name.clone().unwrap_or_else(|| contact.email.clone())}
+                }
+                div {
+                    class: "mt-1 flex flex-wrap gap-x-4 text-sm text-gray-500 dark:text-gray-400",
+                    a {
+                        href: "mailto:{contact.email}",
+                        class: "hover:text-indigo-600 dark:hover:text-indigo-400",
+                        {contact.email.clone()}
+                    }
+                    if let (Some(company_id), Some(company_name)) = (contact.company_id.clone(), contact.company_name.clone()) {
+                        Link {
+                            to: format!("/companies/{}", company_id),
+                            class: "hover:text-indigo-600 dark:hover:text-indigo-400",
+                            {company_name}
+                        }
+                    }
+                    if let Some(linkedin) = contact.linkedin.clone().filter(|l| !l.is_empty()) {
+                        a {
+                            href: "{linkedin}",
+                            target: "_blank",
+                            rel: "noopener noreferrer",
+                            class: "text-indigo-600 dark:text-indigo-400",
+                            "LinkedIn"
+                        }
+                    }
+                    if let Some(website) = contact.website.clone().filter(|w| !w.is_empty()) {
+                        a {
+                            href: "{website}",
+                            target: "_blank",
+                            rel: "noopener noreferrer",
+                            class: "text-indigo-600 dark:text-indigo-400",
+                            "Website"
+                        }
+                    }
+                }
+                dl {
+                    class: "mt-4 grid grid-cols-2 gap-4 sm:grid-cols-4",
+                    div {
+                        dt {
+                            class: "text-xs text-gray-500 dark:text-gray-400",
+                            "First contact"
+                        }
+                        dd {
+                            class: "mt-1 text-sm font-medium text-gray-900 dark:text-white",
+                            {data.first_contact.as_deref().map(|d| format_date(d, timezone)).unwrap_or_else(|| "—".to_string())}
+                        }
+                    }
+                    div {
+                        dt {
+                            class: "text-xs text-gray-500 dark:text-gray-400",
+                            "Last contact"
+                        }
+                        dd {
+                            class: "mt-1 text-sm font-medium text-gray-900 dark:text-white",
+                            {data.last_contact.as_deref().map(|d| format_date(d, timezone)).unwrap_or_else(|| "—".to_string())}
+                        }
+                    }
+                    div {
+                        dt {
+                            class: "text-xs text-gray-500 dark:text-gray-400",
+                            "Emails received"
+                        }
+                        dd {
+                            class: "mt-1 text-sm font-medium text-gray-900 dark:text-white",
+                            "{data.emails_received}"
+                        }
+                    }
+                    div {
+                        dt {
+                            class: "text-xs text-gray-500 dark:text-gray-400",
+                            "Emails sent"
+                        }
+                        dd {
+                            class: "mt-1 text-sm font-medium text-gray-900 dark:text-white",
+                            "{data.emails_sent}"
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "grid grid-cols-1 gap-6 lg:grid-cols-3",
+                // Email history
+                div {
+                    class: "rounded-lg bg-white dark:bg-gray-800 p-5 ring-1 ring-inset ring-gray-200 dark:ring-white/15 lg:col-span-2",
+                    h2 {
+                        class: "text-lg font-semibold text-gray-900 dark:text-white mb-3",
+                        "Emails"
+                    }
+                    if data.emails.is_empty() {
+                        p {
+                            class: "text-sm text-gray-500 dark:text-gray-400",
+                            "No emails with this contact."
+                        }
+                    }
+                    ul {
+                        role: "list",
+                        class: "divide-y divide-gray-100 dark:divide-white/5",
+                        for message in data.emails.iter() {
+                            li {
+                                key: "{message.gmail_id}",
+                                class: "py-3",
+                                div {
+                                    class: "flex justify-between gap-x-4",
+                                    p {
+                                        class: "truncate text-sm font-medium text-gray-900 dark:text-white",
+                                        {message.subject.clone().unwrap_or_else(|| "(no subject)".to_string())}
+                                    }
+                                    p {
+                                        class: "flex-none text-xs text-gray-500 dark:text-gray-400",
+                                        {message.date.as_deref().map(|d| format_date(d, timezone)).unwrap_or_default()}
+                                    }
+                                }
+                                div {
+                                    class: "flex justify-between gap-x-4 text-xs text-gray-500 dark:text-gray-400",
+                                    span {
+                                        class: if message.inbound { "text-green-700 dark:text-green-400" } else { "text-indigo-600 dark:text-indigo-400" },
+                                        if message.inbound { "Received" } else { "Sent" }
+                                    }
+                                    if let Some(job_id) = message.job_id.clone() {
+                                        Link {
+                                            to: format!("/jobs/{}", job_id),
+                                            class: "flex-none hover:text-indigo-600 dark:hover:text-indigo-400",
+                                            "View job"
+                                        }
+                                    }
+                                }
+                                if let Some(snippet) = message.snippet.clone() {
+                                    p {
+                                        class: "mt-1 line-clamp-2 text-xs text-gray-500 dark:text-gray-400",
+                                        {snippet}
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                div {
+                    class: "space-y-6",
+                    // Jobs
+                    div {
+                        class: "rounded-lg bg-white dark:bg-gray-800 p-5 ring-1 ring-inset ring-gray-200 dark:ring-white/15",
+                        h2 {
+                            class: "text-lg font-semibold text-gray-900 dark:text-white mb-3",
+                            "Jobs"
+                        }
+                        if data.jobs.is_empty() {
+                            p {
+                                class: "text-sm text-gray-500 dark:text-gray-400",
+                                "Not linked to any jobs."
+                            }
+                        }
+                        ul {
+                            role: "list",
+                            class: "space-y-3",
+                            for job in data.jobs.iter() {
+                                li {
+                                    key: "{job.id}",
+                                    Link {
+                                        to: format!("/jobs/{}", job.id),
+                                        class: "block truncate text-sm font-medium text-gray-900 dark:text-white hover:text-indigo-600 dark:hover:text-indigo-400",
+                                        {job.title.clone()}
+                                    }
+                                    div {
+                                        class: "flex items-center gap-x-2 text-xs text-gray-500 dark:text-gray-400",
+                                        span { {job.company.clone()} }
+                                        if let Some(status_name) = job.status_name.clone() {
+                                            span { "· {status_name}" }
+                                        }
+                                        if job.referred {
+                                            span {
+                                                class: "rounded-full bg-indigo-50 dark:bg-indigo-900/30 px-2 py-0.5 font-medium text-indigo-700 dark:text-indigo-300",
+                                                "Referrer"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    // Notes
+                    div {
+                        class: "rounded-lg bg-white dark:bg-gray-800 p-5 ring-1 ring-inset ring-gray-200 dark:ring-white/15",
+                        h2 {
+                            class: "text-lg font-semibold text-gray-900 dark:text-white mb-3",
+                            "Notes"
+                        }
+                        textarea {
+                            rows: 6,
+                            placeholder: "How you met, what you talked about, when to follow up...",
+                            class: "block w-full rounded-md bg-white px-3 py-1.5 text-sm text-gray-900 outline outline-1 -outline-offset-1 outline-gray-300 placeholder:text-gray-400 dark:bg-white/5 dark:text-white dark:outline-white/10",
+                            value: "{notes}",
+                            oninput: move |e: Event<FormData>| {
+                                *notes.write() = e.value();
+                                *saved.write() = false;
+                            },
+                        }
+                        div {
+                            class: "mt-3 flex items-center gap-x-3",
+                            Button {
+                                variant: ButtonVariant::Primary,
+                                disabled: saving(),
+                                onclick: {
+                                    let email = email.clone();
+                                    let mut error = error;
+                                    move |_| {
+                                        let email = email.clone();
+                                        spawn(async move {
+                                            *saving.write() = true;
+                                            match EmailContactsService::update_contact_notes(&email, notes()).await {
+                                                Ok(data) => {
+                                                    *history.write() = Some(data);
+                                                    *saved.write() = true;
+                                                    *error.write() = None;
+                                                }
+                                                Err(e) => *error.write() = Some(format!("Failed to save notes: {}", e)),
+                                            }
+                                            *saving.write() = false;
+                                        });
+                                    }
+                                },
+                                if saving() {
+                                    "Saving..."
+                                } else {
+                                    "Save notes"
+                                }
+                            }
+                            if saved() {
+                                span {
+                                    class: "text-sm text-green-700 dark:text-green-400",
+                                    "Saved"
+                                }
+                            }
+                        }
+                        if let Some(err) = error() {
+                            p {
+                                class: "mt-3 text-sm text-red-600 dark:text-red-400",
+                                {err}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,335 @@
+//! Contacts list component
+
+use crate::services::email_contacts_service::{ContactSummary, EmailContactsService, StaleContact};
+use crate::state::use_auth;
+use crate::utils::format_date;
+use dioxus::prelude::*;
+use dioxus_router::Link;
+
+/// Day thresholds offered for stale contacts
+const STALE_DAY_OPTIONS: [i64; 3] = [7, 14, 30];
+
+/// Contacts list with the contacts waiting on a reply at the top
+#[component]
+pub fn ContactsList() -> Element {
+    let auth = use_auth();
+    let contacts = use_signal(Vec::<ContactSummary>::new);
+    let stale = use_signal(Vec::<StaleContact>::new);
+    let loading = use_signal(|| true);
+    let error = use_signal(|| None::<String>);
+    let mut stale_days = use_signal(|| STALE_DAY_OPTIONS[0]);
+    let mut search = use_signal(String::new);
+    let importing = use_signal(|| false);
+    let import_result = use_signal(|| None::<String>);
+
+    let user = auth.user.read();
+    let timezone = user.as_ref().and_then(|u| u.timezone.as_deref());
+
+    let load = {
+        let mut contacts = contacts;
+        let mut loading = loading;
+        let mut error = error;
+        move || {
+            spawn(async move {
+                *loading.write() = true;
+                match EmailContactsService::fetch_contacts().await {
+                    Ok(list) => {
+                        *contacts.write() = list;
+                        *error.write() = None;
+                    }
+                    Err(e) => *error.write() = Some(format!("Failed to load contacts: {}", e)),
+                }
+                *loading.write() = false;
+            });
+        }
+    };
+
+    // Fetch contacts on mount
+    use_effect(move || load());
+
+    // Fetch stale contacts whenever the threshold changes
+    use_effect(move || {
+        let days = stale_days();
+        let mut stale = stale;
+        let mut error = error;
+        spawn(async move {
+            match EmailContactsService::fetch_stale_contacts(days).await {
+                Ok(list) => *stale.write() = list,
+                Err(e) => *error.write() = Some(format!("Failed to load stale contacts: {}", e)),
+            }
+        });
+    });
+
+    let query = search().trim().to_lowercase();
+    let visible: Vec<ContactSummary> = contacts()
+        .into_iter()
+        .filter(|c| {
+            query.is_empty()
+                || c.email.contains(query.as_str())
+                || c.name
+                    .as_deref()
+                    .is_some_and(|n| n.to_lowercase().contains(&query))
+                || c.company_name
+                    .as_deref()
+                    .is_some_and(|n| n.to_lowercase().contains(&query))
+        })
+        .collect();
+
+    rsx! {
+        div {
+            class: "px-4 sm:px-6 lg:px-8 py-6 space-y-8",
+            div {
+                class: "sm:flex sm:items-center",
+                div {
+                    class: "sm:flex-auto",
+                    h1 {
+                        class: "text-2xl font-semibold text-gray-900 dark:text-white",
+                        "Contacts"
+                    }
+                    p {
+                        class: "mt-2 text-sm text-gray-700 dark:text-gray-300",
+                        "Recruiters, hiring managers and referrers you have been in touch with."
+                    }
+                }
+                div {
+                    class: "mt-4 flex items-center gap-x-4 sm:ml-16 sm:mt-0 sm:flex-none",
+                    label {
+                        class: "cursor-pointer text-sm font-medium text-indigo-600 dark:text-indigo-400 hover:text-indigo-500",
+                        if importing() {
+                            "Importing..."
+                        } else {
+                            "Import vCard"
+                        }
+                        input {
+                            r#type: "file",
+                            class: "sr-only",
+                            accept: ".vcf,text/vcard",
+                            disabled: importing(),
+                            onchange: move |e: Event<FormData>| {
+                                let Some(file) = e.files().into_iter().next() else {
+                                    return;
+                                };
+                                let mut importing = importing;
+                                let mut import_result = import_result;
+                                let mut error = error;
+                                spawn(async move {
+                                    *importing.write() = true;
+                                    *import_result.write() = None;
+                                    match file.read_bytes().await {
+                                        Ok(bytes) => match EmailContactsService::import_vcards(bytes.to_vec()).await {
+                                            Ok(summary) => {
+                                                *import_result.write() = Some(format!(
+                                                    "Imported {} new and {} updated contacts ({} unchanged, {} without an email address).",
+                                                    summary.created, summary.updated, summary.unchanged, summary.skipped
+                                                ));
+                                                load();
+                                            }
+                                            Err(e) => *error.write() = Some(format!("Import failed: {}", e)),
+                                        },
+                                        Err(e) => *error.write() = Some(format!("Failed to read file: {}", e)),
+                                    }
+                                    *importing.write() = false;
+                                });
+                            },
+                        }
+                    }
+                    a {
+                        href: EmailContactsService::vcard_url(None),
+                        download: "contacts.vcf",
+                        class: "text-sm font-medium text-indigo-600 dark:text-indigo-400 hover:text-indigo-500",
+                        "Export all"
+                    }
+                    input {
+                        r#type: "search",
+                        placeholder: "Search contacts",
+                        class: "block w-64 rounded-md bg-white px-3 py-1.5 text-sm text-gray-900 outline outline-1 -outline-offset-1 outline-gray-300 placeholder:text-gray-400 dark:bg-white/5 dark:text-white dark:outline-white/10",
+                        value: "{search}",
+                        oninput: move |e: Event<FormData>| *search.write() = e.value(),
+                    }
+                }
+            }
+
+            if let Some(message) = import_result() {
+                div {
+                    class: "rounded-md bg-green-50 dark:bg-green-900/20 p-4",
+                    p {
+                        class: "text-sm text-green-800 dark:text-green-200",
+                        {message}
+                    }
+                }
+            }
+
+            if let Some(err) = error() {
+                div {
+                    class: "rounded-md bg-red-50 dark:bg-red-900/20 p-4",
+                    p {
+                        class: "text-sm text-red-800 dark:text-red-200",
+                        {err}
+                    }
+                }
+            }
+
+            // Contacts waiting on a reply
+            div {
+                class: "rounded-lg bg-white dark:bg-gray-800 p-5 ring-1 ring-inset ring-gray-200 dark:ring-white/15",
+                div {
+                    class: "flex items-center justify-between gap-x-4 mb-3",
+                    div {
+                        h2 {
+                            class: "text-lg font-semibold text-gray-900 dark:text-white",
+                            "Waiting on your reply"
+                        }
+                        p {
+                            class: "text-xs text-gray-500 dark:text-gray-400",
+                            "People who emailed about a job and haven't heard back from you."
+                        }
+                    }
+                    select {
+                        class: "rounded-md bg-white py-1.5 pl-3 pr-8 text-sm text-gray-900 outline outline-1 -outline-offset-1 outline-gray-300 dark:bg-white/5 dark:text-white dark:outline-white/10",
+                        value: "{stale_days}",
+                        onchange: move |e: Event<FormData>| {
+                            if let Ok(days) = e.value().parse::<i64>() {
+                                *stale_days.write() = days;
+                            }
+                        },
+                        for days in STALE_DAY_OPTIONS {
+                            option {
+                                value: "{days}",
+                                selected: days == stale_days(),
+                                "{days}+ days"
+                            }
+                        }
+                    }
+                }
+                if stale().is_empty() {
+                    p {
+                        class: "text-sm text-gray-500 dark:text-gray-400",
+                        "You're all caught up."
+                    }
+                }
+                ul {
+                    role: "list",
+                    class: "divide-y divide-gray-100 dark:divide-white/5",
+                    for contact in stale().iter() {
+                        li {
+                            key: "{contact.email}",
+                            class: "flex items-center justify-between gap-x-4 py-3",
+                            div {
+                                class: "min-w-0",
+                                Link {
+                                    to: format!("/contacts/{}", contact.email),
+                                    class: "block truncate text-sm font-medium text-gray-900 dark:text-white hover:text-indigo-600 dark:hover:text-indigo-400",
+                                    {contact.name.clone().unwrap_or_else(|| contact.email.clone())}
+                                }
+                                p {
+                                    class: "text-xs text-gray-500 dark:text-gray-400",
+                                    {[
+                                        contact.company_name.clone(),
+                                        Some(format!("Last email {}", format_date(&contact.last_received, timezone))),
+                                        contact.last_replied.as_deref().map(|d| format!("you replied {}", format_date(d, timezone))),
+                                    ]
+                                    .into_iter()
+                                    .flatten()
+                                    .collect::<Vec<_>>()
+                                    .join(" · ")}
+                                }
+                            }
+                            div {
+                                class: "flex flex-none items-center gap-x-3",
+                                if let Some(job_id) = contact.job_id.clone() {
+                                    Link {
+                                        to: format!("/jobs/{}", job_id),
+                                        class: "text-xs font-medium text-indigo-600 dark:text-indigo-400 hover:text-indigo-500",
+                                        "View job"
+                                    }
+                                }
+                                span {
+                                    class: "rounded-full bg-yellow-100 dark:bg-yellow-900/30 px-2 py-0.5 text-xs font-medium text-yellow-800 dark:text-yellow-200",
+                                    "{contact.days_waiting} days"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            // All contacts
+            if loading() {
+                div {
+                    class: "text-center text-gray-500 dark:text-gray-400",
+                    "Loading contacts..."
+                }
+            } else if visible.is_empty() {
+                div {
+                    class: "text-center text-gray-500 dark:text-gray-400",
+                    if query.is_empty() {
+                        "No contacts yet. Contacts are created from the emails linked to your jobs."
+                    } else {
+                        "No contacts match your search."
+                    }
+                }
+            } else {
+                div {
+                    class: "overflow-x-auto",
+                    table {
+                        class: "min-w-full divide-y divide-gray-300 dark:divide-white/15",
+                        thead {
+                            tr {
+                                th {
+                                    class: "py-3.5 pl-4 pr-3 text-left text-sm font-semibold text-gray-900 dark:text-white sm:pl-0",
+                                    "Name"
+                                }
+                                th {
+                                    class: "px-3 py-3.5 text-left text-sm font-semibold text-gray-900 dark:text-white",
+                                    "Email"
+                                }
+                                th {
+                                    class: "px-3 py-3.5 text-left text-sm font-semibold text-gray-900 dark:text-white",
+                                    "Company"
+                                }
+                                th {
+                                    class: "px-3 py-3.5 text-left text-sm font-semibold text-gray-900 dark:text-white",
+                                    "Last contact"
+                                }
+                            }
+                        }
+                        tbody {
+                            class: "divide-y divide-gray-200 dark:divide-white/10",
+                            for contact in visible.iter() {
+                                tr {
+                                    key: "{contact.email}",
+                                    td {
+                                        class: "whitespace-nowrap py-4 pl-4 pr-3 text-sm font-medium sm:pl-0",
+                                        Link {
+                                            to: format!("/contacts/{}", contact.email),
+                                            class: "text-gray-900 dark:text-white hover:text-indigo-600 dark:hover:text-indigo-400",
+                                            {contact.name.clone().unwrap_or_else(|| contact.email.clone())}
+                                        }
+                                    }
+                                    td {
+                                        class: "whitespace-nowrap px-3 py-4 text-sm text-gray-500 dark:text-gray-400",
+                                        {contact.email.clone()}
+                                    }
+                                    td {
+                                        class: "whitespace-nowrap px-3 py-4 text-sm text-gray-500 dark:text-gray-400",
+                                        if let (Some(company_id), Some(company_name)) = (contact.company_id.clone(), contact.company_name.clone()) {
+                                            Link {
+                                                to: format!("/companies/{}", company_id),
+                                                class: "hover:text-indigo-600 dark:hover:text-indigo-400",
+                                                {company_name}
+                                            }
+                                        }
+                                    }
+                                    td {
+                                        class: "whitespace-nowrap px-3 py-4 text-sm text-gray-500 dark:text-gray-400",
+                                        {contact.last_contact.as_deref().map(|d| format_date(d, timezone)).unwrap_or_default()}
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
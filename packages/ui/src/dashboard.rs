@@ -8,8 +8,13 @@ use crate::components::dropdown_menu::{
     DropdownMenu, DropdownMenuContent, DropdownMenuItem, DropdownMenuTrigger,
 };
 use crate::components::statistics_card::StatisticsCard;
+use crate::dashboard_components::{
+    AnalyticsFilters, FunnelChart, SourceBreakdown, WeeklyVolumeChart,
+};
+use crate::hooks::use_trash_retention;
 use crate::job_form::JobForm;
-use crate::services::jobs_service::Job;
+use crate::services::analytics_service::{AnalyticsFilter, AnalyticsService, FunnelAnalytics};
+use crate::services::jobs_service::{retention_period, Job};
 use crate::state::use_jobs;
 use dioxus::prelude::*;
 use dioxus_free_icons::{
@@ -17,6 +22,7 @@ use dioxus_free_icons::{
     Icon,
 };
 use dioxus_router::Link;
+use std::collections::BTreeSet;
 use std::rc::Rc;
 
 /// Dashboard component showing job statistics and recent applications
@@ -27,19 +33,51 @@ pub fn DashboardContent() -> Element {
     let job_to_edit = use_signal(|| None::<Job>);
     let mut show_delete_dialog = use_signal(|| Some(false));
     let mut job_to_delete = use_signal(|| None::<String>);
+    let trash_retention = use_trash_retention();
+
+    // Analytics state
+    let filter = use_signal(AnalyticsFilter::default);
+    let mut analytics = use_signal(FunnelAnalytics::default);
+    let mut analytics_error = use_signal(|| None::<String>);
 
     // Fetch jobs on mount
     use_effect(move || {
         jobs_state.fetch_jobs();
     });
 
+    // Fetch analytics on mount and whenever the filters change
+    use_effect(move || {
+        let filter = filter();
+        spawn(async move {
+            match AnalyticsService::fetch_funnel(filter).await {
+                Ok(result) => {
+                    *analytics.write() = result;
+                    *analytics_error.write() = None;
+                }
+                Err(e) => *analytics_error.write() = Some(e.to_string()),
+            }
+        });
+    });
+
     let jobs = jobs_state.jobs.read().clone();
-    let total_jobs = jobs.len();
-    let applied_count = jobs.iter().filter(|j| j.status_id == Some(200)).count();
-    let interviewing_count = jobs.iter().filter(|j| j.status_id == Some(300)).count();
-    let offer_count = jobs.iter().filter(|j| j.status_id == Some(400)).count();
-    let rejected_count = jobs.iter().filter(|j| j.status_id == Some(500)).count();
     let recent_jobs: Vec<Job> = jobs.iter().take(5).cloned().collect();
+    let tags: Vec<String> = jobs
+        .iter()
+        .flat_map(|j| j.tags.iter().cloned())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let metrics = analytics();
+    let response_rate = metrics
+        .response_rate
+        .map(|rate| format!("{:.0}%", rate * 100.0))
+        .unwrap_or_else(|| "–".to_string());
+    let time_to_response = metrics
+        .median_days_to_first_response
+        .map(|days| format!("{:.1} days", days))
+        .unwrap_or_else(|| "–".to_string());
+    let retention = retention_period(trash_retention());
 
     rsx! {
         div {
@@ -91,6 +129,18 @@ pub fn DashboardContent() -> Element {
 
             // Dashboard content
             if !*jobs_state.loading.read() && jobs_state.error.read().is_none() {
+                AnalyticsFilters {
+                    filter,
+                    tags,
+                }
+
+                if let Some(error) = analytics_error() {
+                    p {
+                        class: "mb-6 text-sm text-red-600 dark:text-red-400",
+                        "Error loading analytics: {error}"
+                    }
+                }
+
                 // Statistics cards
                 div {
                     class: "grid grid-cols-1 gap-5 sm:grid-cols-2 lg:grid-cols-5 mb-6",
@@ -103,7 +153,7 @@ pub fn DashboardContent() -> Element {
                                 icon: BsBarChart,
                             }
                         },
-                        value: total_jobs,
+                        value: metrics.total_jobs,
                         label: "Total Applications".to_string(),
                     }
                     StatisticsCard {
@@ -115,7 +165,7 @@ pub fn DashboardContent() -> Element {
                                 icon: BsFileText,
                             }
                         },
-                        value: applied_count,
+                        value: metrics.applied,
                         label: "Applied".to_string(),
                         value_color: "text-brand-600 dark:text-brand-400".to_string(),
                     }
@@ -128,7 +178,7 @@ pub fn DashboardContent() -> Element {
                                 icon: BsBriefcase,
                             }
                         },
-                        value: interviewing_count,
+                        value: metrics.reached("interviewing"),
                         label: "Interviewing".to_string(),
                         value_color: "text-yellow-600 dark:text-yellow-400".to_string(),
                     }
@@ -141,7 +191,7 @@ pub fn DashboardContent() -> Element {
                                 icon: BsTrophy,
                             }
                         },
-                        value: offer_count,
+                        value: metrics.reached("offer"),
                         label: "Offers".to_string(),
                         value_color: "text-green-600 dark:text-green-400".to_string(),
                     }
@@ -154,12 +204,60 @@ pub fn DashboardContent() -> Element {
                                 icon: BsXCircle,
                             }
                         },
-                        value: rejected_count,
+                        value: metrics.rejected,
                         label: "Rejected".to_string(),
                         value_color: "text-red-600 dark:text-red-400".to_string(),
                     }
                 }
 
+                // Charts
+                div {
+                    class: "grid grid-cols-1 gap-5 lg:grid-cols-2 mb-6",
+                    FunnelChart {
+                        stages: metrics.stages.clone(),
+                        rejected: metrics.rejected,
+                    }
+                    div {
+                        class: "flex flex-col gap-5",
+                        WeeklyVolumeChart {
+                            weeks: metrics.weekly_volume.clone(),
+                        }
+                        div {
+                            class: "bg-white dark:bg-gray-800 shadow rounded-lg p-5 grid grid-cols-2 gap-4",
+                            div {
+                                p {
+                                    class: "text-sm font-medium text-gray-500 dark:text-gray-400",
+                                    "Response rate"
+                                }
+                                p {
+                                    class: "mt-1 text-2xl font-semibold text-gray-900 dark:text-white",
+                                    "{response_rate}"
+                                }
+                                p {
+                                    class: "text-xs text-gray-500 dark:text-gray-400",
+                                    "{metrics.responded} of {metrics.applied} applications"
+                                }
+                            }
+                            div {
+                                p {
+                                    class: "text-sm font-medium text-gray-500 dark:text-gray-400",
+                                    "Median time to first response"
+                                }
+                                p {
+                                    class: "mt-1 text-2xl font-semibold text-gray-900 dark:text-white",
+                                    "{time_to_response}"
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // Source breakdown
+                SourceBreakdown {
+                    sources: metrics.by_source.clone(),
+                    stages: metrics.stages.clone(),
+                }
+
                 // Recent applications
                 div {
                     class: "mt-6 flow-root",
@@ -315,7 +413,7 @@ pub fn DashboardContent() -> Element {
                         "Delete Job"
                     }
                     AlertDialogDescription {
-                        "Are you sure you want to delete this job? This action cannot be undone."
+                        "This job will be moved to the trash. You can restore it within {retention}."
                     }
                     AlertDialogActions {
                         AlertDialogCancel {
@@ -0,0 +1,63 @@
+use crate::services::analytics_service::AnalyticsFilter;
+use dioxus::prelude::*;
+
+/// Date range and tag filters for the dashboard analytics
+#[component]
+pub fn AnalyticsFilters(mut filter: Signal<AnalyticsFilter>, tags: Vec<String>) -> Element {
+    let current = filter();
+    let input_class = "rounded-md bg-white px-3 py-1.5 text-sm text-gray-900 outline outline-1 -outline-offset-1 outline-gray-300 dark:bg-white/5 dark:text-white dark:outline-white/10";
+    let non_empty = |value: String| Some(value).filter(|v| !v.is_empty());
+
+    rsx! {
+        div {
+            class: "flex flex-wrap items-end gap-4 mb-6",
+            label {
+                class: "flex flex-col gap-y-1 text-sm font-medium text-gray-700 dark:text-gray-300",
+                "From"
+                input {
+                    r#type: "date",
+                    class: input_class,
+                    value: current.from.clone().unwrap_or_default(),
+                    onchange: move |e: Event<FormData>| filter.write().from = non_empty(e.value()),
+                }
+            }
+            label {
+                class: "flex flex-col gap-y-1 text-sm font-medium text-gray-700 dark:text-gray-300",
+                "To"
+                input {
+                    r#type: "date",
+                    class: input_class,
+                    value: current.to.clone().unwrap_or_default(),
+                    onchange: move |e: Event<FormData>| filter.write().to = non_empty(e.value()),
+                }
+            }
+            label {
+                class: "flex flex-col gap-y-1 text-sm font-medium text-gray-700 dark:text-gray-300",
+                "Tag"
+                select {
+                    class: "{input_class} pr-8",
+                    onchange: move |e: Event<FormData>| filter.write().tag = non_empty(e.value()),
+                    option {
+                        value: "",
+                        selected: current.tag.is_none(),
+                        "All tags"
+                    }
+                    for tag in tags.iter() {
+                        option {
+                            value: "{tag}",
+                            selected: current.tag.as_deref() == Some(tag.as_str()),
+                            {tag.clone()}
+                        }
+                    }
+                }
+            }
+            if current != AnalyticsFilter::default() {
+                button {
+                    class: "pb-1.5 text-sm font-semibold text-brand-600 hover:text-brand-500 dark:text-brand-400",
+                    onclick: move |_| filter.set(AnalyticsFilter::default()),
+                    "Clear filters"
+                }
+            }
+        }
+    }
+}
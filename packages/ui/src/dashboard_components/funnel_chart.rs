@@ -0,0 +1,68 @@
+use crate::services::analytics_service::StageMetrics;
+use dioxus::prelude::*;
+
+fn format_days(days: f64) -> String {
+    if days < 1.0 {
+        format!("{:.0}h", days * 24.0)
+    } else {
+        format!("{:.1}d", days)
+    }
+}
+
+/// Horizontal bar chart of jobs reaching each pipeline stage, with the
+/// conversion from the previous stage and the median time spent in the stage
+#[component]
+pub fn FunnelChart(stages: Vec<StageMetrics>, rejected: usize) -> Element {
+    let max = stages.iter().map(|s| s.reached).max().unwrap_or(0).max(1);
+
+    rsx! {
+        div {
+            class: "bg-white dark:bg-gray-800 shadow rounded-lg p-5",
+            h3 {
+                class: "text-base font-semibold text-gray-900 dark:text-white mb-4",
+                "Application funnel"
+            }
+            div {
+                class: "space-y-3",
+                for stage in stages.iter() {
+                    div {
+                        key: "{stage.status_id}",
+                        div {
+                            class: "flex justify-between text-sm text-gray-700 dark:text-gray-300 mb-1",
+                            span {
+                                class: "font-medium",
+                                {stage.display_name.clone()}
+                            }
+                            span {
+                                "{stage.reached}"
+                                if let Some(rate) = stage.conversion_rate {
+                                    span {
+                                        class: "ml-2 text-gray-500 dark:text-gray-400",
+                                        {format!("{:.0}% converted", rate * 100.0)}
+                                    }
+                                }
+                                if let Some(days) = stage.median_days_in_stage {
+                                    span {
+                                        class: "ml-2 text-gray-500 dark:text-gray-400",
+                                        {format!("· median {}", format_days(days))}
+                                    }
+                                }
+                            }
+                        }
+                        div {
+                            class: "h-3 w-full rounded-full bg-gray-100 dark:bg-white/10",
+                            div {
+                                class: "h-3 rounded-full bg-brand-500",
+                                style: format!("width: {:.1}%", stage.reached as f64 / max as f64 * 100.0),
+                            }
+                        }
+                    }
+                }
+            }
+            p {
+                class: "mt-4 text-sm text-gray-500 dark:text-gray-400",
+                "{rejected} rejected"
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+//! Dashboard analytics components
+
+mod analytics_filters;
+mod funnel_chart;
+mod source_breakdown;
+mod weekly_volume_chart;
+
+pub use analytics_filters::AnalyticsFilters;
+pub use funnel_chart::FunnelChart;
+pub use source_breakdown::SourceBreakdown;
+pub use weekly_volume_chart::WeeklyVolumeChart;
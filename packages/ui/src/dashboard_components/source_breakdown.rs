@@ -0,0 +1,102 @@
+use crate::services::analytics_service::{SourceMetrics, StageMetrics};
+use dioxus::prelude::*;
+
+/// Status ID of the "applied" stage; later stages are shown as columns
+const APPLIED_STATUS_ID: i32 = 200;
+
+/// Table comparing job sources by volume, response rate and how far their
+/// applications got in the pipeline
+#[component]
+pub fn SourceBreakdown(sources: Vec<SourceMetrics>, stages: Vec<StageMetrics>) -> Element {
+    // Stages after applied, with their index into `stages_reached`
+    let later_stages: Vec<(usize, String)> = stages
+        .iter()
+        .enumerate()
+        .filter(|(_, stage)| stage.status_id > APPLIED_STATUS_ID)
+        .map(|(index, stage)| (index, stage.display_name.clone()))
+        .collect();
+
+    rsx! {
+        div {
+            class: "bg-white dark:bg-gray-800 shadow rounded-lg p-5 mb-6",
+            h3 {
+                class: "text-base font-semibold text-gray-900 dark:text-white mb-4",
+                "By source"
+            }
+            if sources.is_empty() {
+                p {
+                    class: "text-sm text-gray-500 dark:text-gray-400",
+                    "No jobs in this period."
+                }
+            } else {
+                div {
+                    class: "overflow-x-auto",
+                    table {
+                        class: "min-w-full divide-y divide-gray-200 dark:divide-white/10 text-sm",
+                        thead {
+                            tr {
+                                th {
+                                    class: "py-2 pr-4 text-left font-semibold text-gray-900 dark:text-white",
+                                    "Source"
+                                }
+                                th {
+                                    class: "px-4 py-2 text-right font-semibold text-gray-900 dark:text-white",
+                                    "Jobs"
+                                }
+                                th {
+                                    class: "px-4 py-2 text-right font-semibold text-gray-900 dark:text-white",
+                                    "Applied"
+                                }
+                                th {
+                                    class: "px-4 py-2 text-right font-semibold text-gray-900 dark:text-white",
+                                    "Response rate"
+                                }
+                                for (index, name) in later_stages.iter() {
+                                    th {
+                                        key: "{index}",
+                                        class: "px-4 py-2 text-right font-semibold text-gray-900 dark:text-white",
+                                        {name.clone()}
+                                    }
+                                }
+                            }
+                        }
+                        tbody {
+                            class: "divide-y divide-gray-100 dark:divide-white/5",
+                            for source in sources.iter() {
+                                tr {
+                                    key: "{source.source}",
+                                    td {
+                                        class: "py-2 pr-4 font-medium text-gray-900 dark:text-white",
+                                        {source.source.clone()}
+                                    }
+                                    td {
+                                        class: "px-4 py-2 text-right text-gray-700 dark:text-gray-300",
+                                        "{source.jobs}"
+                                    }
+                                    td {
+                                        class: "px-4 py-2 text-right text-gray-700 dark:text-gray-300",
+                                        "{source.applied}"
+                                    }
+                                    td {
+                                        class: "px-4 py-2 text-right text-gray-700 dark:text-gray-300",
+                                        {source
+                                            .response_rate
+                                            .map(|rate| format!("{:.0}%", rate * 100.0))
+                                            .unwrap_or_else(|| "—".to_string())}
+                                    }
+                                    for (index, _) in later_stages.iter() {
+                                        td {
+                                            key: "{index}",
+                                            class: "px-4 py-2 text-right text-gray-700 dark:text-gray-300",
+                                            {source.stages_reached.get(*index).copied().unwrap_or(0).to_string()}
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use crate::services::analytics_service::WeeklyVolume;
+use dioxus::prelude::*;
+
+/// Column chart of applications per week
+#[component]
+pub fn WeeklyVolumeChart(weeks: Vec<WeeklyVolume>) -> Element {
+    let max = weeks
+        .iter()
+        .map(|w| w.applications)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    rsx! {
+        div {
+            class: "bg-white dark:bg-gray-800 shadow rounded-lg p-5",
+            h3 {
+                class: "text-base font-semibold text-gray-900 dark:text-white mb-4",
+                "Applications per week"
+            }
+            if weeks.is_empty() {
+                p {
+                    class: "text-sm text-gray-500 dark:text-gray-400",
+                    "No applications in this period."
+                }
+            } else {
+                div {
+                    class: "flex h-40 items-end gap-1",
+                    for week in weeks.iter() {
+                        div {
+                            key: "{week.week_start}",
+                            class: "flex h-full flex-1 flex-col justify-end",
+                            title: "Week of {week.week_start}: {week.applications}",
+                            div {
+                                class: "w-full rounded-t bg-brand-500",
+                                style: format!("height: {:.1}%", week.applications as f64 / max as f64 * 100.0),
+                            }
+                        }
+                    }
+                }
+                div {
+                    class: "mt-2 flex justify-between text-xs text-gray-500 dark:text-gray-400",
+                    span {
+                        {weeks.first().map(|w| w.week_start.clone()).unwrap_or_default()}
+                    }
+                    span {
+                        {weeks.last().map(|w| w.week_start.clone()).unwrap_or_default()}
+                    }
+                }
+            }
+        }
+    }
+}
@@ -6,7 +6,7 @@ use crate::components::button::{Button, ButtonVariant};
 use crate::job_form::JobForm;
 #[allow(unused_imports)]
 use crate::job_select_dialog::JobSelectDialog;
-use crate::services::ai_service::AiService;
+use crate::services::ai_service::{AiResult, AiService};
 use crate::state::{use_auth, use_emails};
 use crate::utils::format_date_full;
 use dioxus::prelude::*;
@@ -15,6 +15,23 @@ use dioxus_free_icons::icons::bs_icons::BsX;
 #[allow(unused_imports)]
 use dioxus_free_icons::Icon;
 
+/// Version, time and models of an AI result
+fn describe_result(result: &AiResult) -> String {
+    let mut parts = vec![
+        format!("Version {}", result.version),
+        result.created_at.clone(),
+    ];
+    if !result.models.is_empty() {
+        let models: Vec<String> = result
+            .models
+            .iter()
+            .map(|(stage, model)| format!("{}: {}", stage, model))
+            .collect();
+        parts.push(models.join(", "));
+    }
+    parts.join(" · ")
+}
+
 /// Email slideout component
 #[component]
 pub fn EmailSlideout() -> Element {
@@ -239,6 +256,21 @@ pub fn EmailSlideout() -> Element {
                             "AI Analysis"
                         }
                         if let Some(ref ai) = ai_result() {
+                            if ai.status == "failed" {
+                                div {
+                                    class: "rounded-md bg-red-50 dark:bg-red-900/20 p-3 text-sm text-red-800 dark:text-red-200",
+                                    p {
+                                        class: "font-medium",
+                                        {format!("AI processing failed at the {} stage", ai.failed_stage.as_deref().unwrap_or("unknown"))}
+                                    }
+                                    if let Some(ref error) = ai.error {
+                                        p {
+                                            class: "mt-1 text-xs break-words",
+                                            {error.clone()}
+                                        }
+                                    }
+                                }
+                            }
                             if let Some(ref summary) = ai.summary {
                                 div {
                                     class: "space-y-1",
@@ -293,6 +325,38 @@ pub fn EmailSlideout() -> Element {
                                     }
                                 }
                             }
+                            p {
+                                class: "text-xs text-gray-500 dark:text-gray-400",
+                                {describe_result(ai)}
+                            }
+                            if !ai.history.is_empty() {
+                                details {
+                                    summary {
+                                        class: "cursor-pointer text-xs font-medium text-gray-500 dark:text-gray-400 uppercase",
+                                        {format!("Earlier results ({})", ai.history.len())}
+                                    }
+                                    ul {
+                                        class: "mt-2 space-y-2",
+                                        for earlier in ai.history.iter() {
+                                            li {
+                                                key: "{earlier.id}",
+                                                class: "rounded bg-gray-50 dark:bg-gray-800 p-2 text-xs text-gray-700 dark:text-gray-300",
+                                                p {
+                                                    class: "font-medium",
+                                                    {earlier.category.clone().unwrap_or_else(|| earlier.status.clone())}
+                                                }
+                                                if let Some(ref summary) = earlier.summary {
+                                                    p { {summary.clone()} }
+                                                }
+                                                p {
+                                                    class: "text-gray-500 dark:text-gray-400",
+                                                    {describe_result(earlier)}
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
                         } else if *loading_ai.read() {
                             p {
                                 class: "text-sm text-gray-500 dark:text-gray-400",
@@ -311,27 +375,15 @@ pub fn EmailSlideout() -> Element {
                                 disabled: processing(),
                                 onclick: {
                                     let email_id = email.gmail_id.clone();
-                                    let user_id = auth.user.read().as_ref().map(|u| u.id.clone()).unwrap_or_default();
                                     let mut processing_signal = processing;
                                     let mut ai_result_signal = ai_result;
-                                    let mut loading_signal = loading_ai;
                                     move |_| {
                                         let email_id = email_id.clone();
-                                        let user_id = user_id.clone();
                                         spawn(async move {
                                             *processing_signal.write() = true;
-                                            if AiService::process_email(&email_id, &user_id).await.is_ok() {
-                                                // Reload AI results
-                                                *loading_signal.write() = true;
-                                                match AiService::get_ai_results(&email_id).await {
-                                                    Ok(result) => {
-                                                        *ai_result_signal.write() = Some(result);
-                                                    }
-                                                    Err(_) => {
-                                                        *ai_result_signal.write() = None;
-                                                    }
-                                                }
-                                                *loading_signal.write() = false;
+                                            // Failed runs come back as a result too
+                                            if let Ok(result) = AiService::reprocess_email(&email_id).await {
+                                                *ai_result_signal.write() = Some(result);
                                             }
                                             *processing_signal.write() = false;
                                         });
@@ -339,6 +391,8 @@ pub fn EmailSlideout() -> Element {
                                 },
                                 if processing() {
                                     "Processing..."
+                                } else if ai_result().is_some() {
+                                    "Reprocess with AI"
                                 } else {
                                     "Process with AI"
                                 }
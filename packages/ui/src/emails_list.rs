@@ -16,6 +16,7 @@ pub fn EmailsList() -> Element {
     let emails_state = use_emails();
     let scanning = use_signal(|| false);
     let scan_error = use_signal(|| None::<String>);
+    let scan_notice = use_signal(|| None::<String>);
     let ai_results = use_signal(HashMap::<String, crate::services::ai_service::AiResult>::new);
 
     // Compute timezone once for all emails
@@ -30,22 +31,30 @@ pub fn EmailsList() -> Element {
     let handle_scan = {
         let scanning_signal = scanning;
         let error_signal = scan_error;
+        let notice_signal = scan_notice;
         let emails = emails_state;
 
         move |_| {
             let mut scanning_clone = scanning_signal;
             let mut error_clone = error_signal;
+            let mut notice_clone = notice_signal;
             let emails_clone = emails;
 
             spawn(async move {
                 *scanning_clone.write() = true;
                 *error_clone.write() = None;
+                *notice_clone.write() = None;
 
                 match GmailScannerService::scan_emails(None, None).await {
-                    Ok(_) => {
+                    Ok(response) => {
                         // Refresh emails list after successful scan
                         emails_clone.fetch_emails(Some(100), Some(0));
                         *error_clone.write() = None;
+                        if response.message_limit_reached {
+                            *notice_clone.write() = Some(
+                                "Your daily limit of fetched messages was reached, so some emails weren't scanned. Scan again after it resets to pick up the rest.".to_string(),
+                            );
+                        }
                     }
                     Err(e) => {
                         *error_clone.write() = Some(format!("Failed to scan Gmail: {}", e));
@@ -99,6 +108,16 @@ pub fn EmailsList() -> Element {
                 }
             }
 
+            if let Some(notice) = scan_notice.read().as_ref() {
+                div {
+                    class: "mt-4 rounded-md bg-yellow-50 dark:bg-yellow-900/20 p-4",
+                    p {
+                        class: "text-sm text-yellow-800 dark:text-yellow-200",
+                        {notice.clone()}
+                    }
+                }
+            }
+
             // Loading state
             if *emails_state.loading.read() {
                 div {
@@ -1,5 +1,7 @@
 //! Hooks for reusable component logic
 
 pub mod use_system_email_detection;
+pub mod use_trash_retention;
 
 pub use use_system_email_detection::use_system_email_detection;
+pub use use_trash_retention::use_trash_retention;
@@ -0,0 +1,20 @@
+//! Hook for the trash retention window
+
+use crate::services::jobs_service::JobsService;
+use dioxus::prelude::*;
+
+/// Days trashed jobs are kept before they're purged, `None` until the API
+/// has answered or if it couldn't be reached
+pub fn use_trash_retention() -> Signal<Option<u32>> {
+    let mut retention_days = use_signal(|| None::<u32>);
+
+    use_effect(move || {
+        spawn(async move {
+            if let Ok(days) = JobsService::fetch_trash_retention_days().await {
+                *retention_days.write() = Some(days);
+            }
+        });
+    });
+
+    retention_days
+}
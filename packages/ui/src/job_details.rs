@@ -4,7 +4,11 @@ use crate::comment_form::CommentForm;
 use crate::components::status_change_dialog::StatusChangeDialog;
 use crate::email_contact_card::EmailContactCard;
 use crate::email_contact_slideout::EmailContactSlideout;
-use crate::job_details_components::{DetailsTab, EmailsTab, JobDetailsHeader};
+use crate::hooks::use_trash_retention;
+use crate::job_details_components::{
+    DetailsTab, DocumentsTab, DuplicatesCard, EmailsTab, JobDetailsHeader, SourceCard,
+};
+use crate::services::jobs_service::{retention_period, JobsService};
 use crate::state::{use_comments_provider, use_email_contacts_provider, use_emails, use_jobs};
 use crate::timeline::Timeline;
 use dioxus::prelude::*;
@@ -13,6 +17,7 @@ use dioxus::prelude::*;
 enum DetailsTabType {
     Details,
     Emails,
+    Documents,
 }
 
 /// Job details component
@@ -22,6 +27,7 @@ pub fn JobDetails(job_id: String) -> Element {
     let email_contacts_state = use_email_contacts_provider();
     let comments_state = use_comments_provider();
     let emails_state = use_emails();
+    let trash_retention = use_trash_retention();
 
     // Editing state for each field
     let editing_title = use_signal(|| false);
@@ -87,6 +93,13 @@ pub fn JobDetails(job_id: String) -> Element {
         let emails = emails_state.emails.read().clone();
         let statuses = jobs_state.job_statuses.read().clone();
         let current_status_id = job.status_id;
+        // Remount the source card when the saved values change
+        let source_key = format!(
+            "{}-{}-{}",
+            job_id,
+            job.source_id.as_deref().unwrap_or(""),
+            job.referrer_email.as_deref().unwrap_or("")
+        );
 
         // Find current and new status for dialog
         let current_status = statuses
@@ -95,6 +108,7 @@ pub fn JobDetails(job_id: String) -> Element {
             .cloned();
         let new_status =
             selected_status_id().and_then(|id| statuses.iter().find(|s| s.id == id).cloned());
+        let retention = retention_period(trash_retention());
 
         rsx! {
             div {
@@ -103,6 +117,7 @@ pub fn JobDetails(job_id: String) -> Element {
                     job_id: job_id.clone(),
                     title: job.title.clone(),
                     company: job.company.clone(),
+                    company_id: job.company_id.clone(),
                     location: job.location.clone(),
                     status_id: current_status_id,
                     statuses: statuses.clone(),
@@ -120,6 +135,41 @@ pub fn JobDetails(job_id: String) -> Element {
                     edit_location_value,
                 }
 
+                // Archived or trashed job notice
+                if job.deleted_at.is_some() || job.archived_at.is_some() {
+                    div {
+                        class: "mb-6 flex items-center justify-between gap-x-4 rounded-md bg-yellow-50 dark:bg-yellow-900/20 px-4 py-3",
+                        p {
+                            class: "text-sm text-yellow-800 dark:text-yellow-200",
+                            if job.deleted_at.is_some() {
+                                "This job is in the trash and will be deleted permanently after {retention}."
+                            } else {
+                                "This job is archived."
+                            }
+                        }
+                        button {
+                            class: "text-sm font-semibold text-yellow-800 hover:text-yellow-900 dark:text-yellow-200 dark:hover:text-white",
+                            onclick: {
+                                let job_id = job_id.clone();
+                                move |_| {
+                                    let job_id = job_id.clone();
+                                    spawn(async move {
+                                        if JobsService::restore_job(job_id.clone()).await.is_ok() {
+                                            jobs_state.fetch_job_details(
+                                                job_id,
+                                                email_contacts_state,
+                                                emails_state,
+                                                comments_state,
+                                            );
+                                        }
+                                    });
+                                }
+                            },
+                            "Restore"
+                        }
+                    }
+                }
+
                 // Two-column layout (stacked on mobile)
                 div {
                     class: "grid grid-cols-1 lg:grid-cols-3 gap-6",
@@ -144,6 +194,22 @@ pub fn JobDetails(job_id: String) -> Element {
                     // Right column: Job details + Emails
                     div {
                         class: "space-y-6",
+                        // Possible duplicates of this job
+                        DuplicatesCard {
+                            key: "{job_id}",
+                            job_id: job_id.clone(),
+                        }
+
+                        // Where the application came from
+                        SourceCard {
+                            key: "{source_key}",
+                            job_id: job_id.clone(),
+                            source_id: job.source_id.clone(),
+                            source_name: job.source_name.clone(),
+                            referrer_email: job.referrer_email.clone(),
+                            referrer_name: job.referrer_name.clone(),
+                        }
+
                         // Details/Emails card with tabs
                         div {
                             class: "rounded-lg bg-white dark:bg-gray-800 p-5 ring-1 ring-inset ring-gray-200 dark:ring-white/15",
@@ -176,6 +242,18 @@ pub fn JobDetails(job_id: String) -> Element {
                                         },
                                         "Emails"
                                     }
+                                    button {
+                                        class: if *active_tab.read() == DetailsTabType::Documents {
+                                            "border-indigo-500 text-indigo-600 dark:text-indigo-400 whitespace-nowrap border-b-2 py-4 px-1 text-sm font-medium"
+                                        } else {
+                                            "border-transparent text-gray-500 dark:text-gray-400 hover:border-gray-300 dark:hover:border-gray-600 hover:text-gray-700 dark:hover:text-gray-300 whitespace-nowrap border-b-2 py-4 px-1 text-sm font-medium"
+                                        },
+                                        onclick: move |_| {
+                                            let mut tab = active_tab;
+                                            *tab.write() = DetailsTabType::Documents;
+                                        },
+                                        "Documents"
+                                    }
                                 }
                             }
 
@@ -189,11 +267,16 @@ pub fn JobDetails(job_id: String) -> Element {
                                     editing_description,
                                     edit_description_value,
                                 }
-                            } else {
+                            } else if *active_tab.read() == DetailsTabType::Emails {
                                 EmailsTab {
                                     job_id: job_id.clone(),
                                     emails,
                                 }
+                            } else {
+                                DocumentsTab {
+                                    key: "{job_id}",
+                                    job_id: job_id.clone(),
+                                }
                             }
                         }
 
@@ -0,0 +1,229 @@
+//! Job attachments component
+
+use crate::services::attachments_service::{Attachment, AttachmentsService};
+use crate::state::use_auth;
+use crate::utils::format_date;
+use dioxus::prelude::*;
+use dioxus_free_icons::icons::bs_icons::{BsFileEarmarkText, BsTrash};
+use dioxus_free_icons::Icon;
+
+/// Maximum upload size accepted by the API (10 MB)
+const MAX_UPLOAD_SIZE: u64 = 10 * 1024 * 1024;
+
+/// File types accepted by the API
+const ACCEPTED_FILE_TYPES: &str = ".pdf,.doc,.docx,.odt,.rtf,.txt,.md,.png,.jpg,.jpeg";
+
+#[component]
+pub fn AttachmentsField(job_id: String) -> Element {
+    let auth = use_auth();
+    let attachments = use_signal(Vec::<Attachment>::new);
+    let loading = use_signal(|| true);
+    let uploading = use_signal(|| false);
+    let error = use_signal(|| None::<String>);
+
+    // Fetch attachments on mount
+    use_effect({
+        let job_id = job_id.clone();
+        let mut attachments = attachments;
+        let mut loading = loading;
+        let mut error = error;
+        move || {
+            let job_id = job_id.clone();
+            spawn(async move {
+                *loading.write() = true;
+                match AttachmentsService::fetch_attachments(job_id).await {
+                    Ok(list) => *attachments.write() = list,
+                    Err(e) => *error.write() = Some(format!("Failed to load attachments: {}", e)),
+                }
+                *loading.write() = false;
+            });
+        }
+    });
+
+    let user = auth.user.read();
+    let timezone = user.as_ref().and_then(|u| u.timezone.clone());
+    let job_id_upload = job_id.clone();
+
+    rsx! {
+        div {
+            class: "mt-4 pt-4 border-t border-gray-200 dark:border-gray-700",
+            div {
+                class: "flex items-center justify-between mb-2",
+                label {
+                    class: "text-sm font-medium text-gray-500 dark:text-gray-400",
+                    "Attachments"
+                }
+                label {
+                    class: "cursor-pointer text-sm font-medium text-indigo-600 dark:text-indigo-400 hover:text-indigo-500",
+                    if uploading() {
+                        "Uploading..."
+                    } else {
+                        "Upload"
+                    }
+                    input {
+                        r#type: "file",
+                        class: "sr-only",
+                        accept: ACCEPTED_FILE_TYPES,
+                        disabled: uploading(),
+                        onchange: move |e: Event<FormData>| {
+                            let Some(file) = e.files().into_iter().next() else {
+                                return;
+                            };
+                            let job_id = job_id_upload.clone();
+                            let mut attachments = attachments;
+                            let mut uploading = uploading;
+                            let mut error = error;
+                            spawn(async move {
+                                let file_name = file.name();
+                                if file.size() > MAX_UPLOAD_SIZE {
+                                    *error.write() = Some(format!("{} is larger than 10 MB", file_name));
+                                    return;
+                                }
+                                let content_type = file
+                                    .content_type()
+                                    .filter(|ct| !ct.is_empty())
+                                    .unwrap_or_else(|| guess_content_type(&file_name).to_string());
+
+                                *uploading.write() = true;
+                                *error.write() = None;
+                                match file.read_bytes().await {
+                                    Ok(bytes) => {
+                                        match AttachmentsService::upload_attachment(
+                                            job_id,
+                                            file_name,
+                                            content_type,
+                                            bytes.to_vec(),
+                                        )
+                                        .await
+                                        {
+                                            Ok(attachment) => attachments.write().insert(0, attachment),
+                                            Err(e) => *error.write() = Some(format!("Upload failed: {}", e)),
+                                        }
+                                    }
+                                    Err(e) => {
+                                        *error.write() = Some(format!("Failed to read file: {}", e));
+                                    }
+                                }
+                                *uploading.write() = false;
+                            });
+                        },
+                    }
+                }
+            }
+
+            if let Some(ref err) = error() {
+                p {
+                    class: "text-sm text-red-600 dark:text-red-400 mb-2",
+                    {err.clone()}
+                }
+            }
+
+            if loading() {
+                p {
+                    class: "text-sm text-gray-400 dark:text-gray-500",
+                    "Loading attachments..."
+                }
+            } else if attachments().is_empty() {
+                p {
+                    class: "text-sm text-gray-400 dark:text-gray-500 italic",
+                    "No attachments"
+                }
+            } else {
+                ul {
+                    role: "list",
+                    class: "divide-y divide-gray-100 dark:divide-gray-700",
+                    for attachment in attachments().into_iter() {
+                        li {
+                            key: "{attachment.id}",
+                            class: "flex items-center justify-between gap-x-3 py-2",
+                            div {
+                                class: "flex min-w-0 items-center gap-x-2",
+                                Icon {
+                                    class: "h-4 w-4 shrink-0 text-gray-400",
+                                    width: 16,
+                                    height: 16,
+                                    fill: "currentColor",
+                                    icon: BsFileEarmarkText,
+                                }
+                                div {
+                                    class: "min-w-0",
+                                    a {
+                                        href: AttachmentsService::download_url(&attachment.job_id, &attachment.id),
+                                        target: "_blank",
+                                        rel: "noopener noreferrer",
+                                        class: "block truncate text-sm font-medium text-gray-900 dark:text-white hover:text-indigo-600 dark:hover:text-indigo-400",
+                                        {attachment.file_name.clone()}
+                                    }
+                                    p {
+                                        class: "text-xs text-gray-500 dark:text-gray-400",
+                                        {format!(
+                                            "{} · {}",
+                                            format_file_size(attachment.size_bytes),
+                                            format_date(&attachment.created_at, timezone.as_deref())
+                                        )}
+                                    }
+                                }
+                            }
+                            button {
+                                class: "text-gray-400 hover:text-red-600 dark:hover:text-red-400",
+                                title: "Delete attachment",
+                                onclick: {
+                                    let job_id = attachment.job_id.clone();
+                                    let attachment_id = attachment.id.clone();
+                                    let mut attachments = attachments;
+                                    let mut error = error;
+                                    move |_| {
+                                        let job_id = job_id.clone();
+                                        let attachment_id = attachment_id.clone();
+                                        spawn(async move {
+                                            match AttachmentsService::delete_attachment(job_id, attachment_id.clone()).await {
+                                                Ok(()) => attachments.write().retain(|a| a.id != attachment_id),
+                                                Err(e) => *error.write() = Some(format!("Delete failed: {}", e)),
+                                            }
+                                        });
+                                    }
+                                },
+                                Icon {
+                                    class: "h-4 w-4",
+                                    width: 16,
+                                    height: 16,
+                                    fill: "currentColor",
+                                    icon: BsTrash,
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Human readable file size
+fn format_file_size(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else if bytes >= 1024 {
+        format!("{:.0} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+/// Fallback MIME type from the file extension when the browser doesn't report one
+fn guess_content_type(file_name: &str) -> &'static str {
+    let extension = file_name.rsplit('.').next().unwrap_or("").to_lowercase();
+
+    match extension.as_str() {
+        "pdf" => "application/pdf",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "odt" => "application/vnd.oasis.opendocument.text",
+        "rtf" => "application/rtf",
+        "txt" => "text/plain",
+        "md" => "text/markdown",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        _ => "application/octet-stream",
+    }
+}
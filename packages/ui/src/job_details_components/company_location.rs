@@ -6,11 +6,13 @@ use crate::state::use_jobs;
 use dioxus::prelude::*;
 use dioxus_free_icons::icons::bs_icons::{BsBuilding, BsGeoAlt, BsPencilSquare};
 use dioxus_free_icons::Icon;
+use dioxus_router::Link;
 
 #[component]
 pub fn CompanyField(
     job_id: String,
     company: String,
+    company_id: Option<String>,
     editing: Signal<bool>,
     edit_value: Signal<String>,
 ) -> Element {
@@ -65,8 +67,16 @@ pub fn CompanyField(
                     fill: "currentColor",
                     icon: BsBuilding,
                 }
-                span {
-                    {company.clone()}
+                if let Some(company_id) = company_id {
+                    Link {
+                        to: format!("/companies/{}", company_id),
+                        class: "hover:text-indigo-600 dark:hover:text-indigo-400",
+                        {company.clone()}
+                    }
+                } else {
+                    span {
+                        {company.clone()}
+                    }
                 }
                 button {
                     class: "ml-1 text-gray-400 hover:text-gray-600 dark:hover:text-gray-300",
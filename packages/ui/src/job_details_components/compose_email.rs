@@ -0,0 +1,244 @@
+//! Compose email component
+
+use crate::components::button::{Button, ButtonVariant};
+use crate::services::ai_service::AiService;
+use crate::services::emails_service::StoredEmail;
+use crate::services::error::ServiceError;
+use crate::services::gmail_scanner_service::{GmailScannerService, SendEmailRequest};
+use dioxus::prelude::*;
+
+const INPUT_CLASS: &str = "block w-full rounded-md bg-white px-3 py-1.5 text-sm text-gray-900 outline outline-1 -outline-offset-1 outline-gray-300 placeholder:text-gray-400 dark:bg-white/5 dark:text-white dark:outline-white/10";
+
+/// Subject of a reply to an email
+fn reply_subject(email: &StoredEmail) -> String {
+    let subject = email.subject.clone().unwrap_or_default();
+    if subject.to_lowercase().starts_with("re:") {
+        subject
+    } else {
+        format!("Re: {}", subject)
+    }
+}
+
+/// Form for writing a new email about a job, or a reply to one of its emails,
+/// and sending it through the user's Gmail account. The subject and body can
+/// be drafted with AI, edited, then sent or copied.
+#[component]
+pub fn ComposeEmail(
+    job_id: String,
+    reply_to: Option<StoredEmail>,
+    on_close: EventHandler,
+    on_sent: EventHandler<StoredEmail>,
+) -> Element {
+    let reply_to_for_init = reply_to.clone();
+    let mut to = use_signal(move || {
+        reply_to_for_init
+            .as_ref()
+            .and_then(|e| e.from.clone())
+            .unwrap_or_default()
+    });
+    let mut cc = use_signal(String::new);
+    let reply_to_for_subject = reply_to.clone();
+    let mut subject = use_signal(move || {
+        reply_to_for_subject
+            .as_ref()
+            .map(reply_subject)
+            .unwrap_or_default()
+    });
+    let mut body = use_signal(String::new);
+    let mut sending = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+    let mut needs_permission = use_signal(|| false);
+    let mut drafting = use_signal(|| false);
+    let mut instructions = use_signal(String::new);
+    let mut copied = use_signal(|| false);
+
+    let reply_to_id = reply_to.as_ref().map(|e| e.gmail_id.clone());
+    let draft = {
+        let job_id = job_id.clone();
+        let reply_to_id = reply_to_id.clone();
+        move |kind: &'static str| {
+            let job_id = job_id.clone();
+            let reply_to_id = reply_to_id.clone();
+            spawn(async move {
+                *drafting.write() = true;
+                *error.write() = None;
+                match AiService::draft_email(&job_id, kind, reply_to_id, Some(instructions())).await
+                {
+                    Ok(draft) => {
+                        *subject.write() = draft.subject;
+                        *body.write() = draft.body;
+                        *copied.write() = false;
+                    }
+                    Err(e) => *error.write() = Some(format!("Failed to draft email: {}", e)),
+                }
+                *drafting.write() = false;
+            });
+        }
+    };
+    let draft_follow_up = draft.clone();
+    let draft_thank_you = draft;
+
+    let heading = if reply_to.is_some() {
+        "Reply"
+    } else {
+        "New email"
+    };
+
+    rsx! {
+        div {
+            class: "rounded-lg bg-gray-50 dark:bg-white/5 p-4 ring-1 ring-inset ring-gray-200 dark:ring-white/15 space-y-3",
+            div {
+                class: "flex items-center justify-between gap-x-4",
+                h3 {
+                    class: "text-sm font-semibold text-gray-900 dark:text-white",
+                    {heading}
+                }
+                div {
+                    class: "flex items-center gap-x-3",
+                    span {
+                        class: "text-xs text-gray-500 dark:text-gray-400",
+                        if drafting() {
+                            "Drafting..."
+                        } else {
+                            "Draft with AI:"
+                        }
+                    }
+                    button {
+                        class: "text-sm font-medium text-indigo-600 dark:text-indigo-400 hover:text-indigo-500 disabled:opacity-50",
+                        disabled: drafting(),
+                        onclick: move |_| draft_thank_you("thank_you"),
+                        "Thank-you"
+                    }
+                    button {
+                        class: "text-sm font-medium text-indigo-600 dark:text-indigo-400 hover:text-indigo-500 disabled:opacity-50",
+                        disabled: drafting(),
+                        onclick: move |_| draft_follow_up("follow_up"),
+                        "Follow-up"
+                    }
+                }
+            }
+            input {
+                r#type: "text",
+                placeholder: "Instructions for the AI draft (optional), e.g. mention my portfolio",
+                class: INPUT_CLASS,
+                value: "{instructions}",
+                oninput: move |e: Event<FormData>| *instructions.write() = e.value(),
+            }
+            input {
+                r#type: "text",
+                placeholder: "To",
+                class: INPUT_CLASS,
+                value: "{to}",
+                oninput: move |e: Event<FormData>| *to.write() = e.value(),
+            }
+            input {
+                r#type: "text",
+                placeholder: "Cc",
+                class: INPUT_CLASS,
+                value: "{cc}",
+                oninput: move |e: Event<FormData>| *cc.write() = e.value(),
+            }
+            input {
+                r#type: "text",
+                placeholder: "Subject",
+                class: INPUT_CLASS,
+                value: "{subject}",
+                oninput: move |e: Event<FormData>| *subject.write() = e.value(),
+            }
+            textarea {
+                rows: 8,
+                placeholder: "Write your message...",
+                class: INPUT_CLASS,
+                value: "{body}",
+                oninput: move |e: Event<FormData>| {
+                    *body.write() = e.value();
+                    *copied.write() = false;
+                },
+            }
+
+            if let Some(err) = error() {
+                p {
+                    class: "text-sm text-red-600 dark:text-red-400",
+                    {err}
+                }
+            }
+            if needs_permission() {
+                div {
+                    class: "flex items-center justify-between gap-x-4 rounded-md bg-yellow-50 dark:bg-yellow-900/20 p-3",
+                    p {
+                        class: "text-sm text-yellow-800 dark:text-yellow-200",
+                        "ApplyMonitor needs permission to send email from your Gmail account."
+                    }
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        onclick: move |_| {
+                            #[cfg(target_arch = "wasm32")]
+                            {
+                                let auth_url = GmailScannerService::get_gmail_send_auth_url();
+                                let window = web_sys::window().expect("no global `window` exists");
+                                let _ = window.location().set_href(&auth_url);
+                            }
+                        },
+                        "Allow sending"
+                    }
+                }
+            }
+
+            div {
+                class: "flex gap-x-2",
+                Button {
+                    variant: ButtonVariant::Primary,
+                    disabled: sending() || body().trim().is_empty(),
+                    onclick: {
+                        let job_id = job_id.clone();
+                        let reply_to_id = reply_to_id.clone();
+                        move |_| {
+                            let request = SendEmailRequest {
+                                to: Some(to()).filter(|t| !t.trim().is_empty()),
+                                cc: Some(cc()).filter(|c| !c.trim().is_empty()),
+                                subject: Some(subject()).filter(|s| !s.trim().is_empty()),
+                                body: body(),
+                                reply_to_gmail_id: reply_to_id.clone(),
+                                job_id: Some(job_id.clone()),
+                            };
+                            spawn(async move {
+                                *sending.write() = true;
+                                *error.write() = None;
+                                match GmailScannerService::send_email(request).await {
+                                    Ok(email) => on_sent.call(email),
+                                    Err(ServiceError::Server(403, _)) => *needs_permission.write() = true,
+                                    Err(e) => *error.write() = Some(format!("Failed to send email: {}", e)),
+                                }
+                                *sending.write() = false;
+                            });
+                        }
+                    },
+                    if sending() {
+                        "Sending..."
+                    } else {
+                        "Send"
+                    }
+                }
+                Button {
+                    variant: ButtonVariant::Secondary,
+                    disabled: body().trim().is_empty(),
+                    onclick: move |_| {
+                        let text = serde_json::to_string(&body()).unwrap_or_default();
+                        document::eval(&format!("navigator.clipboard.writeText({})", text));
+                        *copied.write() = true;
+                    },
+                    if copied() {
+                        "Copied"
+                    } else {
+                        "Copy"
+                    }
+                }
+                Button {
+                    variant: ButtonVariant::Secondary,
+                    onclick: move |_| on_close.call(()),
+                    "Cancel"
+                }
+            }
+        }
+    }
+}
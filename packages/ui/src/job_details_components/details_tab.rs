@@ -1,6 +1,6 @@
 //! Details tab component
 
-use crate::job_details_components::DescriptionField;
+use crate::job_details_components::{AttachmentsField, DescriptionField};
 use crate::state::use_auth;
 use crate::utils::format_date;
 use dioxus::prelude::*;
@@ -58,5 +58,9 @@ pub fn DetailsTab(
             editing: editing_description,
             edit_value: edit_description_value,
         }
+
+        AttachmentsField {
+            job_id: job_id.clone(),
+        }
     }
 }
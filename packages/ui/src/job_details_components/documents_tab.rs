@@ -0,0 +1,154 @@
+//! Documents tab component
+
+use crate::components::button::{Button, ButtonVariant};
+use crate::services::job_documents_service::{JobDocument, JobDocumentsService};
+use dioxus::prelude::*;
+
+/// AI-tailored cover letters and resume suggestions for a job. Each
+/// generation is saved as a new version; older versions stay selectable.
+#[component]
+pub fn DocumentsTab(job_id: String) -> Element {
+    let documents = use_signal(Vec::<JobDocument>::new);
+    let mut selected_version = use_signal(|| None::<i64>);
+    let mut generating = use_signal(|| false);
+    let error = use_signal(|| None::<String>);
+
+    let load = {
+        let job_id = job_id.clone();
+        let mut documents = documents;
+        let mut error = error;
+        move || {
+            let job_id = job_id.clone();
+            spawn(async move {
+                match JobDocumentsService::fetch_documents(job_id).await {
+                    Ok(loaded) => *documents.write() = loaded,
+                    Err(e) => *error.write() = Some(format!("Failed to load documents: {}", e)),
+                }
+            });
+        }
+    };
+
+    use_effect(move || load());
+
+    let all_documents = documents();
+    let current = selected_version()
+        .and_then(|v| all_documents.iter().find(|d| d.version == v))
+        .or(all_documents.first())
+        .cloned();
+
+    rsx! {
+        div {
+            class: "space-y-4",
+            div {
+                class: "flex items-center justify-between gap-x-4",
+                if all_documents.len() > 1 {
+                    select {
+                        class: "rounded-md bg-white dark:bg-white/5 py-1.5 pl-3 pr-8 text-sm text-gray-900 dark:text-white outline outline-1 -outline-offset-1 outline-gray-300 dark:outline-white/10",
+                        onchange: move |e: Event<FormData>| {
+                            *selected_version.write() = e.value().parse::<i64>().ok();
+                        },
+                        for doc in all_documents.iter() {
+                            option {
+                                value: "{doc.version}",
+                                selected: current.as_ref().is_some_and(|d| d.version == doc.version),
+                                "Version {doc.version} - {doc.created_at}"
+                            }
+                        }
+                    }
+                } else {
+                    p {
+                        class: "text-sm text-gray-500 dark:text-gray-400",
+                        "Cover letter and resume suggestions tailored to this job."
+                    }
+                }
+                Button {
+                    variant: ButtonVariant::Secondary,
+                    disabled: generating(),
+                    onclick: {
+                        let job_id = job_id.clone();
+                        let mut error = error;
+                        let mut documents = documents;
+                        move |_| {
+                            let job_id = job_id.clone();
+                            spawn(async move {
+                                *generating.write() = true;
+                                *error.write() = None;
+                                match JobDocumentsService::generate_documents(job_id).await {
+                                    Ok(created) => {
+                                        *selected_version.write() = Some(created.version);
+                                        documents.write().insert(0, created);
+                                    }
+                                    Err(e) => *error.write() = Some(format!("Failed to generate documents: {}", e)),
+                                }
+                                *generating.write() = false;
+                            });
+                        }
+                    },
+                    if generating() {
+                        "Generating..."
+                    } else if all_documents.is_empty() {
+                        "Generate"
+                    } else {
+                        "Generate new version"
+                    }
+                }
+            }
+
+            if let Some(err) = error() {
+                p {
+                    class: "text-sm text-red-600 dark:text-red-400",
+                    {err}
+                }
+            }
+
+            if let Some(doc) = current {
+                div {
+                    class: "space-y-4",
+                    div {
+                        div {
+                            class: "mb-2 flex items-center justify-between",
+                            h3 {
+                                class: "text-sm font-semibold text-gray-900 dark:text-white",
+                                "Cover letter"
+                            }
+                            button {
+                                class: "text-sm font-medium text-indigo-600 dark:text-indigo-400 hover:text-indigo-500",
+                                onclick: {
+                                    let cover_letter = doc.cover_letter.clone();
+                                    move |_| {
+                                        let text = serde_json::to_string(&cover_letter).unwrap_or_default();
+                                        document::eval(&format!("navigator.clipboard.writeText({})", text));
+                                    }
+                                },
+                                "Copy"
+                            }
+                        }
+                        p {
+                            class: "whitespace-pre-wrap text-sm text-gray-700 dark:text-gray-300",
+                            {doc.cover_letter.clone()}
+                        }
+                    }
+                    if !doc.resume_suggestions.is_empty() {
+                        div {
+                            h3 {
+                                class: "mb-2 text-sm font-semibold text-gray-900 dark:text-white",
+                                "Resume suggestions"
+                            }
+                            ul {
+                                class: "list-disc space-y-1 pl-5 text-sm text-gray-700 dark:text-gray-300",
+                                for suggestion in doc.resume_suggestions.iter() {
+                                    li { {suggestion.clone()} }
+                                }
+                            }
+                        }
+                    }
+                }
+            } else if !generating() {
+                p {
+                    class: "text-sm text-gray-400 dark:text-gray-500 italic",
+                    "No documents yet. Add the job description and your resume in account settings, then generate."
+                }
+            }
+        }
+    }
+}
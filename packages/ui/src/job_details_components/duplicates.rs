@@ -0,0 +1,186 @@
+//! Possible duplicates component
+
+use crate::services::jobs_service::{DuplicateSuggestion, JobsService};
+use crate::state::{use_comments, use_email_contacts, use_emails, use_jobs};
+use dioxus::prelude::*;
+use dioxus_router::Link;
+
+/// Lists jobs that look like duplicates of this one and merges them into it.
+/// Renders nothing when there are no suggestions.
+#[component]
+pub fn DuplicatesCard(job_id: String) -> Element {
+    let jobs_state = use_jobs();
+    let contacts_state = use_email_contacts();
+    let emails_state = use_emails();
+    let comments_state = use_comments();
+    let suggestions = use_signal(Vec::<DuplicateSuggestion>::new);
+    let confirming = use_signal(|| None::<String>);
+    let merging = use_signal(|| false);
+    let error = use_signal(|| None::<String>);
+
+    // Fetch suggestions on mount
+    use_effect({
+        let job_id = job_id.clone();
+        let mut suggestions = suggestions;
+        move || {
+            let job_id = job_id.clone();
+            spawn(async move {
+                if let Ok(list) = JobsService::fetch_duplicates(Some(job_id)).await {
+                    *suggestions.write() = list;
+                }
+            });
+        }
+    });
+
+    // The other job of each pair with its label
+    let others: Vec<(DuplicateSuggestion, String, String)> = suggestions()
+        .into_iter()
+        .filter_map(|s| {
+            let other = if s.job.id.as_deref() == Some(job_id.as_str()) {
+                &s.duplicate
+            } else {
+                &s.job
+            };
+            let label = format!("{} at {}", other.title, other.company);
+            other.id.clone().map(|id| (s.clone(), id, label))
+        })
+        .collect();
+
+    if others.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            class: "rounded-lg bg-amber-50 dark:bg-amber-900/20 p-5 ring-1 ring-inset ring-amber-200 dark:ring-amber-500/30",
+            h2 {
+                class: "text-lg font-semibold text-gray-900 dark:text-white mb-1",
+                "Possible duplicates"
+            }
+            p {
+                class: "text-sm text-gray-600 dark:text-gray-300 mb-4",
+                "Merging moves emails, comments and attachments into this job and deletes the other one."
+            }
+
+            if let Some(ref err) = error() {
+                p {
+                    class: "text-sm text-red-600 dark:text-red-400 mb-2",
+                    {err.clone()}
+                }
+            }
+
+            ul {
+                role: "list",
+                class: "space-y-3",
+                for (suggestion, other_id, label) in others.into_iter() {
+                    li {
+                        key: "{other_id}",
+                        class: "flex items-start justify-between gap-x-3",
+                        div {
+                            class: "min-w-0",
+                            Link {
+                                to: format!("/jobs/{}", other_id),
+                                class: "block truncate text-sm font-medium text-gray-900 dark:text-white hover:text-indigo-600 dark:hover:text-indigo-400",
+                                {label}
+                            }
+                            p {
+                                class: "text-xs text-gray-500 dark:text-gray-400",
+                                {format!(
+                                    "{}% match{}",
+                                    (suggestion.score * 100.0).round() as i64,
+                                    format_reasons(&suggestion.reasons)
+                                )}
+                            }
+                        }
+                        if confirming().as_deref() == Some(other_id.as_str()) {
+                            div {
+                                class: "flex flex-none gap-x-3",
+                                button {
+                                    class: "text-sm font-medium text-red-600 hover:text-red-500 dark:text-red-400 disabled:opacity-50",
+                                    disabled: merging(),
+                                    onclick: {
+                                        let job_id = job_id.clone();
+                                        let other_id = other_id.clone();
+                                        let mut suggestions = suggestions;
+                                        let mut confirming = confirming;
+                                        let mut merging = merging;
+                                        let mut error = error;
+                                        move |_| {
+                                            let job_id = job_id.clone();
+                                            let other_id = other_id.clone();
+                                            spawn(async move {
+                                                *merging.write() = true;
+                                                *error.write() = None;
+                                                match JobsService::merge_job(job_id.clone(), other_id.clone()).await {
+                                                    Ok(_) => {
+                                                        suggestions.write().retain(|s| {
+                                                            s.job.id.as_deref() != Some(other_id.as_str())
+                                                                && s.duplicate.id.as_deref() != Some(other_id.as_str())
+                                                        });
+                                                        jobs_state.fetch_job_details(
+                                                            job_id,
+                                                            contacts_state,
+                                                            emails_state,
+                                                            comments_state,
+                                                        );
+                                                    }
+                                                    Err(e) => *error.write() = Some(format!("Merge failed: {}", e)),
+                                                }
+                                                *confirming.write() = None;
+                                                *merging.write() = false;
+                                            });
+                                        }
+                                    },
+                                    if merging() {
+                                        "Merging..."
+                                    } else {
+                                        "Confirm"
+                                    }
+                                }
+                                button {
+                                    class: "text-sm font-medium text-gray-500 hover:text-gray-700 dark:text-gray-400 dark:hover:text-gray-200",
+                                    onclick: move |_| {
+                                        let mut confirming = confirming;
+                                        *confirming.write() = None;
+                                    },
+                                    "Cancel"
+                                }
+                            }
+                        } else {
+                            button {
+                                class: "flex-none text-sm font-medium text-indigo-600 dark:text-indigo-400 hover:text-indigo-500",
+                                onclick: {
+                                    let other_id = other_id.clone();
+                                    move |_| {
+                                        let mut confirming = confirming;
+                                        *confirming.write() = Some(other_id.clone());
+                                    }
+                                },
+                                "Merge"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Human-readable list of why two jobs were matched
+fn format_reasons(reasons: &[String]) -> String {
+    let labels: Vec<&str> = reasons
+        .iter()
+        .map(|r| match r.as_str() {
+            "same_company" => "same company",
+            "similar_title" => "similar title",
+            "shared_thread" => "shared email thread",
+            other => other,
+        })
+        .collect();
+
+    if labels.is_empty() {
+        String::new()
+    } else {
+        format!(" · {}", labels.join(", "))
+    }
+}
@@ -1,5 +1,6 @@
 //! Emails tab component
 
+use crate::job_details_components::compose_email::ComposeEmail;
 use crate::services::emails_service::{EmailsService, StoredEmail};
 use crate::state::{use_comments_provider, use_email_contacts_provider, use_emails, use_jobs};
 use dioxus::prelude::*;
@@ -12,9 +13,54 @@ pub fn EmailsTab(job_id: String, emails: Vec<StoredEmail>) -> Element {
     let email_contacts_state = use_email_contacts_provider();
     let emails_state = use_emails();
     let comments_state = use_comments_provider();
+    let mut composing = use_signal(|| false);
+    let mut reply_to = use_signal(|| None::<StoredEmail>);
+
+    let compose_key = reply_to()
+        .map(|e| e.gmail_id)
+        .unwrap_or_else(|| "new".to_string());
 
     rsx! {
         div {
+            if composing() {
+                div {
+                    class: "mb-4",
+                    ComposeEmail {
+                        key: "{compose_key}",
+                        job_id: job_id.clone(),
+                        reply_to: reply_to(),
+                        on_close: move |_| {
+                            *composing.write() = false;
+                            *reply_to.write() = None;
+                        },
+                        on_sent: {
+                            let job_id = job_id.clone();
+                            move |_| {
+                                *composing.write() = false;
+                                *reply_to.write() = None;
+                                jobs_state.fetch_job_details(
+                                    job_id.clone(),
+                                    email_contacts_state,
+                                    emails_state,
+                                    comments_state,
+                                );
+                            }
+                        },
+                    }
+                }
+            } else {
+                div {
+                    class: "mb-2 flex justify-end",
+                    button {
+                        class: "text-sm font-medium text-indigo-600 dark:text-indigo-400 hover:text-indigo-500",
+                        onclick: move |_| {
+                            *reply_to.write() = None;
+                            *composing.write() = true;
+                        },
+                        "New email"
+                    }
+                }
+            }
             if emails.is_empty() {
                 p {
                     class: "text-sm text-gray-400 dark:text-gray-500 italic",
@@ -41,6 +87,17 @@ pub fn EmailsTab(job_id: String, emails: Vec<StoredEmail>) -> Element {
                                     }
                                 }
                             }
+                            button {
+                                class: "flex-shrink-0 text-sm font-medium text-indigo-600 dark:text-indigo-400 hover:text-indigo-500",
+                                onclick: {
+                                    let email = email.clone();
+                                    move |_| {
+                                        *reply_to.write() = Some(email.clone());
+                                        *composing.write() = true;
+                                    }
+                                },
+                                "Reply"
+                            }
                             button {
                                 class: "flex-shrink-0 text-gray-400 hover:text-red-600 dark:hover:text-red-400",
                                 onclick: {
@@ -15,6 +15,7 @@ pub fn JobDetailsHeader(
     job_id: String,
     title: String,
     company: String,
+    company_id: Option<String>,
     location: Option<String>,
     status_id: Option<i32>,
     statuses: Vec<JobStatus>,
@@ -109,6 +110,7 @@ pub fn JobDetailsHeader(
                 CompanyField {
                     job_id: job_id_company,
                     company: company.clone(),
+                    company_id: company_id.clone(),
                     editing: editing_company,
                     edit_value: edit_company_value,
                 }
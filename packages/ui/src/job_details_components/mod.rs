@@ -1,13 +1,23 @@
 //! Job details components
 
+mod attachments;
 mod company_location;
+mod compose_email;
 mod description;
 mod details_tab;
+mod documents_tab;
+mod duplicates;
 mod emails_tab;
 mod header;
+mod source;
 
+pub use attachments::AttachmentsField;
 pub use company_location::{CompanyField, LocationField};
+pub use compose_email::ComposeEmail;
 pub use description::DescriptionField;
 pub use details_tab::DetailsTab;
+pub use documents_tab::DocumentsTab;
+pub use duplicates::DuplicatesCard;
 pub use emails_tab::EmailsTab;
 pub use header::JobDetailsHeader;
+pub use source::SourceCard;
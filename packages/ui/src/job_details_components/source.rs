@@ -0,0 +1,201 @@
+//! Application source and referrer component
+
+use crate::components::button::{Button, ButtonVariant};
+use crate::services::jobs_service::{JobSource, JobsService, UpdateJobSourceRequest};
+use crate::state::{use_comments, use_email_contacts, use_emails, use_jobs};
+use dioxus::prelude::*;
+
+/// Shows where the application came from and who referred it, and lets the
+/// user change both. Referrers are suggested from the job's contacts.
+#[component]
+pub fn SourceCard(
+    job_id: String,
+    source_id: Option<String>,
+    source_name: Option<String>,
+    referrer_email: Option<String>,
+    referrer_name: Option<String>,
+) -> Element {
+    let jobs_state = use_jobs();
+    let contacts_state = use_email_contacts();
+    let emails_state = use_emails();
+    let comments_state = use_comments();
+    let sources = use_signal(Vec::<JobSource>::new);
+    let mut editing = use_signal(|| false);
+    let mut selected_source = use_signal(|| source_id.clone().unwrap_or_default());
+    let mut referrer = use_signal(|| referrer_email.clone().unwrap_or_default());
+    let mut saving = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+
+    // Fetch the source list on mount
+    use_effect({
+        let mut sources = sources;
+        move || {
+            spawn(async move {
+                if let Ok(list) = JobsService::fetch_job_sources().await {
+                    *sources.write() = list;
+                }
+            });
+        }
+    });
+
+    let contacts: Vec<String> = contacts_state
+        .contacts
+        .read()
+        .iter()
+        .filter(|c| !c.is_system)
+        .map(|c| c.email.clone())
+        .collect();
+    let referrer_label = match (&referrer_name, &referrer_email) {
+        (Some(name), Some(email)) => Some(format!("{} <{}>", name, email)),
+        (None, Some(email)) => Some(email.clone()),
+        _ => None,
+    };
+    let datalist_id = format!("referrers-{}", job_id);
+
+    rsx! {
+        div {
+            class: "rounded-lg bg-white dark:bg-gray-800 p-5 ring-1 ring-inset ring-gray-200 dark:ring-white/15",
+            div {
+                class: "flex items-center justify-between mb-3",
+                h2 {
+                    class: "text-lg font-semibold text-gray-900 dark:text-white",
+                    "Source"
+                }
+                if !editing() {
+                    button {
+                        class: "text-sm font-medium text-indigo-600 dark:text-indigo-400 hover:text-indigo-500",
+                        onclick: move |_| *editing.write() = true,
+                        "Edit"
+                    }
+                }
+            }
+
+            if editing() {
+                div {
+                    class: "space-y-3",
+                    select {
+                        class: "block w-full rounded-md bg-white py-1.5 pl-3 pr-8 text-sm text-gray-900 outline outline-1 -outline-offset-1 outline-gray-300 dark:bg-white/5 dark:text-white dark:outline-white/10",
+                        onchange: move |e: Event<FormData>| *selected_source.write() = e.value(),
+                        option {
+                            value: "",
+                            selected: selected_source().is_empty(),
+                            "Unknown"
+                        }
+                        for source in sources().iter() {
+                            option {
+                                value: "{source.id}",
+                                selected: selected_source() == source.id,
+                                {source.name.clone()}
+                            }
+                        }
+                    }
+                    input {
+                        r#type: "email",
+                        placeholder: "Referrer email (optional)",
+                        list: "{datalist_id}",
+                        class: "block w-full rounded-md bg-white px-3 py-1.5 text-sm text-gray-900 outline outline-1 -outline-offset-1 outline-gray-300 placeholder:text-gray-400 dark:bg-white/5 dark:text-white dark:outline-white/10",
+                        value: "{referrer}",
+                        oninput: move |e: Event<FormData>| *referrer.write() = e.value(),
+                    }
+                    datalist {
+                        id: "{datalist_id}",
+                        for email in contacts.iter() {
+                            option {
+                                value: "{email}",
+                            }
+                        }
+                    }
+                    if let Some(err) = error() {
+                        p {
+                            class: "text-sm text-red-600 dark:text-red-400",
+                            {err}
+                        }
+                    }
+                    div {
+                        class: "flex gap-x-2",
+                        Button {
+                            variant: ButtonVariant::Primary,
+                            disabled: saving(),
+                            onclick: {
+                                let job_id = job_id.clone();
+                                move |_| {
+                                    let job_id = job_id.clone();
+                                    let request = UpdateJobSourceRequest {
+                                        source_id: Some(selected_source()).filter(|s| !s.is_empty()),
+                                        referrer_email: Some(referrer().trim().to_string())
+                                            .filter(|r| !r.is_empty()),
+                                    };
+                                    spawn(async move {
+                                        *saving.write() = true;
+                                        *error.write() = None;
+                                        match JobsService::update_job_source(job_id.clone(), request).await {
+                                            Ok(_) => {
+                                                *editing.write() = false;
+                                                jobs_state.fetch_job_details(
+                                                    job_id,
+                                                    contacts_state,
+                                                    emails_state,
+                                                    comments_state,
+                                                );
+                                            }
+                                            Err(e) => *error.write() = Some(format!("Failed to save source: {}", e)),
+                                        }
+                                        *saving.write() = false;
+                                    });
+                                }
+                            },
+                            if saving() {
+                                "Saving..."
+                            } else {
+                                "Save"
+                            }
+                        }
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            disabled: saving(),
+                            onclick: {
+                                let source_id = source_id.clone();
+                                let referrer_email = referrer_email.clone();
+                                move |_| {
+                                    *selected_source.write() = source_id.clone().unwrap_or_default();
+                                    *referrer.write() = referrer_email.clone().unwrap_or_default();
+                                    *error.write() = None;
+                                    *editing.write() = false;
+                                }
+                            },
+                            "Cancel"
+                        }
+                    }
+                }
+            } else {
+                dl {
+                    class: "space-y-2 text-sm",
+                    div {
+                        class: "flex justify-between gap-x-4",
+                        dt {
+                            class: "text-gray-500 dark:text-gray-400",
+                            "Came from"
+                        }
+                        dd {
+                            class: "text-gray-900 dark:text-white",
+                            {source_name.clone().unwrap_or_else(|| "Unknown".to_string())}
+                        }
+                    }
+                    if let Some(label) = referrer_label {
+                        div {
+                            class: "flex justify-between gap-x-4",
+                            dt {
+                                class: "text-gray-500 dark:text-gray-400",
+                                "Referred by"
+                            }
+                            dd {
+                                class: "truncate text-gray-900 dark:text-white",
+                                {label}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
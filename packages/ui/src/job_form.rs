@@ -120,63 +120,41 @@ pub fn JobForm(
                             let mut open_signal = open;
 
                             spawn(async move {
-                                // If gmail_id is provided, use assign_email_to_job endpoint
-                                // which creates job AND links email in one call
-                                if let Some(gmail_id) = gmail_id_clone {
-                                    let create_req = crate::services::emails_service::CreateJobFromEmail {
-                                        title: title_val,
-                                        company: company_val,
-                                        location: location_val,
-                                        status_id: Some(status_id_val),
-                                    };
-                                    let assign_req = crate::services::emails_service::AssignJobRequest {
-                                        job_id: None,
-                                        create_job: Some(create_req),
-                                    };
-
-                                    match crate::services::emails_service::EmailsService::assign_email_to_job(gmail_id, assign_req).await {
-                                        Ok(response) => {
-                                            // Fetch the created job to add to list
-                                            if let Ok(created_job) = crate::services::jobs_service::JobsService::fetch_job(response.job_id.clone()).await {
-                                                let mut jobs_list = jobs_state_clone.jobs.read().clone();
-                                                jobs_list.push(created_job.clone());
-                                                *jobs_state_clone.jobs.write() = jobs_list;
+                                // Jobs are always created through the jobs API so they're
+                                // linked to their company, then the email is assigned
+                                let create_req = CreateJobRequest {
+                                    title: title_val,
+                                    company: company_val,
+                                    location: location_val,
+                                    status_id: Some(status_id_val),
+                                };
+
+                                match crate::services::jobs_service::JobsService::create_job(create_req).await {
+                                    Ok(created_job) => {
+                                        // Update jobs list
+                                        let mut jobs_list = jobs_state_clone.jobs.read().clone();
+                                        jobs_list.push(created_job.clone());
+                                        *jobs_state_clone.jobs.write() = jobs_list;
+
+                                        if let (Some(gmail_id), Some(job_id)) = (gmail_id_clone, created_job.id.clone()) {
+                                            let assign_req = crate::services::emails_service::AssignJobRequest {
+                                                job_id: Some(job_id),
+                                                create_job: None,
+                                            };
+                                            if let Err(e) = crate::services::emails_service::EmailsService::assign_email_to_job(gmail_id, assign_req).await {
+                                                *jobs_state_clone.error.write() = Some(crate::services::error::ServiceError::Server(500, format!("Failed to link email to job: {:?}", e)));
                                             }
-
-                                            // Set created job ID for navigation
-                                            jobs_state_clone.set_created_job_id(response.job_id);
-                                            *open_signal.write() = false;
-                                        }
-                                        Err(e) => {
-                                            *jobs_state_clone.error.write() = Some(crate::services::error::ServiceError::Server(500, format!("Failed to create job: {:?}", e)));
                                         }
-                                    }
-                                } else {
-                                    // No gmail_id, use regular create_job endpoint
-                                    let create_req = CreateJobRequest {
-                                        title: title_val,
-                                        company: company_val,
-                                        location: location_val,
-                                        status_id: Some(status_id_val),
-                                    };
-
-                                    match crate::services::jobs_service::JobsService::create_job(create_req).await {
-                                        Ok(created_job) => {
-                                            // Update jobs list
-                                            let mut jobs_list = jobs_state_clone.jobs.read().clone();
-                                            jobs_list.push(created_job.clone());
-                                            *jobs_state_clone.jobs.write() = jobs_list;
 
-                                            // Set created job ID for navigation
-                                            if let Some(id) = created_job.id {
-                                                jobs_state_clone.set_created_job_id(id);
-                                            }
-
-                                            *open_signal.write() = false;
-                                        }
-                                        Err(e) => {
-                                            *jobs_state_clone.error.write() = Some(e);
+                                        // Set created job ID for navigation
+                                        if let Some(id) = created_job.id {
+                                            jobs_state_clone.set_created_job_id(id);
                                         }
+
+                                        *open_signal.write() = false;
+                                    }
+                                    Err(e) => {
+                                        *jobs_state_clone.error.write() = Some(e);
                                     }
                                 }
                             });
@@ -8,9 +8,17 @@ use crate::components::button::{Button, ButtonVariant};
 use crate::components::dropdown_menu::{
     DropdownMenu, DropdownMenuContent, DropdownMenuItem, DropdownMenuTrigger,
 };
-use crate::{job_form::JobForm, services::jobs_service::Job, state::use_jobs};
+use crate::{
+    hooks::use_trash_retention,
+    job_form::JobForm,
+    services::jobs_service::{
+        retention_period, BulkAction, BulkJobsRequest, Job, JobListView, JobsService,
+    },
+    state::use_jobs,
+};
 use dioxus::prelude::*;
 use dioxus_router::Link;
+use std::collections::HashSet;
 use std::rc::Rc;
 
 /// Jobs list component
@@ -23,11 +31,81 @@ pub fn JobsList() -> Element {
     let mut show_delete_dialog = use_signal(|| Some(false));
     let mut job_to_delete = use_signal(|| None::<String>);
 
-    // Fetch jobs on mount
+    // Multi-select and bulk actions
+    let mut selected = use_signal(HashSet::<String>::new);
+    let mut bulk_tag = use_signal(String::new);
+    let mut bulk_busy = use_signal(|| false);
+    let mut bulk_message = use_signal(|| None::<String>);
+    let mut show_bulk_delete_dialog = use_signal(|| Some(false));
+
+    // Active, archived or trashed jobs
+    let mut view = use_signal(JobListView::default);
+    let trash_retention = use_trash_retention();
+
+    // Fetch jobs on mount and whenever the view changes
+    use_effect(move || {
+        jobs_state.fetch_jobs_in_view(view());
+    });
+
+    // Fetch job statuses for the bulk status picker
     use_effect(move || {
-        jobs_state.fetch_jobs();
+        if jobs_state.job_statuses.peek().is_empty() {
+            jobs_state.fetch_job_statuses();
+        }
     });
 
+    let run_bulk = move |action: BulkAction| {
+        let job_ids: Vec<String> = selected.read().iter().cloned().collect();
+        if job_ids.is_empty() {
+            return;
+        }
+        spawn(async move {
+            *bulk_busy.write() = true;
+            *bulk_message.write() = None;
+            match JobsService::bulk_update_jobs(BulkJobsRequest { job_ids, action }).await {
+                Ok(response) => {
+                    let failed: HashSet<String> = response
+                        .results
+                        .iter()
+                        .filter(|r| !r.success)
+                        .map(|r| r.job_id.clone())
+                        .collect();
+                    if response.failed > 0 {
+                        let reason = response
+                            .results
+                            .iter()
+                            .find_map(|r| r.error.clone())
+                            .unwrap_or_default();
+                        *bulk_message.write() = Some(format!(
+                            "{} updated, {} failed: {}",
+                            response.succeeded, response.failed, reason
+                        ));
+                    } else {
+                        *bulk_message.write() =
+                            Some(format!("{} jobs updated", response.succeeded));
+                    }
+                    // Keep failed jobs selected so the action can be retried
+                    *selected.write() = failed;
+                    jobs_state.fetch_jobs_in_view(*view.peek());
+                }
+                Err(e) => *bulk_message.write() = Some(format!("Bulk update failed: {}", e)),
+            }
+            *bulk_busy.write() = false;
+        });
+    };
+
+    let all_job_ids: Vec<String> = jobs_state
+        .jobs
+        .read()
+        .iter()
+        .filter_map(|j| j.id.clone())
+        .collect();
+    let selected_count = selected.read().len();
+    let all_selected = !all_job_ids.is_empty() && selected_count == all_job_ids.len();
+    let statuses = jobs_state.job_statuses.read().clone();
+    let current_view = view();
+    let retention = retention_period(trash_retention());
+
     rsx! {
         div {
             class: "px-4 sm:px-6 lg:px-8 py-6",
@@ -54,6 +132,38 @@ pub fn JobsList() -> Element {
                 }
             }
 
+            // View tabs
+            nav {
+                class: "mt-6 flex gap-x-6 border-b border-gray-200 dark:border-white/10",
+                for (tab, label) in [
+                    (JobListView::Active, "Active"),
+                    (JobListView::Archived, "Archived"),
+                    (JobListView::Trash, "Trash"),
+                ] {
+                    button {
+                        class: if current_view == tab {
+                            "border-b-2 border-indigo-500 px-1 pb-3 text-sm font-medium text-indigo-600 dark:border-indigo-400 dark:text-indigo-400"
+                        } else {
+                            "border-b-2 border-transparent px-1 pb-3 text-sm font-medium text-gray-500 hover:border-gray-300 hover:text-gray-700 dark:text-gray-400 dark:hover:text-gray-300"
+                        },
+                        onclick: move |_| {
+                            if *view.peek() != tab {
+                                selected.write().clear();
+                                *bulk_message.write() = None;
+                                *view.write() = tab;
+                            }
+                        },
+                        "{label}"
+                    }
+                }
+            }
+            if current_view == JobListView::Trash {
+                p {
+                    class: "mt-4 text-sm text-gray-500 dark:text-gray-400",
+                    "Jobs in the trash are deleted permanently after {retention}."
+                }
+            }
+
             // Loading state
             if *jobs_state.loading.read() {
                 div {
@@ -86,19 +196,146 @@ pub fn JobsList() -> Element {
                 }
             }
 
+            // Bulk actions for the selected jobs
+            if let Some(ref message) = bulk_message() {
+                div {
+                    class: "mt-6 rounded-md bg-gray-50 dark:bg-white/5 px-4 py-3 text-sm text-gray-700 dark:text-gray-300",
+                    {message.clone()}
+                }
+            }
+
             // Jobs list
             if !*jobs_state.loading.read() && jobs_state.error.read().is_none() {
                 div {
-                    class: "mt-6 flow-root",
+                    class: "mt-6 flex flex-wrap items-center gap-3 border-b border-gray-100 dark:border-white/5 pb-3",
+                    label {
+                        class: "flex items-center gap-x-2 text-sm text-gray-700 dark:text-gray-300",
+                        input {
+                            r#type: "checkbox",
+                            class: "size-4 rounded border-gray-300 text-indigo-600 dark:border-white/10 dark:bg-white/5",
+                            checked: all_selected,
+                            disabled: all_job_ids.is_empty(),
+                            onchange: {
+                                let all_job_ids = all_job_ids.clone();
+                                move |_| {
+                                    if all_selected {
+                                        selected.write().clear();
+                                    } else {
+                                        *selected.write() = all_job_ids.iter().cloned().collect();
+                                    }
+                                }
+                            },
+                        }
+                        if selected_count > 0 {
+                            "{selected_count} selected"
+                        } else {
+                            "Select all"
+                        }
+                    }
+                    if selected_count > 0 && current_view == JobListView::Active {
+                        select {
+                            class: "rounded-md bg-white py-1.5 pl-3 pr-8 text-sm text-gray-900 outline outline-1 -outline-offset-1 outline-gray-300 dark:bg-white/5 dark:text-white dark:outline-white/10",
+                            disabled: bulk_busy(),
+                            onchange: move |e: Event<FormData>| {
+                                if let Ok(status_id) = e.value().parse::<i32>() {
+                                    run_bulk(BulkAction::SetStatus { status_id });
+                                }
+                            },
+                            option {
+                                value: "",
+                                "Set status..."
+                            }
+                            for status in statuses.iter() {
+                                option {
+                                    value: "{status.id}",
+                                    {status.display_name.clone()}
+                                }
+                            }
+                        }
+                        input {
+                            r#type: "text",
+                            placeholder: "Tag",
+                            class: "w-32 rounded-md bg-white px-3 py-1.5 text-sm text-gray-900 outline outline-1 -outline-offset-1 outline-gray-300 placeholder:text-gray-400 dark:bg-white/5 dark:text-white dark:outline-white/10",
+                            value: "{bulk_tag}",
+                            oninput: move |e: Event<FormData>| *bulk_tag.write() = e.value(),
+                        }
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            disabled: bulk_busy() || bulk_tag().trim().is_empty(),
+                            onclick: move |_| {
+                                run_bulk(BulkAction::AddTags { tags: vec![bulk_tag().trim().to_string()] });
+                                *bulk_tag.write() = String::new();
+                            },
+                            "Add tag"
+                        }
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            disabled: bulk_busy() || bulk_tag().trim().is_empty(),
+                            onclick: move |_| {
+                                run_bulk(BulkAction::RemoveTags { tags: vec![bulk_tag().trim().to_string()] });
+                                *bulk_tag.write() = String::new();
+                            },
+                            "Remove tag"
+                        }
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            disabled: bulk_busy(),
+                            onclick: move |_| run_bulk(BulkAction::Archive),
+                            "Archive"
+                        }
+                    }
+                    if selected_count > 0 && current_view != JobListView::Active {
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            disabled: bulk_busy(),
+                            onclick: move |_| run_bulk(BulkAction::Restore),
+                            "Restore"
+                        }
+                    }
+                    if selected_count > 0 && current_view != JobListView::Trash {
+                        Button {
+                            variant: ButtonVariant::Destructive,
+                            disabled: bulk_busy(),
+                            onclick: move |_| run_bulk(BulkAction::Delete),
+                            "Move to trash"
+                        }
+                    }
+                    if selected_count > 0 && current_view == JobListView::Trash {
+                        Button {
+                            variant: ButtonVariant::Destructive,
+                            disabled: bulk_busy(),
+                            onclick: move |_| *show_bulk_delete_dialog.write() = Some(true),
+                            "Delete forever"
+                        }
+                    }
+                }
+                div {
+                    class: "flow-root",
                     ul {
                         role: "list",
                         class: "divide-y divide-gray-100 dark:divide-white/5",
                         for job in jobs_state.jobs.read().clone() {
                             li {
                                 class: "flex justify-between gap-x-6 py-5",
-                                // Left section: Avatar, title, company
+                                // Left section: Checkbox, avatar, title, company
                                 div {
-                                    class: "flex min-w-0 gap-x-4",
+                                    class: "flex min-w-0 items-center gap-x-4",
+                                    if let Some(ref job_id) = job.id {
+                                        input {
+                                            r#type: "checkbox",
+                                            class: "size-4 flex-none rounded border-gray-300 text-indigo-600 dark:border-white/10 dark:bg-white/5",
+                                            checked: selected.read().contains(job_id),
+                                            onchange: {
+                                                let job_id = job_id.clone();
+                                                move |_| {
+                                                    let mut current = selected.write();
+                                                    if !current.remove(&job_id) {
+                                                        current.insert(job_id.clone());
+                                                    }
+                                                }
+                                            },
+                                        }
+                                    }
                                     // Company initial avatar
                                     div {
                                         class: "size-12 flex-none rounded-full bg-brand-100 dark:bg-brand-900 flex items-center justify-center dark:outline dark:outline-1 dark:-outline-offset-1 dark:outline-white/10",
@@ -134,6 +371,17 @@ pub fn JobsList() -> Element {
                                                 }
                                             }
                                         }
+                                        if !job.tags.is_empty() {
+                                            div {
+                                                class: "mt-1 flex flex-wrap gap-1",
+                                                for tag in job.tags.iter() {
+                                                    span {
+                                                        class: "inline-flex items-center rounded-full bg-gray-100 px-2 py-0.5 text-xs text-gray-600 dark:bg-white/10 dark:text-gray-300",
+                                                        {tag.clone()}
+                                                    }
+                                                }
+                                            }
+                                        }
                                     }
                                 }
                                 // Right section: Status, dropdown menu
@@ -147,60 +395,112 @@ pub fn JobsList() -> Element {
                                             {job.status_name.clone().unwrap_or_else(|| "Unknown".to_string())}
                                         }
                                     }
-                                    // Dropdown menu
-                                    DropdownMenu {
-                                        DropdownMenuTrigger {
-                                            button {
-                                                class: "relative block text-gray-500 hover:text-gray-900 dark:text-gray-400 dark:hover:text-white",
-                                                span {
-                                                    class: "absolute -inset-2.5",
-                                                }
-                                                span {
-                                                    class: "sr-only",
-                                                    "Open options"
+                                    // Restore or delete archived and trashed jobs
+                                    if current_view != JobListView::Active {
+                                        if let Some(ref job_id) = job.id {
+                                            div {
+                                                class: "flex items-center gap-x-2",
+                                                Button {
+                                                    variant: ButtonVariant::Secondary,
+                                                    onclick: {
+                                                        let job_id = job_id.clone();
+                                                        move |_| jobs_state.restore_job(job_id.clone())
+                                                    },
+                                                    "Restore"
                                                 }
-                                                svg {
-                                                    view_box: "0 0 20 20",
-                                                    fill: "currentColor",
-                                                    "aria-hidden": "true",
-                                                    class: "size-5",
-                                                    path {
-                                                        d: "M10 3a1.5 1.5 0 1 1 0 3 1.5 1.5 0 0 1 0-3ZM10 8.5a1.5 1.5 0 1 1 0 3 1.5 1.5 0 0 1 0-3ZM11.5 15.5a1.5 1.5 0 1 0-3 0 1.5 1.5 0 0 0 3 0Z",
+                                                if current_view == JobListView::Trash {
+                                                    Button {
+                                                        variant: ButtonVariant::Destructive,
+                                                        onclick: {
+                                                            let job_id = job_id.clone();
+                                                            move |_| {
+                                                                *job_to_delete.write() = Some(job_id.clone());
+                                                                *show_delete_dialog.write() = Some(true);
+                                                            }
+                                                        },
+                                                        "Delete forever"
+                                                    }
+                                                } else {
+                                                    Button {
+                                                        variant: ButtonVariant::Destructive,
+                                                        onclick: {
+                                                            let job_id = job_id.clone();
+                                                            move |_| jobs_state.delete_job(job_id.clone())
+                                                        },
+                                                        "Move to trash"
                                                     }
                                                 }
                                             }
                                         }
-                                        DropdownMenuContent {
-                                            class: "right-0 w-32",
-                                            DropdownMenuItem::<String> {
-                                                index: use_signal(|| 0usize),
-                                                value: "edit".to_string(),
-                                                on_select: {
-                                                    let job_rc = Rc::new(job.clone());
-                                                    let mut show_edit = show_edit_dialog;
-                                                    let mut job_edit = job_to_edit;
-                                                    move |_| {
-                                                        *job_edit.write() = Some((*job_rc).clone());
-                                                        *show_edit.write() = true;
+                                    } else {
+                                        // Dropdown menu
+                                        DropdownMenu {
+                                            DropdownMenuTrigger {
+                                                button {
+                                                    class: "relative block text-gray-500 hover:text-gray-900 dark:text-gray-400 dark:hover:text-white",
+                                                    span {
+                                                        class: "absolute -inset-2.5",
                                                     }
-                                                },
-                                                "Edit"
-                                            }
-                                            DropdownMenuItem::<String> {
-                                                index: use_signal(|| 1usize),
-                                                value: "delete".to_string(),
-                                                on_select: {
-                                                    let job_id = job.id.clone();
-                                                    let mut show_delete = show_delete_dialog;
-                                                    let mut job_delete = job_to_delete;
-                                                    move |_| {
-                                                        if let Some(id) = job_id.clone() {
-                                                            *job_delete.write() = Some(id);
-                                                            *show_delete.write() = Some(true);
+                                                    span {
+                                                        class: "sr-only",
+                                                        "Open options"
+                                                    }
+                                                    svg {
+                                                        view_box: "0 0 20 20",
+                                                        fill: "currentColor",
+                                                        "aria-hidden": "true",
+                                                        class: "size-5",
+                                                        path {
+                                                            d: "M10 3a1.5 1.5 0 1 1 0 3 1.5 1.5 0 0 1 0-3ZM10 8.5a1.5 1.5 0 1 1 0 3 1.5 1.5 0 0 1 0-3ZM11.5 15.5a1.5 1.5 0 1 0-3 0 1.5 1.5 0 0 0 3 0Z",
                                                         }
                                                     }
-                                                },
-                                                "Delete"
+                                                }
+                                            }
+                                            DropdownMenuContent {
+                                                class: "right-0 w-32",
+                                                DropdownMenuItem::<String> {
+                                                    index: use_signal(|| 0usize),
+                                                    value: "edit".to_string(),
+                                                    on_select: {
+                                                        let job_rc = Rc::new(job.clone());
+                                                        let mut show_edit = show_edit_dialog;
+                                                        let mut job_edit = job_to_edit;
+                                                        move |_| {
+                                                            *job_edit.write() = Some((*job_rc).clone());
+                                                            *show_edit.write() = true;
+                                                        }
+                                                    },
+                                                    "Edit"
+                                                }
+                                                DropdownMenuItem::<String> {
+                                                    index: use_signal(|| 1usize),
+                                                    value: "archive".to_string(),
+                                                    on_select: {
+                                                        let job_id = job.id.clone();
+                                                        move |_| {
+                                                            if let Some(id) = job_id.clone() {
+                                                                jobs_state.archive_job(id);
+                                                            }
+                                                        }
+                                                    },
+                                                    "Archive"
+                                                }
+                                                DropdownMenuItem::<String> {
+                                                    index: use_signal(|| 2usize),
+                                                    value: "delete".to_string(),
+                                                    on_select: {
+                                                        let job_id = job.id.clone();
+                                                        let mut show_delete = show_delete_dialog;
+                                                        let mut job_delete = job_to_delete;
+                                                        move |_| {
+                                                            if let Some(id) = job_id.clone() {
+                                                                *job_delete.write() = Some(id);
+                                                                *show_delete.write() = Some(true);
+                                                            }
+                                                        }
+                                                    },
+                                                    "Delete"
+                                                }
                                             }
                                         }
                                     }
@@ -235,7 +535,11 @@ pub fn JobsList() -> Element {
                         "Delete Job"
                     }
                     AlertDialogDescription {
-                        "Are you sure you want to delete this job? This action cannot be undone."
+                        if current_view == JobListView::Trash {
+                            "Are you sure you want to permanently delete this job? This action cannot be undone."
+                        } else {
+                            "This job will be moved to the trash. You can restore it within {retention}."
+                        }
                     }
                     AlertDialogActions {
                         AlertDialogCancel {
@@ -249,7 +553,11 @@ pub fn JobsList() -> Element {
                             on_click: move |_| {
                                 let id_opt = job_to_delete.read().clone();
                                 if let Some(id) = id_opt {
-                                    jobs_state.delete_job(id);
+                                    if *view.peek() == JobListView::Trash {
+                                        jobs_state.purge_job(id);
+                                    } else {
+                                        jobs_state.delete_job(id);
+                                    }
                                     *show_delete_dialog.write() = Some(false);
                                     *job_to_delete.write() = None;
                                 }
@@ -259,6 +567,32 @@ pub fn JobsList() -> Element {
                     }
                 }
             }
+
+            // Bulk delete confirmation dialog
+            AlertDialogRoot {
+                open: show_bulk_delete_dialog,
+                AlertDialogContent {
+                    AlertDialogTitle {
+                        "Delete Jobs"
+                    }
+                    AlertDialogDescription {
+                        "Are you sure you want to permanently delete {selected_count} jobs? This action cannot be undone."
+                    }
+                    AlertDialogActions {
+                        AlertDialogCancel {
+                            on_click: move |_| *show_bulk_delete_dialog.write() = Some(false),
+                            "Cancel"
+                        }
+                        AlertDialogAction {
+                            on_click: move |_| {
+                                run_bulk(BulkAction::Purge);
+                                *show_bulk_delete_dialog.write() = Some(false);
+                            },
+                            "Delete"
+                        }
+                    }
+                }
+            }
         }
     }
 }
@@ -21,6 +21,8 @@ pub use job_form::JobForm;
 mod dashboard;
 pub use dashboard::DashboardContent;
 
+mod dashboard_components;
+
 mod emails_list;
 pub use emails_list::EmailsList;
 
@@ -44,12 +46,27 @@ pub use job_details::JobDetails;
 
 mod job_details_components;
 
+mod companies_list;
+pub use companies_list::CompaniesList;
+
+mod company_details;
+pub use company_details::CompanyDetails;
+
+mod contacts_list;
+pub use contacts_list::ContactsList;
+
+mod contact_details;
+pub use contact_details::ContactDetails;
+
 mod email_contact_card;
 pub use email_contact_card::EmailContactCard;
 
 mod email_contact_slideout;
 pub use email_contact_slideout::EmailContactSlideout;
 
+mod review_queue;
+pub use review_queue::ReviewQueue;
+
 pub mod services;
 pub mod state;
 pub mod utils;
@@ -63,5 +80,5 @@ pub use components::login_button::LoginButton;
 pub use components::login_page::LoginPage;
 pub use components::sidebar_nav::{SidebarNav, SidebarNavItem};
 pub use components::user_profile::UserProfile;
-pub use hooks::use_system_email_detection;
+pub use hooks::{use_system_email_detection, use_trash_retention};
 pub use state::{use_auth, use_auth_provider};
@@ -0,0 +1,279 @@
+//! Review queue for AI results with low confidence
+
+use crate::services::ai_service::{AiService, ReviewItem};
+use crate::state::use_auth;
+use crate::utils::format_date;
+use dioxus::prelude::*;
+use serde_json::{Map, Value};
+
+const CATEGORIES: [&str; 5] = [
+    "interview",
+    "rejection",
+    "new_job",
+    "application_sent",
+    "other",
+];
+
+/// Text fields of the extract stage with their labels
+const TEXT_FIELDS: [(&str, &str); 6] = [
+    ("company", "Company"),
+    ("job_title", "Job title"),
+    ("recruiter_name", "Recruiter name"),
+    ("recruiter_email", "Recruiter email"),
+    ("interview_date", "Interview date"),
+    ("location", "Location"),
+];
+
+const INPUT_CLASS: &str = "block w-full rounded-md border-0 px-2 py-1.5 text-sm text-gray-900 dark:text-white bg-white dark:bg-gray-800 shadow-sm ring-1 ring-inset ring-gray-300 dark:ring-gray-700 focus:ring-2 focus:ring-inset focus:ring-indigo-600";
+
+/// Emails the AI wasn't sure about, to accept or correct one by one
+#[component]
+pub fn ReviewQueue() -> Element {
+    let items = use_signal(Vec::<ReviewItem>::new);
+    let loading = use_signal(|| true);
+    let error = use_signal(|| None::<String>);
+
+    use_effect(move || {
+        let mut items = items;
+        let mut loading = loading;
+        let mut error = error;
+        spawn(async move {
+            *loading.write() = true;
+            match AiService::list_review_queue().await {
+                Ok(list) => {
+                    *items.write() = list;
+                    *error.write() = None;
+                }
+                Err(e) => *error.write() = Some(format!("Failed to load review queue: {}", e)),
+            }
+            *loading.write() = false;
+        });
+    });
+
+    rsx! {
+        div {
+            class: "px-4 sm:px-6 lg:px-8 py-6 space-y-6",
+            div {
+                h1 {
+                    class: "text-2xl font-semibold text-gray-900 dark:text-white",
+                    "Review"
+                }
+                p {
+                    class: "mt-2 text-sm text-gray-700 dark:text-gray-300",
+                    "Emails the AI wasn't confident about. Accept the result or correct it; corrections teach it how you label your emails."
+                }
+            }
+
+            if let Some(err) = error() {
+                div {
+                    class: "rounded-md bg-red-50 dark:bg-red-900/20 p-4 text-sm text-red-800 dark:text-red-200",
+                    {err}
+                }
+            }
+
+            if loading() {
+                div {
+                    class: "text-center py-8 text-gray-500 dark:text-gray-400",
+                    "Loading..."
+                }
+            } else if items().is_empty() {
+                div {
+                    class: "text-center py-8 text-gray-500 dark:text-gray-400",
+                    "Nothing to review."
+                }
+            } else {
+                for item in items().iter() {
+                    ReviewCard {
+                        key: "{item.email_id}",
+                        item: item.clone(),
+                        on_reviewed: move |email_id: String| {
+                            let mut items = items;
+                            items.write().retain(|i| i.email_id != email_id);
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn field_text(fields: &Map<String, Value>, key: &str) -> String {
+    match fields.get(key) {
+        Some(Value::String(s)) => s.clone(),
+        _ => String::new(),
+    }
+}
+
+/// One flagged email with an editable copy of its AI result
+#[component]
+fn ReviewCard(item: ReviewItem, on_reviewed: EventHandler<String>) -> Element {
+    let auth = use_auth();
+    let user = auth.user.read();
+    let timezone = user.as_ref().and_then(|u| u.timezone.as_deref());
+
+    let initial_category = item.category.clone().unwrap_or_else(|| "other".to_string());
+    let mut category = use_signal(|| initial_category);
+    let mut fields = use_signal(|| {
+        TEXT_FIELDS
+            .iter()
+            .map(|(key, _)| (key.to_string(), field_text(&item.fields, key)))
+            .collect::<Vec<_>>()
+    });
+    let initial_remote = match item.fields.get("remote") {
+        Some(Value::Bool(true)) => "yes",
+        Some(Value::Bool(false)) => "no",
+        _ => "",
+    };
+    let mut remote = use_signal(|| initial_remote.to_string());
+    let saving = use_signal(|| false);
+    let error = use_signal(|| None::<String>);
+
+    let submit = {
+        let email_id = item.email_id.clone();
+        let ai_category = item.category.clone().unwrap_or_else(|| "other".to_string());
+        let ai_fields = item.fields.clone();
+        let mut saving = saving;
+        let mut error = error;
+        move |accept: bool| {
+            let email_id = email_id.clone();
+            let (review_category, review_fields) = if accept {
+                (ai_category.clone(), ai_fields.clone())
+            } else {
+                let mut edited: Map<String, Value> = fields()
+                    .into_iter()
+                    .map(|(key, value)| (key, Value::String(value.trim().to_string())))
+                    .collect();
+                let remote_value = match remote().as_str() {
+                    "yes" => Value::Bool(true),
+                    "no" => Value::Bool(false),
+                    _ => Value::Null,
+                };
+                edited.insert("remote".to_string(), remote_value);
+                (category(), edited)
+            };
+            spawn(async move {
+                *saving.write() = true;
+                *error.write() = None;
+                match AiService::review_email(&email_id, &review_category, review_fields).await {
+                    Ok(_) => on_reviewed.call(email_id),
+                    Err(e) => *error.write() = Some(format!("Failed to save review: {}", e)),
+                }
+                *saving.write() = false;
+            });
+        }
+    };
+    let accept = submit.clone();
+    let correct = submit;
+
+    rsx! {
+        div {
+            class: "rounded-lg border border-gray-200 dark:border-gray-700 bg-white dark:bg-gray-900 p-5 space-y-4",
+            div {
+                class: "flex items-start justify-between gap-4",
+                div {
+                    class: "min-w-0",
+                    h3 {
+                        class: "truncate font-medium text-gray-900 dark:text-white",
+                        {item.subject.clone().unwrap_or_else(|| "No subject".to_string())}
+                    }
+                    p {
+                        class: "text-sm text-gray-500 dark:text-gray-400",
+                        {item.from.clone().unwrap_or_default()}
+                        if let Some(date) = item.date.as_deref() {
+                            {format!(" · {}", format_date(date, timezone))}
+                        }
+                    }
+                }
+                if let Some(confidence) = item.confidence {
+                    span {
+                        class: "shrink-0 rounded-full bg-yellow-100 dark:bg-yellow-900/20 px-2 py-1 text-xs font-medium text-yellow-800 dark:text-yellow-200",
+                        {format!("{:.0}% confident", confidence * 100.0)}
+                    }
+                }
+            }
+
+            if let Some(snippet) = item.snippet.clone() {
+                p {
+                    class: "text-sm text-gray-700 dark:text-gray-300",
+                    {snippet}
+                }
+            }
+            if let Some(summary) = item.summary.clone() {
+                p {
+                    class: "text-sm italic text-gray-600 dark:text-gray-400",
+                    "AI summary: {summary}"
+                }
+            }
+
+            div {
+                class: "grid grid-cols-1 gap-3 sm:grid-cols-2 lg:grid-cols-4",
+                label {
+                    class: "text-xs font-medium text-gray-700 dark:text-gray-300",
+                    "Category"
+                    select {
+                        class: "mt-1 {INPUT_CLASS}",
+                        onchange: move |e: Event<FormData>| *category.write() = e.value(),
+                        for option_category in CATEGORIES {
+                            option {
+                                value: option_category,
+                                selected: category() == option_category,
+                                {option_category}
+                            }
+                        }
+                    }
+                }
+                for (index, (key, label_text)) in TEXT_FIELDS.iter().enumerate() {
+                    label {
+                        key: "{key}",
+                        class: "text-xs font-medium text-gray-700 dark:text-gray-300",
+                        {*label_text}
+                        input {
+                            class: "mt-1 {INPUT_CLASS}",
+                            r#type: "text",
+                            value: fields().get(index).map(|(_, v)| v.clone()).unwrap_or_default(),
+                            oninput: move |e| {
+                                if let Some(field) = fields.write().get_mut(index) {
+                                    field.1 = e.value();
+                                }
+                            },
+                        }
+                    }
+                }
+                label {
+                    class: "text-xs font-medium text-gray-700 dark:text-gray-300",
+                    "Remote"
+                    select {
+                        class: "mt-1 {INPUT_CLASS}",
+                        onchange: move |e: Event<FormData>| *remote.write() = e.value(),
+                        option { value: "", selected: remote().is_empty(), "Unknown" }
+                        option { value: "yes", selected: remote() == "yes", "Yes" }
+                        option { value: "no", selected: remote() == "no", "No" }
+                    }
+                }
+            }
+
+            if let Some(err) = error() {
+                p {
+                    class: "text-sm text-red-600 dark:text-red-400",
+                    {err}
+                }
+            }
+
+            div {
+                class: "flex gap-3",
+                button {
+                    class: "rounded-md bg-indigo-600 px-3 py-2 text-sm font-semibold text-white shadow-sm hover:bg-indigo-500 disabled:opacity-50",
+                    disabled: saving(),
+                    onclick: move |_| accept(true),
+                    "Accept"
+                }
+                button {
+                    class: "rounded-md bg-white dark:bg-gray-800 px-3 py-2 text-sm font-semibold text-gray-900 dark:text-white shadow-sm ring-1 ring-inset ring-gray-300 dark:ring-gray-700 hover:bg-gray-50 dark:hover:bg-gray-700 disabled:opacity-50",
+                    disabled: saving(),
+                    onclick: move |_| correct(false),
+                    "Save Correction"
+                }
+            }
+        }
+    }
+}
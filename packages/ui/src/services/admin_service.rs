@@ -4,6 +4,8 @@ use crate::services::{api_config::get_api_base_url, error::ServiceError, http_cl
 use serde::{Deserialize, Serialize};
 
 use super::auth_service::User;
+use super::jobs_service::JobSource;
+use super::usage_service::Quota;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AdminStats {
@@ -13,10 +15,67 @@ pub struct AdminStats {
     pub admin_count: i32,
 }
 
+/// AI processing stats of one day, or totals over a range of days
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct AiDailyStats {
+    pub date: String,
+    pub emails_processed: u32,
+    pub avg_confidence: Option<f64>,
+    pub needs_review_count: u32,
+    pub category_breakdown: std::collections::BTreeMap<String, u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AiStats {
+    pub days: Vec<AiDailyStats>,
+    pub totals: AiDailyStats,
+}
+
+/// A daily limit, the default for everyone when it has no user
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UsageLimit {
+    pub user_id: Option<String>,
+    pub kind: String,
+    pub daily_limit: u32,
+}
+
+/// A user's usage today against each of their limits
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UserUsage {
+    pub user_id: String,
+    pub email: String,
+    pub name: Option<String>,
+    pub quotas: Vec<Quota>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UsageOverview {
+    pub users: Vec<UserUsage>,
+    pub limits: Vec<UsageLimit>,
+    pub resets_in: u64,
+}
+
 /// Admin API service
 pub struct AdminService;
 
 impl AdminService {
+    /// Daily AI processing stats of the last `days` days (admin only)
+    pub async fn fetch_ai_stats(days: u32) -> Result<AiStats, ServiceError> {
+        let url = format!("{}/api/admin/ai-stats?days={}", get_api_base_url(), days);
+
+        let response = http_client::get(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<AiStats>(response).await
+        } else if status == 401 || status == 403 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
     /// List all users (admin only)
     pub async fn list_users() -> Result<Vec<User>, ServiceError> {
         let url = format!("{}/api/admin/users", get_api_base_url());
@@ -77,4 +136,89 @@ impl AdminService {
             Err(ServiceError::Server(status, text))
         }
     }
+
+    /// Today's usage of everyone who used anything, with all limits (admin only)
+    pub async fn fetch_usage() -> Result<UsageOverview, ServiceError> {
+        let url = format!("{}/api/admin/usage", get_api_base_url());
+
+        let response = http_client::get(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<UsageOverview>(response).await
+        } else if status == 401 || status == 403 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Set a default limit, or a user's own one; a user's limit without a
+    /// value is removed (admin only)
+    pub async fn set_usage_limit(
+        user_id: Option<&str>,
+        kind: &str,
+        daily_limit: Option<u32>,
+    ) -> Result<(), ServiceError> {
+        let url = format!("{}/api/admin/usage/limits", get_api_base_url());
+
+        let body = serde_json::to_string(&serde_json::json!({
+            "user_id": user_id,
+            "kind": kind,
+            "daily_limit": daily_limit
+        }))
+        .map_err(|e| ServiceError::Parse(format!("Failed to serialize request: {}", e)))?;
+
+        let response = http_client::put(&url, Some(&body)).await?;
+        let status = response.status();
+
+        if status == 200 {
+            Ok(())
+        } else if status == 401 || status == 403 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Add a job source (admin only)
+    pub async fn create_job_source(name: &str) -> Result<JobSource, ServiceError> {
+        let url = format!("{}/api/admin/job-sources", get_api_base_url());
+
+        let body = serde_json::to_string(&serde_json::json!({ "name": name }))
+            .map_err(|e| ServiceError::Parse(format!("Failed to serialize request: {}", e)))?;
+
+        let response = http_client::post(&url, Some(&body)).await?;
+        let status = response.status();
+
+        if status == 201 {
+            http_client::json::<JobSource>(response).await
+        } else if status == 401 || status == 403 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Delete a job source (admin only)
+    pub async fn delete_job_source(id: &str) -> Result<(), ServiceError> {
+        let url = format!("{}/api/admin/job-sources/{}", get_api_base_url(), id);
+
+        let response = http_client::delete(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            Ok(())
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 || status == 403 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
 }
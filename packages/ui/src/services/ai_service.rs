@@ -10,10 +10,28 @@ pub struct AiPrompt {
     pub stage: String,
     pub prompt: String,
     pub is_active: bool,
+    /// Models tried in order; None uses the worker's defaults
+    #[serde(default)]
+    pub models: Option<Vec<String>>,
+    #[serde(default)]
+    pub temperature: Option<f64>,
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
     pub created_at: String,
     pub updated_at: String,
 }
 
+/// Model settings saved with a new prompt
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct PromptModelSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub models: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AiResult {
     pub id: String,
@@ -25,12 +43,144 @@ pub struct AiResult {
     pub job_title: Option<String>,
     pub summary: Option<String>,
     pub extracted_data: Option<String>,
+    /// "ok", or "failed" when a processing stage failed
+    #[serde(default = "default_result_status")]
+    pub status: String,
+    #[serde(default)]
+    pub failed_stage: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+    /// Number of the processing run of the email, starting at 1
+    #[serde(default)]
+    pub version: u32,
+    /// Prompt IDs and models used, keyed by stage
+    #[serde(default)]
+    pub prompt_ids: std::collections::BTreeMap<String, String>,
+    #[serde(default)]
+    pub models: std::collections::BTreeMap<String, String>,
+    /// Earlier results of the email, newest first
+    #[serde(default)]
+    pub history: Vec<AiResult>,
     pub created_at: String,
 }
 
+fn default_result_status() -> String {
+    "ok".to_string()
+}
+
+/// Email drafted by the AI worker
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DraftEmail {
+    pub subject: String,
+    pub body: String,
+}
+
 #[derive(Debug, Serialize)]
-struct ProcessRequest {
-    user_id: String,
+struct DraftRequest {
+    kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply_to_email_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    instructions: Option<String>,
+}
+
+/// Labelled dataset for evaluating prompts
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EvalDataset {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub example_count: i64,
+    pub created_at: String,
+}
+
+/// Email in an evaluation dataset with its expected results
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EvalExample {
+    pub id: String,
+    pub dataset_id: String,
+    pub email_id: Option<String>,
+    pub from_email: Option<String>,
+    pub subject: Option<String>,
+    pub body: Option<String>,
+    pub expected_category: String,
+    pub expected_fields: serde_json::Map<String, serde_json::Value>,
+    pub created_at: String,
+}
+
+/// New dataset example; the content is copied from `email_id` when given
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct NewEvalExample {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    pub expected_category: String,
+    pub expected_fields: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FieldMetrics {
+    pub true_positives: u32,
+    pub false_positives: u32,
+    pub false_negatives: u32,
+    pub precision: Option<f64>,
+    pub recall: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EvalReport {
+    pub total: u32,
+    pub correct: u32,
+    pub accuracy: f64,
+    pub labels: Vec<String>,
+    /// `confusion_matrix[expected][predicted]`, indexed like `labels`
+    pub confusion_matrix: Vec<Vec<u32>>,
+    pub fields: std::collections::BTreeMap<String, FieldMetrics>,
+}
+
+/// A prompt's scored run against a dataset
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EvalRun {
+    pub id: String,
+    pub dataset_id: String,
+    pub prompt_id: String,
+    pub prompt_name: Option<String>,
+    pub stage: String,
+    pub accuracy: Option<f64>,
+    pub report: EvalReport,
+    pub created_at: String,
+}
+
+/// A candidate prompt's run next to the active prompt's run on the same dataset
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EvalComparison {
+    pub candidate: EvalRun,
+    pub baseline: Option<EvalRun>,
+}
+
+/// An email flagged for review with its latest AI result
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReviewItem {
+    pub email_id: String,
+    pub from: Option<String>,
+    pub subject: Option<String>,
+    pub snippet: Option<String>,
+    pub date: Option<String>,
+    pub ai_result_id: String,
+    pub category: Option<String>,
+    pub confidence: Option<f64>,
+    pub summary: Option<String>,
+    pub fields: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Outcome of a review: `accepted` or `corrected`, with the fields that changed
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReviewOutcome {
+    pub status: String,
+    pub corrected_fields: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -38,6 +188,8 @@ struct CreatePromptRequest {
     name: String,
     stage: String,
     prompt: String,
+    #[serde(flatten)]
+    settings: PromptModelSettings,
 }
 
 #[derive(Debug, Serialize)]
@@ -48,65 +200,103 @@ struct ActivatePromptRequest {
 #[derive(Debug, Serialize)]
 struct TestPromptRequest {
     email_ids: Vec<String>,
-    user_id: String,
+    prompts: std::collections::HashMap<String, String>,
 }
 
-/// Get AI worker base URL
-fn get_ai_worker_base_url() -> String {
-    if let Ok(url) = std::env::var("AI_WORKER_URL") {
-        if !url.is_empty() {
-            return url;
-        }
-    }
-
-    #[cfg(feature = "api-prod")]
-    {
-        return "https://applymonitor-ai-processor.workers.dev".to_string();
-    }
+/// One stage of a sandbox prompt test
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StageTrace {
+    pub stage: String,
+    pub rendered_prompt: String,
+    pub raw_output: Option<String>,
+    pub parsed: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
 
-    #[cfg(feature = "api-staging")]
-    {
-        return "https://applymonitor-ai-processor-staging.workers.dev".to_string();
-    }
+/// Sandbox prompt test of one email; `error` is set when the email couldn't be loaded
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SandboxResult {
+    pub email_id: String,
+    pub subject: Option<String>,
+    pub error: Option<String>,
+    #[serde(default)]
+    pub stages: Vec<StageTrace>,
+}
 
-    "http://localhost:8002".to_string()
+#[derive(Debug, Deserialize)]
+struct SandboxResponse {
+    results: Vec<SandboxResult>,
 }
 
 /// AI API service
 pub struct AiService;
 
 impl AiService {
-    /// Process a single email through AI pipeline
-    pub async fn process_email(email_id: &str, user_id: &str) -> Result<(), ServiceError> {
-        let url = format!("{}/process/{}", get_ai_worker_base_url(), email_id);
+    /// Get AI results for an email
+    pub async fn get_ai_results(email_id: &str) -> Result<AiResult, ServiceError> {
+        let url = format!("{}/api/emails/{}/ai-results", get_api_base_url(), email_id);
 
-        let request_body = ProcessRequest {
-            user_id: user_id.to_string(),
-        };
+        let response = http_client::get(&url).await?;
+        let status = response.status();
 
-        let body = serde_json::to_string(&request_body)
-            .map_err(|e| ServiceError::Parse(format!("Failed to serialize request: {}", e)))?;
+        if status == 200 {
+            http_client::json::<AiResult>(response).await
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
 
-        let response = http_client::post(&url, Some(&body)).await?;
+    /// Run an email through the AI again with the current prompts and return
+    /// the new result with the earlier ones as history
+    pub async fn reprocess_email(email_id: &str) -> Result<AiResult, ServiceError> {
+        let url = format!("{}/api/emails/{}/reprocess", get_api_base_url(), email_id);
+
+        let response = http_client::post(&url, None).await?;
         let status = response.status();
 
         if status == 200 {
-            Ok(())
+            http_client::json::<AiResult>(response).await
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
         } else {
             let text = http_client::text(response).await.unwrap_or_default();
             Err(ServiceError::Server(status, text))
         }
     }
 
-    /// Get AI results for an email
-    pub async fn get_ai_results(email_id: &str) -> Result<AiResult, ServiceError> {
-        let url = format!("{}/api/emails/{}/ai-results", get_api_base_url(), email_id);
+    /// Draft a thank-you ("thank_you") or follow-up ("follow_up") email for a
+    /// job, optionally as a reply to one of its emails and following the
+    /// user's instructions ("mention my portfolio", "keep it short")
+    pub async fn draft_email(
+        job_id: &str,
+        kind: &str,
+        reply_to_email_id: Option<String>,
+        instructions: Option<String>,
+    ) -> Result<DraftEmail, ServiceError> {
+        let url = format!("{}/jobs/{}/draft", get_api_base_url(), job_id);
+
+        let request_body = DraftRequest {
+            kind: kind.to_string(),
+            reply_to_email_id,
+            instructions: instructions.filter(|i| !i.trim().is_empty()),
+        };
 
-        let response = http_client::get(&url).await?;
+        let body = serde_json::to_string(&request_body)
+            .map_err(|e| ServiceError::Parse(format!("Failed to serialize request: {}", e)))?;
+
+        let response = http_client::post(&url, Some(&body)).await?;
         let status = response.status();
 
         if status == 200 {
-            http_client::json::<AiResult>(response).await
+            http_client::json::<DraftEmail>(response).await
         } else if status == 404 {
             Err(ServiceError::NotFound)
         } else if status == 401 {
@@ -142,6 +332,7 @@ impl AiService {
         name: &str,
         stage: &str,
         prompt: &str,
+        settings: PromptModelSettings,
     ) -> Result<String, ServiceError> {
         let url = format!("{}/api/admin/prompts", get_api_base_url());
 
@@ -149,6 +340,7 @@ impl AiService {
             name: name.to_string(),
             stage: stage.to_string(),
             prompt: prompt.to_string(),
+            settings,
         };
 
         let body = serde_json::to_string(&request_body)
@@ -200,16 +392,18 @@ impl AiService {
         }
     }
 
-    /// Test a prompt on sample emails (admin only)
+    /// Test a draft prompt on emails without saving anything (admin only).
+    /// The other stages use their active prompts.
     pub async fn test_prompt(
         email_ids: Vec<String>,
-        user_id: &str,
-    ) -> Result<serde_json::Value, ServiceError> {
+        stage: &str,
+        prompt: &str,
+    ) -> Result<Vec<SandboxResult>, ServiceError> {
         let url = format!("{}/api/admin/prompts/test", get_api_base_url());
 
         let request_body = TestPromptRequest {
             email_ids,
-            user_id: user_id.to_string(),
+            prompts: std::collections::HashMap::from([(stage.to_string(), prompt.to_string())]),
         };
 
         let body = serde_json::to_string(&request_body)
@@ -219,7 +413,254 @@ impl AiService {
         let status = response.status();
 
         if status == 200 {
-            http_client::json::<serde_json::Value>(response).await
+            let sandbox = http_client::json::<SandboxResponse>(response).await?;
+            Ok(sandbox.results)
+        } else if status == 401 || status == 403 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// List evaluation datasets (admin only)
+    pub async fn list_eval_datasets() -> Result<Vec<EvalDataset>, ServiceError> {
+        let url = format!("{}/api/admin/eval/datasets", get_api_base_url());
+
+        let response = http_client::get(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<Vec<EvalDataset>>(response).await
+        } else if status == 401 || status == 403 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Create an evaluation dataset (admin only)
+    pub async fn create_eval_dataset(name: &str) -> Result<String, ServiceError> {
+        let url = format!("{}/api/admin/eval/datasets", get_api_base_url());
+
+        let body = serde_json::json!({ "name": name }).to_string();
+
+        let response = http_client::post(&url, Some(&body)).await?;
+        let status = response.status();
+
+        if status == 200 {
+            #[derive(Deserialize)]
+            struct CreateResponse {
+                id: String,
+            }
+            let result: CreateResponse = http_client::json::<CreateResponse>(response).await?;
+            Ok(result.id)
+        } else if status == 401 || status == 403 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// List the examples of an evaluation dataset (admin only)
+    pub async fn list_eval_examples(dataset_id: &str) -> Result<Vec<EvalExample>, ServiceError> {
+        let url = format!(
+            "{}/api/admin/eval/datasets/{}/examples",
+            get_api_base_url(),
+            dataset_id
+        );
+
+        let response = http_client::get(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<Vec<EvalExample>>(response).await
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 || status == 403 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Add a labelled example to an evaluation dataset (admin only)
+    pub async fn add_eval_example(
+        dataset_id: &str,
+        example: NewEvalExample,
+    ) -> Result<(), ServiceError> {
+        let url = format!(
+            "{}/api/admin/eval/datasets/{}/examples",
+            get_api_base_url(),
+            dataset_id
+        );
+
+        let body = serde_json::to_string(&example)
+            .map_err(|e| ServiceError::Parse(format!("Failed to serialize request: {}", e)))?;
+
+        let response = http_client::post(&url, Some(&body)).await?;
+        let status = response.status();
+
+        if status == 200 {
+            Ok(())
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 || status == 403 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Remove an example from an evaluation dataset (admin only)
+    pub async fn delete_eval_example(
+        dataset_id: &str,
+        example_id: &str,
+    ) -> Result<(), ServiceError> {
+        let url = format!(
+            "{}/api/admin/eval/datasets/{}/examples/{}",
+            get_api_base_url(),
+            dataset_id,
+            example_id
+        );
+
+        let response = http_client::delete(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            Ok(())
+        } else if status == 401 || status == 403 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// List past evaluation runs for a dataset, newest first (admin only)
+    pub async fn list_eval_runs(dataset_id: &str) -> Result<Vec<EvalRun>, ServiceError> {
+        let url = format!(
+            "{}/api/admin/eval/runs?dataset_id={}",
+            get_api_base_url(),
+            dataset_id
+        );
+
+        let response = http_client::get(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<Vec<EvalRun>>(response).await
+        } else if status == 401 || status == 403 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Evaluate a prompt against a dataset, next to the active prompt of its
+    /// stage (admin only)
+    pub async fn run_eval(
+        dataset_id: &str,
+        prompt_id: &str,
+    ) -> Result<EvalComparison, ServiceError> {
+        let url = format!("{}/api/admin/eval/runs", get_api_base_url());
+
+        let body = serde_json::json!({
+            "dataset_id": dataset_id,
+            "prompt_id": prompt_id
+        })
+        .to_string();
+
+        let response = http_client::post(&url, Some(&body)).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<EvalComparison>(response).await
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 || status == 403 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Emails whose AI results need review
+    pub async fn list_review_queue() -> Result<Vec<ReviewItem>, ServiceError> {
+        let url = format!("{}/api/ai-reviews", get_api_base_url());
+
+        let response = http_client::get(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<Vec<ReviewItem>>(response).await
+        } else if status == 401 || status == 403 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Accept or correct the AI result of an email
+    pub async fn review_email(
+        email_id: &str,
+        category: &str,
+        fields: serde_json::Map<String, serde_json::Value>,
+    ) -> Result<ReviewOutcome, ServiceError> {
+        let url = format!("{}/api/emails/{}/ai-review", get_api_base_url(), email_id);
+
+        let body = serde_json::json!({
+            "category": category,
+            "fields": fields
+        })
+        .to_string();
+
+        let response = http_client::post(&url, Some(&body)).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<ReviewOutcome>(response).await
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 || status == 403 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Copy reviewed AI results into a dataset; returns how many were added (admin only)
+    pub async fn import_eval_reviews(
+        dataset_id: &str,
+        corrected_only: bool,
+    ) -> Result<u32, ServiceError> {
+        let url = format!(
+            "{}/api/admin/eval/datasets/{}/import-reviews",
+            get_api_base_url(),
+            dataset_id
+        );
+
+        let body = serde_json::json!({ "corrected_only": corrected_only }).to_string();
+
+        let response = http_client::post(&url, Some(&body)).await?;
+        let status = response.status();
+
+        if status == 200 {
+            let result = http_client::json::<serde_json::Value>(response).await?;
+            Ok(result
+                .get("imported")
+                .and_then(|v| v.as_u64())
+                .unwrap_or_default() as u32)
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
         } else if status == 401 || status == 403 {
             Err(ServiceError::Unauthorized)
         } else {
@@ -0,0 +1,107 @@
+//! Analytics API service
+
+use crate::services::{api_config::get_api_base_url, error::ServiceError, http_client};
+use serde::{Deserialize, Serialize};
+
+/// Metrics for one pipeline stage
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StageMetrics {
+    pub status_id: i32,
+    pub name: String,
+    pub display_name: String,
+    pub reached: usize,
+    pub conversion_rate: Option<f64>,
+    pub median_days_in_stage: Option<f64>,
+}
+
+/// Metrics for jobs from one source
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SourceMetrics {
+    pub source: String,
+    pub jobs: usize,
+    pub applied: usize,
+    pub responded: usize,
+    pub response_rate: Option<f64>,
+    /// Jobs that reached each stage, in the order of `FunnelAnalytics::stages`
+    pub stages_reached: Vec<usize>,
+}
+
+/// Applications in a week starting on `week_start` (a Monday)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WeeklyVolume {
+    pub week_start: String,
+    pub applications: usize,
+}
+
+/// Application funnel metrics
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct FunnelAnalytics {
+    pub total_jobs: usize,
+    pub applied: usize,
+    pub rejected: usize,
+    pub stages: Vec<StageMetrics>,
+    pub responded: usize,
+    pub response_rate: Option<f64>,
+    pub median_days_to_first_response: Option<f64>,
+    pub weekly_volume: Vec<WeeklyVolume>,
+    #[serde(default)]
+    pub by_source: Vec<SourceMetrics>,
+}
+
+impl FunnelAnalytics {
+    /// Jobs that reached the stage with the given status name
+    pub fn reached(&self, name: &str) -> usize {
+        self.stages
+            .iter()
+            .find(|s| s.name == name)
+            .map(|s| s.reached)
+            .unwrap_or(0)
+    }
+}
+
+/// Filters for the analytics; dates are `YYYY-MM-DD`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AnalyticsFilter {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub tag: Option<String>,
+}
+
+/// Analytics service
+pub struct AnalyticsService;
+
+impl AnalyticsService {
+    /// Fetch funnel analytics for jobs matching the filter
+    pub async fn fetch_funnel(filter: AnalyticsFilter) -> Result<FunnelAnalytics, ServiceError> {
+        let params: Vec<String> = [
+            ("from", filter.from),
+            ("to", filter.to),
+            ("tag", filter.tag),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| {
+            value.map(|v| {
+                let encoded: String = js_sys::encode_uri_component(&v).into();
+                format!("{}={}", key, encoded)
+            })
+        })
+        .collect();
+        let url = if params.is_empty() {
+            format!("{}/job-analytics", get_api_base_url())
+        } else {
+            format!("{}/job-analytics?{}", get_api_base_url(), params.join("&"))
+        };
+
+        let response = http_client::get(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<FunnelAnalytics>(response).await
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Job attachments API service
+
+use crate::services::{api_config::get_api_base_url, error::ServiceError, http_client};
+use serde::{Deserialize, Serialize};
+
+/// Attachment metadata matching API response
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Attachment {
+    pub id: String,
+    pub job_id: String,
+    pub user_id: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: u64,
+    pub created_at: String,
+}
+
+/// Job attachments API service
+pub struct AttachmentsService;
+
+impl AttachmentsService {
+    /// Fetch attachments for a job
+    pub async fn fetch_attachments(job_id: String) -> Result<Vec<Attachment>, ServiceError> {
+        let url = format!("{}/jobs/{}/attachments", get_api_base_url(), job_id);
+
+        let response = http_client::get(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<Vec<Attachment>>(response).await
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Upload a file as an attachment on a job
+    pub async fn upload_attachment(
+        job_id: String,
+        file_name: String,
+        content_type: String,
+        bytes: Vec<u8>,
+    ) -> Result<Attachment, ServiceError> {
+        let encoded_name: String = js_sys::encode_uri_component(&file_name).into();
+        let url = format!(
+            "{}/jobs/{}/attachments?filename={}",
+            get_api_base_url(),
+            job_id,
+            encoded_name
+        );
+
+        let response = http_client::post_bytes(&url, &bytes, &content_type).await?;
+        let status = response.status();
+
+        if status == 201 || status == 200 {
+            http_client::json::<Attachment>(response).await
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Delete an attachment
+    pub async fn delete_attachment(job_id: String, id: String) -> Result<(), ServiceError> {
+        let url = format!("{}/jobs/{}/attachments/{}", get_api_base_url(), job_id, id);
+
+        let response = http_client::delete(&url).await?;
+        let status = response.status();
+
+        if status == 200 || status == 204 {
+            Ok(())
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// URL that downloads an attachment (authenticated by the session cookie)
+    pub fn download_url(job_id: &str, id: &str) -> String {
+        format!("{}/jobs/{}/attachments/{}", get_api_base_url(), job_id, id)
+    }
+}
@@ -14,12 +14,26 @@ pub struct Comment {
     pub name: Option<String>,
     pub email: Option<String>,
     pub picture: Option<String>,
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub deleted_at: Option<String>,
 }
 
 /// Request struct for creating a comment
 #[derive(Debug, Serialize)]
 pub struct CreateCommentRequest {
     pub content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+}
+
+/// Request struct for editing a comment
+#[derive(Debug, Serialize)]
+pub struct UpdateCommentRequest {
+    pub content: String,
 }
 
 /// Comments API service
@@ -45,11 +59,15 @@ impl CommentsService {
         }
     }
 
-    /// Create a new comment
-    pub async fn create_comment(job_id: String, content: String) -> Result<Comment, ServiceError> {
+    /// Create a new comment, optionally as a reply to `parent_id`
+    pub async fn create_comment(
+        job_id: String,
+        content: String,
+        parent_id: Option<String>,
+    ) -> Result<Comment, ServiceError> {
         let url = format!("{}/jobs/{}/comments", get_api_base_url(), job_id);
 
-        let request = CreateCommentRequest { content };
+        let request = CreateCommentRequest { content, parent_id };
         let body = serde_json::to_string(&request)
             .map_err(|e| ServiceError::Parse(format!("Failed to serialize comment: {}", e)))?;
 
@@ -65,4 +83,63 @@ impl CommentsService {
             Err(ServiceError::Server(status, text))
         }
     }
+
+    /// Edit a comment (author only)
+    pub async fn update_comment(
+        job_id: String,
+        comment_id: String,
+        content: String,
+    ) -> Result<Comment, ServiceError> {
+        let url = format!(
+            "{}/jobs/{}/comments/{}",
+            get_api_base_url(),
+            job_id,
+            comment_id
+        );
+
+        let request = UpdateCommentRequest { content };
+        let body = serde_json::to_string(&request)
+            .map_err(|e| ServiceError::Parse(format!("Failed to serialize comment: {}", e)))?;
+
+        let response = http_client::put(&url, Some(&body)).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<Comment>(response).await
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Delete a comment (author only). Returns the comment marked as deleted.
+    pub async fn delete_comment(
+        job_id: String,
+        comment_id: String,
+    ) -> Result<Comment, ServiceError> {
+        let url = format!(
+            "{}/jobs/{}/comments/{}",
+            get_api_base_url(),
+            job_id,
+            comment_id
+        );
+
+        let response = http_client::delete(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<Comment>(response).await
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
 }
@@ -0,0 +1,154 @@
+//! Companies API service
+
+use crate::services::{api_config::get_api_base_url, error::ServiceError, http_client};
+use serde::{Deserialize, Serialize};
+
+/// Company in the companies list
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Company {
+    pub id: String,
+    pub name: String,
+    pub domain: Option<String>,
+    pub website: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub job_count: i64,
+    #[serde(default)]
+    pub active_job_count: i64,
+    #[serde(default)]
+    pub contact_count: i64,
+    #[serde(default)]
+    pub last_activity: Option<String>,
+}
+
+/// Job at a company
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CompanyJob {
+    pub id: String,
+    pub title: String,
+    pub location: Option<String>,
+    pub status_id: Option<i32>,
+    pub status_name: Option<String>,
+    pub source_name: Option<String>,
+    pub archived_at: Option<String>,
+    pub created_at: Option<String>,
+}
+
+/// Contact at a company
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CompanyContact {
+    pub email: String,
+    pub name: Option<String>,
+    pub linkedin: Option<String>,
+    pub website: Option<String>,
+}
+
+/// Email linked to one of the company's jobs or sent from its domain
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CompanyEmail {
+    pub gmail_id: String,
+    pub subject: Option<String>,
+    pub from: Option<String>,
+    pub date: Option<String>,
+    pub job_id: Option<String>,
+    pub job_title: Option<String>,
+}
+
+/// Number of the company's jobs in a status
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StatusCount {
+    pub status_id: i32,
+    pub status_name: String,
+    pub count: i64,
+}
+
+/// Company page data
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CompanyDetails {
+    pub company: Company,
+    pub jobs: Vec<CompanyJob>,
+    pub contacts: Vec<CompanyContact>,
+    pub emails: Vec<CompanyEmail>,
+    pub outcomes: Vec<StatusCount>,
+}
+
+/// Company fields to update; `None` leaves a field unchanged and an empty
+/// string clears it
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct UpdateCompanyRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub website: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+/// Companies service
+pub struct CompaniesService;
+
+impl CompaniesService {
+    /// Fetch companies that have jobs or contacts
+    pub async fn fetch_companies() -> Result<Vec<Company>, ServiceError> {
+        let url = format!("{}/companies", get_api_base_url());
+
+        let response = http_client::get(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<Vec<Company>>(response).await
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Fetch a company with its jobs, contacts, emails and outcomes
+    pub async fn fetch_company(id: String) -> Result<CompanyDetails, ServiceError> {
+        let url = format!("{}/companies/{}", get_api_base_url(), id);
+
+        let response = http_client::get(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<CompanyDetails>(response).await
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Update a company's name, domain, website or notes
+    pub async fn update_company(
+        id: String,
+        request: UpdateCompanyRequest,
+    ) -> Result<Company, ServiceError> {
+        let url = format!("{}/companies/{}", get_api_base_url(), id);
+
+        let body = serde_json::to_string(&request)
+            .map_err(|e| ServiceError::Parse(format!("Failed to serialize: {}", e)))?;
+
+        let response = http_client::put(&url, Some(&body)).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<Company>(response).await
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+}
@@ -2,7 +2,7 @@
 
 use crate::services::jobs_service::EmailContact;
 use crate::services::{error::ServiceError, http_client};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 /// Request for updating a contact
 #[derive(Debug, Serialize)]
@@ -12,6 +12,93 @@ pub struct UpdateContactRequest {
     pub website: Option<String>,
 }
 
+/// Contact in the contacts list
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ContactSummary {
+    pub email: String,
+    pub name: Option<String>,
+    pub company_id: Option<String>,
+    pub company_name: Option<String>,
+    pub last_contact: Option<String>,
+}
+
+/// Contact who emailed about a job and is waiting on a reply
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StaleContact {
+    pub email: String,
+    pub name: Option<String>,
+    pub company_name: Option<String>,
+    pub last_received: String,
+    pub last_replied: Option<String>,
+    pub days_waiting: i64,
+    pub job_id: Option<String>,
+}
+
+/// Contact details shown on the contact page
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ContactProfile {
+    pub email: String,
+    pub name: Option<String>,
+    pub linkedin: Option<String>,
+    pub website: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    pub company_id: Option<String>,
+    pub company_name: Option<String>,
+}
+
+/// Email exchanged with a contact
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ContactHistoryEmail {
+    pub gmail_id: String,
+    pub subject: Option<String>,
+    pub from: Option<String>,
+    pub snippet: Option<String>,
+    pub date: Option<String>,
+    pub job_id: Option<String>,
+    /// Sent by the contact rather than the user
+    pub inbound: bool,
+}
+
+/// Job a contact is involved in
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ContactJob {
+    pub id: String,
+    pub title: String,
+    pub company: String,
+    pub company_id: Option<String>,
+    pub status_name: Option<String>,
+    /// The contact referred the user for the job
+    pub referred: bool,
+}
+
+/// Relationship history with a contact
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ContactHistory {
+    pub contact: ContactProfile,
+    pub emails: Vec<ContactHistoryEmail>,
+    pub jobs: Vec<ContactJob>,
+    pub first_contact: Option<String>,
+    pub last_contact: Option<String>,
+    pub emails_sent: i64,
+    pub emails_received: i64,
+}
+
+/// Result of a vCard import
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ImportSummary {
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub skipped: usize,
+}
+
+/// Request for updating a contact's notes
+#[derive(Debug, Serialize)]
+pub struct UpdateNotesRequest {
+    pub notes: Option<String>,
+}
+
 /// Email contacts API service
 pub struct EmailContactsService;
 
@@ -145,4 +232,127 @@ impl EmailContactsService {
             Err(ServiceError::Server(status, text))
         }
     }
+
+    /// Get all of the user's contacts
+    pub async fn fetch_contacts() -> Result<Vec<ContactSummary>, ServiceError> {
+        let url = format!("{}/email-contacts", Self::get_api_base_url());
+
+        let response = http_client::get(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<Vec<ContactSummary>>(response).await
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Get contacts without a reply for at least `days` days
+    pub async fn fetch_stale_contacts(days: i64) -> Result<Vec<StaleContact>, ServiceError> {
+        let url = format!(
+            "{}/email-contacts?stale_days={}",
+            Self::get_api_base_url(),
+            days
+        );
+
+        let response = http_client::get(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<Vec<StaleContact>>(response).await
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Get a contact with its emails, jobs and notes
+    pub async fn fetch_contact_history(email: &str) -> Result<ContactHistory, ServiceError> {
+        let encoded = email.replace('@', "%40");
+        let url = format!(
+            "{}/email-contacts/{}?include=history",
+            Self::get_api_base_url(),
+            encoded
+        );
+
+        let response = http_client::get(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<ContactHistory>(response).await
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Save notes on a contact; empty notes clear them
+    pub async fn update_contact_notes(
+        email: &str,
+        notes: String,
+    ) -> Result<ContactHistory, ServiceError> {
+        let encoded = email.replace('@', "%40");
+        let url = format!(
+            "{}/email-contacts/{}/notes",
+            Self::get_api_base_url(),
+            encoded
+        );
+
+        let body = UpdateNotesRequest { notes: Some(notes) };
+        let body_str = serde_json::to_string(&body)
+            .map_err(|e| ServiceError::Parse(format!("Failed to serialize: {}", e)))?;
+
+        let response = http_client::put(&url, Some(&body_str)).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<ContactHistory>(response).await
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// URL that downloads one contact, or all contacts, as a vCard file
+    /// (authenticated by the session cookie)
+    pub fn vcard_url(email: Option<&str>) -> String {
+        match email {
+            Some(email) => format!(
+                "{}/email-contacts/{}?format=vcard",
+                Self::get_api_base_url(),
+                email.replace('@', "%40")
+            ),
+            None => format!("{}/email-contacts?format=vcard", Self::get_api_base_url()),
+        }
+    }
+
+    /// Import contacts from a vCard file
+    pub async fn import_vcards(bytes: Vec<u8>) -> Result<ImportSummary, ServiceError> {
+        let url = format!("{}/email-contacts?action=import", Self::get_api_base_url());
+
+        let response = http_client::post_bytes(&url, &bytes, "text/vcard").await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<ImportSummary>(response).await
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
 }
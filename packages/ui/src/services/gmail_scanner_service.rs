@@ -1,5 +1,6 @@
 //! Gmail Scanner API service
 
+use crate::services::emails_service::StoredEmail;
 use crate::services::{error::ServiceError, http_client};
 use chrono::{Duration, Utc};
 use serde::{Deserialize, Serialize};
@@ -17,6 +18,51 @@ pub struct ScanResponse {
     pub scan_id: String,
     pub emails_found: usize,
     pub messages: Vec<GmailMessage>,
+    /// The scan stopped early at the daily limit of fetched messages
+    #[serde(default)]
+    pub message_limit_reached: bool,
+}
+
+/// Pre-filters narrowing what scans fetch from Gmail
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ScanFilters {
+    pub include_labels: Vec<String>,
+    pub exclude_labels: Vec<String>,
+    pub include_categories: Vec<String>,
+    pub exclude_categories: Vec<String>,
+    pub allow_keywords: Vec<String>,
+    pub deny_keywords: Vec<String>,
+    pub blocked_senders: Vec<String>,
+}
+
+/// The Gmail search a scan would run and how many messages it roughly matches
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct FilterPreview {
+    pub query: String,
+    pub estimated_count: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct FilterPreviewRequest<'a> {
+    filters: &'a ScanFilters,
+}
+
+/// Email to send through Gmail
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct SendEmailRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cc: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    pub body: String,
+    /// Gmail ID of the email being replied to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_to_gmail_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_id: Option<String>,
 }
 
 /// Gmail message structure
@@ -104,10 +150,121 @@ impl GmailScannerService {
         }
     }
 
+    /// Get the user's scan filters
+    pub async fn get_scan_filters() -> Result<ScanFilters, ServiceError> {
+        let url = format!("{}/filters", Self::get_scanner_base_url());
+
+        let response = http_client::get(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<ScanFilters>(response).await
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Save the user's scan filters, returning them as the scanner cleaned them up
+    pub async fn update_scan_filters(filters: &ScanFilters) -> Result<ScanFilters, ServiceError> {
+        let url = format!("{}/filters", Self::get_scanner_base_url());
+
+        let body = serde_json::to_string(filters)
+            .map_err(|e| ServiceError::Parse(format!("Failed to serialize request: {}", e)))?;
+
+        let response = http_client::put(&url, Some(&body)).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<ScanFilters>(response).await
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Preview the search of a scan of the last 7 days with these filters
+    pub async fn preview_scan_filters(
+        filters: &ScanFilters,
+    ) -> Result<FilterPreview, ServiceError> {
+        let url = format!("{}/filters/preview", Self::get_scanner_base_url());
+
+        let body = serde_json::to_string(&FilterPreviewRequest { filters })
+            .map_err(|e| ServiceError::Parse(format!("Failed to serialize request: {}", e)))?;
+
+        let response = http_client::post(&url, Some(&body)).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<FilterPreview>(response).await
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
     /// Get Gmail OAuth URL for connecting Gmail
     pub fn get_gmail_auth_url() -> String {
         format!("{}/auth", Self::get_scanner_base_url())
     }
+
+    /// Get Gmail OAuth URL for granting send access
+    pub fn get_gmail_send_auth_url() -> String {
+        format!("{}/auth?scope=send", Self::get_scanner_base_url())
+    }
+
+    /// Get Gmail OAuth URL for granting label access
+    pub fn get_gmail_modify_auth_url() -> String {
+        format!("{}/auth?scope=modify", Self::get_scanner_base_url())
+    }
+
+    /// Turn writing ApplyMonitor labels back to Gmail on or off. Turning it
+    /// on fails with a 403 server error when label access hasn't been granted.
+    pub async fn set_label_sync(enabled: bool) -> Result<(), ServiceError> {
+        let url = format!("{}/labels", Self::get_scanner_base_url());
+        let body = serde_json::json!({ "enabled": enabled }).to_string();
+
+        let response = http_client::put(&url, Some(&body)).await?;
+        let status = response.status();
+
+        if status == 200 {
+            Ok(())
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Send an email or a reply through Gmail. Fails with a 403 server error
+    /// when send access hasn't been granted.
+    pub async fn send_email(request: SendEmailRequest) -> Result<StoredEmail, ServiceError> {
+        let url = format!("{}/send", Self::get_scanner_base_url());
+
+        let body = serde_json::to_string(&request)
+            .map_err(|e| ServiceError::Parse(format!("Failed to serialize request: {}", e)))?;
+
+        let response = http_client::post(&url, Some(&body)).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<StoredEmail>(response).await
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
 }
 
 /// Gmail connection status
@@ -115,4 +272,13 @@ impl GmailScannerService {
 pub struct GmailStatus {
     pub connected: bool,
     pub expires_at: Option<String>,
+    /// Send access has been granted
+    #[serde(default)]
+    pub can_send: bool,
+    /// Label access has been granted
+    #[serde(default)]
+    pub can_modify: bool,
+    /// ApplyMonitor labels are written back to Gmail
+    #[serde(default)]
+    pub label_sync: bool,
 }
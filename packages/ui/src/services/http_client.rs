@@ -29,6 +29,31 @@ pub async fn patch(url: &str, body: Option<&str>) -> Result<Response, ServiceErr
     request(url, "PATCH", body).await
 }
 
+/// Make a POST request with a raw binary body (e.g. file uploads)
+pub async fn post_bytes(
+    url: &str,
+    bytes: &[u8],
+    content_type: &str,
+) -> Result<Response, ServiceError> {
+    let opts = RequestInit::new();
+    opts.set_method("POST");
+    opts.set_mode(RequestMode::Cors);
+    opts.set_credentials(RequestCredentials::Include);
+
+    let body = js_sys::Uint8Array::from(bytes);
+    opts.set_body(&body);
+
+    let request = Request::new_with_str_and_init(url, &opts)
+        .map_err(|e| ServiceError::Network(format!("Failed to create request: {:?}", e)))?;
+
+    request
+        .headers()
+        .set("Content-Type", content_type)
+        .map_err(|e| ServiceError::Network(format!("Failed to set Content-Type: {:?}", e)))?;
+
+    send(request).await
+}
+
 /// Internal function to make HTTP requests with credentials
 async fn request(url: &str, method: &str, body: Option<&str>) -> Result<Response, ServiceError> {
     let opts = RequestInit::new();
@@ -57,6 +82,11 @@ async fn request(url: &str, method: &str, body: Option<&str>) -> Result<Response
             .map_err(|e| ServiceError::Network(format!("Failed to set Content-Type: {:?}", e)))?;
     }
 
+    send(request).await
+}
+
+/// Dispatch a prepared request through the window's fetch
+async fn send(request: Request) -> Result<Response, ServiceError> {
     let window =
         web_sys::window().ok_or_else(|| ServiceError::Network("No window object".to_string()))?;
 
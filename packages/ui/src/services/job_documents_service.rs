@@ -0,0 +1,100 @@
+//! Job documents API service
+
+use crate::services::{api_config::get_api_base_url, error::ServiceError, http_client};
+use serde::{Deserialize, Serialize};
+
+/// A tailored cover letter and resume suggestions saved against a job
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct JobDocument {
+    pub id: String,
+    pub job_id: String,
+    pub version: i64,
+    pub cover_letter: String,
+    pub resume_suggestions: Vec<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct ResumeBody {
+    resume: Option<String>,
+}
+
+/// Job documents API service
+pub struct JobDocumentsService;
+
+impl JobDocumentsService {
+    /// Fetch a job's document versions, newest first
+    pub async fn fetch_documents(job_id: String) -> Result<Vec<JobDocument>, ServiceError> {
+        let url = format!("{}/jobs/{}/documents", get_api_base_url(), job_id);
+
+        let response = http_client::get(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<Vec<JobDocument>>(response).await
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Generate a new version from the job description and the user's resume
+    pub async fn generate_documents(job_id: String) -> Result<JobDocument, ServiceError> {
+        let url = format!("{}/jobs/{}/documents", get_api_base_url(), job_id);
+
+        let response = http_client::post(&url, Some("{}")).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<JobDocument>(response).await
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Fetch the user's resume text
+    pub async fn fetch_resume() -> Result<Option<String>, ServiceError> {
+        let url = format!("{}/api/settings/resume", get_api_base_url());
+
+        let response = http_client::get(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            Ok(http_client::json::<ResumeBody>(response).await?.resume)
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Save or clear the user's resume text
+    pub async fn update_resume(resume: Option<String>) -> Result<Option<String>, ServiceError> {
+        let url = format!("{}/api/settings/resume", get_api_base_url());
+
+        let body = serde_json::to_string(&ResumeBody { resume })
+            .map_err(|e| ServiceError::Parse(format!("Failed to serialize request: {}", e)))?;
+
+        let response = http_client::put(&url, Some(&body)).await?;
+        let status = response.status();
+
+        if status == 200 {
+            Ok(http_client::json::<ResumeBody>(response).await?.resume)
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+}
@@ -15,6 +15,57 @@ pub struct Job {
     pub description: Option<String>,
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
+    #[serde(default)]
+    pub archived_at: Option<String>,
+    #[serde(default)]
+    pub deleted_at: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub source_id: Option<String>,
+    #[serde(default)]
+    pub source_name: Option<String>,
+    #[serde(default)]
+    pub referrer_email: Option<String>,
+    #[serde(default)]
+    pub referrer_name: Option<String>,
+    #[serde(default)]
+    pub company_id: Option<String>,
+}
+
+/// Which jobs a listing returns
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum JobListView {
+    #[default]
+    Active,
+    Archived,
+    Trash,
+}
+
+impl JobListView {
+    /// Value of the `view` query parameter
+    pub fn as_query(&self) -> &'static str {
+        match self {
+            JobListView::Active => "active",
+            JobListView::Archived => "archived",
+            JobListView::Trash => "trash",
+        }
+    }
+}
+
+/// How long trashed jobs are kept, as configured on the API
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TrashSettings {
+    pub retention_days: u32,
+}
+
+/// "30 days", or a generic phrase while the retention window isn't known
+pub fn retention_period(days: Option<u32>) -> String {
+    match days {
+        Some(1) => "1 day".to_string(),
+        Some(days) => format!("{} days", days),
+        None => "the retention period".to_string(),
+    }
 }
 
 /// Job status struct
@@ -26,6 +77,20 @@ pub struct JobStatus {
     pub description: Option<String>,
 }
 
+/// Where an application came from
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct JobSource {
+    pub id: String,
+    pub name: String,
+}
+
+/// Request struct for setting a job's source and referrer
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UpdateJobSourceRequest {
+    pub source_id: Option<String>,
+    pub referrer_email: Option<String>,
+}
+
 /// Request struct for creating a job
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CreateJobRequest {
@@ -44,13 +109,76 @@ pub struct UpdateJobRequest {
     pub status_id: Option<i32>,
 }
 
+/// Action applied to every selected job in a bulk request
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum BulkAction {
+    SetStatus {
+        status_id: i32,
+    },
+    AddTags {
+        tags: Vec<String>,
+    },
+    RemoveTags {
+        tags: Vec<String>,
+    },
+    Archive,
+    /// Move to the trash
+    Delete,
+    /// Bring back from the archive or the trash
+    Restore,
+    /// Delete permanently
+    Purge,
+}
+
+/// Request struct for bulk job operations
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BulkJobsRequest {
+    pub job_ids: Vec<String>,
+    #[serde(flatten)]
+    pub action: BulkAction,
+}
+
+/// Outcome for a single job in a bulk request
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BulkItemResult {
+    pub job_id: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// Response for bulk job operations
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BulkJobsResponse {
+    pub results: Vec<BulkItemResult>,
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
+/// Suggested duplicate pair matching API response
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DuplicateSuggestion {
+    pub job: Job,
+    pub duplicate: Job,
+    pub score: f64,
+    #[serde(default)]
+    pub shared_threads: u32,
+    #[serde(default)]
+    pub reasons: Vec<String>,
+}
+
 /// Jobs API service
 pub struct JobsService;
 
 impl JobsService {
-    /// Fetch all jobs
+    /// Fetch active jobs
     pub async fn fetch_jobs() -> Result<Vec<Job>, ServiceError> {
-        let url = format!("{}/jobs", get_api_base_url());
+        Self::fetch_jobs_in_view(JobListView::Active).await
+    }
+
+    /// Fetch the jobs in a view (active, archived or trash)
+    pub async fn fetch_jobs_in_view(view: JobListView) -> Result<Vec<Job>, ServiceError> {
+        let url = format!("{}/jobs?view={}", get_api_base_url(), view.as_query());
 
         let response = http_client::get(&url).await?;
         let status = response.status();
@@ -128,7 +256,7 @@ impl JobsService {
         }
     }
 
-    /// Delete a job
+    /// Move a job to the trash
     pub async fn delete_job(id: String) -> Result<(), ServiceError> {
         let url = format!("{}/jobs/{}", get_api_base_url(), id);
 
@@ -147,6 +275,81 @@ impl JobsService {
         }
     }
 
+    /// Permanently delete a job
+    pub async fn purge_job(id: String) -> Result<(), ServiceError> {
+        let url = format!("{}/jobs/{}?permanent=true", get_api_base_url(), id);
+
+        let response = http_client::delete(&url).await?;
+        let status = response.status();
+
+        if status == 200 || status == 204 {
+            Ok(())
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Archive a job
+    pub async fn archive_job(id: String) -> Result<(), ServiceError> {
+        let url = format!("{}/jobs/{}/archive", get_api_base_url(), id);
+
+        let response = http_client::post(&url, None).await?;
+        let status = response.status();
+
+        if status == 200 || status == 204 {
+            Ok(())
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Restore a job from the archive or the trash
+    pub async fn restore_job(id: String) -> Result<(), ServiceError> {
+        let url = format!("{}/jobs/{}/restore", get_api_base_url(), id);
+
+        let response = http_client::post(&url, None).await?;
+        let status = response.status();
+
+        if status == 200 || status == 204 {
+            Ok(())
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Fetch how many days trashed jobs are kept before they're purged
+    pub async fn fetch_trash_retention_days() -> Result<u32, ServiceError> {
+        let url = format!("{}/api/settings/trash", get_api_base_url());
+
+        let response = http_client::get(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            let settings = http_client::json::<TrashSettings>(response).await?;
+            Ok(settings.retention_days)
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
     /// Fetch job details with related data (emails, comments, timeline)
     pub async fn fetch_job_details(id: String) -> Result<JobDetailsApiResponse, ServiceError> {
         let url = format!("{}/jobs/{}?include=details", get_api_base_url(), id);
@@ -192,7 +395,115 @@ impl JobsService {
         }
     }
 
+    /// Apply one action to many jobs
+    pub async fn bulk_update_jobs(
+        request: BulkJobsRequest,
+    ) -> Result<BulkJobsResponse, ServiceError> {
+        let url = format!("{}/job-bulk", get_api_base_url());
+
+        let body = serde_json::to_string(&request)
+            .map_err(|e| ServiceError::Parse(format!("Failed to serialize: {}", e)))?;
+
+        let response = http_client::post(&url, Some(&body)).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<BulkJobsResponse>(response).await
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Fetch suggested duplicates for a job, or for all jobs when `job_id` is `None`
+    pub async fn fetch_duplicates(
+        job_id: Option<String>,
+    ) -> Result<Vec<DuplicateSuggestion>, ServiceError> {
+        let url = match job_id {
+            Some(id) => format!("{}/jobs/{}/duplicates", get_api_base_url(), id),
+            None => format!("{}/job-duplicates", get_api_base_url()),
+        };
+
+        let response = http_client::get(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<Vec<DuplicateSuggestion>>(response).await
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Merge `duplicate_id` into the job with `id`, returning the merged job
+    pub async fn merge_job(id: String, duplicate_id: String) -> Result<Job, ServiceError> {
+        let url = format!("{}/jobs/{}/merge", get_api_base_url(), id);
+
+        let body = serde_json::json!({ "duplicate_id": duplicate_id });
+        let body_str = serde_json::to_string(&body)
+            .map_err(|e| ServiceError::Parse(format!("Failed to serialize: {}", e)))?;
+
+        let response = http_client::post(&url, Some(&body_str)).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<Job>(response).await
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
     /// Fetch all job statuses
+    pub async fn fetch_job_sources() -> Result<Vec<JobSource>, ServiceError> {
+        let url = format!("{}/job-sources", get_api_base_url());
+
+        let response = http_client::get(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<Vec<JobSource>>(response).await
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
+    /// Set the source and referrer of a job
+    pub async fn update_job_source(
+        id: String,
+        request: UpdateJobSourceRequest,
+    ) -> Result<Job, ServiceError> {
+        let url = format!("{}/jobs/{}/source", get_api_base_url(), id);
+
+        let body = serde_json::to_string(&request)
+            .map_err(|e| ServiceError::Parse(format!("Failed to serialize: {}", e)))?;
+
+        let response = http_client::put(&url, Some(&body)).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<Job>(response).await
+        } else if status == 404 {
+            Err(ServiceError::NotFound)
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+
     pub async fn fetch_job_statuses() -> Result<Vec<JobStatus>, ServiceError> {
         let url = format!("{}/job-statuses", get_api_base_url());
 
@@ -2,24 +2,34 @@
 
 pub mod admin_service;
 pub mod ai_service;
+pub mod analytics_service;
 pub mod api_config;
+pub mod attachments_service;
 pub mod auth_service;
 pub mod comments_service;
+pub mod companies_service;
 pub mod email_contacts_service;
 pub mod emails_service;
 pub mod error;
 pub mod gmail_scanner_service;
 pub mod http_client;
+pub mod job_documents_service;
 pub mod jobs_service;
+pub mod usage_service;
 
 pub use admin_service::*;
 pub use ai_service::*;
+pub use analytics_service::*;
 pub use api_config::*;
+pub use attachments_service::*;
 pub use auth_service::*;
 pub use comments_service::*;
+pub use companies_service::*;
 pub use email_contacts_service::*;
 pub use emails_service::*;
 pub use error::*;
 pub use gmail_scanner_service::*;
 pub use http_client::*;
+pub use job_documents_service::*;
 pub use jobs_service::*;
+pub use usage_service::*;
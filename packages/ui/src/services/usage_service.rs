@@ -0,0 +1,57 @@
+//! Usage of the daily limits on scans, fetched messages and AI calls
+
+use crate::services::{api_config::get_api_base_url, error::ServiceError, http_client};
+use serde::{Deserialize, Serialize};
+
+/// A daily limit of one kind of usage and what's been used of it today
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Quota {
+    pub kind: String,
+    pub limit: u32,
+    pub used: u32,
+}
+
+/// The user's usage today
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UsageSummary {
+    pub quotas: Vec<Quota>,
+    /// Seconds until the limits start over at midnight UTC
+    pub resets_in: u64,
+}
+
+/// How a kind of usage is labelled
+pub fn describe_kind(kind: &str) -> &str {
+    match kind {
+        "scans" => "Gmail scans",
+        "messages" => "Fetched messages",
+        "ai_calls" => "AI calls",
+        other => other,
+    }
+}
+
+/// "3h 20m" until the limits reset
+pub fn format_resets_in(seconds: u64) -> String {
+    format!("{}h {}m", seconds / 3600, seconds % 3600 / 60)
+}
+
+/// Usage API service
+pub struct UsageService;
+
+impl UsageService {
+    /// Fetch the user's usage of their daily limits
+    pub async fn fetch_usage() -> Result<UsageSummary, ServiceError> {
+        let url = format!("{}/api/settings/usage", get_api_base_url());
+
+        let response = http_client::get(&url).await?;
+        let status = response.status();
+
+        if status == 200 {
+            http_client::json::<UsageSummary>(response).await
+        } else if status == 401 {
+            Err(ServiceError::Unauthorized)
+        } else {
+            let text = http_client::text(response).await.unwrap_or_default();
+            Err(ServiceError::Server(status, text))
+        }
+    }
+}
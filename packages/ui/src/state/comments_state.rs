@@ -59,8 +59,8 @@ impl CommentsState {
         });
     }
 
-    /// Add a new comment
-    pub fn add_comment(&self, job_id: String, content: String) {
+    /// Add a new comment, or a reply when `parent_id` is set
+    pub fn add_comment(&self, job_id: String, content: String, parent_id: Option<String>) {
         let mut comments = self.comments;
         let mut loading = self.loading;
         let mut error = self.error;
@@ -69,7 +69,7 @@ impl CommentsState {
             *loading.write() = true;
             *error.write() = None;
 
-            match CommentsService::create_comment(job_id, content).await {
+            match CommentsService::create_comment(job_id, content, parent_id).await {
                 Ok(new_comment) => {
                     // Add comment to the beginning of the list
                     let mut comments_list = comments.read().clone();
@@ -86,9 +86,55 @@ impl CommentsState {
         });
     }
 
+    /// Edit an existing comment
+    pub fn edit_comment(&self, job_id: String, comment_id: String, content: String) {
+        let mut comments = self.comments;
+        let mut error = self.error;
+
+        spawn(async move {
+            *error.write() = None;
+
+            match CommentsService::update_comment(job_id, comment_id, content).await {
+                Ok(updated) => {
+                    replace_comment(&mut comments, updated);
+                }
+                Err(e) => {
+                    *error.write() = Some(e);
+                }
+            }
+        });
+    }
+
+    /// Delete a comment (kept in the list as a deleted placeholder)
+    pub fn delete_comment(&self, job_id: String, comment_id: String) {
+        let mut comments = self.comments;
+        let mut error = self.error;
+
+        spawn(async move {
+            *error.write() = None;
+
+            match CommentsService::delete_comment(job_id, comment_id).await {
+                Ok(deleted) => {
+                    replace_comment(&mut comments, deleted);
+                }
+                Err(e) => {
+                    *error.write() = Some(e);
+                }
+            }
+        });
+    }
+
     /// Set comments (used when loading from job details)
     pub fn set_comments(&self, new_comments: Vec<Comment>) {
         let mut comments = self.comments;
         *comments.write() = new_comments;
     }
 }
+
+/// Replace a comment in the list with an updated copy from the API
+fn replace_comment(comments: &mut Signal<Vec<Comment>>, updated: Comment) {
+    let mut list = comments.write();
+    if let Some(existing) = list.iter_mut().find(|c| c.id == updated.id) {
+        *existing = updated;
+    }
+}
@@ -4,7 +4,9 @@ use crate::services::{
     comments_service::Comment,
     emails_service::StoredEmail,
     error::ServiceError,
-    jobs_service::{CreateJobRequest, Job, JobDetails, JobStatus, JobsService, UpdateJobRequest},
+    jobs_service::{
+        CreateJobRequest, Job, JobDetails, JobListView, JobStatus, JobsService, UpdateJobRequest,
+    },
 };
 use crate::state::{CommentsState, EmailContactsState, EmailsState};
 use dioxus::prelude::*;
@@ -47,8 +49,13 @@ pub fn use_jobs() -> JobsState {
 }
 
 impl JobsState {
-    /// Fetch all jobs
+    /// Fetch active jobs
     pub fn fetch_jobs(&self) {
+        self.fetch_jobs_in_view(JobListView::Active);
+    }
+
+    /// Fetch the jobs in a view (active, archived or trash)
+    pub fn fetch_jobs_in_view(&self, view: JobListView) {
         let mut jobs = self.jobs;
         let mut loading = self.loading;
         let mut error = self.error;
@@ -57,7 +64,7 @@ impl JobsState {
             *loading.write() = true;
             *error.write() = None;
 
-            match JobsService::fetch_jobs().await {
+            match JobsService::fetch_jobs_in_view(view).await {
                 Ok(fetched_jobs) => {
                     *jobs.write() = fetched_jobs;
                     *error.write() = None;
@@ -160,7 +167,7 @@ impl JobsState {
         });
     }
 
-    /// Delete a job
+    /// Move a job to the trash and remove it from the current list
     pub fn delete_job(&self, id: String) {
         let mut jobs = self.jobs;
         let mut loading = self.loading;
@@ -187,6 +194,87 @@ impl JobsState {
         });
     }
 
+    /// Permanently delete a job and remove it from the current list
+    pub fn purge_job(&self, id: String) {
+        let mut jobs = self.jobs;
+        let mut loading = self.loading;
+        let mut error = self.error;
+        let id_clone = id.clone();
+
+        spawn(async move {
+            *loading.write() = true;
+            *error.write() = None;
+
+            match JobsService::purge_job(id).await {
+                Ok(_) => {
+                    let mut jobs_list = jobs.read().clone();
+                    jobs_list.retain(|j| j.id != Some(id_clone.clone()));
+                    *jobs.write() = jobs_list;
+                    *error.write() = None;
+                }
+                Err(e) => {
+                    *error.write() = Some(e);
+                }
+            }
+
+            *loading.write() = false;
+        });
+    }
+
+    /// Archive a job and remove it from the current list
+    pub fn archive_job(&self, id: String) {
+        let mut jobs = self.jobs;
+        let mut loading = self.loading;
+        let mut error = self.error;
+        let id_clone = id.clone();
+
+        spawn(async move {
+            *loading.write() = true;
+            *error.write() = None;
+
+            match JobsService::archive_job(id).await {
+                Ok(_) => {
+                    let mut jobs_list = jobs.read().clone();
+                    jobs_list.retain(|j| j.id != Some(id_clone.clone()));
+                    *jobs.write() = jobs_list;
+                    *error.write() = None;
+                }
+                Err(e) => {
+                    *error.write() = Some(e);
+                }
+            }
+
+            *loading.write() = false;
+        });
+    }
+
+    /// Restore an archived or trashed job and remove it from the current list
+    pub fn restore_job(&self, id: String) {
+        let mut jobs = self.jobs;
+        let mut loading = self.loading;
+        let mut error = self.error;
+        let id_clone = id.clone();
+
+        spawn(async move {
+            *loading.write() = true;
+            *error.write() = None;
+
+            match JobsService::restore_job(id).await {
+                Ok(_) => {
+                    let mut jobs_list = jobs.read().clone();
+                    jobs_list.retain(|j| j.id != Some(id_clone.clone()));
+                    *jobs.write() = jobs_list;
+                    *error.write() = None;
+                }
+                Err(e) => {
+                    *error.write() = Some(e);
+                }
+            }
+
+            *loading.write() = false;
+        });
+    }
+
     /// Fetch job details with related data and route to appropriate states
     pub fn fetch_job_details(
         &self,
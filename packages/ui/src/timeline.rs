@@ -1,5 +1,6 @@
 //! Timeline component for displaying job events
 
+use crate::components::markdown::Markdown;
 use crate::state::use_auth;
 use crate::utils::format_relative_time;
 use dioxus::prelude::*;
@@ -12,6 +13,9 @@ pub enum TimelineEventType {
     StatusChanged,
     EmailReceived,
     CommentAdded,
+    CommentEdited,
+    CommentDeleted,
+    JobMerged,
 }
 
 impl TimelineEventType {
@@ -21,6 +25,9 @@ impl TimelineEventType {
             "status_changed" => TimelineEventType::StatusChanged,
             "email_received" => TimelineEventType::EmailReceived,
             "comment_added" => TimelineEventType::CommentAdded,
+            "comment_edited" => TimelineEventType::CommentEdited,
+            "comment_deleted" => TimelineEventType::CommentDeleted,
+            "job_merged" => TimelineEventType::JobMerged,
             _ => TimelineEventType::JobCreated,
         }
     }
@@ -31,6 +38,9 @@ impl TimelineEventType {
             TimelineEventType::StatusChanged => "changed the status",
             TimelineEventType::EmailReceived => "received an email",
             TimelineEventType::CommentAdded => "commented",
+            TimelineEventType::CommentEdited => "edited a comment",
+            TimelineEventType::CommentDeleted => "deleted a comment",
+            TimelineEventType::JobMerged => "merged a duplicate job",
         }
     }
 }
@@ -85,6 +95,16 @@ fn TimelineEvent(event: Value, is_last: bool) -> Element {
             let content = data.get("content").and_then(|v| v.as_str()).unwrap_or("");
             let user_name = data.get("user_name").and_then(|v| v.as_str());
             let user_picture = data.get("user_picture").and_then(|v| v.as_str());
+            let is_reply = data.get("parent_id").and_then(|v| v.as_str()).is_some();
+            let edited = data
+                .get("edited")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let deleted = data
+                .get("deleted")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let action = if is_reply { " replied" } else { " commented" };
 
             rsx! {
                 li {
@@ -119,9 +139,16 @@ fn TimelineEvent(event: Value, is_last: bool) -> Element {
                                         class: "font-medium text-gray-900 dark:text-white",
                                         {name}
                                     }
-                                    " commented"
+                                    {action}
                                 } else {
-                                    "Someone commented"
+                                    "Someone"
+                                    {action}
+                                }
+                                if edited && !deleted {
+                                    span {
+                                        class: "ml-1 italic",
+                                        "(edited)"
+                                    }
                                 }
                             }
                             time {
@@ -130,9 +157,16 @@ fn TimelineEvent(event: Value, is_last: bool) -> Element {
                                 {relative_time}
                             }
                         }
-                        p {
-                            class: "text-sm/6 text-gray-500 dark:text-gray-400",
-                            {content}
+                        if deleted {
+                            p {
+                                class: "text-sm/6 italic text-gray-400 dark:text-gray-500",
+                                "This comment was deleted"
+                            }
+                        } else {
+                            Markdown {
+                                content: content.to_string(),
+                                class: "text-sm/6 text-gray-500 dark:text-gray-400",
+                            }
                         }
                     }
                 }
@@ -153,8 +187,23 @@ fn TimelineEvent(event: Value, is_last: bool) -> Element {
                     let subject = data.get("subject").and_then(|v| v.as_str()).unwrap_or("");
                     format!("received email: {}", subject)
                 }
+                TimelineEventType::JobMerged => {
+                    let title = data.get("title").and_then(|v| v.as_str()).unwrap_or("");
+                    let company = data.get("company").and_then(|v| v.as_str()).unwrap_or("");
+                    format!("merged duplicate job: {} at {}", title, company)
+                }
                 _ => label.to_string(),
             };
+            let actor = match event_type {
+                TimelineEventType::CommentEdited
+                | TimelineEventType::CommentDeleted
+                | TimelineEventType::JobMerged => data
+                    .get("user_name")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("Someone")
+                    .to_string(),
+                _ => "Saved".to_string(),
+            };
 
             rsx! {
                 li {
@@ -199,7 +248,7 @@ fn TimelineEvent(event: Value, is_last: bool) -> Element {
                         class: "flex-auto py-0.5 text-xs/5 text-gray-500 dark:text-gray-400",
                         span {
                             class: "font-medium text-gray-900 dark:text-white",
-                            "Saved"
+                            {actor}
                         }
                         " {display_text}"
                     }
@@ -0,0 +1,220 @@
+//! Minimal Markdown parsing for user-written text (comments)
+//!
+//! Supports paragraphs, headings, bullet and numbered lists, block quotes,
+//! fenced code blocks, and inline bold, italic, code and links. Output is a
+//! small AST that components render as elements, so raw HTML in the source
+//! is never interpreted.
+
+/// Block-level Markdown element
+#[derive(Debug, Clone, PartialEq)]
+pub enum MarkdownBlock {
+    /// Consecutive lines of text (rendered with line breaks between them)
+    Paragraph(Vec<Vec<MarkdownInline>>),
+    Heading(u8, Vec<MarkdownInline>),
+    List {
+        ordered: bool,
+        items: Vec<Vec<MarkdownInline>>,
+    },
+    Quote(Vec<MarkdownInline>),
+    CodeBlock(String),
+}
+
+/// Inline Markdown element
+#[derive(Debug, Clone, PartialEq)]
+pub enum MarkdownInline {
+    Text(String),
+    Bold(String),
+    Italic(String),
+    Code(String),
+    Link { text: String, url: String },
+}
+
+/// Parse Markdown source into blocks
+pub fn parse_markdown(source: &str) -> Vec<MarkdownBlock> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<Vec<MarkdownInline>> = Vec::new();
+    let mut list: Option<(bool, Vec<Vec<MarkdownInline>>)> = None;
+    let mut lines = source.lines();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```") {
+            flush_paragraph(&mut blocks, &mut paragraph);
+            flush_list(&mut blocks, &mut list);
+            let mut code = Vec::new();
+            for code_line in lines.by_ref() {
+                if code_line.trim().starts_with("```") {
+                    break;
+                }
+                code.push(code_line);
+            }
+            blocks.push(MarkdownBlock::CodeBlock(code.join("\n")));
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            flush_paragraph(&mut blocks, &mut paragraph);
+            flush_list(&mut blocks, &mut list);
+            continue;
+        }
+
+        if let Some((ordered, item)) = list_item(trimmed) {
+            flush_paragraph(&mut blocks, &mut paragraph);
+            if list.as_ref().is_some_and(|(o, _)| *o != ordered) {
+                flush_list(&mut blocks, &mut list);
+            }
+            list.get_or_insert_with(|| (ordered, Vec::new()))
+                .1
+                .push(parse_inline(item));
+            continue;
+        }
+
+        flush_list(&mut blocks, &mut list);
+
+        if let Some((level, text)) = heading(trimmed) {
+            flush_paragraph(&mut blocks, &mut paragraph);
+            blocks.push(MarkdownBlock::Heading(level, parse_inline(text)));
+        } else if let Some(rest) = trimmed.strip_prefix('>') {
+            flush_paragraph(&mut blocks, &mut paragraph);
+            blocks.push(MarkdownBlock::Quote(parse_inline(rest.trim())));
+        } else {
+            paragraph.push(parse_inline(trimmed));
+        }
+    }
+
+    flush_paragraph(&mut blocks, &mut paragraph);
+    flush_list(&mut blocks, &mut list);
+    blocks
+}
+
+/// Parse inline formatting within a single line
+pub fn parse_inline(text: &str) -> Vec<MarkdownInline> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut inlines = Vec::new();
+    let mut buffer = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c == '`' {
+            if let Some(end) = find_char(&chars, i + 1, '`') {
+                flush_text(&mut inlines, &mut buffer);
+                inlines.push(MarkdownInline::Code(collect(&chars[i + 1..end])));
+                i = end + 1;
+                continue;
+            }
+        } else if c == '*' && chars.get(i + 1) == Some(&'*') {
+            if let Some(end) = find_double_star(&chars, i + 2) {
+                if end > i + 2 {
+                    flush_text(&mut inlines, &mut buffer);
+                    inlines.push(MarkdownInline::Bold(collect(&chars[i + 2..end])));
+                    i = end + 2;
+                    continue;
+                }
+            }
+        } else if c == '*' || (c == '_' && (i == 0 || !chars[i - 1].is_alphanumeric())) {
+            if let Some(end) = find_char(&chars, i + 1, c) {
+                if end > i + 1 {
+                    flush_text(&mut inlines, &mut buffer);
+                    inlines.push(MarkdownInline::Italic(collect(&chars[i + 1..end])));
+                    i = end + 1;
+                    continue;
+                }
+            }
+        } else if c == '[' {
+            if let Some(close) = find_char(&chars, i + 1, ']') {
+                if chars.get(close + 1) == Some(&'(') {
+                    if let Some(paren) = find_char(&chars, close + 2, ')') {
+                        let url = collect(&chars[close + 2..paren]).trim().to_string();
+                        if is_safe_url(&url) {
+                            flush_text(&mut inlines, &mut buffer);
+                            inlines.push(MarkdownInline::Link {
+                                text: collect(&chars[i + 1..close]),
+                                url,
+                            });
+                            i = paren + 1;
+                            continue;
+                        }
+                    }
+                }
+            }
+        }
+
+        buffer.push(c);
+        i += 1;
+    }
+
+    flush_text(&mut inlines, &mut buffer);
+    inlines
+}
+
+/// Only allow links that can't execute script
+fn is_safe_url(url: &str) -> bool {
+    let lower = url.to_lowercase();
+    lower.starts_with("https://") || lower.starts_with("http://") || lower.starts_with("mailto:")
+}
+
+fn list_item(line: &str) -> Option<(bool, &str)> {
+    if let Some(rest) = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))
+    {
+        return Some((false, rest.trim()));
+    }
+
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        if let Some(rest) = line[digits..].strip_prefix(". ") {
+            return Some((true, rest.trim()));
+        }
+    }
+
+    None
+}
+
+fn heading(line: &str) -> Option<(u8, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if (1..=3).contains(&level) {
+        line[level..]
+            .strip_prefix(' ')
+            .map(|text| (level as u8, text.trim()))
+    } else {
+        None
+    }
+}
+
+fn flush_paragraph(blocks: &mut Vec<MarkdownBlock>, paragraph: &mut Vec<Vec<MarkdownInline>>) {
+    if !paragraph.is_empty() {
+        blocks.push(MarkdownBlock::Paragraph(std::mem::take(paragraph)));
+    }
+}
+
+fn flush_list(
+    blocks: &mut Vec<MarkdownBlock>,
+    list: &mut Option<(bool, Vec<Vec<MarkdownInline>>)>,
+) {
+    if let Some((ordered, items)) = list.take() {
+        blocks.push(MarkdownBlock::List { ordered, items });
+    }
+}
+
+fn flush_text(inlines: &mut Vec<MarkdownInline>, buffer: &mut String) {
+    if !buffer.is_empty() {
+        inlines.push(MarkdownInline::Text(std::mem::take(buffer)));
+    }
+}
+
+fn find_char(chars: &[char], from: usize, target: char) -> Option<usize> {
+    (from..chars.len()).find(|&idx| chars[idx] == target)
+}
+
+fn find_double_star(chars: &[char], from: usize) -> Option<usize> {
+    (from..chars.len().saturating_sub(1)).find(|&idx| chars[idx] == '*' && chars[idx + 1] == '*')
+}
+
+fn collect(chars: &[char]) -> String {
+    chars.iter().collect()
+}
@@ -2,6 +2,8 @@
 
 pub mod date;
 pub mod email;
+pub mod markdown;
 
 pub use date::*;
 pub use email::*;
+pub use markdown::*;
@@ -0,0 +1,102 @@
+//! Tests for rendering comment Markdown
+
+use ui::utils::markdown::{parse_inline, parse_markdown, MarkdownBlock, MarkdownInline};
+
+fn text(value: &str) -> MarkdownInline {
+    MarkdownInline::Text(value.to_string())
+}
+
+#[test]
+fn test_blocks_are_parsed() {
+    let source = "## Notes\n\
+                  First line\nsecond line\n\
+                  \n\
+                  - one\n- two\n\
+                  1. first\n2. second\n\
+                  > quoted\n\
+                  ```\nlet x = **1**;\n```";
+
+    assert_eq!(
+        parse_markdown(source),
+        vec![
+            MarkdownBlock::Heading(2, vec![text("Notes")]),
+            MarkdownBlock::Paragraph(vec![vec![text("First line")], vec![text("second line")]]),
+            MarkdownBlock::List {
+                ordered: false,
+                items: vec![vec![text("one")], vec![text("two")]],
+            },
+            MarkdownBlock::List {
+                ordered: true,
+                items: vec![vec![text("first")], vec![text("second")]],
+            },
+            MarkdownBlock::Quote(vec![text("quoted")]),
+            MarkdownBlock::CodeBlock("let x = **1**;".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_inline_formatting_is_parsed() {
+    assert_eq!(
+        parse_inline("**bold**, *italic*, _also_ and `code` in snake_case_name"),
+        vec![
+            MarkdownInline::Bold("bold".to_string()),
+            text(", "),
+            MarkdownInline::Italic("italic".to_string()),
+            text(", "),
+            MarkdownInline::Italic("also".to_string()),
+            text(" and "),
+            MarkdownInline::Code("code".to_string()),
+            text(" in snake_case_name"),
+        ]
+    );
+}
+
+#[test]
+fn test_safe_links_are_parsed() {
+    assert_eq!(
+        parse_inline(
+            "See [the posting](https://example.com/jobs/1) or [mail](mailto:hr@example.com)"
+        ),
+        vec![
+            text("See "),
+            MarkdownInline::Link {
+                text: "the posting".to_string(),
+                url: "https://example.com/jobs/1".to_string(),
+            },
+            text(" or "),
+            MarkdownInline::Link {
+                text: "mail".to_string(),
+                url: "mailto:hr@example.com".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_unsafe_links_stay_text() {
+    for source in [
+        "[click](javascript:alert(1))",
+        "[click](JavaScript:alert(1))",
+        "[click](data:text/html,<script>alert(1)</script>)",
+    ] {
+        let inlines = parse_inline(source);
+        assert!(
+            inlines
+                .iter()
+                .all(|inline| !matches!(inline, MarkdownInline::Link { .. })),
+            "{} was parsed as a link",
+            source
+        );
+    }
+}
+
+#[test]
+fn test_html_is_never_interpreted() {
+    assert_eq!(
+        parse_markdown("<script>alert('x')</script> <b>hi</b>"),
+        vec![MarkdownBlock::Paragraph(vec![vec![text(
+            "<script>alert('x')</script> <b>hi</b>"
+        )]])]
+    );
+}
@@ -1,11 +1,17 @@
 use dioxus::prelude::*;
 use dioxus_free_icons::{
-    icons::bs_icons::{BsBriefcase, BsEnvelope, BsGear, BsHouse, BsShieldCheck},
+    icons::bs_icons::{
+        BsBriefcase, BsBuilding, BsClipboardCheck, BsEnvelope, BsGear, BsHouse, BsPeople,
+        BsShieldCheck,
+    },
     Icon,
 };
 
 use ui::{state::use_jobs_provider, use_auth_provider, SidebarLayout};
-use views::{Accounts, Admin, Blog, Dashboard, Emails, Home, JobDetails, Jobs, Login};
+use views::{
+    Accounts, Admin, Blog, Companies, CompanyDetails, ContactDetails, Contacts, Dashboard, Emails,
+    Home, JobDetails, Jobs, Login, Review,
+};
 
 mod views;
 
@@ -25,8 +31,18 @@ pub enum Route {
     Jobs {},
     #[route("/jobs/:id")]
     JobDetails { id: String },
+    #[route("/companies")]
+    Companies {},
+    #[route("/companies/:id")]
+    CompanyDetails { id: String },
+    #[route("/contacts")]
+    Contacts {},
+    #[route("/contacts/:email")]
+    ContactDetails { email: String },
     #[route("/emails")]
     Emails {},
+    #[route("/review")]
+    Review {},
     #[route("/settings/accounts")]
     Accounts {},
     #[route("/admin")]
@@ -126,6 +142,30 @@ fn WebSidebar() -> Element {
                             }
                             "Jobs"
                         }
+                        Link {
+                            to: Route::Companies {},
+                            class: "group flex gap-x-3 rounded-md p-2 text-sm leading-6 font-semibold text-gray-700 dark:text-gray-300 hover:text-brand-600 dark:hover:text-brand-400 hover:bg-gray-50 dark:hover:bg-gray-800",
+                            Icon {
+                                class: "h-6 w-6 shrink-0",
+                                width: 24,
+                                height: 24,
+                                fill: "currentColor",
+                                icon: BsBuilding,
+                            }
+                            "Companies"
+                        }
+                        Link {
+                            to: Route::Contacts {},
+                            class: "group flex gap-x-3 rounded-md p-2 text-sm leading-6 font-semibold text-gray-700 dark:text-gray-300 hover:text-brand-600 dark:hover:text-brand-400 hover:bg-gray-50 dark:hover:bg-gray-800",
+                            Icon {
+                                class: "h-6 w-6 shrink-0",
+                                width: 24,
+                                height: 24,
+                                fill: "currentColor",
+                                icon: BsPeople,
+                            }
+                            "Contacts"
+                        }
                         Link {
                             to: Route::Emails {},
                             class: "group flex gap-x-3 rounded-md p-2 text-sm leading-6 font-semibold text-gray-700 dark:text-gray-300 hover:text-brand-600 dark:hover:text-brand-400 hover:bg-gray-50 dark:hover:bg-gray-800",
@@ -138,6 +178,18 @@ fn WebSidebar() -> Element {
                             }
                             "Emails"
                         }
+                        Link {
+                            to: Route::Review {},
+                            class: "group flex gap-x-3 rounded-md p-2 text-sm leading-6 font-semibold text-gray-700 dark:text-gray-300 hover:text-brand-600 dark:hover:text-brand-400 hover:bg-gray-50 dark:hover:bg-gray-800",
+                            Icon {
+                                class: "h-6 w-6 shrink-0",
+                                width: 24,
+                                height: 24,
+                                fill: "currentColor",
+                                icon: BsClipboardCheck,
+                            }
+                            "Review"
+                        }
                         Link {
                             to: Route::Accounts {},
                             class: "group flex gap-x-3 rounded-md p-2 text-sm leading-6 font-semibold text-gray-700 dark:text-gray-300 hover:text-brand-600 dark:hover:text-brand-400 hover:bg-gray-50 dark:hover:bg-gray-800",
@@ -2,7 +2,11 @@ use crate::Route;
 use dioxus::prelude::*;
 use dioxus_router::use_navigator;
 use ui::{
-    components::{account_linking::AccountLinking, timezone_settings::TimezoneSettings},
+    components::{
+        account_linking::AccountLinking, resume_settings::ResumeSettings,
+        scan_filter_settings::ScanFilterSettings, timezone_settings::TimezoneSettings,
+        usage_settings::UsageSettings,
+    },
     use_auth,
 };
 
@@ -86,10 +90,25 @@ pub fn Accounts() -> Element {
 
             AccountLinking {}
 
+            div {
+                class: "border-t border-gray-200 dark:border-white/5",
+                ScanFilterSettings {}
+            }
+
             div {
                 class: "border-t border-gray-200 dark:border-white/5",
                 TimezoneSettings {}
             }
+
+            div {
+                class: "border-t border-gray-200 dark:border-white/5",
+                ResumeSettings {}
+            }
+
+            div {
+                class: "border-t border-gray-200 dark:border-white/5",
+                UsageSettings {}
+            }
         }
     }
 }
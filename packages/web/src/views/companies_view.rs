@@ -0,0 +1,46 @@
+//! Web-specific companies view
+
+use crate::Route;
+use dioxus::prelude::*;
+use dioxus_router::use_navigator;
+use ui::{use_auth, CompaniesList};
+
+/// Web-specific companies view wrapper
+#[component]
+pub fn Companies() -> Element {
+    let auth = use_auth();
+    let navigator = use_navigator();
+
+    // Fetch user on mount
+    use_effect(move || {
+        auth.fetch_user();
+    });
+
+    // Redirect to login if not authenticated
+    use_effect(move || {
+        let user = auth.user;
+        let loading = auth.loading;
+
+        if !loading() && user().is_none() {
+            navigator.push(Route::Login {});
+        }
+    });
+
+    rsx! {
+        document::Title { "Companies - ApplyMonitor" }
+        document::Meta {
+            name: "description",
+            content: "See every employer you have applied to, with their jobs, contacts and emails.",
+        }
+        document::Meta {
+            property: "og:title",
+            content: "Companies - ApplyMonitor",
+        }
+        document::Meta {
+            property: "og:description",
+            content: "See every employer you have applied to, with their jobs, contacts and emails.",
+        }
+
+        CompaniesList {}
+    }
+}
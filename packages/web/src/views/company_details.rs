@@ -0,0 +1,49 @@
+//! Web-specific company details view
+
+use crate::Route;
+use dioxus::prelude::*;
+use dioxus_router::use_navigator;
+use ui::{use_auth, CompanyDetails as CompanyDetailsComponent};
+
+/// Web-specific company details view wrapper
+#[component]
+pub fn CompanyDetails(id: String) -> Element {
+    let auth = use_auth();
+    let navigator = use_navigator();
+
+    // Fetch user on mount
+    use_effect(move || {
+        auth.fetch_user();
+    });
+
+    // Redirect to login if not authenticated
+    use_effect(move || {
+        let user = auth.user;
+        let loading = auth.loading;
+
+        if !loading() && user().is_none() {
+            navigator.push(Route::Login {});
+        }
+    });
+
+    rsx! {
+        document::Title { "Company - ApplyMonitor" }
+        document::Meta {
+            name: "description",
+            content: "All jobs, emails, contacts and outcomes for one employer.",
+        }
+        document::Meta {
+            property: "og:title",
+            content: "Company - ApplyMonitor",
+        }
+        document::Meta {
+            property: "og:description",
+            content: "All jobs, emails, contacts and outcomes for one employer.",
+        }
+
+        CompanyDetailsComponent {
+            key: "{id}",
+            company_id: id,
+        }
+    }
+}
@@ -0,0 +1,49 @@
+//! Web-specific contact details view
+
+use crate::Route;
+use dioxus::prelude::*;
+use dioxus_router::use_navigator;
+use ui::{use_auth, ContactDetails as ContactDetailsComponent};
+
+/// Web-specific contact details view wrapper
+#[component]
+pub fn ContactDetails(email: String) -> Element {
+    let auth = use_auth();
+    let navigator = use_navigator();
+
+    // Fetch user on mount
+    use_effect(move || {
+        auth.fetch_user();
+    });
+
+    // Redirect to login if not authenticated
+    use_effect(move || {
+        let user = auth.user;
+        let loading = auth.loading;
+
+        if !loading() && user().is_none() {
+            navigator.push(Route::Login {});
+        }
+    });
+
+    rsx! {
+        document::Title { "Contact - ApplyMonitor" }
+        document::Meta {
+            name: "description",
+            content: "Emails, jobs and notes for one contact.",
+        }
+        document::Meta {
+            property: "og:title",
+            content: "Contact - ApplyMonitor",
+        }
+        document::Meta {
+            property: "og:description",
+            content: "Emails, jobs and notes for one contact.",
+        }
+
+        ContactDetailsComponent {
+            key: "{email}",
+            email,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! Web-specific contacts view
+
+use crate::Route;
+use dioxus::prelude::*;
+use dioxus_router::use_navigator;
+use ui::{use_auth, ContactsList};
+
+/// Web-specific contacts view wrapper
+#[component]
+pub fn Contacts() -> Element {
+    let auth = use_auth();
+    let navigator = use_navigator();
+
+    // Fetch user on mount
+    use_effect(move || {
+        auth.fetch_user();
+    });
+
+    // Redirect to login if not authenticated
+    use_effect(move || {
+        let user = auth.user;
+        let loading = auth.loading;
+
+        if !loading() && user().is_none() {
+            navigator.push(Route::Login {});
+        }
+    });
+
+    rsx! {
+        document::Title { "Contacts - ApplyMonitor" }
+        document::Meta {
+            name: "description",
+            content: "See everyone you have been in touch with and who is waiting on a reply.",
+        }
+        document::Meta {
+            property: "og:title",
+            content: "Contacts - ApplyMonitor",
+        }
+        document::Meta {
+            property: "og:description",
+            content: "See everyone you have been in touch with and who is waiting on a reply.",
+        }
+
+        ContactsList {}
+    }
+}
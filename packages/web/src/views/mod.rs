@@ -10,12 +10,27 @@ pub use dashboard::Dashboard;
 mod emails_view;
 pub use emails_view::Emails;
 
+mod review_view;
+pub use review_view::Review;
+
 mod jobs_view;
 pub use jobs_view::Jobs;
 
 mod job_details;
 pub use job_details::JobDetails;
 
+mod companies_view;
+pub use companies_view::Companies;
+
+mod company_details;
+pub use company_details::CompanyDetails;
+
+mod contacts_view;
+pub use contacts_view::Contacts;
+
+mod contact_details;
+pub use contact_details::ContactDetails;
+
 mod login;
 pub use login::Login;
 
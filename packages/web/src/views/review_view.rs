@@ -0,0 +1,46 @@
+//! Web-specific AI review view
+
+use crate::Route;
+use dioxus::prelude::*;
+use dioxus_router::use_navigator;
+use ui::{use_auth, ReviewQueue};
+
+/// Web-specific AI review view wrapper
+#[component]
+pub fn Review() -> Element {
+    let auth = use_auth();
+    let navigator = use_navigator();
+
+    // Fetch user on mount
+    use_effect(move || {
+        auth.fetch_user();
+    });
+
+    // Redirect to login if not authenticated
+    use_effect(move || {
+        let user = auth.user;
+        let loading = auth.loading;
+
+        if !loading() && user().is_none() {
+            navigator.push(Route::Login {});
+        }
+    });
+
+    rsx! {
+        document::Title { "Review - ApplyMonitor" }
+        document::Meta {
+            name: "description",
+            content: "Accept or correct the AI results for emails it was unsure about.",
+        }
+        document::Meta {
+            property: "og:title",
+            content: "Review - ApplyMonitor",
+        }
+        document::Meta {
+            property: "og:description",
+            content: "Accept or correct the AI results for emails it was unsure about.",
+        }
+
+        ReviewQueue {}
+    }
+}